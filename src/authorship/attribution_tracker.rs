@@ -4,7 +4,9 @@
 //! authorship information even through moves, edits, and whitespace changes.
 
 use crate::authorship::imara_diff_utils::{ByteDiff, ByteDiffOp, DiffOp, capture_diff_slices};
-use crate::authorship::move_detection::{DeletedLine, InsertedLine, detect_moves};
+use crate::authorship::move_detection::{
+    DEFAULT_MOVE_LINES_THRESHOLD, DeletedLine, InsertedLine, detect_moves,
+};
 use crate::authorship::working_log::CheckpointKind;
 use crate::error::GitAiError;
 use crate::utils::debug_log;
@@ -226,6 +228,16 @@ fn collect_line_metadata(content: &str) -> Vec<LineMetadata> {
     metadata
 }
 
+/// Key used to line-diff two versions of a file. Lines are compared without their line ending
+/// style, so a CRLF <-> LF conversion is not treated as a change, but a missing final newline is.
+fn line_diff_key<'a>(content: &'a str, line: &LineMetadata) -> (&'a str, bool) {
+    let slice = &content[line.start..line.end];
+    match slice.strip_suffix('\n') {
+        Some(body) => (body.strip_suffix('\r').unwrap_or(body), true),
+        None => (slice, false),
+    }
+}
+
 #[derive(Clone, Debug)]
 struct Token {
     lexeme: String,
@@ -275,7 +287,7 @@ pub struct AttributionConfig {
 impl Default for AttributionConfig {
     fn default() -> Self {
         AttributionConfig {
-            move_lines_threshold: 3,
+            move_lines_threshold: DEFAULT_MOVE_LINES_THRESHOLD,
         }
     }
 }
@@ -314,16 +326,16 @@ impl AttributionTracker {
         ));
 
         let capture_start = Instant::now();
-        let old_line_slices: Vec<&str> = old_lines
+        let old_line_keys: Vec<(&str, bool)> = old_lines
             .iter()
-            .map(|line| &old_content[line.start..line.end])
+            .map(|line| line_diff_key(old_content, line))
             .collect();
-        let new_line_slices: Vec<&str> = new_lines
+        let new_line_keys: Vec<(&str, bool)> = new_lines
             .iter()
-            .map(|line| &new_content[line.start..line.end])
+            .map(|line| line_diff_key(new_content, line))
             .collect();
 
-        let line_ops = capture_diff_slices(&old_line_slices, &new_line_slices);
+        let line_ops = capture_diff_slices(&old_line_keys, &new_line_keys);
         let line_ops_len = line_ops.len();
         debug_log(&format!(
             "[BENCHMARK] capture_diff_slices produced {} ops in {:?}",
@@ -349,7 +361,14 @@ impl AttributionTracker {
                     pending_changed.clear();
                 }
 
-                self.push_equal_lines(op, &old_lines, old_content, &mut computation.diffs)?;
+                self.push_equal_lines(
+                    op,
+                    &old_lines,
+                    &new_lines,
+                    old_content,
+                    new_content,
+                    &mut computation.diffs,
+                )?;
             } else {
                 pending_changed.push(op);
             }
@@ -381,22 +400,63 @@ impl AttributionTracker {
         &self,
         op: DiffOp,
         old_lines: &[LineMetadata],
+        new_lines: &[LineMetadata],
         old_content: &str,
+        new_content: &str,
         diffs: &mut Vec<ByteDiff>,
     ) -> Result<(), GitAiError> {
-        if let DiffOp::Equal { old_index, len, .. } = op {
+        if let DiffOp::Equal {
+            old_index,
+            new_index,
+            len,
+        } = op
+        {
             if len == 0 {
                 return Ok(());
             }
 
             let (start, end) =
                 line_range_to_byte_range(old_lines, old_index, old_index + len, old_content.len());
+            let (new_start, new_end) =
+                line_range_to_byte_range(new_lines, new_index, new_index + len, new_content.len());
 
-            if start < end {
-                diffs.push(ByteDiff::new(
-                    ByteDiffOp::Equal,
-                    &old_content.as_bytes()[start..end],
-                ));
+            if old_content.as_bytes()[start..end] == new_content.as_bytes()[new_start..new_end] {
+                if start < end {
+                    diffs.push(ByteDiff::new(
+                        ByteDiffOp::Equal,
+                        &old_content.as_bytes()[start..end],
+                    ));
+                }
+                return Ok(());
+            }
+
+            // The lines only differ in their line endings (CRLF vs LF). Keep the line bodies
+            // equal so their attribution carries over, and swap just the terminators.
+            for offset in 0..len {
+                let old_line = &old_lines[old_index + offset];
+                let new_line = &new_lines[new_index + offset];
+                let (body, _) = line_diff_key(old_content, old_line);
+                let old_body_end = old_line.start + body.len();
+                let new_body_end = new_line.start + body.len();
+
+                if !body.is_empty() {
+                    diffs.push(ByteDiff::new(ByteDiffOp::Equal, body.as_bytes()));
+                }
+
+                let old_ending = &old_content.as_bytes()[old_body_end..old_line.end];
+                let new_ending = &new_content.as_bytes()[new_body_end..new_line.end];
+                if old_ending == new_ending {
+                    if !old_ending.is_empty() {
+                        diffs.push(ByteDiff::new(ByteDiffOp::Equal, old_ending));
+                    }
+                } else {
+                    if !old_ending.is_empty() {
+                        diffs.push(ByteDiff::new(ByteDiffOp::Delete, old_ending));
+                    }
+                    if !new_ending.is_empty() {
+                        diffs.push(ByteDiff::new(ByteDiffOp::Insert, new_ending));
+                    }
+                }
             }
 
             return Ok(());
@@ -1167,6 +1227,66 @@ fn should_use_line_aligned_hunk_diff(
             && changed_lines >= TOKEN_DIFF_FAST_PATH_MIN_LINES)
 }
 
+/// When content is appended to a file whose last line had no trailing newline, git reports
+/// that line as removed and added again ("\ No newline at end of file"). Only its line break
+/// is new, which is whitespace and would leave the line with its previous author, so hand
+/// the whole line to the author of the append to keep line counts in step with git's.
+pub fn reattribute_terminated_last_line(
+    old_content: &str,
+    new_content: &str,
+    attributions: Vec<Attribution>,
+    current_author: &str,
+    ts: u128,
+) -> Vec<Attribution> {
+    if old_content.is_empty() || old_content.ends_with('\n') {
+        return attributions;
+    }
+    let Some(appended) = new_content.strip_prefix(old_content) else {
+        return attributions;
+    };
+    let line_break_len = if appended.starts_with('\n') {
+        1
+    } else if appended.starts_with("\r\n") {
+        2
+    } else {
+        return attributions;
+    };
+
+    let line_start = old_content.rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = old_content.len() + line_break_len;
+    let mut result = Vec::with_capacity(attributions.len() + 1);
+    for attr in attributions {
+        if !attr.overlaps(line_start, line_end) {
+            result.push(attr);
+            continue;
+        }
+        if attr.start < line_start {
+            result.push(Attribution::new(
+                attr.start,
+                line_start,
+                attr.author_id.clone(),
+                attr.ts,
+            ));
+        }
+        if attr.end > line_end {
+            result.push(Attribution::new(
+                line_end,
+                attr.end,
+                attr.author_id,
+                attr.ts,
+            ));
+        }
+    }
+    result.push(Attribution::new(
+        line_start,
+        line_end,
+        current_author.to_string(),
+        ts,
+    ));
+    result.sort_by_key(|attr| (attr.start, attr.end));
+    result
+}
+
 fn line_range_to_byte_range(
     lines: &[LineMetadata],
     start_idx: usize,
@@ -2001,6 +2121,59 @@ pub fn attributions_to_line_attributions(
     merged_line_authors
 }
 
+/// Split lines into runs of characters by the author who last wrote them.
+///
+/// For each requested line, returns `(start, end, author_id)` runs with 0-based character columns
+/// (end exclusive) within the line, excluding the line terminator. Characters no attribution
+/// covers are left out.
+pub fn line_author_char_runs(
+    attributions: &[Attribution],
+    content: &str,
+    line_nums: &[u32],
+) -> HashMap<u32, Vec<(u32, u32, String)>> {
+    let boundaries = LineBoundaries::new(content);
+    let mut result = HashMap::new();
+
+    for &line_num in line_nums {
+        let Some((line_start, line_end)) = boundaries.get_line_range(line_num) else {
+            continue;
+        };
+        let line = content[line_start..line_end].trim_end_matches(['\n', '\r']);
+
+        let candidates: Vec<&Attribution> = attributions
+            .iter()
+            .filter(|attr| attr.overlaps(line_start, line_start + line.len()))
+            .collect();
+
+        let mut runs: Vec<(u32, u32, String)> = Vec::new();
+        for (column, (offset, _)) in line.char_indices().enumerate() {
+            let position = line_start + offset;
+            // Latest write wins, keeping the first match on ties like line-level attribution
+            let mut owner: Option<&Attribution> = None;
+            for attr in candidates
+                .iter()
+                .filter(|attr| attr.start <= position && attr.end > position)
+            {
+                if owner.is_none_or(|current| attr.ts > current.ts) {
+                    owner = Some(attr);
+                }
+            }
+            let Some(owner) = owner else {
+                continue;
+            };
+
+            let column = column as u32;
+            match runs.last_mut() {
+                Some(run) if run.1 == column && run.2 == owner.author_id => run.1 = column + 1,
+                _ => runs.push((column, column + 1, owner.author_id.clone())),
+            }
+        }
+        result.insert(line_num, runs);
+    }
+
+    result
+}
+
 /// Find the dominant author for a specific line from overlapping attribution candidates.
 fn find_dominant_author_for_line_candidates(
     line_start: usize,
@@ -2228,6 +2401,108 @@ mod tests {
         );
     }
 
+    #[test]
+    fn line_ending_conversion_preserves_attribution() {
+        let tracker = AttributionTracker::new();
+        let old = "fn test() {\n    do_stuff();\n}\n";
+        let new = "fn test() {\r\n    do_stuff();\r\n}\r\n";
+        let old_attrs = vec![Attribution::new(0, old.len(), "Alice".into(), TEST_TS)];
+
+        let updated = tracker
+            .update_attributions(old, new, &old_attrs, "Bob", TEST_TS + 1)
+            .unwrap();
+        assert_non_ws_owned_by(&updated, new, "Alice", "LF -> CRLF should keep authorship");
+
+        let reverted = tracker
+            .update_attributions(new, old, &updated, "Bob", TEST_TS + 2)
+            .unwrap();
+        assert_non_ws_owned_by(&reverted, old, "Alice", "CRLF -> LF should keep authorship");
+    }
+
+    #[test]
+    fn appending_after_unterminated_last_line_reattributes_it() {
+        let tracker = AttributionTracker::new();
+        let old = "fn a() {}\nfn b() {}";
+        let new = "fn a() {}\nfn b() {}\nfn c() {}";
+        let old_attrs = vec![Attribution::new(0, old.len(), "Alice".into(), TEST_TS)];
+
+        let updated = tracker
+            .update_attributions(old, new, &old_attrs, "Bob", TEST_TS + 1)
+            .unwrap();
+        let updated = reattribute_terminated_last_line(old, new, updated, "Bob", TEST_TS + 1);
+
+        let lines = attributions_to_line_attributions(&updated, new);
+        assert_eq!(lines.len(), 2);
+        assert_eq!((lines[0].start_line, lines[0].end_line), (1, 1));
+        assert_eq!(lines[0].author_id, "Alice");
+        assert_eq!((lines[1].start_line, lines[1].end_line), (2, 3));
+        assert_eq!(lines[1].author_id, "Bob");
+
+        // A file that already ends in a newline keeps its last line's author
+        let terminated = "fn a() {}\n";
+        let attrs = vec![Attribution::new(
+            0,
+            terminated.len(),
+            "Alice".into(),
+            TEST_TS,
+        )];
+        let unchanged =
+            reattribute_terminated_last_line(terminated, new, attrs.clone(), "Bob", TEST_TS + 1);
+        assert_eq!(unchanged, attrs);
+    }
+
+    #[test]
+    fn line_ending_conversion_with_edit_only_moves_edited_line() {
+        let tracker = AttributionTracker::new();
+        let old = "let a = 1;\nlet b = 2;\nlet c = 3;\n";
+        let new = "let a = 1;\r\nlet b = 20;\r\nlet c = 3;\r\n";
+        let old_attrs = vec![Attribution::new(0, old.len(), "Alice".into(), TEST_TS)];
+
+        let updated = tracker
+            .update_attributions(old, new, &old_attrs, "Bob", TEST_TS + 1)
+            .unwrap();
+
+        let first_line_end = new.find('\r').unwrap();
+        assert_non_ws_owned_by(
+            &updated,
+            &new[..first_line_end],
+            "Alice",
+            "untouched line should stay Alice",
+        );
+        let twenty = new.find("20").unwrap();
+        assert_range_owned_by(&updated, twenty, twenty + 2, "Bob");
+    }
+
+    #[test]
+    fn line_author_char_runs_splits_partially_edited_line() {
+        let tracker = AttributionTracker::new();
+        let old = "fn main() {}\nlet x = 1;\n";
+        let new = "fn main() {}\nlet x = 2;\n";
+        let old_attrs = vec![Attribution::new(0, old.len(), "ai".into(), TEST_TS)];
+
+        let updated = tracker
+            .update_attributions(old, new, &old_attrs, "human", TEST_TS + 1)
+            .unwrap();
+        let runs = line_author_char_runs(&updated, new, &[2, 3]);
+        assert!(runs[&3].is_empty());
+        let runs = &runs[&2];
+
+        let chars_by = |author: &str| -> u32 {
+            runs.iter()
+                .filter(|run| run.2 == author)
+                .map(|run| run.1 - run.0)
+                .sum()
+        };
+        assert!(chars_by("ai") >= 8, "runs: {:?}", runs);
+        assert!(
+            runs.iter()
+                .any(|run| run.2 == "human" && run.0 <= 8 && run.1 > 8),
+            "edited token should be human: {:?}",
+            runs
+        );
+        assert_eq!(chars_by("ai") + chars_by("human"), 10);
+    }
+
     #[test]
     fn large_file_small_edit_preserves_unchanged_tokens() {
         let tracker = AttributionTracker::new();
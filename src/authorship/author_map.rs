@@ -0,0 +1,101 @@
+use crate::error::GitAiError;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+/// Alias emails mapped to the canonical email of the person using them, so commits from a
+/// work laptop and a CI bot count as one identity. Emails compare case-insensitively.
+///
+/// The file holds one `alias = canonical` pair per line; blank lines and lines starting with
+/// `#` are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct AuthorMap {
+    canonical_by_alias: HashMap<String, String>,
+}
+
+impl AuthorMap {
+    pub fn load(path: &Path) -> Result<AuthorMap, GitAiError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            GitAiError::Generic(format!(
+                "Failed to read author map {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        AuthorMap::parse(&content).map_err(|e| {
+            GitAiError::Generic(format!("Invalid author map {}: {}", path.display(), e))
+        })
+    }
+
+    pub fn parse(content: &str) -> Result<AuthorMap, String> {
+        let mut canonical_by_alias = HashMap::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((alias, canonical)) = line.split_once('=') else {
+                return Err(format!(
+                    "line {}: expected `alias = canonical`, got `{}`",
+                    index + 1,
+                    line
+                ));
+            };
+            let (alias, canonical) = (alias.trim(), canonical.trim());
+            if alias.is_empty() || canonical.is_empty() {
+                return Err(format!("line {}: empty email in `{}`", index + 1, line));
+            }
+            canonical_by_alias.insert(alias.to_lowercase(), canonical.to_lowercase());
+        }
+        Ok(AuthorMap { canonical_by_alias })
+    }
+
+    /// The canonical identity of `email`, lowercased. Unmapped emails are their own identity.
+    pub fn canonical(&self, email: &str) -> String {
+        let email = email.to_lowercase();
+        self.canonical_by_alias
+            .get(&email)
+            .cloned()
+            .unwrap_or(email)
+    }
+
+    /// Every email that belongs to the same identity as `email`, itself included.
+    pub fn emails_of(&self, email: &str) -> Vec<String> {
+        let canonical = self.canonical(email);
+        let mut emails: BTreeSet<String> = self
+            .canonical_by_alias
+            .iter()
+            .filter(|(_, target)| **target == canonical)
+            .map(|(alias, _)| alias.clone())
+            .collect();
+        emails.insert(email.to_lowercase());
+        emails.insert(canonical);
+        emails.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_maps_aliases_case_insensitively() {
+        let map = AuthorMap::parse(
+            "# laptop and CI\nDev@Laptop.local = dev@example.com\n\nci-bot@example.com=dev@example.com\n",
+        )
+        .unwrap();
+        assert_eq!(map.canonical("dev@laptop.local"), "dev@example.com");
+        assert_eq!(map.canonical("CI-Bot@example.com"), "dev@example.com");
+        assert_eq!(map.canonical("Other@example.com"), "other@example.com");
+        assert_eq!(
+            map.emails_of("ci-bot@example.com"),
+            vec![
+                "ci-bot@example.com".to_string(),
+                "dev@example.com".to_string(),
+                "dev@laptop.local".to_string(),
+            ]
+        );
+
+        assert!(AuthorMap::parse("dev@example.com").is_err());
+        assert!(AuthorMap::parse(" = dev@example.com").is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use crate::authorship::transcript::Message;
 use crate::authorship::working_log::AgentId;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fmt;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -187,7 +189,7 @@ impl fmt::Display for LineRange {
 }
 
 /// Prompt session details stored in the top-level prompts map keyed by short hash (agent_id + tool)
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct PromptRecord {
     pub agent_id: AgentId,
     pub human_author: Option<String>,
@@ -203,6 +205,20 @@ pub struct PromptRecord {
     /// Full URL to CAS-stored messages (format: {api_base_url}/cas/{hash})
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub messages_url: Option<String>,
+    /// Agent session that produced the lines, from `GIT_AI_SESSION_ID` when the checkpoint ran
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Sorted `suggested_line_hash`es of every line the agent wrote, as it first wrote them.
+    /// A committed line whose hash is missing here was edited after the suggestion.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggested_line_hashes: Vec<String>,
+}
+
+/// Hash of a line of text without its line ending, as kept in `suggested_line_hashes`
+pub fn suggested_line_hash(line: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(line.as_bytes());
+    format!("{:x}", hasher.finalize())[..16].to_string()
 }
 
 impl Eq for PromptRecord {}
@@ -249,6 +265,8 @@ mod tests {
             accepted_lines: 0,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         }
     }
 
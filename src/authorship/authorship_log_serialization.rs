@@ -1,15 +1,48 @@
 use crate::authorship::authorship_log::{Author, LineRange, PromptRecord};
 use crate::authorship::working_log::CheckpointKind;
+use crate::config::{AuthorshipLogFormat, Config};
 use crate::git::repository::Repository;
+use crate::utils::{nfc_path, same_path};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
 use std::io::{BufRead, Write};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Authorship log format version identifier
-pub const AUTHORSHIP_LOG_VERSION: &str = "authorship/3.0.0";
+pub const AUTHORSHIP_LOG_VERSION: &str = "authorship/3.1.0";
+
+/// Schema version of logs written without the syntax added in 3.1.0 (character ranges,
+/// binary/symlink markers and shared entry blocks). Text logs that don't need it keep this
+/// version so older clients can still read them.
+pub(crate) const AUTHORSHIP_LOG_BASE_VERSION: &str = "authorship/3.0.0";
+
+fn is_supported_schema_version(schema_version: &str) -> bool {
+    schema_version == AUTHORSHIP_LOG_VERSION || schema_version == AUTHORSHIP_LOG_BASE_VERSION
+}
+
+/// Version of the log layout, stored as `version` in the metadata. Bump it whenever the
+/// format changes and add the step upgrading logs of the previous version to
+/// `FORMAT_MIGRATIONS`.
+pub const AUTHORSHIP_LOG_FORMAT_VERSION: u32 = 3;
+
+/// Logs written before the `version` field existed
+pub(crate) const LEGACY_FORMAT_VERSION: u32 = 1;
+
+/// Upgrades applied to logs on read; the step at index `i` takes a log from version `i + 1`
+/// to `i + 2`
+const FORMAT_MIGRATIONS: &[fn(&mut AuthorshipLog)] = &[
+    // v2 only adds the `version` field: character ranges and binary/symlink markers read the
+    // same in both
+    |_| {},
+    // v3 lets a file reference the identical entries of an earlier file, which are expanded
+    // while parsing
+    |_| {},
+];
 
 #[cfg(all(debug_assertions, test))]
 pub const GIT_AI_VERSION: &str = "development";
@@ -21,12 +54,30 @@ pub const GIT_AI_VERSION: &str = concat!("development:", env!("CARGO_PKG_VERSION
 pub const GIT_AI_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Metadata section that goes below the divider as JSON
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct AuthorshipMetadata {
     pub schema_version: String,
     pub git_ai_version: Option<String>,
     pub base_commit_sha: String,
     pub prompts: BTreeMap<String, PromptRecord>,
+    /// Ed25519 signature over the rest of the log (see `authorship::signing`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Layout version of the log (see `AUTHORSHIP_LOG_FORMAT_VERSION`). Missing in logs
+    /// written before it was added, which read as version 1 and are not written with it.
+    #[serde(
+        default = "legacy_format_version",
+        skip_serializing_if = "is_legacy_format_version"
+    )]
+    pub version: u32,
+}
+
+fn legacy_format_version() -> u32 {
+    LEGACY_FORMAT_VERSION
+}
+
+fn is_legacy_format_version(version: &u32) -> bool {
+    *version == LEGACY_FORMAT_VERSION
 }
 
 impl AuthorshipMetadata {
@@ -36,6 +87,8 @@ impl AuthorshipMetadata {
             git_ai_version: Some(GIT_AI_VERSION.to_string()),
             base_commit_sha: String::new(),
             prompts: BTreeMap::new(),
+            signature: None,
+            version: AUTHORSHIP_LOG_FORMAT_VERSION,
         }
     }
 }
@@ -46,21 +99,54 @@ impl Default for AuthorshipMetadata {
     }
 }
 
+/// Characters of a single line that a prompt wrote when the line as a whole is not attributed
+/// to it, e.g. an AI line where a human later changed one token.
+/// Serialized as `line:start-end`; columns are 0-based character offsets, end exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharRange {
+    pub line: u32,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl CharRange {
+    pub fn len(&self) -> u32 {
+        self.end.saturating_sub(self.start)
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+}
+
 /// Attestation entry: short hash followed by line ranges
 ///
 /// IMPORTANT: The hash ALWAYS corresponds to a prompt in the prompts section.
 /// This system only tracks AI-generated content, not human-authored content.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AttestationEntry {
-    /// Short hash (7 chars) that maps to an entry in the prompts section of the metadata
+    /// Short hash (7 chars) that maps to an entry in the prompts section of the metadata, whose
+    /// `agent_id.tool` names the tool that wrote these lines
     pub hash: String,
     /// Line ranges that this prompt is responsible for
     pub line_ranges: Vec<LineRange>,
+    /// Partial lines this prompt is responsible for (lines not covered by `line_ranges`)
+    pub char_ranges: Vec<CharRange>,
 }
 
 impl AttestationEntry {
     pub fn new(hash: String, line_ranges: Vec<LineRange>) -> Self {
-        Self { hash, line_ranges }
+        Self {
+            hash,
+            line_ranges,
+            char_ranges: Vec::new(),
+        }
+    }
+
+    pub fn with_char_ranges(mut self, char_ranges: Vec<CharRange>) -> Self {
+        self.char_ranges = char_ranges;
+        self
     }
 
     #[allow(dead_code)]
@@ -96,6 +182,10 @@ impl AttestationEntry {
 pub struct FileAttestation {
     pub file_path: String,
     pub entries: Vec<AttestationEntry>,
+    /// Binary files are attested as a whole: their entries name the prompts but carry no ranges
+    pub is_binary: bool,
+    /// Symlinks are attested as a whole too, since their only "line" is the link target
+    pub is_symlink: bool,
 }
 
 impl FileAttestation {
@@ -103,12 +193,29 @@ impl FileAttestation {
         Self {
             file_path,
             entries: Vec::new(),
+            is_binary: false,
+            is_symlink: false,
         }
     }
 
     pub fn add_entry(&mut self, entry: AttestationEntry) {
         self.entries.push(entry);
     }
+
+    /// Number of distinct lines in this file attested to a prompt. Whole-file attestations
+    /// (binary files and symlinks) carry no lines and count as zero.
+    pub fn ai_count(&self) -> u32 {
+        let mut lines = BTreeSet::new();
+        for range in self.entries.iter().flat_map(|entry| &entry.line_ranges) {
+            match range {
+                LineRange::Single(line) => {
+                    lines.insert(*line);
+                }
+                LineRange::Range(start, end) => lines.extend(*start..=*end),
+            }
+        }
+        lines.len() as u32
+    }
 }
 
 /// The complete authorship log format
@@ -135,28 +242,106 @@ impl AuthorshipLog {
         }
     }
 
+    /// The attestation of `file`, created empty if the log has none. New paths are stored in
+    /// NFC so logs written on macOS match paths from git.
     pub fn get_or_create_file(&mut self, file: &str) -> &mut FileAttestation {
         // Check if file already exists
-        let exists = self.attestations.iter().any(|f| f.file_path == file);
+        let exists = self
+            .attestations
+            .iter()
+            .any(|f| same_path(&f.file_path, file));
 
         if !exists {
             self.attestations
-                .push(FileAttestation::new(file.to_string()));
+                .push(FileAttestation::new(nfc_path(file).into_owned()));
         }
 
         // Now get the reference
         self.attestations
             .iter_mut()
-            .find(|f| f.file_path == file)
+            .find(|f| same_path(&f.file_path, file))
             .unwrap()
     }
 
-    /// Serialize to the new text format
+    /// The attestation of `file`, matching its path in either Unicode normalization.
+    pub fn file_attestation(&self, file: &str) -> Option<&FileAttestation> {
+        self.attestations
+            .iter()
+            .find(|f| same_path(&f.file_path, file))
+    }
+
+    /// Iterate over the per-file attestations in the log.
+    ///
+    /// ```
+    /// use git_ai::authorship::authorship_log::LineRange;
+    /// use git_ai::authorship::authorship_log_serialization::{AttestationEntry, AuthorshipLog};
+    ///
+    /// // A 5-line file whose first three lines came from a prompt and last two from a human
+    /// let mut log = AuthorshipLog::new();
+    /// log.get_or_create_file("src/lib.rs")
+    ///     .add_entry(AttestationEntry::new("1234567".to_string(), vec![LineRange::Range(1, 3)]));
+    ///
+    /// let files: Vec<&str> = log.attestations().map(|file| file.file_path.as_str()).collect();
+    /// assert_eq!(files, vec!["src/lib.rs"]);
+    /// ```
+    pub fn attestations(&self) -> impl Iterator<Item = &FileAttestation> {
+        self.attestations.iter()
+    }
+
+    /// Number of lines attested to a prompt, across all files.
+    ///
+    /// ```
+    /// use git_ai::authorship::authorship_log::LineRange;
+    /// use git_ai::authorship::authorship_log_serialization::{AttestationEntry, AuthorshipLog};
+    ///
+    /// let mut log = AuthorshipLog::new();
+    /// log.get_or_create_file("src/lib.rs").add_entry(AttestationEntry::new(
+    ///     "1234567".to_string(),
+    ///     vec![LineRange::Range(1, 2), LineRange::Single(3)],
+    /// ));
+    /// assert_eq!(log.ai_count(), 3);
+    /// ```
+    pub fn ai_count(&self) -> u32 {
+        self.attestations().map(FileAttestation::ai_count).sum()
+    }
+
+    /// Number of human lines among `added_lines` lines added by the commit.
+    ///
+    /// The log only records lines written by prompts, so human lines are whatever else was added.
+    ///
+    /// ```
+    /// use git_ai::authorship::authorship_log::LineRange;
+    /// use git_ai::authorship::authorship_log_serialization::{AttestationEntry, AuthorshipLog};
+    ///
+    /// // A 5-line file: lines 1-3 from a prompt, lines 4-5 typed by a human
+    /// let mut log = AuthorshipLog::new();
+    /// log.get_or_create_file("src/lib.rs")
+    ///     .add_entry(AttestationEntry::new("1234567".to_string(), vec![LineRange::Range(1, 3)]));
+    /// assert_eq!(log.ai_count(), 3);
+    /// assert_eq!(log.human_count(5), 2);
+    /// ```
+    pub fn human_count(&self, added_lines: u32) -> u32 {
+        added_lines.saturating_sub(self.ai_count())
+    }
+
+    /// Serialize to the new text format. Files are written in byte order of their paths, so
+    /// the same attestations always serialize to the same text. A file whose entries are
+    /// identical to an earlier file's is written as a reference to their content hash.
+    ///
+    /// Logs that use none of the syntax added in 3.1.0 are written as
+    /// `AUTHORSHIP_LOG_BASE_VERSION`, so only the logs older clients can't parse say they are
+    /// newer.
     pub fn serialize_to_string(&self) -> Result<String, fmt::Error> {
         let mut output = String::new();
 
+        let mut attestations: Vec<&FileAttestation> = self.attestations.iter().collect();
+        attestations.sort_by(|a, b| a.file_path.as_bytes().cmp(b.file_path.as_bytes()));
+
+        let mut written_blocks: HashSet<String> = HashSet::new();
+        let mut uses_new_syntax = false;
+
         // Write attestation section
-        for file_attestation in &self.attestations {
+        for file_attestation in attestations {
             // Quote file names that contain spaces or whitespace
             let file_path = if needs_quoting(&file_attestation.file_path) {
                 format!("\"{}\"", &file_attestation.file_path)
@@ -164,14 +349,36 @@ impl AuthorshipLog {
                 file_attestation.file_path.clone()
             };
             output.push_str(&file_path);
+            if file_attestation.is_binary {
+                output.push_str(BINARY_FILE_MARKER);
+                uses_new_syntax = true;
+            } else if file_attestation.is_symlink {
+                output.push_str(SYMLINK_FILE_MARKER);
+                uses_new_syntax = true;
+            }
             output.push('\n');
 
+            let mut block = String::new();
             for entry in &file_attestation.entries {
-                output.push_str("  ");
-                output.push_str(&entry.hash);
-                output.push(' ');
-                output.push_str(&format_line_ranges(&entry.line_ranges));
-                output.push('\n');
+                uses_new_syntax |= !entry.char_ranges.is_empty();
+                block.push_str("  ");
+                block.push_str(&entry.hash);
+                let ranges = format_entry_ranges(entry);
+                if !ranges.is_empty() {
+                    block.push(' ');
+                    block.push_str(&ranges);
+                }
+                block.push('\n');
+            }
+
+            // Copies of the same generated file share one block; short blocks are cheaper inline
+            let block_hash = entry_block_hash(&block);
+            let reference = format!("  {}{}\n", ENTRY_BLOCK_REFERENCE, block_hash);
+            if block.len() > reference.len() && !written_blocks.insert(block_hash) {
+                output.push_str(&reference);
+                uses_new_syntax = true;
+            } else {
+                output.push_str(&block);
             }
         }
 
@@ -179,7 +386,16 @@ impl AuthorshipLog {
         output.push_str("---\n");
 
         // Write JSON metadata section
-        let json_str = serde_json::to_string_pretty(&self.metadata).map_err(|_| fmt::Error)?;
+        let mut metadata = self.metadata.clone();
+        if is_supported_schema_version(&metadata.schema_version) {
+            metadata.schema_version = if uses_new_syntax {
+                AUTHORSHIP_LOG_VERSION
+            } else {
+                AUTHORSHIP_LOG_BASE_VERSION
+            }
+            .to_string();
+        }
+        let json_str = serde_json::to_string_pretty(&metadata).map_err(|_| fmt::Error)?;
         output.push_str(&json_str);
 
         Ok(output)
@@ -194,8 +410,171 @@ impl AuthorshipLog {
         Ok(())
     }
 
-    /// Deserialize from the new text format
+    /// Serialize in the encoding selected by the `authorship_log_format` config option, for
+    /// writing to notes. Binary logs are base64-armored behind `BINARY_LOG_PREFIX` so they
+    /// can be stored wherever text logs are.
+    pub fn serialize_for_storage(&self) -> Result<String, fmt::Error> {
+        match Config::get().authorship_log_format() {
+            AuthorshipLogFormat::Text => self.serialize_to_string(),
+            AuthorshipLogFormat::Binary => Ok(format!(
+                "{}{}",
+                BINARY_LOG_PREFIX,
+                BASE64.encode(self.serialize_to_binary()?)
+            )),
+        }
+    }
+
+    /// Serialize to the compact binary format: a format-version byte, a table of the prompt
+    /// hashes the entries index into, then the files and their ranges as varints with
+    /// length-prefixed strings, and finally the metadata as compact JSON.
+    pub fn serialize_to_binary(&self) -> Result<Vec<u8>, fmt::Error> {
+        let mut output = vec![BINARY_LOG_VERSION];
+
+        let mut attestations: Vec<&FileAttestation> = self.attestations.iter().collect();
+        attestations.sort_by(|a, b| a.file_path.as_bytes().cmp(b.file_path.as_bytes()));
+
+        let mut hashes: Vec<&str> = Vec::new();
+        let mut hash_indices: HashMap<&str, usize> = HashMap::new();
+        for entry in attestations.iter().flat_map(|file| &file.entries) {
+            hash_indices.entry(entry.hash.as_str()).or_insert_with(|| {
+                hashes.push(&entry.hash);
+                hashes.len() - 1
+            });
+        }
+        write_varint(&mut output, hashes.len() as u64);
+        for hash in &hashes {
+            write_bytes(&mut output, hash.as_bytes());
+        }
+
+        write_varint(&mut output, attestations.len() as u64);
+        for file_attestation in attestations {
+            write_bytes(&mut output, file_attestation.file_path.as_bytes());
+            let mut flags = 0u8;
+            if file_attestation.is_binary {
+                flags |= BINARY_FILE_FLAG;
+            }
+            if file_attestation.is_symlink {
+                flags |= SYMLINK_FILE_FLAG;
+            }
+            output.push(flags);
+
+            write_varint(&mut output, file_attestation.entries.len() as u64);
+            for entry in &file_attestation.entries {
+                write_varint(&mut output, hash_indices[entry.hash.as_str()] as u64);
+
+                let mut line_ranges = entry.line_ranges.clone();
+                line_ranges.sort_by_key(|range| match range {
+                    LineRange::Single(line) => *line,
+                    LineRange::Range(start, _) => *start,
+                });
+                write_varint(&mut output, line_ranges.len() as u64);
+                for range in line_ranges {
+                    // The low bit tells a single line from a range, which also stores its length
+                    match range {
+                        LineRange::Single(line) => write_varint(&mut output, (line as u64) << 1),
+                        LineRange::Range(start, end) => {
+                            write_varint(&mut output, ((start as u64) << 1) | 1);
+                            write_varint(&mut output, end.saturating_sub(start) as u64);
+                        }
+                    }
+                }
+
+                let mut char_ranges = entry.char_ranges.clone();
+                char_ranges.sort_by_key(|range| (range.line, range.start));
+                write_varint(&mut output, char_ranges.len() as u64);
+                for range in char_ranges {
+                    write_varint(&mut output, range.line as u64);
+                    write_varint(&mut output, range.start as u64);
+                    write_varint(&mut output, range.len() as u64);
+                }
+            }
+        }
+
+        let json = serde_json::to_vec(&self.metadata).map_err(|_| fmt::Error)?;
+        write_bytes(&mut output, &json);
+
+        Ok(output)
+    }
+
+    /// Deserialize from the compact binary format
+    pub fn deserialize_from_binary(bytes: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut reader = BinaryReader { bytes, pos: 0 };
+
+        let version = reader.read_u8()?;
+        if version != BINARY_LOG_VERSION {
+            return Err(format!("Unsupported binary authorship log version: {}", version).into());
+        }
+
+        let hash_count = reader.read_len()?;
+        let mut hashes = Vec::with_capacity(hash_count);
+        for _ in 0..hash_count {
+            hashes.push(reader.read_string()?);
+        }
+
+        let file_count = reader.read_len()?;
+        let mut attestations = Vec::with_capacity(file_count);
+        for _ in 0..file_count {
+            let mut file_attestation = FileAttestation::new(reader.read_string()?);
+            let flags = reader.read_u8()?;
+            file_attestation.is_binary = flags & BINARY_FILE_FLAG != 0;
+            file_attestation.is_symlink = flags & SYMLINK_FILE_FLAG != 0;
+
+            let entry_count = reader.read_len()?;
+            for _ in 0..entry_count {
+                let hash = hashes
+                    .get(usize::try_from(reader.read_varint()?)?)
+                    .ok_or("Prompt hash index out of range in binary authorship log")?
+                    .clone();
+
+                let line_range_count = reader.read_len()?;
+                let mut line_ranges = Vec::with_capacity(line_range_count);
+                for _ in 0..line_range_count {
+                    let value = reader.read_varint()?;
+                    let start = u32::try_from(value >> 1)?;
+                    if value & 1 == 0 {
+                        line_ranges.push(LineRange::Single(start));
+                    } else {
+                        let len = reader.read_u32()?;
+                        let end = start.checked_add(len).ok_or("Line range out of bounds")?;
+                        line_ranges.push(LineRange::Range(start, end));
+                    }
+                }
+
+                let char_range_count = reader.read_len()?;
+                let mut char_ranges = Vec::with_capacity(char_range_count);
+                for _ in 0..char_range_count {
+                    let line = reader.read_u32()?;
+                    let start = reader.read_u32()?;
+                    let len = reader.read_u32()?;
+                    let end = start
+                        .checked_add(len)
+                        .ok_or("Character range out of bounds")?;
+                    char_ranges.push(CharRange { line, start, end });
+                }
+
+                file_attestation.add_entry(
+                    AttestationEntry::new(hash, line_ranges).with_char_ranges(char_ranges),
+                );
+            }
+            attestations.push(file_attestation);
+        }
+
+        let metadata: AuthorshipMetadata = serde_json::from_slice(reader.read_bytes()?)?;
+
+        let mut log = Self {
+            attestations,
+            metadata,
+        };
+        log.upgrade_format()?;
+        Ok(log)
+    }
+
+    /// Deserialize from the new text format, or from a base64-armored binary log
     pub fn deserialize_from_string(content: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        if let Some(encoded) = content.trim().strip_prefix(BINARY_LOG_PREFIX) {
+            return Self::deserialize_from_binary(&BASE64.decode(encoded)?);
+        }
+
         let lines: Vec<&str> = content.lines().collect();
 
         // Find the divider
@@ -213,10 +592,42 @@ impl AuthorshipLog {
         let json_content = json_lines.join("\n");
         let metadata: AuthorshipMetadata = serde_json::from_str(&json_content)?;
 
-        Ok(Self {
+        let mut log = Self {
             attestations,
             metadata,
-        })
+        };
+        log.upgrade_format()?;
+        Ok(log)
+    }
+
+    /// Bring a log read in an older layout up to `AUTHORSHIP_LOG_FORMAT_VERSION` in memory,
+    /// one version at a time. Logs from a newer git-ai cannot be read. Supported schema
+    /// versions read as `AUTHORSHIP_LOG_VERSION`; the one written depends on the syntax the log
+    /// needs.
+    pub fn upgrade_format(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if is_supported_schema_version(&self.metadata.schema_version) {
+            self.metadata.schema_version = AUTHORSHIP_LOG_VERSION.to_string();
+        }
+        if self.metadata.version > AUTHORSHIP_LOG_FORMAT_VERSION {
+            return Err(format!(
+                "Authorship log format version {} is newer than the supported version {}",
+                self.metadata.version, AUTHORSHIP_LOG_FORMAT_VERSION
+            )
+            .into());
+        }
+        while self.metadata.version < AUTHORSHIP_LOG_FORMAT_VERSION {
+            let migrate = FORMAT_MIGRATIONS
+                .get(self.metadata.version.saturating_sub(1) as usize)
+                .ok_or_else(|| {
+                    format!(
+                        "No migration for authorship log format version {}",
+                        self.metadata.version
+                    )
+                })?;
+            migrate(self);
+            self.metadata.version += 1;
+        }
+        Ok(())
     }
 
     /// Read from a reader in the new format
@@ -237,7 +648,7 @@ impl AuthorshipLog {
         foreign_prompts_cache: &mut HashMap<String, Option<PromptRecord>>,
     ) -> Option<(Author, Option<String>, Option<PromptRecord>)> {
         // Find the file attestation
-        let file_attestation = self.attestations.iter().find(|f| f.file_path == file)?;
+        let file_attestation = self.file_attestation(file)?;
 
         // Check entries in reverse order (latest wins)
         for entry in file_attestation.entries.iter().rev() {
@@ -528,6 +939,38 @@ fn format_line_ranges(ranges: &[LineRange]) -> String {
         .join(",")
 }
 
+/// Format an entry's line ranges followed by its character ranges, e.g. "1-3,7:0-8"
+fn format_entry_ranges(entry: &AttestationEntry) -> String {
+    let mut char_ranges = entry.char_ranges.clone();
+    char_ranges.sort_by_key(|range| (range.line, range.start));
+
+    let mut parts = Vec::new();
+    if !entry.line_ranges.is_empty() {
+        parts.push(format_line_ranges(&entry.line_ranges));
+    }
+    parts.extend(
+        char_ranges
+            .iter()
+            .map(|range| format!("{}:{}-{}", range.line, range.start, range.end)),
+    );
+    parts.join(",")
+}
+
+/// Parse a character range like "7:0-8"
+fn parse_char_range(part: &str) -> Result<CharRange, Box<dyn std::error::Error>> {
+    let (line, columns) = part
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid character range: {}", part))?;
+    let (start, end) = columns
+        .split_once('-')
+        .ok_or_else(|| format!("Invalid character range: {}", part))?;
+    Ok(CharRange {
+        line: line.parse()?,
+        start: start.parse()?,
+        end: end.parse()?,
+    })
+}
+
 /// Parse line ranges from a string like "1,2,19-222"
 /// No spaces are expected in the format
 fn parse_line_ranges(input: &str) -> Result<Vec<LineRange>, Box<dyn std::error::Error>> {
@@ -561,6 +1004,10 @@ fn parse_attestation_section(
 ) -> Result<Vec<FileAttestation>, Box<dyn std::error::Error>> {
     let mut attestations = Vec::new();
     let mut current_file: Option<FileAttestation> = None;
+    // Entry lines of the current file as written, hashed once the file ends so later files
+    // can reference them
+    let mut current_block = String::new();
+    let mut blocks: HashMap<String, Vec<AttestationEntry>> = HashMap::new();
 
     for line in lines {
         let line = line.trim_end(); // Remove trailing whitespace but preserve leading
@@ -573,29 +1020,72 @@ fn parse_attestation_section(
             // Attestation entry line (indented)
             // Remove "  " prefix
 
+            if let Some(block_hash) = entry_line.strip_prefix(ENTRY_BLOCK_REFERENCE) {
+                let entries = blocks.get(block_hash).ok_or_else(|| {
+                    format!("Unknown attestation block reference: {}", block_hash)
+                })?;
+                match current_file {
+                    Some(ref mut file_attestation) => {
+                        file_attestation.entries.extend(entries.iter().cloned())
+                    }
+                    None => return Err("Attestation entry found without a file path".into()),
+                }
+                continue;
+            }
+            current_block.push_str(line);
+            current_block.push('\n');
+
             // Split on first space to separate hash from line ranges
             if let Some(space_pos) = entry_line.find(' ') {
                 let hash = entry_line[..space_pos].to_string();
                 let ranges_str = &entry_line[space_pos + 1..];
-                let line_ranges = parse_line_ranges(ranges_str)?;
+                // Character ranges (`line:start-end`) share the comma-separated list with line ranges
+                let (char_parts, line_parts): (Vec<&str>, Vec<&str>) =
+                    ranges_str.split(',').partition(|part| part.contains(':'));
+                let line_ranges = parse_line_ranges(&line_parts.join(","))?;
+                let char_ranges = char_parts
+                    .into_iter()
+                    .map(parse_char_range)
+                    .collect::<Result<Vec<_>, _>>()?;
 
-                let entry = AttestationEntry::new(hash, line_ranges);
+                let entry = AttestationEntry::new(hash, line_ranges).with_char_ranges(char_ranges);
 
                 if let Some(ref mut file_attestation) = current_file {
                     file_attestation.add_entry(entry);
                 } else {
                     return Err("Attestation entry found without a file path".into());
                 }
+            } else if let Some(ref mut file_attestation) = current_file
+                && (file_attestation.is_binary || file_attestation.is_symlink)
+            {
+                // Whole-file entries are just the prompt hash
+                file_attestation
+                    .add_entry(AttestationEntry::new(entry_line.to_string(), Vec::new()));
             } else {
                 return Err(format!("Invalid attestation entry format: {}", entry_line).into());
             }
         } else {
             // File path line (not indented)
-            if let Some(file_attestation) = current_file.take()
-                && !file_attestation.entries.is_empty()
+            finish_file(
+                current_file.take(),
+                &mut current_block,
+                &mut attestations,
+                &mut blocks,
+            );
+
+            // Unquoted paths never contain spaces, so a trailing marker cannot be part of the path
+            let strip_marker = |marker: &str| {
+                line.strip_suffix(marker)
+                    .filter(|path| path.ends_with('"') || !path.contains(' '))
+            };
+            let (line, is_binary, is_symlink) = if let Some(path) = strip_marker(BINARY_FILE_MARKER)
             {
-                attestations.push(file_attestation);
-            }
+                (path, true, false)
+            } else if let Some(path) = strip_marker(SYMLINK_FILE_MARKER) {
+                (path, false, true)
+            } else {
+                (line, false, false)
+            };
 
             // Parse file path, handling quoted paths
             let file_path = if line.starts_with('"') && line.ends_with('"') {
@@ -606,25 +1096,140 @@ fn parse_attestation_section(
                 line.to_string()
             };
 
-            current_file = Some(FileAttestation::new(file_path));
+            let mut file_attestation = FileAttestation::new(file_path);
+            file_attestation.is_binary = is_binary;
+            file_attestation.is_symlink = is_symlink;
+            current_file = Some(file_attestation);
         }
     }
 
     // Don't forget the last file
-    if let Some(file_attestation) = current_file
+    finish_file(
+        current_file,
+        &mut current_block,
+        &mut attestations,
+        &mut blocks,
+    );
+
+    Ok(attestations)
+}
+
+/// Keep a parsed file and remember its entry lines' content hash for later references
+fn finish_file(
+    file_attestation: Option<FileAttestation>,
+    block: &mut String,
+    attestations: &mut Vec<FileAttestation>,
+    blocks: &mut HashMap<String, Vec<AttestationEntry>>,
+) {
+    if let Some(file_attestation) = file_attestation
         && !file_attestation.entries.is_empty()
     {
+        if !block.is_empty() {
+            blocks
+                .entry(entry_block_hash(block))
+                .or_insert_with(|| file_attestation.entries.clone());
+        }
         attestations.push(file_attestation);
     }
-
-    Ok(attestations)
+    block.clear();
 }
 
 /// Check if a file path needs quoting (contains spaces or whitespace)
+/// Suffix on a file path line marking a binary file attestation
+const BINARY_FILE_MARKER: &str = " binary";
+/// Suffix on a file path line marking a symlink attestation
+const SYMLINK_FILE_MARKER: &str = " symlink";
+
 fn needs_quoting(path: &str) -> bool {
     path.contains(' ') || path.contains('\t') || path.contains('\n')
 }
 
+/// Entry line naming the content hash of an earlier file's entries instead of repeating them.
+/// Prompt hashes are hex, so the marker cannot start a regular entry.
+const ENTRY_BLOCK_REFERENCE: &str = "= ";
+
+/// Content hash of a file's entry lines as written, used to dedupe identical files
+fn entry_block_hash(block: &str) -> String {
+    let digest = Sha256::digest(block.as_bytes());
+    format!("{:x}", digest)[..16].to_string()
+}
+
+/// Prefix of a binary authorship log stored as text, followed by the base64 of its bytes
+pub const BINARY_LOG_PREFIX: &str = "authorship-bin:";
+/// Format-version byte that starts every binary authorship log
+const BINARY_LOG_VERSION: u8 = 1;
+/// File flag bits in the binary format
+const BINARY_FILE_FLAG: u8 = 1;
+const SYMLINK_FILE_FLAG: u8 = 1 << 1;
+
+/// Append an unsigned LEB128 varint
+fn write_varint(output: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        output.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+/// Append a varint length followed by the bytes themselves
+fn write_bytes(output: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(output, bytes.len() as u64);
+    output.extend_from_slice(bytes);
+}
+
+/// Cursor over a binary authorship log
+struct BinaryReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn read_u8(&mut self) -> Result<u8, Box<dyn std::error::Error>> {
+        let byte = *self
+            .bytes
+            .get(self.pos)
+            .ok_or("Truncated binary authorship log")?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, Box<dyn std::error::Error>> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("Varint too long in binary authorship log".into())
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Box<dyn std::error::Error>> {
+        Ok(u32::try_from(self.read_varint()?)?)
+    }
+
+    fn read_len(&mut self) -> Result<usize, Box<dyn std::error::Error>> {
+        let len = usize::try_from(self.read_varint()?)?;
+        // Every counted item takes at least one byte, so a larger count can only be corrupt
+        if len > self.bytes.len() - self.pos {
+            return Err("Truncated binary authorship log".into());
+        }
+        Ok(len)
+    }
+
+    fn read_bytes(&mut self) -> Result<&'a [u8], Box<dyn std::error::Error>> {
+        let len = self.read_len()?;
+        let bytes = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn read_string(&mut self) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(std::str::from_utf8(self.read_bytes()?)?.to_string())
+    }
+}
+
 /// Generate a short hash (7 characters) from agent_id and tool
 pub fn generate_short_hash(agent_id: &str, tool: &str) -> String {
     let combined = format!("{}:{}", tool, agent_id);
@@ -657,6 +1262,330 @@ mod tests {
         assert_debug_snapshot!(ranges);
     }
 
+    #[test]
+    fn test_char_ranges_roundtrip() {
+        let mut log = AuthorshipLog::new();
+        let mut file = FileAttestation::new("src/main.rs".to_string());
+        file.add_entry(
+            AttestationEntry::new("abcd123".to_string(), vec![LineRange::Range(1, 3)])
+                .with_char_ranges(vec![CharRange {
+                    line: 4,
+                    start: 0,
+                    end: 8,
+                }]),
+        );
+        file.add_entry(
+            AttestationEntry::new("ef45678".to_string(), Vec::new()).with_char_ranges(vec![
+                CharRange {
+                    line: 6,
+                    start: 10,
+                    end: 12,
+                },
+                CharRange {
+                    line: 5,
+                    start: 2,
+                    end: 4,
+                },
+            ]),
+        );
+        log.attestations.push(file);
+
+        let serialized = log.serialize_to_string().unwrap();
+        assert!(
+            serialized.starts_with("src/main.rs\n  abcd123 1-3,4:0-8\n  ef45678 5:2-4,6:10-12\n")
+        );
+
+        let deserialized = AuthorshipLog::deserialize_from_string(&serialized).unwrap();
+        let entries = &deserialized.attestations[0].entries;
+        assert_eq!(entries[0].line_ranges, vec![LineRange::Range(1, 3)]);
+        assert_eq!(entries[0].char_ranges.len(), 1);
+        assert!(entries[1].line_ranges.is_empty());
+        assert_eq!(
+            entries[1].char_ranges.iter().map(|r| r.len()).sum::<u32>(),
+            4
+        );
+
+        assert!(parse_char_range("4:0").is_err());
+        assert!(parse_char_range("x:0-1").is_err());
+    }
+
+    #[test]
+    fn test_whole_file_attestation_roundtrip() {
+        let mut log = AuthorshipLog::new();
+        for path in ["assets/logo.png", "assets/my icon.png"] {
+            let mut file = FileAttestation::new(path.to_string());
+            file.is_binary = true;
+            file.add_entry(AttestationEntry::new("abcd123".to_string(), Vec::new()));
+            log.attestations.push(file);
+        }
+        let mut link = FileAttestation::new("current".to_string());
+        link.is_symlink = true;
+        link.add_entry(AttestationEntry::new("abcd123".to_string(), Vec::new()));
+        log.attestations.push(link);
+        let mut text = FileAttestation::new("src/main.rs".to_string());
+        text.add_entry(AttestationEntry::new(
+            "abcd123".to_string(),
+            vec![LineRange::Single(1)],
+        ));
+        log.attestations.push(text);
+
+        let serialized = log.serialize_to_string().unwrap();
+        assert!(serialized.starts_with(
+            "assets/logo.png binary\n  abcd123\n\"assets/my icon.png\" binary\n  abcd123\ncurrent symlink\n  abcd123\nsrc/main.rs\n"
+        ));
+
+        let deserialized = AuthorshipLog::deserialize_from_string(&serialized).unwrap();
+        assert_eq!(deserialized.attestations, log.attestations);
+    }
+
+    fn binary_test_log(file_count: usize) -> AuthorshipLog {
+        let mut log = AuthorshipLog::new();
+        log.metadata.base_commit_sha = "0123456789abcdef0123456789abcdef01234567".to_string();
+        let hashes: Vec<String> = ["session_a", "session_b", "session_c"]
+            .iter()
+            .map(|session| generate_short_hash(session, "cursor"))
+            .collect();
+        for (hash, session) in hashes.iter().zip(["session_a", "session_b", "session_c"]) {
+            log.metadata.prompts.insert(
+                hash.clone(),
+                crate::authorship::authorship_log::PromptRecord {
+                    agent_id: crate::authorship::working_log::AgentId {
+                        tool: "cursor".to_string(),
+                        id: session.to_string(),
+                        model: "claude-3-sonnet".to_string(),
+                    },
+                    human_author: None,
+                    messages: vec![],
+                    total_additions: 0,
+                    total_deletions: 0,
+                    accepted_lines: 0,
+                    overriden_lines: 0,
+                    messages_url: None,
+                    session_id: None,
+                    suggested_line_hashes: Vec::new(),
+                },
+            );
+        }
+
+        let mut files: Vec<FileAttestation> = (0..file_count)
+            .map(|i| {
+                let mut file = FileAttestation::new(format!("src/模块 {}/文件_{}.rs", i % 7, i));
+                for (n, hash) in hashes.iter().enumerate() {
+                    let base = (n as u32 + 1) * 100 + i as u32;
+                    file.add_entry(
+                        AttestationEntry::new(
+                            hash.clone(),
+                            vec![
+                                LineRange::Range(base, base + 12),
+                                LineRange::Single(base + 20),
+                                LineRange::Range(base + 30, base + 30),
+                            ],
+                        )
+                        .with_char_ranges(vec![CharRange {
+                            line: base + 40,
+                            start: 4,
+                            end: 17,
+                        }]),
+                    );
+                }
+                file
+            })
+            .collect();
+        let mut image = FileAttestation::new("assets/图标.png".to_string());
+        image.is_binary = true;
+        image.add_entry(AttestationEntry::new(hashes[0].clone(), Vec::new()));
+        files.push(image);
+        let mut link = FileAttestation::new("current".to_string());
+        link.is_symlink = true;
+        link.add_entry(AttestationEntry::new(hashes[1].clone(), Vec::new()));
+        files.push(link);
+
+        // Binary logs are written in path order, so build the log already sorted
+        files.sort_by(|a, b| a.file_path.as_bytes().cmp(b.file_path.as_bytes()));
+        log.attestations = files;
+        log
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let log = binary_test_log(12);
+
+        let bytes = log.serialize_to_binary().unwrap();
+        assert_eq!(bytes[0], BINARY_LOG_VERSION);
+        assert_eq!(AuthorshipLog::deserialize_from_binary(&bytes).unwrap(), log);
+
+        // Armored binary logs are read through the same entry point as text logs
+        let armored = format!("{}{}\n", BINARY_LOG_PREFIX, BASE64.encode(&bytes));
+        assert_eq!(
+            AuthorshipLog::deserialize_from_string(&armored).unwrap(),
+            log
+        );
+        let text = log.serialize_to_string().unwrap();
+        assert_eq!(AuthorshipLog::deserialize_from_string(&text).unwrap(), log);
+    }
+
+    #[test]
+    fn test_binary_rejects_unknown_version_and_truncation() {
+        let mut bytes = binary_test_log(2).serialize_to_binary().unwrap();
+        let truncated = &bytes[..bytes.len() / 2];
+        assert!(AuthorshipLog::deserialize_from_binary(truncated).is_err());
+
+        bytes[0] = BINARY_LOG_VERSION + 1;
+        assert!(AuthorshipLog::deserialize_from_binary(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_binary_is_smaller_than_text() {
+        let log = binary_test_log(300);
+
+        let text = log.serialize_to_string().unwrap();
+        let bytes = log.serialize_to_binary().unwrap();
+        let armored = format!("{}{}", BINARY_LOG_PREFIX, BASE64.encode(&bytes));
+        assert!(
+            bytes.len() * 2 < text.len(),
+            "binary log is {} bytes, text log is {} bytes",
+            bytes.len(),
+            text.len()
+        );
+        assert!(armored.len() < text.len());
+        assert_eq!(
+            AuthorshipLog::deserialize_from_string(&armored).unwrap(),
+            log
+        );
+    }
+
+    #[test]
+    fn test_identical_files_share_one_entry_block() {
+        let mut log = AuthorshipLog::new();
+        for path in ["gen/a/index.ts", "gen/b/index.ts", "gen/c/index.ts"] {
+            log.get_or_create_file(path)
+                .add_entry(AttestationEntry::new(
+                    "c9883b05a2487d6d".to_string(),
+                    vec![LineRange::Range(1, 40), LineRange::Single(42)],
+                ));
+        }
+        log.get_or_create_file("src/lib.rs")
+            .add_entry(AttestationEntry::new(
+                "c9883b05a2487d6d".to_string(),
+                vec![LineRange::Range(1, 3)],
+            ));
+
+        let serialized = log.serialize_to_string().unwrap();
+        assert_eq!(serialized.matches("c9883b05a2487d6d 1-40,42").count(), 1);
+        assert_eq!(serialized.matches("\n  = ").count(), 2);
+
+        let deserialized = AuthorshipLog::deserialize_from_string(&serialized).unwrap();
+        assert_eq!(deserialized.attestations, log.attestations);
+        assert_eq!(deserialized.ai_count(), 3 * 41 + 3);
+
+        let unknown = serialized.replace("gen/b/index.ts\n  = ", "gen/b/index.ts\n  = 0");
+        assert!(AuthorshipLog::deserialize_from_string(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_schema_version_written_for_syntax_used() {
+        let base_version = format!("\"schema_version\": \"{}\"", AUTHORSHIP_LOG_BASE_VERSION);
+        let current_version = format!("\"schema_version\": \"{}\"", AUTHORSHIP_LOG_VERSION);
+
+        // Logs older clients can parse keep the old version
+        let mut log = AuthorshipLog::new();
+        log.get_or_create_file("src/lib.rs")
+            .add_entry(AttestationEntry::new(
+                "c9883b05a2487d6d".to_string(),
+                vec![LineRange::Range(1, 3)],
+            ));
+        let serialized = log.serialize_to_string().unwrap();
+        assert!(serialized.contains(&base_version));
+        let deserialized = AuthorshipLog::deserialize_from_string(&serialized).unwrap();
+        assert_eq!(deserialized.metadata.schema_version, AUTHORSHIP_LOG_VERSION);
+
+        // Character ranges need the new version
+        log.attestations[0].entries[0].char_ranges = vec![CharRange {
+            line: 4,
+            start: 0,
+            end: 8,
+        }];
+        let serialized = log.serialize_to_string().unwrap();
+        assert!(serialized.contains(&current_version));
+        let deserialized = AuthorshipLog::deserialize_from_string(&serialized).unwrap();
+        assert_eq!(deserialized, log);
+
+        // So do binary files
+        let mut log = AuthorshipLog::new();
+        let image = log.get_or_create_file("logo.png");
+        image.is_binary = true;
+        image.add_entry(AttestationEntry::new(
+            "c9883b05a2487d6d".to_string(),
+            Vec::new(),
+        ));
+        assert!(
+            log.serialize_to_string()
+                .unwrap()
+                .contains(&current_version)
+        );
+
+        // Versions this client doesn't know are left alone
+        log.metadata.schema_version = "authorship/9.0.0".to_string();
+        assert!(
+            log.serialize_to_string()
+                .unwrap()
+                .contains("\"schema_version\": \"authorship/9.0.0\"")
+        );
+    }
+
+    #[test]
+    fn test_file_lookup_matches_across_unicode_normalization() {
+        let nfc = "docs/中文/caf\u{e9}.md";
+        let nfd = "docs/中文/cafe\u{301}.md";
+
+        // Recorded under NFC, looked up under NFD
+        let mut log = AuthorshipLog::new();
+        log.get_or_create_file(nfc).add_entry(AttestationEntry::new(
+            "c9883b05a2487d6d".to_string(),
+            vec![LineRange::Range(1, 3)],
+        ));
+        let serialized = log.serialize_to_string().unwrap();
+        let deserialized = AuthorshipLog::deserialize_from_string(&serialized).unwrap();
+        let file = deserialized
+            .file_attestation(nfd)
+            .expect("NFD path should find the NFC attestation");
+        assert!(file.entries[0].line_ranges[0].contains(2));
+
+        // Recording under NFD stores NFC and reuses the same attestation
+        let mut log = AuthorshipLog::new();
+        log.get_or_create_file(nfd);
+        log.get_or_create_file(nfc);
+        assert_eq!(log.attestations.len(), 1);
+        assert_eq!(log.attestations[0].file_path, nfc);
+
+        // Logs written with NFD paths before normalization still resolve
+        let legacy = serialized.replace(nfc, nfd);
+        let legacy = AuthorshipLog::deserialize_from_string(&legacy).unwrap();
+        assert!(legacy.file_attestation(nfc).is_some());
+    }
+
+    #[test]
+    fn test_ai_count_dedupes_overlapping_entries() {
+        let mut log = AuthorshipLog::new();
+        let file = log.get_or_create_file("src/main.rs");
+        file.add_entry(AttestationEntry::new(
+            "abcd123".to_string(),
+            vec![LineRange::Range(1, 4)],
+        ));
+        file.add_entry(AttestationEntry::new(
+            "efgh456".to_string(),
+            vec![LineRange::Range(3, 5), LineRange::Single(9)],
+        ));
+        let logo = log.get_or_create_file("assets/logo.png");
+        logo.is_binary = true;
+        logo.add_entry(AttestationEntry::new("abcd123".to_string(), Vec::new()));
+
+        assert_eq!(log.attestations().count(), 2);
+        assert_eq!(log.ai_count(), 6);
+        assert_eq!(log.human_count(10), 4);
+        assert_eq!(log.human_count(3), 0);
+    }
+
     #[test]
     fn test_serialize_deserialize_roundtrip() {
         let mut log = AuthorshipLog::new();
@@ -752,6 +1681,37 @@ mod tests {
         // Should be sorted as: 1, 5, 10-15, 25-30, 50, 100-200
     }
 
+    #[test]
+    fn test_attestations_serialize_in_path_order() {
+        let paths = ["src/数据.rs", "b.rs", "a/z.rs", "B.rs", "a.rs"];
+        let build = |order: &[usize]| {
+            let mut log = AuthorshipLog::new();
+            for &i in order {
+                let mut file = FileAttestation::new(paths[i].to_string());
+                file.add_entry(AttestationEntry::new(
+                    "abcd1234abcd1234".to_string(),
+                    vec![LineRange::Single(i as u32 + 1)],
+                ));
+                log.attestations.push(file);
+            }
+            log.serialize_to_string().unwrap()
+        };
+
+        let forward = build(&[0, 1, 2, 3, 4]);
+        assert_eq!(forward, build(&[4, 3, 2, 1, 0]));
+        assert_eq!(forward, build(&[2, 0, 4, 1, 3]));
+
+        let written: Vec<&str> = forward
+            .lines()
+            .take_while(|line| *line != "---")
+            .filter(|line| !line.starts_with("  "))
+            .collect();
+        assert_eq!(
+            written,
+            vec!["B.rs", "a.rs", "a/z.rs", "b.rs", "src/数据.rs"]
+        );
+    }
+
     #[test]
     fn test_file_names_with_spaces() {
         // Test file names with spaces and special characters
@@ -775,6 +1735,8 @@ mod tests {
                 accepted_lines: 0,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -842,6 +1804,8 @@ mod tests {
                 accepted_lines: 0,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -868,6 +1832,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_v1_log_upgrades_to_current_format_version() {
+        // Written before the metadata carried a `version`
+        let v1_note = r#"src/main.rs
+  c9883b05a2487d6d 1-3,7
+"docs/my notes.md"
+  c9883b05a2487d6d 2
+---
+{
+  "schema_version": "authorship/3.0.0",
+  "git_ai_version": "1.0.0",
+  "base_commit_sha": "abc123",
+  "prompts": {}
+}"#;
+
+        let log = AuthorshipLog::deserialize_from_string(v1_note).unwrap();
+        assert_eq!(log.metadata.version, AUTHORSHIP_LOG_FORMAT_VERSION);
+        assert_eq!(log.attestations.len(), 2);
+        assert_eq!(log.attestations[0].file_path, "src/main.rs");
+        assert_eq!(log.attestations[1].file_path, "docs/my notes.md");
+        assert_eq!(log.ai_count(), 5);
+
+        let rewritten = log.serialize_to_string().unwrap();
+        assert!(rewritten.contains(&format!("\"version\": {}", AUTHORSHIP_LOG_FORMAT_VERSION)));
+        assert_eq!(
+            AuthorshipLog::deserialize_from_string(&rewritten).unwrap(),
+            log
+        );
+    }
+
+    #[test]
+    fn test_log_from_newer_format_version_is_rejected() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.version = AUTHORSHIP_LOG_FORMAT_VERSION + 1;
+        let note = log.serialize_to_string().unwrap();
+        assert!(AuthorshipLog::deserialize_from_string(&note).is_err());
+    }
+
     #[test]
     fn test_serialize_deserialize_no_attestations() {
         // Test that serialization and deserialization work correctly when there are no attestations
@@ -891,6 +1893,8 @@ mod tests {
                 accepted_lines: 0,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -1070,6 +2074,8 @@ mod tests {
                 accepted_lines: 11,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -1241,6 +2247,8 @@ mod tests {
                 accepted_lines: 10,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -1265,6 +2273,8 @@ mod tests {
                 accepted_lines: 20,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+
+use crate::authorship::diff_ai_accepted::lines_to_ranges;
+use crate::commands::blame::GitAiBlameOptions;
+use crate::error::GitAiError;
+use crate::git::repository::{Repository, exec_git};
+
+/// A line of a file that a commit in the range deleted, with who had written it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeletedLine {
+    /// Commit that deleted the line
+    pub deleted_by: String,
+    /// Line number in the parent of `deleted_by`
+    pub line: u32,
+    pub content: String,
+    /// Tool name for AI lines, git author name for human ones
+    pub author: String,
+    pub ai: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_id: Option<String>,
+}
+
+/// Lines of `file_path` deleted by the commits in `revision_range` (a `git rev-list` spec such
+/// as `A..B`), oldest first. Each line is blamed in the parent of the commit that deleted it, so
+/// its author comes from the attestation log of the commit that introduced it. Merge commits
+/// are skipped because the lines they drop were deleted on one of the merged branches.
+pub fn deleted_lines(
+    repo: &Repository,
+    file_path: &str,
+    revision_range: &str,
+) -> Result<Vec<DeletedLine>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-list".to_string());
+    args.push("--reverse".to_string());
+    args.push("--no-merges".to_string());
+    args.push(revision_range.to_string());
+    args.push("--".to_string());
+    args.push(file_path.to_string());
+    let output = exec_git(&args)?;
+    let commits = String::from_utf8(output.stdout)?;
+
+    let mut result = Vec::new();
+    for commit_sha in commits.lines().map(str::trim).filter(|sha| !sha.is_empty()) {
+        let commit = repo.find_commit(commit_sha.to_string())?;
+        if commit.parent_count()? == 0 {
+            continue;
+        }
+        let parent_sha = commit.parent(0)?.id();
+
+        let Some(changes) = repo
+            .diff_line_changes(&parent_sha, commit_sha)?
+            .remove(file_path)
+        else {
+            continue;
+        };
+        if changes.deleted.is_empty() {
+            continue;
+        }
+
+        let line_numbers: Vec<u32> = changes.deleted.iter().map(|(line, _)| *line).collect();
+        let options = GitAiBlameOptions {
+            newest_commit: Some(parent_sha),
+            line_ranges: lines_to_ranges(&line_numbers),
+            use_prompt_hashes_as_names: true,
+            no_output: true,
+            ..Default::default()
+        };
+        let (line_authors, prompt_records) = repo.blame(file_path, &options)?;
+
+        for (line, content) in changes.deleted {
+            let author = line_authors.get(&line).cloned().unwrap_or_default();
+            let deleted = match prompt_records.get(&author) {
+                Some(prompt) => DeletedLine {
+                    deleted_by: commit_sha.to_string(),
+                    line,
+                    content,
+                    author: prompt.agent_id.tool.clone(),
+                    ai: true,
+                    prompt_id: Some(author),
+                },
+                None => DeletedLine {
+                    deleted_by: commit_sha.to_string(),
+                    line,
+                    content,
+                    author,
+                    ai: false,
+                    prompt_id: None,
+                },
+            };
+            result.push(deleted);
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn print_deleted_lines(lines: &[DeletedLine]) {
+    let author_width = lines
+        .iter()
+        .map(|line| line.author.chars().count())
+        .max()
+        .unwrap_or(0);
+    let line_width = lines
+        .iter()
+        .map(|line| line.line.to_string().len())
+        .max()
+        .unwrap_or(0);
+
+    for line in lines {
+        println!(
+            "{} ({:<author_width$} {} {:>line_width$}) -{}",
+            &line.deleted_by[..7.min(line.deleted_by.len())],
+            line.author,
+            if line.ai { "AI   " } else { "human" },
+            line.line,
+            line.content,
+        );
+    }
+}
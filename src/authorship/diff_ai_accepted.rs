@@ -3,7 +3,7 @@ use std::collections::{BTreeMap, HashMap};
 use crate::authorship::ignore::{build_ignore_matcher, should_ignore_file_with_matcher};
 use crate::commands::blame::GitAiBlameOptions;
 use crate::error::GitAiError;
-use crate::git::repository::Repository;
+use crate::git::repository::{DiffAlgorithm, Repository};
 
 #[derive(Debug, Default)]
 pub struct DiffAiAcceptedStats {
@@ -79,7 +79,77 @@ pub fn diff_ai_accepted_stats(
     Ok(stats)
 }
 
-fn lines_to_ranges(lines: &[u32]) -> Vec<(u32, u32)> {
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DiffDeletedStats {
+    pub ai_deletions: u32,
+    pub human_deletions: u32,
+}
+
+/// Classify lines deleted between `from_ref` and `to_ref` by blaming them at `from_ref`
+/// and checking the attestations of the commits that introduced them.
+pub fn diff_deleted_stats(
+    repo: &Repository,
+    from_ref: &str,
+    to_ref: &str,
+    ignore_patterns: &[String],
+    ignore_whitespace: bool,
+    diff_algorithm: Option<DiffAlgorithm>,
+) -> Result<DiffDeletedStats, GitAiError> {
+    let deleted_lines_by_file =
+        repo.diff_deleted_lines(from_ref, to_ref, ignore_whitespace, diff_algorithm)?;
+    let ignore_matcher = build_ignore_matcher(ignore_patterns);
+    // A removed symlink target or submodule commit is not a deleted line of code
+    let links = repo.diff_link_paths(from_ref, to_ref)?;
+
+    let mut stats = DiffDeletedStats::default();
+
+    for (file_path, mut lines) in deleted_lines_by_file {
+        if should_ignore_file_with_matcher(&file_path, &ignore_matcher)
+            || links.contains(&file_path)
+        {
+            continue;
+        }
+
+        if lines.is_empty() {
+            continue;
+        }
+
+        lines.sort_unstable();
+        lines.dedup();
+        let line_ranges = lines_to_ranges(&lines);
+
+        let mut options = GitAiBlameOptions::default();
+        #[allow(clippy::field_reassign_with_default)]
+        {
+            options.newest_commit = Some(from_ref.to_string());
+            options.line_ranges = line_ranges;
+            options.no_output = true;
+            options.use_prompt_hashes_as_names = true;
+        }
+
+        // Lines we cannot blame are counted as human, matching how unattested lines are treated
+        let (line_authors, prompt_records) = match repo.blame(&file_path, &options) {
+            Ok(result) => result,
+            Err(_) => {
+                stats.human_deletions += lines.len() as u32;
+                continue;
+            }
+        };
+
+        for line in &lines {
+            match line_authors.get(line) {
+                Some(prompt_hash) if prompt_records.contains_key(prompt_hash) => {
+                    stats.ai_deletions += 1;
+                }
+                _ => stats.human_deletions += 1,
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+pub(crate) fn lines_to_ranges(lines: &[u32]) -> Vec<(u32, u32)> {
     if lines.is_empty() {
         return Vec::new();
     }
@@ -1,3 +1,4 @@
+use crate::error::GitAiError;
 use crate::git::repository::Repository;
 use glob::Pattern;
 use std::collections::HashSet;
@@ -26,6 +27,28 @@ const DEFAULT_IGNORE_PATTERNS: &[&str] = &[
     "**/*.snap.new",
 ];
 
+/// Paths that code generators produce by convention, excluded by `--exclude-generated`
+const GENERATED_PATH_PATTERNS: &[&str] = &[
+    "*.pb.go",
+    "*.pb.cc",
+    "*.pb.h",
+    "*_pb2.py",
+    "*_pb2_grpc.py",
+    "*.pb.swift",
+    "*.g.dart",
+    "*.freezed.dart",
+    "*_generated.go",
+    "zz_generated.*",
+    "*.designer.cs",
+    "*.min.js",
+    "*.min.css",
+];
+
+/// Generators write their marker in a header comment, so only the first lines are scanned
+const GENERATED_MARKER_SCAN_LINES: usize = 5;
+
+const EMPTY_TREE_HASH: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
 #[derive(Clone, Debug)]
 enum CompiledPattern {
     Glob(Pattern),
@@ -34,37 +57,70 @@ enum CompiledPattern {
 
 #[derive(Clone, Debug, Default)]
 pub struct IgnoreMatcher {
-    patterns: Vec<CompiledPattern>,
+    /// Patterns in order, with whether each one was negated with a leading `!`. A leading `\!`
+    /// matches a literal `!` instead.
+    patterns: Vec<(bool, CompiledPattern)>,
 }
 
 impl IgnoreMatcher {
     pub fn new(patterns: &[String]) -> Self {
         let patterns = patterns
             .iter()
-            .map(|pattern| match Pattern::new(pattern) {
-                Ok(glob) => CompiledPattern::Glob(glob),
-                Err(_) => CompiledPattern::Exact(pattern.clone()),
+            .map(|pattern| {
+                let (negated, pattern) = if pattern.starts_with("\\!") {
+                    (false, &pattern[1..])
+                } else {
+                    match pattern.strip_prefix('!') {
+                        Some(rest) if !rest.is_empty() => (true, rest),
+                        _ => (false, pattern.as_str()),
+                    }
+                };
+                let compiled = match Pattern::new(pattern) {
+                    Ok(glob) => CompiledPattern::Glob(glob),
+                    Err(_) => CompiledPattern::Exact(pattern.to_string()),
+                };
+                (negated, compiled)
             })
             .collect();
 
         Self { patterns }
     }
 
+    /// As in gitignore, the last pattern matching the path decides, and a `!` pattern brings
+    /// back a path that an earlier pattern ignored.
     pub fn is_ignored(&self, path: &str) -> bool {
         let filename = std::path::Path::new(path)
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("");
 
-        self.patterns.iter().any(|pattern| match pattern {
-            CompiledPattern::Glob(glob_pattern) => {
-                glob_pattern.matches(path) || glob_pattern.matches(filename)
+        let mut ignored = false;
+        for (negated, pattern) in &self.patterns {
+            let matches = match pattern {
+                CompiledPattern::Glob(glob_pattern) => {
+                    glob_pattern.matches(path) || glob_pattern.matches(filename)
+                }
+                CompiledPattern::Exact(pattern) => filename == pattern || path == pattern,
+            };
+            if matches {
+                ignored = !negated;
             }
-            CompiledPattern::Exact(pattern) => filename == pattern || path == pattern,
-        })
+        }
+        ignored
     }
 }
 
+/// Patterns that ignore every path except those matching one of `globs`. They go before the
+/// regular ignore patterns, so default and user ignores still apply to the kept paths.
+pub fn only_matching_patterns(globs: &[String]) -> Vec<String> {
+    if globs.is_empty() {
+        return Vec::new();
+    }
+    std::iter::once("**".to_string())
+        .chain(globs.iter().map(|glob| format!("!{}", glob)))
+        .collect()
+}
+
 pub fn default_ignore_patterns() -> Vec<String> {
     DEFAULT_IGNORE_PATTERNS
         .iter()
@@ -137,6 +193,39 @@ pub fn load_linguist_generated_patterns_from_root_gitattributes(repo: &Repositor
     dedupe_patterns(patterns)
 }
 
+/// `(path pattern, language)` pairs from `linguist-language=<name>` attributes in the root
+/// `.gitattributes`, in file order.
+pub fn load_linguist_language_overrides_from_root_gitattributes(
+    repo: &Repository,
+) -> Vec<(String, String)> {
+    let Some(contents) = load_root_gitattributes_contents(repo) else {
+        return Vec::new();
+    };
+
+    let mut overrides = Vec::new();
+    for raw_line in contents.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let tokens = split_gitattributes_tokens(line);
+        if tokens.len() < 2 || tokens[0].starts_with("[attr]") {
+            continue;
+        }
+
+        if let Some(language) = tokens[1..]
+            .iter()
+            .rev()
+            .find_map(|attr| attr.strip_prefix("linguist-language="))
+            .filter(|language| !language.is_empty())
+        {
+            overrides.push((tokens[0].clone(), language.to_string()));
+        }
+    }
+    overrides
+}
+
 fn load_root_gitattributes_contents(repo: &Repository) -> Option<String> {
     if repo.is_bare_repository().unwrap_or(false) {
         return repo
@@ -150,37 +239,69 @@ fn load_root_gitattributes_contents(repo: &Repository) -> Option<String> {
     fs::read_to_string(gitattributes_path).ok()
 }
 
-/// Load ignore patterns from a `.git-ai-ignore` file at the repository root.
-/// The file follows `.gitignore` syntax: one glob pattern per line, blank lines
-/// and lines starting with `#` are skipped.
-pub fn load_git_ai_ignore_patterns(repo: &Repository) -> Vec<String> {
-    let Some(contents) = load_root_git_ai_ignore_contents(repo) else {
-        return Vec::new();
-    };
+/// Ignore files read from the repository root, in load order.
+const GIT_AI_IGNORE_FILES: &[&str] = &[".git-ai-ignore", ".gitai-ignore"];
 
+/// Load ignore patterns from `.git-ai-ignore` / `.gitai-ignore` files at the repository root.
+/// The files follow `.gitignore` syntax: one glob pattern per line, blank lines
+/// and lines starting with `#` are skipped. A trailing `/` matches everything under that
+/// directory, a leading `/` anchors the pattern to the repository root, and a leading `!`
+/// brings back paths an earlier pattern ignored (`\!` starts a pattern with a literal `!`).
+pub fn load_git_ai_ignore_patterns(repo: &Repository) -> Vec<String> {
     let mut patterns = Vec::new();
 
-    for raw_line in contents.lines() {
-        let line = raw_line.trim();
-        if line.is_empty() || line.starts_with('#') {
+    for file_name in GIT_AI_IGNORE_FILES {
+        let Some(contents) = load_root_ignore_file_contents(repo, file_name) else {
             continue;
+        };
+
+        for raw_line in contents.lines() {
+            let line = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            patterns.extend(expand_gitignore_pattern(line));
         }
-        patterns.push(line.to_string());
     }
 
     dedupe_patterns(patterns)
 }
 
-fn load_root_git_ai_ignore_contents(repo: &Repository) -> Option<String> {
+fn expand_gitignore_pattern(line: &str) -> Vec<String> {
+    let (prefix, line) = if let Some(literal) = line.strip_prefix("\\!") {
+        ("\\!", literal)
+    } else if let Some(negated) = line.strip_prefix('!') {
+        ("!", negated)
+    } else {
+        ("", line)
+    };
+    let anchored = line.starts_with('/');
+    let pattern = line.trim_start_matches('/');
+
+    let Some(dir) = pattern.strip_suffix('/') else {
+        return vec![format!("{}{}", prefix, pattern)];
+    };
+
+    if anchored || dir.contains('/') {
+        vec![format!("{}{}/**", prefix, dir)]
+    } else {
+        vec![
+            format!("{}{}/**", prefix, dir),
+            format!("{}**/{}/**", prefix, dir),
+        ]
+    }
+}
+
+fn load_root_ignore_file_contents(repo: &Repository, file_name: &str) -> Option<String> {
     if repo.is_bare_repository().unwrap_or(false) {
         return repo
-            .get_file_content(".git-ai-ignore", "HEAD")
+            .get_file_content(file_name, "HEAD")
             .ok()
             .and_then(|bytes| String::from_utf8(bytes).ok());
     }
 
     let workdir = repo.workdir().ok()?;
-    let ignore_path = workdir.join(".git-ai-ignore");
+    let ignore_path = workdir.join(file_name);
     fs::read_to_string(ignore_path).ok()
 }
 
@@ -199,16 +320,64 @@ pub fn effective_ignore_patterns(
     dedupe_patterns(patterns)
 }
 
+/// Ignore patterns for the generated files changed between `base` and `commit`: the
+/// conventional generated paths, plus the exact path of each changed file whose header
+/// contains `marker`. Without a `base` the commit is compared to its first parent.
+pub fn generated_file_patterns(
+    repo: &Repository,
+    base: Option<&str>,
+    commit: &str,
+    marker: &str,
+) -> Result<Vec<String>, GitAiError> {
+    let base = match base {
+        Some(base) => base.to_string(),
+        None => {
+            let commit_obj = repo.revparse_single(commit)?.peel_to_commit()?;
+            if commit_obj.parent_count()? == 0 {
+                EMPTY_TREE_HASH.to_string()
+            } else {
+                commit_obj.parent(0)?.id()
+            }
+        }
+    };
+
+    let mut patterns: Vec<String> = GENERATED_PATH_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect();
+    for path in repo.diff_changed_files(&base, commit)? {
+        // Deleted files have no content at the commit and add no lines anyway
+        let Ok(content) = repo.get_file_content(&path, commit) else {
+            continue;
+        };
+        if has_generated_marker(&content, marker) {
+            patterns.push(Pattern::escape(&path));
+        }
+    }
+    Ok(patterns)
+}
+
+/// True when one of the first lines of `content` contains `marker`.
+pub fn has_generated_marker(content: &[u8], marker: &str) -> bool {
+    String::from_utf8_lossy(content)
+        .lines()
+        .take(GENERATED_MARKER_SCAN_LINES)
+        .any(|line| line.contains(marker))
+}
+
+/// Drop repeated patterns, keeping the last occurrence of each. The last matching pattern
+/// decides whether a path is ignored, so this keeps `!` patterns meaning the same.
 fn dedupe_patterns(patterns: Vec<String>) -> Vec<String> {
     let mut seen = HashSet::new();
     let mut deduped = Vec::new();
 
-    for pattern in patterns {
+    for pattern in patterns.into_iter().rev() {
         if seen.insert(pattern.clone()) {
             deduped.push(pattern);
         }
     }
 
+    deduped.reverse();
     deduped
 }
 
@@ -338,6 +507,39 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn only_matching_patterns_keep_matching_paths_and_defaults_still_apply() {
+        let mut patterns = only_matching_patterns(&["src/**/*.rs".to_string()]);
+        patterns.extend(default_ignore_patterns());
+        let matcher = build_ignore_matcher(&patterns);
+
+        assert!(!should_ignore_file_with_matcher("src/main.rs", &matcher));
+        assert!(!should_ignore_file_with_matcher(
+            "src/数据/模块.rs",
+            &matcher
+        ));
+        assert!(should_ignore_file_with_matcher("src/notes.txt", &matcher));
+        assert!(should_ignore_file_with_matcher("tests/main.rs", &matcher));
+        assert!(should_ignore_file_with_matcher(
+            "src/vendor/lib.rs",
+            &matcher
+        ));
+        assert!(only_matching_patterns(&[]).is_empty());
+    }
+
+    #[test]
+    fn negated_pattern_keeps_earlier_ignored_path() {
+        let patterns = vec!["*.lock".to_string(), "!keep.lock".to_string()];
+        let matcher = build_ignore_matcher(&patterns);
+
+        assert!(should_ignore_file_with_matcher("Cargo.lock", &matcher));
+        assert!(!should_ignore_file_with_matcher("keep.lock", &matcher));
+
+        let matcher = build_ignore_matcher(&["\\!important.md".to_string()]);
+        assert!(should_ignore_file_with_matcher("!important.md", &matcher));
+        assert!(!should_ignore_file_with_matcher("important.md", &matcher));
+    }
+
     #[test]
     fn defaults_ignore_nested_named_lockfiles() {
         let defaults = default_ignore_patterns();
@@ -380,6 +582,64 @@ mod tests {
         assert!(!should_ignore_file_with_matcher("src/main.rs", &matcher));
     }
 
+    #[test]
+    fn expands_gitignore_directory_patterns() {
+        assert_eq!(expand_gitignore_pattern("*.lock"), vec!["*.lock"]);
+        assert_eq!(expand_gitignore_pattern("/build.log"), vec!["build.log"]);
+        assert_eq!(
+            expand_gitignore_pattern("dist/"),
+            vec!["dist/**", "**/dist/**"]
+        );
+        assert_eq!(expand_gitignore_pattern("/dist/"), vec!["dist/**"]);
+        assert_eq!(expand_gitignore_pattern("web/build/"), vec!["web/build/**"]);
+        assert_eq!(
+            expand_gitignore_pattern("!dist/"),
+            vec!["!dist/**", "!**/dist/**"]
+        );
+        assert_eq!(expand_gitignore_pattern("!/keep.lock"), vec!["!keep.lock"]);
+        assert_eq!(
+            expand_gitignore_pattern("\\!important"),
+            vec!["\\!important"]
+        );
+
+        let matcher = build_ignore_matcher(&expand_gitignore_pattern("dist/"));
+        assert!(should_ignore_file_with_matcher("dist/app.js", &matcher));
+        assert!(should_ignore_file_with_matcher(
+            "packages/ui/dist/app.js",
+            &matcher
+        ));
+        assert!(!should_ignore_file_with_matcher("src/dist.rs", &matcher));
+    }
+
+    #[test]
+    fn loads_both_git_ai_ignore_file_names() {
+        let tmp_repo = TmpRepo::new().expect("tmp repo");
+        tmp_repo
+            .write_file(".git-ai-ignore", "docs/**\n", true)
+            .expect("write .git-ai-ignore");
+        tmp_repo
+            .write_file(".gitai-ignore", "# bundles\ndist/\n*.lock\n", true)
+            .expect("write .gitai-ignore");
+
+        let patterns = load_git_ai_ignore_patterns(tmp_repo.gitai_repo());
+        assert_eq!(patterns, vec!["docs/**", "dist/**", "**/dist/**", "*.lock"]);
+    }
+
+    #[test]
+    fn git_ai_ignore_negation_brings_back_paths() {
+        let tmp_repo = TmpRepo::new().expect("tmp repo");
+        tmp_repo
+            .write_file(".git-ai-ignore", "dist/\n!dist/keep.js\n", true)
+            .expect("write .git-ai-ignore");
+
+        let patterns = load_git_ai_ignore_patterns(tmp_repo.gitai_repo());
+        assert_eq!(patterns, vec!["dist/**", "**/dist/**", "!dist/keep.js"]);
+
+        let matcher = build_ignore_matcher(&patterns);
+        assert!(should_ignore_file_with_matcher("dist/app.js", &matcher));
+        assert!(!should_ignore_file_with_matcher("dist/keep.js", &matcher));
+    }
+
     #[test]
     fn loads_positive_linguist_generated_only() {
         let tmp_repo = TmpRepo::new().expect("tmp repo");
@@ -685,4 +945,21 @@ docs/**
         assert!(patterns.contains(&"docs/**".to_string()));
         assert!(patterns.contains(&"*.lock".to_string()));
     }
+
+    #[test]
+    fn generated_marker_is_only_found_in_the_file_header() {
+        assert!(has_generated_marker(
+            b"// Code generated by protoc. @generated\npackage api\n",
+            "@generated"
+        ));
+        assert!(has_generated_marker(
+            b"# DO NOT EDIT\nimport x\n",
+            "DO NOT EDIT"
+        ));
+        assert!(!has_generated_marker(
+            b"fn main() {}\n\n\n\n\n// mentions @generated far below\n",
+            "@generated"
+        ));
+        assert!(!has_generated_marker(b"fn main() {}\n", "@generated"));
+    }
 }
@@ -161,6 +161,8 @@ impl PromptDbRecord {
             accepted_lines: self.accepted_lines.unwrap_or(0),
             overriden_lines: self.overridden_lines.unwrap_or(0),
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         }
     }
 
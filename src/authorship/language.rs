@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+
+use crate::authorship::ignore::{
+    IgnoreMatcher, load_linguist_language_overrides_from_root_gitattributes,
+};
+use crate::authorship::stats::{CommitStats, FileStats};
+use crate::git::repository::Repository;
+
+/// Bucket for files whose language is neither overridden nor known from the extension
+pub const OTHER_LANGUAGE: &str = "other";
+
+const LANGUAGES_BY_EXTENSION: &[(&str, &str)] = &[
+    ("bash", "Shell"),
+    ("c", "C"),
+    ("cc", "C++"),
+    ("cjs", "JavaScript"),
+    ("cpp", "C++"),
+    ("cs", "C#"),
+    ("css", "CSS"),
+    ("cxx", "C++"),
+    ("dart", "Dart"),
+    ("ex", "Elixir"),
+    ("exs", "Elixir"),
+    ("go", "Go"),
+    ("h", "C"),
+    ("hpp", "C++"),
+    ("html", "HTML"),
+    ("java", "Java"),
+    ("js", "JavaScript"),
+    ("json", "JSON"),
+    ("jsx", "JavaScript"),
+    ("kt", "Kotlin"),
+    ("kts", "Kotlin"),
+    ("lua", "Lua"),
+    ("m", "Objective-C"),
+    ("md", "Markdown"),
+    ("mjs", "JavaScript"),
+    ("php", "PHP"),
+    ("py", "Python"),
+    ("rb", "Ruby"),
+    ("rs", "Rust"),
+    ("scala", "Scala"),
+    ("scss", "SCSS"),
+    ("sh", "Shell"),
+    ("sql", "SQL"),
+    ("svelte", "Svelte"),
+    ("swift", "Swift"),
+    ("toml", "TOML"),
+    ("ts", "TypeScript"),
+    ("tsx", "TypeScript"),
+    ("vue", "Vue"),
+    ("yaml", "YAML"),
+    ("yml", "YAML"),
+    ("zig", "Zig"),
+];
+
+/// Maps paths to languages: `linguist-language` overrides from `.gitattributes` first (the last
+/// matching line wins, as in git), then the file extension, then `other`.
+pub struct LanguageClassifier {
+    overrides: Vec<(IgnoreMatcher, String)>,
+}
+
+impl LanguageClassifier {
+    pub fn new(repo: &Repository) -> Self {
+        Self::with_overrides(load_linguist_language_overrides_from_root_gitattributes(
+            repo,
+        ))
+    }
+
+    pub fn with_overrides(overrides: Vec<(String, String)>) -> Self {
+        Self {
+            overrides: overrides
+                .into_iter()
+                .map(|(pattern, language)| (IgnoreMatcher::new(&[pattern]), language))
+                .collect(),
+        }
+    }
+
+    pub fn language_of(&self, path: &str) -> String {
+        if let Some((_, language)) = self
+            .overrides
+            .iter()
+            .rev()
+            .find(|(matcher, _)| matcher.is_ignored(path))
+        {
+            return language.clone();
+        }
+
+        let extension = std::path::Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+        extension
+            .and_then(|ext| {
+                LANGUAGES_BY_EXTENSION
+                    .binary_search_by(|(known, _)| known.cmp(&ext.as_str()))
+                    .ok()
+            })
+            .map(|index| LANGUAGES_BY_EXTENSION[index].1.to_string())
+            .unwrap_or_else(|| OTHER_LANGUAGE.to_string())
+    }
+}
+
+/// Sum a commit's per-file stats by language. Each language gets the addition counts of its
+/// files and the matching percentages.
+pub fn stats_by_language(
+    files: &[FileStats],
+    classifier: &LanguageClassifier,
+) -> BTreeMap<String, CommitStats> {
+    let mut languages: BTreeMap<String, CommitStats> = BTreeMap::new();
+    for file in files {
+        let stats = languages
+            .entry(classifier.language_of(&file.file_path))
+            .or_default();
+        stats.ai_additions += file.ai_additions;
+        stats.ai_accepted += file.ai_accepted;
+        stats.human_additions += file.human_additions;
+        stats.git_diff_added_lines += file.ai_additions + file.human_additions;
+    }
+    for stats in languages.values_mut() {
+        stats.compute_percentages();
+    }
+    languages
+}
+
+pub fn write_language_stats_to_terminal(
+    languages: &BTreeMap<String, CommitStats>,
+    print: bool,
+) -> String {
+    let mut output = String::new();
+    if languages.is_empty() {
+        return output;
+    }
+
+    let header = format!("     {:>6} {:>6} {:>7}  language", "ai", "human", "ai %");
+    output.push_str(&header);
+    output.push('\n');
+    if print {
+        println!("{}", header);
+    }
+
+    for (language, stats) in languages {
+        let row = format!(
+            "     {:>6} {:>6} {:>6.1}%  {}",
+            stats.ai_additions, stats.human_additions, stats.ai_percentage, language
+        );
+        output.push_str(&row);
+        output.push('\n');
+        if print {
+            println!("{}", row);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_table_is_sorted_for_binary_search() {
+        assert!(
+            LANGUAGES_BY_EXTENSION
+                .windows(2)
+                .all(|pair| pair[0].0 < pair[1].0)
+        );
+    }
+
+    #[test]
+    fn test_language_of_uses_overrides_then_extension() {
+        let classifier = LanguageClassifier::with_overrides(vec![
+            ("*.inc".to_string(), "PHP".to_string()),
+            ("vendor/**".to_string(), "Vendored".to_string()),
+            ("vendor/keep.rs".to_string(), "Rust".to_string()),
+        ]);
+
+        assert_eq!(classifier.language_of("src/lib.rs"), "Rust");
+        assert_eq!(classifier.language_of("web/App.TSX"), "TypeScript");
+        assert_eq!(classifier.language_of("lib/header.inc"), "PHP");
+        assert_eq!(classifier.language_of("vendor/x.go"), "Vendored");
+        assert_eq!(classifier.language_of("vendor/keep.rs"), "Rust");
+        assert_eq!(classifier.language_of("LICENSE"), OTHER_LANGUAGE);
+        assert_eq!(classifier.language_of("data.unknownext"), OTHER_LANGUAGE);
+    }
+}
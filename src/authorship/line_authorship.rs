@@ -0,0 +1,37 @@
+use crate::commands::blame::{BlameAuthorType, GitAiBlameOptions};
+use crate::git::repository::find_repository_in_path;
+
+/// Who wrote a single line of a checked-out file, for callers using git-ai as a library.
+///
+/// The line is blamed in the working tree and resolved to the authorship log of the commit that
+/// recorded it. `repo_path` is any path inside the repository. `file_path` is a UTF-8 string,
+/// relative to the repository root or absolute, so non-ASCII names like `测试文件.rs` can be
+/// passed as-is. `line_number` is 1-based.
+///
+/// Returns `None` when the repository, file, or line cannot be found.
+pub fn line_authorship(
+    repo_path: &str,
+    file_path: &str,
+    line_number: u32,
+) -> Option<BlameAuthorType> {
+    if line_number == 0 {
+        return None;
+    }
+    let repo = find_repository_in_path(repo_path).ok()?;
+
+    let mut options = GitAiBlameOptions::default();
+    #[allow(clippy::field_reassign_with_default)]
+    {
+        options.line_ranges = vec![(line_number, line_number)];
+        options.no_output = true;
+        options.use_prompt_hashes_as_names = true;
+    }
+
+    let (line_authors, prompt_records) = repo.blame(file_path, &options).ok()?;
+    let author = line_authors.get(&line_number)?;
+    if prompt_records.contains_key(author) {
+        Some(BlameAuthorType::Ai)
+    } else {
+        Some(BlameAuthorType::Human)
+    }
+}
@@ -1,18 +1,31 @@
 pub mod attribution_tracker;
+pub mod author_map;
 pub mod authorship_log;
 pub mod authorship_log_serialization;
+pub mod deleted_lines;
 pub mod diff_ai_accepted;
 pub mod ignore;
 pub mod imara_diff_utils;
 pub mod internal_db;
+pub mod language;
+pub mod line_authorship;
 pub mod move_detection;
+pub mod multi_repo;
+pub mod net_authorship;
+pub mod notes;
 pub mod post_commit;
 pub mod pre_commit;
 pub mod prompt_utils;
 pub mod range_authorship;
 pub mod rebase_authorship;
 pub mod secrets;
+pub mod signing;
 pub mod stats;
+pub mod stats_cache;
+pub mod stats_profile;
+pub mod test_classification;
 pub mod transcript;
 pub mod virtual_attribution;
 pub mod working_log;
+
+pub use line_authorship::line_authorship;
@@ -1,7 +1,22 @@
-use std::collections::HashMap;
+use crate::authorship::authorship_log::{LineRange, PromptRecord};
+use crate::authorship::authorship_log_serialization::{AttestationEntry, AuthorshipLog};
+use crate::authorship::diff_ai_accepted::lines_to_ranges;
+use crate::authorship::imara_diff_utils::{DiffOp, capture_diff_slices};
+use crate::commands::blame::GitAiBlameOptions;
+use crate::error::GitAiError;
+use crate::git::repository::Repository;
+use crate::utils::{debug_log, same_path};
 use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 
+/// Minimum number of contiguous identical lines that count as a moved block.
+pub const DEFAULT_MOVE_LINES_THRESHOLD: usize = 3;
+
+/// Skip matching blocks across files when a commit pairs more files with deletions against
+/// files with additions than this, since every pair is compared.
+const MAX_CROSS_FILE_MOVE_PAIRS: usize = 2000;
+
 /// Represents a single inserted line from diff-match-patch output.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct InsertedLine {
@@ -149,6 +164,358 @@ pub fn detect_moves(
     mappings
 }
 
+/// Pair up deleted and added lines of one file that belong to moved blocks, as
+/// `(old line, new line)`. Both sides are `(line number, content)` pairs from a diff.
+pub fn moved_lines(
+    deleted: &[(u32, String)],
+    added: &[(u32, String)],
+    threshold: usize,
+) -> Vec<(u32, u32)> {
+    let mut deleted_lines: Vec<DeletedLine> = deleted
+        .iter()
+        .map(|(line, content)| DeletedLine::new(content.clone(), *line as usize, 0))
+        .collect();
+    let mut inserted_lines: Vec<InsertedLine> = added
+        .iter()
+        .map(|(line, content)| InsertedLine::new(content.clone(), *line as usize, 0))
+        .collect();
+
+    detect_moves(&mut inserted_lines, &mut deleted_lines, threshold)
+        .into_iter()
+        .flat_map(|mapping| {
+            mapping
+                .deleted
+                .into_iter()
+                .zip(mapping.inserted)
+                .map(|(old, new)| (old.line_number as u32, new.line_number as u32))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Carry AI attestations over to lines that `to_ref` moved, within a file or to another file.
+///
+/// A cut and paste shows up in the diff as deleted plus added lines, so the pasted lines would
+/// otherwise count as written by the committer. Blocks that reappear unchanged (ignoring
+/// surrounding whitespace) keep the prompt that wrote them at `from_ref`; lines edited along
+/// the way no longer match and are left alone. Moves within a file are matched first, then
+/// blocks deleted from one file are matched against lines added to each of the others, so a
+/// file split across several new files keeps its attribution in all of them. Cross-file
+/// matching is skipped for commits with more than `MAX_CROSS_FILE_MOVE_PAIRS` file pairs, and
+/// each source file is blamed once. Renames are not followed, so a renamed file is matched as
+/// one deleted and one added file. Lines `log` already attests are not touched. Returns the
+/// number of lines attested.
+pub fn attest_moved_lines(
+    repo: &Repository,
+    from_ref: &str,
+    to_ref: &str,
+    log: &mut AuthorshipLog,
+) -> Result<usize, GitAiError> {
+    let changes_by_file: BTreeMap<_, _> = repo
+        .diff_line_changes(from_ref, to_ref)?
+        .into_iter()
+        .collect();
+
+    // source path -> target path -> (old line, new line) pairs
+    let mut moves: BTreeMap<&str, BTreeMap<&str, Vec<(u32, u32)>>> = BTreeMap::new();
+    let mut moved_within_file: HashSet<(&str, u32)> = HashSet::new();
+    for (file_path, changes) in &changes_by_file {
+        let moved = moved_lines(
+            &changes.deleted,
+            &changes.added,
+            DEFAULT_MOVE_LINES_THRESHOLD,
+        );
+        if moved.is_empty() {
+            continue;
+        }
+        moved_within_file.extend(
+            moved
+                .iter()
+                .map(|(_, new_line)| (file_path.as_str(), *new_line)),
+        );
+        moves
+            .entry(file_path.as_str())
+            .or_default()
+            .insert(file_path.as_str(), moved);
+    }
+
+    let sources: Vec<_> = changes_by_file
+        .iter()
+        .filter(|(_, changes)| !changes.deleted.is_empty())
+        .collect();
+    let targets: Vec<_> = changes_by_file
+        .iter()
+        .filter(|(_, changes)| !changes.added.is_empty())
+        .collect();
+    if sources.len() * targets.len() > MAX_CROSS_FILE_MOVE_PAIRS {
+        debug_log(&format!(
+            "Skipping cross-file move detection for {} ({} files with deletions, {} with additions)",
+            to_ref,
+            sources.len(),
+            targets.len()
+        ));
+    } else {
+        for (target_path, target) in &targets {
+            for (source_path, source) in &sources {
+                if source_path == target_path {
+                    continue;
+                }
+                // Moves within the target file take precedence over blocks from other files
+                let moved: Vec<(u32, u32)> =
+                    moved_lines(&source.deleted, &target.added, DEFAULT_MOVE_LINES_THRESHOLD)
+                        .into_iter()
+                        .filter(|(_, new_line)| {
+                            !moved_within_file.contains(&(target_path.as_str(), *new_line))
+                        })
+                        .collect();
+                if !moved.is_empty() {
+                    moves
+                        .entry(source_path.as_str())
+                        .or_default()
+                        .insert(target_path.as_str(), moved);
+                }
+            }
+        }
+    }
+
+    let mut attested_count = 0;
+    for (source_path, copies_by_target) in moves {
+        attested_count += attest_lines_from_source(
+            repo,
+            from_ref,
+            source_path,
+            copies_by_target.into_iter().collect(),
+            log,
+        );
+    }
+
+    Ok(attested_count)
+}
+
+/// The commit named by the `This reverts commit <sha>.` line `git revert` writes into the
+/// message, if any.
+pub fn reverted_commit(message: &str) -> Option<String> {
+    message.lines().find_map(|line| {
+        let sha = line
+            .trim()
+            .strip_prefix("This reverts commit ")?
+            .trim_end_matches('.');
+        let sha = sha.split(|c: char| c.is_whitespace() || c == ',').next()?;
+        (sha.len() >= 7 && sha.chars().all(|c| c.is_ascii_hexdigit())).then(|| sha.to_string())
+    })
+}
+
+/// Carry AI attestations over to lines a revert brings back.
+///
+/// `git revert` applies the inverse diff, so lines the reverted commit deleted come back as
+/// added lines of `to_ref`. Each restored line is matched by content against the lines the
+/// reverted commit deleted and keeps the prompt that wrote it, as blamed at the reverted
+/// commit's parent. Lines `log` already attests are not touched. Returns the number of lines
+/// attested.
+pub fn attest_reverted_lines(
+    repo: &Repository,
+    reverted_ref: &str,
+    from_ref: &str,
+    to_ref: &str,
+    log: &mut AuthorshipLog,
+) -> Result<usize, GitAiError> {
+    let reverted = repo.revparse_single(reverted_ref)?.peel_to_commit()?;
+    if reverted.parent_count()? == 0 {
+        return Ok(0);
+    }
+    let original_ref = reverted.parent(0)?.id();
+    let mut deleted_by_reverted = repo.diff_line_changes(&original_ref, &reverted.id())?;
+
+    let mut attested_count = 0;
+    for (file_path, changes) in repo.diff_line_changes(from_ref, to_ref)? {
+        let Some(original) = deleted_by_reverted.remove(&file_path) else {
+            continue;
+        };
+        let restored = moved_lines(&original.deleted, &changes.added, 1);
+        attested_count +=
+            attest_copied_lines(repo, &original_ref, &file_path, &file_path, restored, log);
+    }
+
+    Ok(attested_count)
+}
+
+/// Carry AI attestations over to files `to_ref` adds as copies of other files.
+///
+/// A copy adds every line of the new file, so it would otherwise count as written by the
+/// committer. Git's copy detection pairs each copy with a source the same commit modifies, and
+/// lines of the copy that are identical to a line of the source at `from_ref` keep the prompt
+/// that wrote it. A copy that was edited before the commit only keeps attribution on its
+/// unchanged lines. Lines `log` already attests are not touched. Returns the number of lines
+/// attested.
+pub fn attest_copied_files(
+    repo: &Repository,
+    from_ref: &str,
+    to_ref: &str,
+    log: &mut AuthorshipLog,
+) -> Result<usize, GitAiError> {
+    let mut attested_count = 0;
+    for (source_path, copy_path) in repo.diff_copied_files(from_ref, to_ref)? {
+        let source = file_content_at(repo, from_ref, &source_path)?;
+        let copy = file_content_at(repo, to_ref, &copy_path)?;
+        let copied = identical_lines(&source, &copy);
+        attested_count +=
+            attest_copied_lines(repo, from_ref, &source_path, &copy_path, copied, log);
+    }
+
+    Ok(attested_count)
+}
+
+/// `(old line, new line)` pairs, 1-based, of the lines a line diff of `old` and `new` leaves
+/// unchanged.
+fn identical_lines(old: &str, new: &str) -> Vec<(u32, u32)> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    let mut pairs = Vec::new();
+    for op in capture_diff_slices(&old_lines, &new_lines) {
+        if let DiffOp::Equal {
+            old_index,
+            new_index,
+            len,
+        } = op
+        {
+            pairs
+                .extend((0..len).map(|i| ((old_index + i + 1) as u32, (new_index + i + 1) as u32)));
+        }
+    }
+    pairs
+}
+
+fn file_content_at(repo: &Repository, rev: &str, path: &str) -> Result<String, GitAiError> {
+    let tree = repo.revparse_single(rev)?.peel_to_commit()?.tree()?;
+    let entry = tree.get_path(std::path::Path::new(path))?;
+    let blob = repo.find_blob(entry.id())?;
+    Ok(String::from_utf8_lossy(&blob.content()?).into_owned())
+}
+
+/// Attest the new side of each `(old line, new line)` pair, a line of `target_path`, with the
+/// prompt that wrote the old line of `source_path` at `source_ref`. Pairs whose new line `log`
+/// already attests, or whose old line was not written by AI, are skipped. Returns the number
+/// of lines attested.
+fn attest_copied_lines(
+    repo: &Repository,
+    source_ref: &str,
+    source_path: &str,
+    target_path: &str,
+    copied: Vec<(u32, u32)>,
+    log: &mut AuthorshipLog,
+) -> usize {
+    attest_lines_from_source(
+        repo,
+        source_ref,
+        source_path,
+        vec![(target_path, copied)],
+        log,
+    )
+}
+
+/// [`attest_copied_lines`] for several target files whose lines all come from `source_path`,
+/// with a single blame of the source.
+fn attest_lines_from_source(
+    repo: &Repository,
+    source_ref: &str,
+    source_path: &str,
+    copies: Vec<(&str, Vec<(u32, u32)>)>,
+    log: &mut AuthorshipLog,
+) -> usize {
+    let copies: Vec<(&str, Vec<(u32, u32)>)> = copies
+        .into_iter()
+        .map(|(target_path, copied)| {
+            let attested = attested_lines(log, target_path);
+            let copied: Vec<(u32, u32)> = copied
+                .into_iter()
+                .filter(|(_, new_line)| !attested.contains(new_line))
+                .collect();
+            (target_path, copied)
+        })
+        .filter(|(_, copied)| !copied.is_empty())
+        .collect();
+    if copies.is_empty() {
+        return 0;
+    }
+
+    let mut old_lines: Vec<u32> = copies
+        .iter()
+        .flat_map(|(_, copied)| copied.iter().map(|(old_line, _)| *old_line))
+        .collect();
+    old_lines.sort_unstable();
+    old_lines.dedup();
+
+    let mut options = GitAiBlameOptions::default();
+    #[allow(clippy::field_reassign_with_default)]
+    {
+        options.newest_commit = Some(source_ref.to_string());
+        options.line_ranges = lines_to_ranges(&old_lines);
+        options.no_output = true;
+        options.use_prompt_hashes_as_names = true;
+    }
+    let Ok((line_authors, prompt_records)) = repo.blame(source_path, &options) else {
+        return 0;
+    };
+
+    let mut attested_count = 0;
+    for (target_path, copied) in copies {
+        let mut new_lines_by_prompt: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for (old_line, new_line) in copied {
+            if let Some(prompt_hash) = line_authors.get(&old_line)
+                && let Some(record) = prompt_records.get(prompt_hash)
+            {
+                new_lines_by_prompt
+                    .entry(prompt_hash.clone())
+                    .or_default()
+                    .push(new_line);
+                log.metadata
+                    .prompts
+                    .entry(prompt_hash.clone())
+                    .or_insert_with(|| copied_prompt_record(record));
+            }
+        }
+
+        for (prompt_hash, mut new_lines) in new_lines_by_prompt {
+            new_lines.sort_unstable();
+            attested_count += new_lines.len();
+            log.get_or_create_file(target_path)
+                .add_entry(AttestationEntry::new(
+                    prompt_hash,
+                    LineRange::compress_lines(&new_lines),
+                ));
+        }
+    }
+
+    attested_count
+}
+
+/// Lines of `file_path` that `log` already attests.
+fn attested_lines(log: &AuthorshipLog, file_path: &str) -> HashSet<u32> {
+    log.attestations()
+        .filter(|file| same_path(&file.file_path, file_path))
+        .flat_map(|file| &file.entries)
+        .flat_map(|entry| &entry.line_ranges)
+        .flat_map(|range| match range {
+            LineRange::Single(line) => *line..=*line,
+            LineRange::Range(start, end) => *start..=*end,
+        })
+        .collect()
+}
+
+/// Copy of a prompt record for a commit that only moved or restored its lines. The prompt's
+/// counters and transcript stay with the commit that produced it, so they are not counted twice.
+pub(crate) fn copied_prompt_record(record: &PromptRecord) -> PromptRecord {
+    PromptRecord {
+        messages: Vec::new(),
+        total_additions: 0,
+        total_deletions: 0,
+        accepted_lines: 0,
+        overriden_lines: 0,
+        ..record.clone()
+    }
+}
+
 trait LineRecord {
     fn line_number(&self) -> usize;
     fn content(&self) -> &str;
@@ -264,6 +631,51 @@ mod tests {
         DeletedLine::new(content, line_number, deletion_idx)
     }
 
+    #[test]
+    fn moved_lines_pairs_old_and_new_line_numbers() {
+        let block = |start: u32| {
+            vec![
+                (start, "fn foo() {".to_string()),
+                (start + 1, "    bar();".to_string()),
+                (start + 2, "}".to_string()),
+            ]
+        };
+
+        assert_eq!(
+            moved_lines(&block(3), &block(10), DEFAULT_MOVE_LINES_THRESHOLD),
+            vec![(3, 10), (4, 11), (5, 12)]
+        );
+
+        // An edited line breaks the block below the threshold
+        let mut edited = block(10);
+        edited[1].1 = "    baz();".to_string();
+        assert!(moved_lines(&block(3), &edited, DEFAULT_MOVE_LINES_THRESHOLD).is_empty());
+    }
+
+    #[test]
+    fn identical_lines_skips_lines_a_copy_changed() {
+        let source = "fn a() {}\nfn b() {}\nfn c() {}\n";
+        let copy = "fn a() {}\nfn b() { todo!() }\nfn c() {}\nfn d() {}\n";
+        assert_eq!(identical_lines(source, copy), vec![(1, 1), (3, 3)]);
+    }
+
+    #[test]
+    fn reverted_commit_reads_git_revert_message() {
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+        let message = format!(
+            "Revert \"Remove helpers\"\n\nThis reverts commit {}.\n",
+            sha
+        );
+        assert_eq!(reverted_commit(&message).as_deref(), Some(sha));
+        assert_eq!(
+            reverted_commit("This reverts commit abc1234, reversing\nchanges made to def5678.\n")
+                .as_deref(),
+            Some("abc1234")
+        );
+        assert_eq!(reverted_commit("Revert the config change"), None);
+        assert_eq!(reverted_commit("This reverts commit HEAD~1."), None);
+    }
+
     #[test]
     fn detects_basic_move() {
         let mut inserted = vec![
@@ -0,0 +1,82 @@
+use crate::authorship::ignore::effective_ignore_patterns;
+use crate::authorship::stats::CommitStats;
+use crate::commands::log::{LogOptions, log_entries};
+use crate::error::GitAiError;
+use crate::git::repository::find_repository_in_path;
+use serde::Serialize;
+
+/// Stats for one repository of a multi-repo aggregate. Exactly one of `stats` and `error` is set.
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoStats {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<CommitStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MultiRepoStats {
+    pub repos: Vec<RepoStats>,
+    pub total: CommitStats,
+}
+
+/// Sum commit stats over the history of each repository, optionally limited to commits whose
+/// commit date falls in `since`..`until` (git date strings).
+///
+/// A path that is not a repository, or whose history cannot be read, is reported in its
+/// `RepoStats::error` and left out of the total rather than failing the whole run.
+pub fn aggregate_repos(
+    paths: &[String],
+    since: Option<&str>,
+    until: Option<&str>,
+    ignore_patterns: &[String],
+) -> MultiRepoStats {
+    let mut total = CommitStats::default();
+    let repos = paths
+        .iter()
+        .map(
+            |path| match repo_stats(path, since, until, ignore_patterns) {
+                Ok(stats) => {
+                    total.accumulate(&stats);
+                    RepoStats {
+                        path: path.clone(),
+                        stats: Some(stats),
+                        error: None,
+                    }
+                }
+                Err(e) => RepoStats {
+                    path: path.clone(),
+                    stats: None,
+                    error: Some(e.to_string()),
+                },
+            },
+        )
+        .collect();
+
+    MultiRepoStats { repos, total }
+}
+
+fn repo_stats(
+    path: &str,
+    since: Option<&str>,
+    until: Option<&str>,
+    ignore_patterns: &[String],
+) -> Result<CommitStats, GitAiError> {
+    let repo = find_repository_in_path(path)?;
+    let patterns = effective_ignore_patterns(&repo, ignore_patterns, &[]);
+
+    // Merge commits repeat the lines of the branch commits they bring in, so leave them out
+    let options = LogOptions {
+        since: since.map(str::to_string),
+        until: until.map(str::to_string),
+        no_merges: true,
+        ..Default::default()
+    };
+
+    let mut stats = CommitStats::default();
+    for entry in log_entries(&repo, &options, &patterns)? {
+        stats.accumulate(&entry.stats);
+    }
+    Ok(stats)
+}
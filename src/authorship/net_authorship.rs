@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+use crate::authorship::ignore::{build_ignore_matcher, should_ignore_file_with_matcher};
+use crate::authorship::stats::{CommitStats, rounded_percentage};
+use crate::commands::blame::{GitAiBlameOptions, overlay_ai_authorship};
+use crate::error::GitAiError;
+use crate::git::repository::{Repository, exec_git};
+
+/// Lines that survive in a commit's tree, split by who wrote them. Unlike `CommitStats`, which
+/// sums what each commit added, lines deleted later are not counted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetAuthorshipStats {
+    pub commit: String,
+    /// When set, only lines introduced after this commit are counted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    pub ai_lines: u32,
+    pub human_lines: u32,
+    pub ai_percentage: f64,
+    pub human_percentage: f64,
+}
+
+impl NetAuthorshipStats {
+    /// Surviving lines as `CommitStats` additions, so stats thresholds apply to them unchanged
+    pub fn as_commit_stats(&self) -> CommitStats {
+        let mut stats = CommitStats {
+            ai_additions: self.ai_lines,
+            human_additions: self.human_lines,
+            ..Default::default()
+        };
+        stats.compute_percentages();
+        stats
+    }
+}
+
+/// Blame every text file in the tree of `commit` and count the lines attributed to AI.
+///
+/// With `base`, lines that already existed at `base` are left out, so the result is what the
+/// commits in `base..commit` contributed to the final tree. Lines from commits without an
+/// authorship log count as human, as they do in `CommitStats`.
+pub fn net_authorship(
+    repo: &Repository,
+    commit: &str,
+    base: Option<&str>,
+    ignore_patterns: &[String],
+) -> Result<NetAuthorshipStats, GitAiError> {
+    let commit_sha = repo.revparse_single(commit)?.id();
+    let base_sha = match base {
+        Some(base) => Some(repo.revparse_single(base)?.id()),
+        None => None,
+    };
+
+    let options = GitAiBlameOptions {
+        newest_commit: Some(commit_sha.clone()),
+        oldest_commit: base_sha.clone(),
+        use_prompt_hashes_as_names: true,
+        no_output: true,
+        ..Default::default()
+    };
+
+    let ignore_matcher = build_ignore_matcher(ignore_patterns);
+    let mut ai_lines = 0;
+    let mut human_lines = 0;
+    for file_path in tree_blob_paths(repo, &commit_sha)? {
+        if should_ignore_file_with_matcher(&file_path, &ignore_matcher) {
+            continue;
+        }
+        let content = repo.get_file_content(&file_path, &commit_sha)?;
+        if content.contains(&0) {
+            continue;
+        }
+        let line_count = String::from_utf8_lossy(&content).lines().count() as u32;
+        if line_count == 0 {
+            continue;
+        }
+
+        let mut hunks = repo.blame_hunks(&file_path, 1, line_count, &options)?;
+        if base_sha.is_some() {
+            hunks.retain(|hunk| !hunk.is_boundary);
+        }
+        let (line_authors, prompt_records, _, _) =
+            overlay_ai_authorship(repo, &hunks, &file_path, &options)?;
+
+        let counted: u32 = hunks
+            .iter()
+            .map(|hunk| hunk.range.1 - hunk.range.0 + 1)
+            .sum();
+        let ai = line_authors
+            .values()
+            .filter(|author| prompt_records.contains_key(*author))
+            .count() as u32;
+        ai_lines += ai;
+        human_lines += counted - ai;
+    }
+
+    let total = ai_lines + human_lines;
+    Ok(NetAuthorshipStats {
+        commit: commit_sha,
+        base: base_sha,
+        ai_lines,
+        human_lines,
+        ai_percentage: rounded_percentage(ai_lines, total),
+        human_percentage: rounded_percentage(human_lines, total),
+    })
+}
+
+/// Paths of the regular files and symlinks in the tree of `commit`, skipping submodules
+fn tree_blob_paths(repo: &Repository, commit: &str) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("ls-tree".to_string());
+    args.push("-r".to_string());
+    args.push("-z".to_string());
+    args.push(commit.to_string());
+
+    let output = exec_git(&args)?;
+    let stdout = String::from_utf8(output.stdout)?;
+    Ok(stdout
+        .split('\0')
+        .filter_map(|entry| {
+            let (meta, path) = entry.split_once('\t')?;
+            (meta.split(' ').nth(1) == Some("blob")).then(|| path.to_string())
+        })
+        .collect())
+}
+
+pub fn print_net_authorship_stats(stats: &NetAuthorshipStats) {
+    match &stats.base {
+        Some(base) => println!(
+            "Lines surviving at {} from commits after {}",
+            &stats.commit[..7],
+            &base[..7]
+        ),
+        None => println!("Lines in the tree at {}", &stats.commit[..7]),
+    }
+    println!(
+        "  AI     {:>8}  {:>6.2}%",
+        stats.ai_lines, stats.ai_percentage
+    );
+    println!(
+        "  Human  {:>8}  {:>6.2}%",
+        stats.human_lines, stats.human_percentage
+    );
+}
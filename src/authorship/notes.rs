@@ -0,0 +1,41 @@
+//! Authorship logs stored as git notes.
+//!
+//! Logs live under `refs/notes/ai`, attached to the commit they describe. Notes are not part of
+//! the tree, so they never show up in the working directory, and they travel with the commit
+//! when the notes ref is pushed. The pre-push and post-fetch hooks keep that ref in sync with
+//! the remote; `fetch_notes` does the same on demand for code that runs outside those hooks.
+//! Reads and writes go through the backend selected by `git-ai.storage`, which is notes unless
+//! the repository opts into sidecar files.
+
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::error::GitAiError;
+use crate::git::refs::{get_authorship, notes_add};
+use crate::git::repository::Repository;
+use crate::git::sync_authorship::{NotesExistence, fetch_authorship_notes};
+
+/// Read the authorship log attached to `commit_sha`, or None when the commit has no log
+pub fn read_notes(repo: &Repository, commit_sha: &str) -> Option<AuthorshipLog> {
+    get_authorship(repo, commit_sha)
+}
+
+/// Attach `log` to `commit_sha`, replacing any log already there
+pub fn write_notes(
+    repo: &Repository,
+    commit_sha: &str,
+    log: &AuthorshipLog,
+) -> Result<(), GitAiError> {
+    let content = log
+        .serialize_for_storage()
+        .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
+    notes_add(repo, commit_sha, &content)
+}
+
+/// Fetch `remote_name`'s notes and merge them into the local notes ref, so logs written for
+/// commits authored on another machine become readable. Returns false when the remote has no
+/// notes yet.
+pub fn fetch_notes(repo: &Repository, remote_name: &str) -> Result<bool, GitAiError> {
+    Ok(matches!(
+        fetch_authorship_notes(repo, remote_name)?,
+        NotesExistence::Found
+    ))
+}
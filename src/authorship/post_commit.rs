@@ -3,8 +3,12 @@ use crate::authorship::authorship_log_serialization::AuthorshipLog;
 use crate::authorship::ignore::{
     build_ignore_matcher, effective_ignore_patterns, should_ignore_file_with_matcher,
 };
+use crate::authorship::move_detection::{
+    attest_copied_files, attest_moved_lines, attest_reverted_lines, reverted_commit,
+};
 use crate::authorship::prompt_utils::{PromptUpdateResult, update_prompt_from_tool};
 use crate::authorship::secrets::{redact_secrets_from_prompts, strip_prompt_messages};
+use crate::authorship::signing::sign_with_configured_key;
 use crate::authorship::stats::{stats_for_commit_stats, write_stats_to_terminal};
 use crate::authorship::virtual_attribution::VirtualAttributions;
 use crate::authorship::working_log::{Checkpoint, CheckpointKind, WorkingLogEntry};
@@ -25,12 +29,163 @@ const STATS_SKIP_MAX_ADDED_LINES: usize = 6000;
 const STATS_SKIP_MAX_FILES_WITH_ADDITIONS: usize = 200;
 
 #[derive(Debug, Clone, Copy)]
-struct StatsCostEstimate {
+pub(crate) struct StatsCostEstimate {
     files_with_additions: usize,
     added_lines: usize,
     hunk_ranges: usize,
 }
 
+impl StatsCostEstimate {
+    /// Estimate from the lines a commit adds to each file, already filtered by ignore patterns
+    pub(crate) fn from_added_lines(added_lines_by_file: &HashMap<String, Vec<u32>>) -> Self {
+        let mut estimate = StatsCostEstimate {
+            files_with_additions: 0,
+            added_lines: 0,
+            hunk_ranges: 0,
+        };
+        for lines in added_lines_by_file.values() {
+            if lines.is_empty() {
+                continue;
+            }
+            estimate.files_with_additions += 1;
+            estimate.added_lines += lines.len();
+            estimate.hunk_ranges += count_line_ranges(lines);
+        }
+        estimate
+    }
+}
+
+/// Attest lines of `commit_sha` that earlier prompts wrote and the commit only moved, copied
+/// or restored with a revert. Failures are logged and leave the log as it was.
+pub fn attest_carried_over_lines(
+    repo: &Repository,
+    parent_sha: &str,
+    commit_sha: &str,
+    authorship_log: &mut AuthorshipLog,
+) {
+    if parent_sha == "initial" || !should_attest_carried_over_lines(repo, parent_sha, commit_sha) {
+        return;
+    }
+
+    // Lines moved within a file keep the prompt that wrote them instead of turning human
+    if let Err(e) = attest_moved_lines(repo, parent_sha, commit_sha, authorship_log) {
+        debug_log(&format!("[Warning] Failed to attest moved lines: {}", e));
+    }
+
+    // Unchanged lines of a copied file keep the prompt that wrote them in the source file
+    if let Err(e) = attest_copied_files(repo, parent_sha, commit_sha, authorship_log) {
+        debug_log(&format!("[Warning] Failed to attest copied files: {}", e));
+    }
+
+    // A revert restores lines the reverted commit deleted; they keep the prompt that wrote them
+    if let Ok(body) = repo
+        .find_commit(commit_sha.to_string())
+        .and_then(|commit| commit.body())
+        && let Some(reverted_sha) = reverted_commit(&body)
+        && let Err(e) =
+            attest_reverted_lines(repo, &reverted_sha, parent_sha, commit_sha, authorship_log)
+    {
+        debug_log(&format!("[Warning] Failed to attest reverted lines: {}", e));
+    }
+}
+
+/// Carrying attestations over diffs and blames the commit's changes, so it only runs with the
+/// `inter_commit_move` feature flag and for commits small enough for post-commit stats.
+fn should_attest_carried_over_lines(repo: &Repository, parent_sha: &str, commit_sha: &str) -> bool {
+    if !Config::get().get_feature_flags().inter_commit_move {
+        return false;
+    }
+    let ignore_patterns = effective_ignore_patterns(repo, &[], &[]);
+    match estimate_stats_cost(repo, parent_sha, commit_sha, &ignore_patterns) {
+        Ok(estimate) if !should_skip_expensive_post_commit_stats(&estimate) => true,
+        Ok(estimate) => {
+            debug_log(&format!(
+                "Skipping carried-over attestations for large commit {} (files_with_additions={}, added_lines={}, hunks={})",
+                commit_sha,
+                estimate.files_with_additions,
+                estimate.added_lines,
+                estimate.hunk_ranges
+            ));
+            false
+        }
+        Err(e) => {
+            debug_log(&format!(
+                "[Warning] Failed to estimate carried-over attestation cost: {}",
+                e
+            ));
+            false
+        }
+    }
+}
+
+/// Drop the attestations of files the repository's ignore patterns exclude (lockfiles,
+/// generated files, `.git-ai-ignore` entries), so the note only attests files stats count.
+pub fn drop_ignored_attestations(repo: &Repository, authorship_log: &mut AuthorshipLog) {
+    let matcher = build_ignore_matcher(&effective_ignore_patterns(repo, &[], &[]));
+    authorship_log
+        .attestations
+        .retain(|attestation| !should_ignore_file_with_matcher(&attestation.file_path, &matcher));
+}
+
+/// Keep, redact or upload the prompt messages in `authorship_log` as the repository's
+/// effective prompt storage mode asks, before the log is written to a note.
+pub fn apply_prompt_storage_mode(repo: &Repository, authorship_log: &mut AuthorshipLog) {
+    // Handle prompts based on effective prompt storage mode for this repository
+    // The effective mode considers include/exclude lists and fallback settings
+    let effective_storage = Config::get().effective_prompt_storage(&Some(repo.clone()));
+
+    match effective_storage {
+        PromptStorageMode::Local => {
+            // Local only: strip all messages from notes (they stay in sqlite only)
+            strip_prompt_messages(&mut authorship_log.metadata.prompts);
+        }
+        PromptStorageMode::Notes => {
+            // Store in notes: redact secrets but keep messages in notes
+            let count = redact_secrets_from_prompts(&mut authorship_log.metadata.prompts);
+            if count > 0 {
+                debug_log(&format!("Redacted {} secrets from prompts", count));
+            }
+        }
+        PromptStorageMode::Default => {
+            // "default" - attempt CAS upload, NEVER keep messages in notes
+            // Check conditions for CAS upload:
+            // - user is logged in OR using custom API URL
+            let context = ApiContext::new(None);
+            let client = ApiClient::new(context);
+            let using_custom_api =
+                Config::get().api_base_url() != crate::config::DEFAULT_API_BASE_URL;
+            let should_enqueue_cas = client.is_logged_in() || using_custom_api;
+
+            if should_enqueue_cas {
+                // Redact secrets before uploading to CAS
+                let redaction_count =
+                    redact_secrets_from_prompts(&mut authorship_log.metadata.prompts);
+                if redaction_count > 0 {
+                    debug_log(&format!(
+                        "Redacted {} secrets from prompts before CAS upload",
+                        redaction_count
+                    ));
+                }
+
+                if let Err(e) =
+                    enqueue_prompt_messages_to_cas(repo, &mut authorship_log.metadata.prompts)
+                {
+                    debug_log(&format!(
+                        "[Warning] Failed to enqueue prompt messages to CAS: {}",
+                        e
+                    ));
+                    // Enqueue failed - still strip messages (never keep in notes for "default")
+                    strip_prompt_messages(&mut authorship_log.metadata.prompts);
+                }
+                // Success: enqueue function already cleared messages
+            } else {
+                // Not enqueueing - strip messages (never keep in notes for "default")
+                strip_prompt_messages(&mut authorship_log.metadata.prompts);
+            }
+        }
+    }
+}
+
 fn checkpoint_entry_requires_post_processing(
     checkpoint: &Checkpoint,
     entry: &WorkingLogEntry,
@@ -132,66 +287,21 @@ pub fn post_commit(
             Some(&pathspecs),
         )?;
 
-    authorship_log.metadata.base_commit_sha = commit_sha.clone();
+    attest_carried_over_lines(repo, &parent_sha, &commit_sha, &mut authorship_log);
+    drop_ignored_attestations(repo, &mut authorship_log);
 
-    // Handle prompts based on effective prompt storage mode for this repository
-    // The effective mode considers include/exclude lists and fallback settings
-    let effective_storage = Config::get().effective_prompt_storage(&Some(repo.clone()));
-
-    match effective_storage {
-        PromptStorageMode::Local => {
-            // Local only: strip all messages from notes (they stay in sqlite only)
-            strip_prompt_messages(&mut authorship_log.metadata.prompts);
-        }
-        PromptStorageMode::Notes => {
-            // Store in notes: redact secrets but keep messages in notes
-            let count = redact_secrets_from_prompts(&mut authorship_log.metadata.prompts);
-            if count > 0 {
-                debug_log(&format!("Redacted {} secrets from prompts", count));
-            }
-        }
-        PromptStorageMode::Default => {
-            // "default" - attempt CAS upload, NEVER keep messages in notes
-            // Check conditions for CAS upload:
-            // - user is logged in OR using custom API URL
-            let context = ApiContext::new(None);
-            let client = ApiClient::new(context);
-            let using_custom_api =
-                Config::get().api_base_url() != crate::config::DEFAULT_API_BASE_URL;
-            let should_enqueue_cas = client.is_logged_in() || using_custom_api;
+    authorship_log.metadata.base_commit_sha = commit_sha.clone();
 
-            if should_enqueue_cas {
-                // Redact secrets before uploading to CAS
-                let redaction_count =
-                    redact_secrets_from_prompts(&mut authorship_log.metadata.prompts);
-                if redaction_count > 0 {
-                    debug_log(&format!(
-                        "Redacted {} secrets from prompts before CAS upload",
-                        redaction_count
-                    ));
-                }
+    apply_prompt_storage_mode(repo, &mut authorship_log);
 
-                if let Err(e) =
-                    enqueue_prompt_messages_to_cas(repo, &mut authorship_log.metadata.prompts)
-                {
-                    debug_log(&format!(
-                        "[Warning] Failed to enqueue prompt messages to CAS: {}",
-                        e
-                    ));
-                    // Enqueue failed - still strip messages (never keep in notes for "default")
-                    strip_prompt_messages(&mut authorship_log.metadata.prompts);
-                }
-                // Success: enqueue function already cleared messages
-            } else {
-                // Not enqueueing - strip messages (never keep in notes for "default")
-                strip_prompt_messages(&mut authorship_log.metadata.prompts);
-            }
-        }
+    // Sign last so the signature covers the final attestations
+    if let Err(e) = sign_with_configured_key(&mut authorship_log) {
+        eprintln!("Warning: Failed to sign authorship log: {}", e);
     }
 
     // Serialize the authorship log
     let authorship_json = authorship_log
-        .serialize_to_string()
+        .serialize_for_storage()
         .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
 
     notes_add(repo, &commit_sha, &authorship_json)?;
@@ -297,7 +407,7 @@ enum StatsSkipReason {
     Expensive(StatsCostEstimate),
 }
 
-fn should_skip_expensive_post_commit_stats(estimate: &StatsCostEstimate) -> bool {
+pub(crate) fn should_skip_expensive_post_commit_stats(estimate: &StatsCostEstimate) -> bool {
     estimate.hunk_ranges >= STATS_SKIP_MAX_HUNKS
         || estimate.added_lines >= STATS_SKIP_MAX_ADDED_LINES
         || estimate.files_with_additions >= STATS_SKIP_MAX_FILES_WITH_ADDITIONS
@@ -314,27 +424,7 @@ fn estimate_stats_cost(
     added_lines_by_file
         .retain(|file_path, _| !should_ignore_file_with_matcher(file_path, &ignore_matcher));
 
-    let files_with_additions = added_lines_by_file
-        .values()
-        .filter(|lines| !lines.is_empty())
-        .count();
-
-    let mut added_lines = 0usize;
-    let mut hunk_ranges = 0usize;
-
-    for (_file, lines) in added_lines_by_file {
-        if lines.is_empty() {
-            continue;
-        }
-        added_lines += lines.len();
-        hunk_ranges += count_line_ranges(&lines);
-    }
-
-    Ok(StatsCostEstimate {
-        files_with_additions,
-        added_lines,
-        hunk_ranges,
-    })
+    Ok(StatsCostEstimate::from_added_lines(&added_lines_by_file))
 }
 
 fn count_line_ranges(lines: &[u32]) -> usize {
@@ -866,4 +956,33 @@ mod tests {
             "File path should be the UTF-8 filename"
         );
     }
+
+    #[test]
+    fn test_post_commit_drops_attestations_of_ignored_files() {
+        let tmp_repo = TmpRepo::new().unwrap();
+        tmp_repo.write_file("README.md", "# Test\n", true).unwrap();
+        tmp_repo
+            .trigger_checkpoint_with_author("test_user")
+            .unwrap();
+        tmp_repo.commit_with_message("Initial commit").unwrap();
+
+        tmp_repo
+            .write_file("src/lib.rs", "pub fn answer() -> u32 { 42 }\n", true)
+            .unwrap();
+        tmp_repo
+            .write_file("Cargo.lock", "# lockfile\n", true)
+            .unwrap();
+        tmp_repo
+            .trigger_checkpoint_with_ai("mock_ai", None, None)
+            .unwrap();
+
+        let authorship_log = tmp_repo.commit_with_message("Add lib").unwrap();
+
+        let attested: Vec<&str> = authorship_log
+            .attestations
+            .iter()
+            .map(|attestation| attestation.file_path.as_str())
+            .collect();
+        assert_eq!(attested, vec!["src/lib.rs"]);
+    }
 }
@@ -636,6 +636,8 @@ mod tests {
             accepted_lines: 8,
             overriden_lines: 2,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         }
     }
 
@@ -7,8 +7,9 @@ use serde::Serialize;
 use crate::authorship::diff_ai_accepted::diff_ai_accepted_stats;
 use crate::authorship::ignore::{build_ignore_matcher, should_ignore_file_with_matcher};
 use crate::authorship::stats::{CommitStats, stats_for_commit_stats, stats_from_authorship_log};
+use crate::authorship::stats_profile::{self, StatsPhase};
 use crate::error::GitAiError;
-use crate::git::refs::{CommitAuthorship, get_commits_with_notes_from_list};
+use crate::git::refs::{CommitAuthorship, get_commits_with_notes_from_list_with_progress};
 use crate::git::repository::{CommitRange, InternalGitProfile, Repository, exec_git_with_profile};
 use crate::utils::debug_log;
 
@@ -44,6 +45,23 @@ pub fn range_authorship(
     commit_range: CommitRange,
     pre_fetch_contents: bool,
     ignore_patterns: &[String],
+) -> Result<RangeAuthorshipStats, GitAiError> {
+    range_authorship_with_progress(
+        commit_range,
+        pre_fetch_contents,
+        ignore_patterns,
+        1,
+        &|_, _| {},
+    )
+}
+
+/// Like `range_authorship`, calling `progress(done, total)` as each commit in the range is read.
+pub fn range_authorship_with_progress(
+    commit_range: CommitRange,
+    pre_fetch_contents: bool,
+    ignore_patterns: &[String],
+    jobs: usize,
+    progress: &(dyn Fn(usize, usize) + Sync),
 ) -> Result<RangeAuthorshipStats, GitAiError> {
     commit_range.is_valid()?;
 
@@ -111,7 +129,9 @@ pub fn range_authorship(
         .into_iter()
         .map(|c| c.id().to_string())
         .collect();
-    let commit_authorship = get_commits_with_notes_from_list(repository, &commit_shas)?;
+    let commit_authorship = stats_profile::time(StatsPhase::Notes, || {
+        get_commits_with_notes_from_list_with_progress(repository, &commit_shas, jobs, progress)
+    })?;
 
     // Calculate range stats - now just pass start, end, and commits
     let range_stats =
@@ -205,6 +225,9 @@ fn create_authorship_log_for_range(
                     ),
                     base_commit_sha: end_sha.to_string(),
                     prompts: std::collections::BTreeMap::new(),
+                    signature: None,
+                    version:
+                        crate::authorship::authorship_log_serialization::AUTHORSHIP_LOG_FORMAT_VERSION,
                 },
             },
         );
@@ -405,25 +428,33 @@ fn calculate_range_stats_direct(
 
     // Step 1: Get git diff stats between start and end
     let (git_diff_added_lines, git_diff_deleted_lines) =
-        get_git_diff_stats_for_range(repo, &start_sha, &end_sha, ignore_patterns)?;
-
-    let diff_ai_stats = diff_ai_accepted_stats(repo, &start_sha, &end_sha, None, ignore_patterns)?;
-
-    // Step 2: Create in-memory authorship log for the range, filtered to only commits in the range
-    let commit_shas = commit_range.clone().all_commits();
-    let authorship_log =
-        create_authorship_log_for_range(repo, &start_sha, &end_sha, &commit_shas, ignore_patterns)?;
-
-    // Step 3: Calculate stats from the authorship log
-    let stats = stats_from_authorship_log(
-        Some(&authorship_log),
-        git_diff_added_lines,
-        git_diff_deleted_lines,
-        diff_ai_stats.total_ai_accepted,
-        &diff_ai_stats.per_tool_model,
-    );
+        stats_profile::time(StatsPhase::Diff, || {
+            get_git_diff_stats_for_range(repo, &start_sha, &end_sha, ignore_patterns)
+        })?;
 
-    Ok(stats)
+    stats_profile::time(StatsPhase::AttributionMatch, || {
+        let diff_ai_stats =
+            diff_ai_accepted_stats(repo, &start_sha, &end_sha, None, ignore_patterns)?;
+
+        // Step 2: Create in-memory authorship log for the range, filtered to only commits in the range
+        let commit_shas = commit_range.clone().all_commits();
+        let authorship_log = create_authorship_log_for_range(
+            repo,
+            &start_sha,
+            &end_sha,
+            &commit_shas,
+            ignore_patterns,
+        )?;
+
+        // Step 3: Calculate stats from the authorship log
+        Ok(stats_from_authorship_log(
+            Some(&authorship_log),
+            git_diff_added_lines,
+            git_diff_deleted_lines,
+            diff_ai_stats.total_ai_accepted,
+            &diff_ai_stats.per_tool_model,
+        ))
+    })
 }
 
 pub fn print_range_authorship_stats(stats: &RangeAuthorshipStats) {
@@ -1,5 +1,6 @@
 use crate::authorship::authorship_log_serialization::AuthorshipLog;
 use crate::authorship::post_commit;
+use crate::authorship::signing::sign_with_configured_key;
 use crate::error::GitAiError;
 use crate::git::authorship_traversal::{
     commits_have_authorship_notes, load_ai_touched_files_for_commits,
@@ -329,14 +330,12 @@ pub fn rewrite_authorship_after_squash_or_rebase(
             debug_log(
                 "No AI-touched files in merge, but notes exist in source commits; writing empty authorship log",
             );
-            if let Some(authorship_log) = build_metadata_only_authorship_log_from_source_notes(
+            if let Some(mut authorship_log) = build_metadata_only_authorship_log_from_source_notes(
                 repo,
                 &source_commits,
                 merge_commit_sha,
             )? {
-                let authorship_json = authorship_log.serialize_to_string().map_err(|_| {
-                    GitAiError::Generic("Failed to serialize authorship log".to_string())
-                })?;
+                let authorship_json = serialize_rewritten_log(&mut authorship_log)?;
                 crate::git::refs::notes_add(repo, merge_commit_sha, &authorship_json)?;
             }
         } else {
@@ -417,9 +416,7 @@ pub fn rewrite_authorship_after_squash_or_rebase(
     ));
 
     // Step 7: Save authorship log to git notes
-    let authorship_json = authorship_log
-        .serialize_to_string()
-        .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
+    let authorship_json = serialize_rewritten_log(&mut authorship_log)?;
 
     crate::git::refs::notes_add(repo, merge_commit_sha, &authorship_json)?;
 
@@ -718,9 +715,7 @@ pub fn rewrite_authorship_after_rebase_v2(
         let computed_note_has_payload = !current_authorship_log.attestations.is_empty()
             || !current_authorship_log.metadata.prompts.is_empty();
         let authorship_json = if computed_note_has_payload {
-            Some(current_authorship_log.serialize_to_string().map_err(|_| {
-                GitAiError::Generic("Failed to serialize authorship log".to_string())
-            })?)
+            Some(serialize_rewritten_log(&mut current_authorship_log)?)
         } else {
             if !original_note_content_loaded {
                 original_note_content_by_new_commit =
@@ -931,9 +926,7 @@ pub fn rewrite_authorship_after_cherry_pick(
         let computed_note_has_payload =
             !authorship_log.attestations.is_empty() || !authorship_log.metadata.prompts.is_empty();
         let authorship_json = if computed_note_has_payload {
-            authorship_log.serialize_to_string().map_err(|_| {
-                GitAiError::Generic("Failed to serialize authorship log".to_string())
-            })?
+            serialize_rewritten_log(&mut authorship_log)?
         } else {
             if !source_note_content_loaded {
                 source_note_content_by_new_commit =
@@ -943,9 +936,7 @@ pub fn rewrite_authorship_after_cherry_pick(
             if let Some(raw_note) = source_note_content_by_new_commit.get(new_commit) {
                 remap_note_content_for_target_commit(raw_note, new_commit)
             } else {
-                authorship_log.serialize_to_string().map_err(|_| {
-                    GitAiError::Generic("Failed to serialize authorship log".to_string())
-                })?
+                serialize_rewritten_log(&mut authorship_log)?
             }
         };
 
@@ -1054,7 +1045,7 @@ fn collect_changed_file_contents_from_diff(
     Ok((changed_files, file_contents))
 }
 
-fn batch_read_blob_contents(
+pub fn batch_read_blob_contents(
     repo: &Repository,
     blob_oids: &[String],
 ) -> Result<HashMap<String, String>, GitAiError> {
@@ -1436,6 +1427,54 @@ fn collect_changed_file_contents_for_commit_pairs(
     Ok(result)
 }
 
+/// Line `git cherry-pick -x` appends to the picked commit's message.
+const CHERRY_PICK_SOURCE_PREFIX: &str = "(cherry picked from commit ";
+
+/// Source commit recorded by `git cherry-pick -x`. When a picked commit is picked again the
+/// message carries one line per pick, and the last one names the most recent source.
+pub fn cherry_pick_source_from_message(message: &str) -> Option<String> {
+    message
+        .lines()
+        .rev()
+        .filter_map(|line| line.trim().strip_prefix(CHERRY_PICK_SOURCE_PREFIX))
+        .filter_map(|rest| rest.strip_suffix(')'))
+        .find(|sha| !sha.is_empty() && sha.chars().all(|c| c.is_ascii_hexdigit()))
+        .map(str::to_string)
+}
+
+/// Recover authorship for a commit that was cherry-picked outside git-ai's hooks (e.g. from a
+/// GUI) using the source recorded by `cherry-pick -x`. The note is written so later reads find it.
+pub fn recover_cherry_pick_authorship(
+    repo: &Repository,
+    commit_sha: &str,
+) -> Result<Option<AuthorshipLog>, GitAiError> {
+    let commit = repo.find_commit(commit_sha.to_string())?;
+    let Some(source) = cherry_pick_source_from_message(&commit.body()?) else {
+        return Ok(None);
+    };
+    let Ok(source_commit) = repo
+        .revparse_single(&source)
+        .and_then(|o| o.peel_to_commit())
+    else {
+        debug_log(&format!(
+            "Cherry-pick source {} of {} is not in this repository",
+            source, commit_sha
+        ));
+        return Ok(None);
+    };
+    let source_sha = source_commit.id();
+    if get_reference_as_authorship_log_v3(repo, &source_sha).is_err() {
+        return Ok(None);
+    }
+
+    debug_log(&format!(
+        "Recovering authorship for {} from cherry-pick source {}",
+        commit_sha, source_sha
+    ));
+    rewrite_authorship_after_cherry_pick(repo, &[source_sha], &[commit_sha.to_string()], "")?;
+    Ok(get_reference_as_authorship_log_v3(repo, commit_sha).ok())
+}
+
 pub fn rewrite_authorship_after_commit_amend(
     repo: &Repository,
     original_commit: &str,
@@ -1503,9 +1542,7 @@ pub fn rewrite_authorship_after_commit_amend(
     authorship_log.metadata.base_commit_sha = amended_commit.to_string();
 
     // Save authorship log
-    let authorship_json = authorship_log
-        .serialize_to_string()
-        .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
+    let authorship_json = serialize_rewritten_log(&mut authorship_log)?;
     crate::git::refs::notes_add(repo, amended_commit, &authorship_json)?;
 
     // Save INITIAL file for uncommitted attributions
@@ -1519,9 +1556,25 @@ pub fn rewrite_authorship_after_commit_amend(
     repo.storage
         .delete_working_log_for_base_commit(original_commit)?;
 
+    // The amended commit supersedes the original one, so its log would only be stale. Keep it
+    // while another branch or tag still points at the original commit.
+    if original_commit != amended_commit && !is_reachable_from_any_ref(repo, original_commit)? {
+        crate::git::refs::notes_remove(repo, original_commit)?;
+    }
+
     Ok(authorship_log)
 }
 
+fn is_reachable_from_any_ref(repo: &Repository, commit_sha: &str) -> Result<bool, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("for-each-ref".to_string());
+    args.push("--count=1".to_string());
+    args.push("--format=%(refname)".to_string());
+    args.push(format!("--contains={}", commit_sha));
+    let output = exec_git(&args)?;
+    Ok(!String::from_utf8(output.stdout)?.trim().is_empty())
+}
+
 pub fn walk_commits_to_base(
     repository: &Repository,
     head: &str,
@@ -1854,14 +1907,29 @@ fn load_note_contents_for_commit_pairs(
     Ok(source_note_content_by_target_commit)
 }
 
+/// Serialize a log written for a rewritten commit. Its signature covered the original commit,
+/// so it is re-signed with the configured key, or dropped when signing is not configured.
+fn serialize_rewritten_log(authorship_log: &mut AuthorshipLog) -> Result<String, GitAiError> {
+    authorship_log.metadata.signature = None;
+    if let Err(e) = sign_with_configured_key(authorship_log) {
+        eprintln!("Warning: Failed to sign authorship log: {}", e);
+    }
+    authorship_log
+        .serialize_for_storage()
+        .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))
+}
+
 fn remap_note_content_for_target_commit(note_content: &str, target_commit: &str) -> String {
-    if let Some(remapped_note) = try_remap_base_commit_sha_field(note_content, target_commit) {
+    // A signature covers the base commit, so signed notes are re-signed rather than patched
+    if !note_content.contains("\"signature\"")
+        && let Some(remapped_note) = try_remap_base_commit_sha_field(note_content, target_commit)
+    {
         return remapped_note;
     }
 
     if let Ok(mut authorship_log) = AuthorshipLog::deserialize_from_string(note_content) {
         authorship_log.metadata.base_commit_sha = target_commit.to_string();
-        if let Ok(serialized) = authorship_log.serialize_to_string() {
+        if let Ok(serialized) = serialize_rewritten_log(&mut authorship_log) {
             return serialized;
         }
     }
@@ -2901,8 +2969,9 @@ fn transform_attributions_to_final_state(
 #[cfg(test)]
 mod tests {
     use super::{
-        collect_changed_file_contents_from_diff, get_pathspecs_from_commits,
-        parse_cat_file_batch_output_with_oids, transform_attributions_to_final_state,
+        cherry_pick_source_from_message, collect_changed_file_contents_from_diff,
+        get_pathspecs_from_commits, parse_cat_file_batch_output_with_oids,
+        remap_note_content_for_target_commit, transform_attributions_to_final_state,
         try_fast_path_rebase_note_remap, walk_commits_to_base,
     };
     use crate::authorship::attribution_tracker::{Attribution, LineAttribution};
@@ -3380,6 +3449,8 @@ mod tests {
                 accepted_lines: 5,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -3568,6 +3639,8 @@ mod tests {
                 accepted_lines: 13,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
         prompts.insert(
@@ -3585,6 +3658,8 @@ mod tests {
                 accepted_lines: 6,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -3691,6 +3766,8 @@ mod tests {
                 accepted_lines: 3,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -3830,6 +3907,8 @@ mod tests {
                 accepted_lines: 4,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
         let old_wl = repo
@@ -3952,6 +4031,8 @@ mod tests {
                 accepted_lines: 8,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
         let v1_wl = repo
@@ -4119,6 +4200,8 @@ mod tests {
                 accepted_lines: 13,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
         prompts.insert(
@@ -4136,6 +4219,8 @@ mod tests {
                 accepted_lines: 16,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -4223,4 +4308,44 @@ mod tests {
         assert_eq!(copilot_prompt.agent_id.tool, "copilot");
         assert_eq!(copilot_prompt.total_additions, 16);
     }
+
+    #[test]
+    fn test_cherry_pick_source_from_message() {
+        assert_eq!(
+            cherry_pick_source_from_message(
+                "Add feature\n\n(cherry picked from commit 1a2b3c4d5e6f)"
+            ),
+            Some("1a2b3c4d5e6f".to_string())
+        );
+        assert_eq!(
+            cherry_pick_source_from_message(
+                "(cherry picked from commit aaaa)\n(cherry picked from commit bbbb)"
+            ),
+            Some("bbbb".to_string())
+        );
+        assert_eq!(cherry_pick_source_from_message("Add feature"), None);
+        assert_eq!(
+            cherry_pick_source_from_message("(cherry picked from commit not-a-sha)"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_remap_drops_stale_signature_without_signing_key() {
+        let mut log = AuthorshipLog::new();
+        log.metadata.base_commit_sha = "a".repeat(40);
+        log.metadata.signature = Some("ed25519:00".to_string());
+        let note = log
+            .serialize_to_string()
+            .expect("serialize authorship note");
+
+        let target = "b".repeat(40);
+        let remapped = AuthorshipLog::deserialize_from_string(
+            &remap_note_content_for_target_commit(&note, &target),
+        )
+        .expect("parse remapped note");
+
+        assert_eq!(remapped.metadata.base_commit_sha, target);
+        assert_eq!(remapped.metadata.signature, None);
+    }
 }
@@ -0,0 +1,350 @@
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::config::Config;
+use crate::error::GitAiError;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Prefix of the signature stored in `metadata.signature`, so other algorithms can be added later
+const SIGNATURE_PREFIX: &str = "ed25519:";
+
+/// Identifies the layout of `signing_payload`. Bump it only together with a way to keep
+/// verifying logs signed under the previous layout.
+const SIGNING_PAYLOAD_VERSION: &str = "git-ai/authorship-signature/1";
+
+/// Result of checking the signature stored on an authorship log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureCheck {
+    Valid,
+    Unsigned,
+    Invalid(String),
+}
+
+/// The bytes covered by the signature: a canonical JSON document of the commit, the attested
+/// lines and characters of each file, and who each prompt belongs to.
+///
+/// It is built from the parsed log rather than from the note text, so it does not depend on
+/// how the log is laid out in storage (text or binary, file order, shared entry blocks, format
+/// version). Transcripts and prompt counters are left out because they are uploaded or
+/// trimmed after the commit is written. Do not change this layout: logs signed by earlier
+/// versions must keep verifying.
+fn signing_payload(log: &AuthorshipLog) -> Result<String, GitAiError> {
+    let mut files: Vec<_> = log.attestations.iter().collect();
+    files.sort_by(|a, b| a.file_path.as_bytes().cmp(b.file_path.as_bytes()));
+
+    let files: Vec<serde_json::Value> = files
+        .into_iter()
+        .map(|file| {
+            let mut entries: Vec<serde_json::Value> = file
+                .entries
+                .iter()
+                .map(|entry| {
+                    let lines: BTreeSet<u32> = entry
+                        .line_ranges
+                        .iter()
+                        .flat_map(|range| range.expand())
+                        .collect();
+                    let mut chars: Vec<[u32; 3]> = entry
+                        .char_ranges
+                        .iter()
+                        .map(|range| [range.line, range.start, range.end])
+                        .collect();
+                    chars.sort_unstable();
+                    json!({
+                        "prompt": entry.hash,
+                        "lines": line_runs(&lines),
+                        "chars": chars,
+                    })
+                })
+                .collect();
+            entries.sort_by_key(|entry| entry.to_string());
+            json!({
+                "path": file.file_path,
+                "binary": file.is_binary,
+                "symlink": file.is_symlink,
+                "entries": entries,
+            })
+        })
+        .collect();
+
+    let prompts: Vec<serde_json::Value> = log
+        .metadata
+        .prompts
+        .iter()
+        .map(|(hash, prompt)| {
+            json!({
+                "hash": hash,
+                "tool": prompt.agent_id.tool,
+                "id": prompt.agent_id.id,
+                "model": prompt.agent_id.model,
+                "human_author": prompt.human_author,
+            })
+        })
+        .collect();
+
+    let payload = json!({
+        "payload": SIGNING_PAYLOAD_VERSION,
+        "base_commit_sha": log.metadata.base_commit_sha,
+        "files": files,
+        "prompts": prompts,
+    });
+    serde_json_canonicalizer::to_string(&payload).map_err(|e| {
+        GitAiError::Generic(format!("Failed to build authorship signing payload: {}", e))
+    })
+}
+
+/// Sorted line numbers as inclusive `[start, end]` runs.
+fn line_runs(lines: &BTreeSet<u32>) -> Vec<[u32; 2]> {
+    let mut runs: Vec<[u32; 2]> = Vec::new();
+    for &line in lines {
+        match runs.last_mut() {
+            Some(run) if run[1] + 1 == line => run[1] = line,
+            _ => runs.push([line, line]),
+        }
+    }
+    runs
+}
+
+/// Sign the log in place, replacing any existing signature.
+pub fn sign_authorship_log(log: &mut AuthorshipLog, key: &SigningKey) -> Result<(), GitAiError> {
+    let payload = signing_payload(log)?;
+    let signature = key.sign(payload.as_bytes());
+    log.metadata.signature = Some(format!(
+        "{}{}",
+        SIGNATURE_PREFIX,
+        encode_hex(&signature.to_bytes())
+    ));
+    Ok(())
+}
+
+pub fn verify_authorship_log_signature(
+    log: &AuthorshipLog,
+    key: &VerifyingKey,
+) -> Result<SignatureCheck, GitAiError> {
+    let Some(stored) = log.metadata.signature.as_deref() else {
+        return Ok(SignatureCheck::Unsigned);
+    };
+
+    let Some(signature_hex) = stored.strip_prefix(SIGNATURE_PREFIX) else {
+        return Ok(SignatureCheck::Invalid(
+            "unsupported signature algorithm".to_string(),
+        ));
+    };
+    let Some(signature_bytes) =
+        decode_hex(signature_hex).and_then(|b| <[u8; 64]>::try_from(b).ok())
+    else {
+        return Ok(SignatureCheck::Invalid("malformed signature".to_string()));
+    };
+
+    let payload = signing_payload(log)?;
+    match key.verify_strict(payload.as_bytes(), &Signature::from_bytes(&signature_bytes)) {
+        Ok(()) => Ok(SignatureCheck::Valid),
+        Err(_) => Ok(SignatureCheck::Invalid(
+            "signature does not match the authorship log".to_string(),
+        )),
+    }
+}
+
+/// Load a signing key from a file containing the hex-encoded 32-byte Ed25519 seed.
+pub fn load_signing_key(path: &Path) -> Result<SigningKey, GitAiError> {
+    Ok(SigningKey::from_bytes(&read_hex_key(path)?))
+}
+
+/// Load a verifying key from a file containing the hex-encoded 32-byte Ed25519 public key.
+pub fn load_verifying_key(path: &Path) -> Result<VerifyingKey, GitAiError> {
+    VerifyingKey::from_bytes(&read_hex_key(path)?).map_err(|e| {
+        GitAiError::Generic(format!("Invalid public key in {}: {}", path.display(), e))
+    })
+}
+
+/// Sign the log with the key from `authorship_signing_key_path`. Returns false when signing
+/// is not configured.
+pub fn sign_with_configured_key(log: &mut AuthorshipLog) -> Result<bool, GitAiError> {
+    let Some(path) = Config::get().authorship_signing_key_path() else {
+        return Ok(false);
+    };
+    let key = load_signing_key(Path::new(path))?;
+    sign_authorship_log(log, &key)?;
+    Ok(true)
+}
+
+fn read_hex_key(path: &Path) -> Result<[u8; 32], GitAiError> {
+    let contents = std::fs::read_to_string(path)?;
+    decode_hex(contents.trim())
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| {
+            GitAiError::Generic(format!(
+                "{} does not contain a hex-encoded 32-byte key",
+                path.display()
+            ))
+        })
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) || !value.is_ascii() {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log::LineRange;
+    use crate::authorship::authorship_log_serialization::{
+        AttestationEntry, FileAttestation, LEGACY_FORMAT_VERSION,
+    };
+
+    fn test_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    fn sample_log() -> AuthorshipLog {
+        let mut log = AuthorshipLog::new();
+        log.metadata.base_commit_sha = "abc123".to_string();
+        let mut file = FileAttestation::new("src/main.rs".to_string());
+        file.add_entry(AttestationEntry::new(
+            "1234567".to_string(),
+            vec![LineRange::Range(1, 4), LineRange::Single(9)],
+        ));
+        log.attestations.push(file);
+        log
+    }
+
+    /// Signed logs are stored as note text, so check them after a serialize/parse round-trip.
+    fn round_trip(log: &AuthorshipLog) -> AuthorshipLog {
+        AuthorshipLog::deserialize_from_string(&log.serialize_to_string().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_signature_round_trip() {
+        let key = test_key();
+        let mut log = sample_log();
+        sign_authorship_log(&mut log, &key).unwrap();
+
+        let stored = round_trip(&log);
+        assert!(
+            stored
+                .metadata
+                .signature
+                .as_deref()
+                .unwrap()
+                .starts_with(SIGNATURE_PREFIX)
+        );
+        assert_eq!(
+            verify_authorship_log_signature(&stored, &key.verifying_key()).unwrap(),
+            SignatureCheck::Valid
+        );
+    }
+
+    #[test]
+    fn test_signature_detects_modified_file_path() {
+        let key = test_key();
+        let mut log = sample_log();
+        sign_authorship_log(&mut log, &key).unwrap();
+
+        let mut tampered = round_trip(&log);
+        tampered.attestations[0].file_path = "src/other.rs".to_string();
+        let tampered = round_trip(&tampered);
+
+        assert!(matches!(
+            verify_authorship_log_signature(&tampered, &key.verifying_key()).unwrap(),
+            SignatureCheck::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn test_signature_survives_format_upgrade_on_read() {
+        let key = test_key();
+        let mut legacy = sample_log();
+        legacy.metadata.version = LEGACY_FORMAT_VERSION;
+        sign_authorship_log(&mut legacy, &key).unwrap();
+        let note = legacy.serialize_to_string().unwrap();
+        assert!(!note.contains("\"version\""));
+
+        let upgraded = AuthorshipLog::deserialize_from_string(&note).unwrap();
+        assert_ne!(upgraded.metadata.version, LEGACY_FORMAT_VERSION);
+        assert_eq!(
+            verify_authorship_log_signature(&upgraded, &key.verifying_key()).unwrap(),
+            SignatureCheck::Valid
+        );
+    }
+
+    #[test]
+    fn test_signature_survives_layout_changes() {
+        let key = test_key();
+        let mut log = sample_log();
+        let mut other = FileAttestation::new("a.rs".to_string());
+        other.add_entry(AttestationEntry::new(
+            "1234567".to_string(),
+            vec![LineRange::Single(2)],
+        ));
+        log.attestations.push(other);
+        sign_authorship_log(&mut log, &key).unwrap();
+
+        // Same attestations in a different order, with ranges split differently
+        let mut relaid = log.clone();
+        relaid.attestations.reverse();
+        let main_rs = relaid
+            .attestations
+            .iter_mut()
+            .find(|file| file.file_path == "src/main.rs")
+            .unwrap();
+        main_rs.entries[0].line_ranges = vec![
+            LineRange::Single(9),
+            LineRange::Range(1, 2),
+            LineRange::Range(3, 4),
+        ];
+        let stored =
+            AuthorshipLog::deserialize_from_binary(&relaid.serialize_to_binary().unwrap()).unwrap();
+
+        assert_eq!(
+            verify_authorship_log_signature(&stored, &key.verifying_key()).unwrap(),
+            SignatureCheck::Valid
+        );
+    }
+
+    #[test]
+    fn test_signature_rejects_wrong_key_and_unsigned_logs() {
+        let mut log = sample_log();
+        assert_eq!(
+            verify_authorship_log_signature(&log, &test_key().verifying_key()).unwrap(),
+            SignatureCheck::Unsigned
+        );
+
+        sign_authorship_log(&mut log, &test_key()).unwrap();
+        let other_key = SigningKey::from_bytes(&[9u8; 32]);
+        assert!(matches!(
+            verify_authorship_log_signature(&log, &other_key.verifying_key()).unwrap(),
+            SignatureCheck::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn test_load_keys_from_hex_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let signing_path = dir.path().join("signing.key");
+        let public_path = dir.path().join("signing.pub");
+        std::fs::write(&signing_path, format!("{}\n", encode_hex(&[7u8; 32]))).unwrap();
+        std::fs::write(
+            &public_path,
+            encode_hex(test_key().verifying_key().as_bytes()),
+        )
+        .unwrap();
+
+        let signing_key = load_signing_key(&signing_path).unwrap();
+        let verifying_key = load_verifying_key(&public_path).unwrap();
+        assert_eq!(signing_key.verifying_key(), verifying_key);
+
+        std::fs::write(&signing_path, "not-a-key").unwrap();
+        assert!(load_signing_key(&signing_path).is_err());
+    }
+}
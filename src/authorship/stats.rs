@@ -1,14 +1,31 @@
-use crate::authorship::authorship_log::LineRange;
-use crate::authorship::ignore::{build_ignore_matcher, should_ignore_file_with_matcher};
+use crate::authorship::authorship_log::{LineRange, PromptRecord, suggested_line_hash};
+use crate::authorship::authorship_log_serialization::{AttestationEntry, AuthorshipLog};
+use crate::authorship::diff_ai_accepted::{diff_deleted_stats, lines_to_ranges};
+use crate::authorship::ignore::{
+    build_ignore_matcher, effective_ignore_patterns, should_ignore_file_with_matcher,
+};
+use crate::authorship::move_detection::copied_prompt_record;
+use crate::authorship::post_commit::{StatsCostEstimate, should_skip_expensive_post_commit_stats};
+use crate::authorship::rebase_authorship::recover_cherry_pick_authorship;
+use crate::authorship::stats_cache::StatsCache;
+use crate::authorship::stats_profile::{self, StatsPhase};
 use crate::authorship::transcript::Message;
+use crate::commands::blame::GitAiBlameOptions;
 use crate::error::GitAiError;
-use crate::git::refs::get_authorship;
-use crate::git::repository::{InternalGitProfile, Repository, exec_git_with_profile};
-use crate::utils::debug_log;
+use crate::git::refs::{get_authorship, note_blob_oids_for_commits};
+use crate::git::repository::{
+    Commit, DiffAlgorithm, GITLINK_MODE, InternalGitProfile, Repository, SYMLINK_MODE,
+    exec_git_with_profile,
+};
+use crate::utils::{debug_log, nfc_path, paint};
+use rayon::prelude::*;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use unicode_width::UnicodeWidthStr;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct ToolModelHeadlineStats {
     #[serde(default)]
     pub ai_additions: u32, // Number of lines committed with AI attribution (full and/or mixed)
@@ -24,7 +41,7 @@ pub struct ToolModelHeadlineStats {
     pub time_waiting_for_ai: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct CommitStats {
     #[serde(default)]
     pub human_additions: u32, // Number of lines committed with human attribution (full and/or mixed)
@@ -46,14 +63,195 @@ pub struct CommitStats {
     pub git_diff_added_lines: u32,
     #[serde(default)]
     pub tool_model_breakdown: BTreeMap<String, ToolModelHeadlineStats>,
+    #[serde(default)]
+    pub ai_by_tool: BTreeMap<String, u32>, // ai_additions grouped by the tool that produced them (sums to ai_additions)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ai_by_session: BTreeMap<String, u32>, // Accepted AI lines grouped by the session recorded on their prompt, "unknown" without one (only computed by `git-ai stats --by-session`)
+    #[serde(default)]
+    pub ai_deletions: u32, // Deleted lines that were last attested as AI-authored (only computed by `git-ai stats` for one commit or with `--breakdowns`)
+    #[serde(default)]
+    pub human_deletions: u32, // Deleted lines that were human-authored or unattested (only computed by `git-ai stats` for one commit or with `--breakdowns`)
+    #[serde(default)]
+    pub ai_chars: u32, // Characters of added lines written by AI, including AI parts of human-edited lines (only computed by `git-ai stats` for one commit or with `--breakdowns`)
+    #[serde(default)]
+    pub human_chars: u32, // Remaining characters of added lines (only computed by `git-ai stats` for one commit or with `--breakdowns`)
+    #[serde(default)]
+    pub ai_accepted_verbatim: u32, // Accepted AI lines committed exactly as the agent suggested them (only computed by `git-ai stats` for one commit or with `--breakdowns`)
+    #[serde(default)]
+    pub ai_accepted_modified: u32, // Accepted AI lines that differ from the agent's suggestion, such as reformatted ones (only computed by `git-ai stats` for one commit or with `--breakdowns`)
+    #[serde(default)]
+    pub ai_percentage: f64, // ai_additions / (ai_additions + human_additions), 0.0 when there are no additions
+    #[serde(default)]
+    pub human_percentage: f64, // human_additions / (ai_additions + human_additions), 0.0 when there are no additions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<FileStats>, // Per-file breakdown, only populated when requested (`--per-file`)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, JsonSchema)]
+pub struct FileStats {
+    pub file_path: String,
+    #[serde(default)]
+    pub human_additions: u32, // Lines added in this file without an AI attestation
+    #[serde(default)]
+    pub ai_additions: u32, // Lines added in this file covered by an AI attestation
+    #[serde(default)]
+    pub ai_accepted: u32, // Mixed lines are tracked per prompt, so this matches ai_additions at file level
+}
+
+impl CommitStats {
+    /// Recompute `ai_percentage` and `human_percentage` from the addition counts.
+    /// Percentages are rounded to two decimals; a commit with no additions reports 0.0 for both.
+    pub fn compute_percentages(&mut self) {
+        let total = self.ai_additions + self.human_additions;
+        self.ai_percentage = rounded_percentage(self.ai_additions, total);
+        self.human_percentage = rounded_percentage(self.human_additions, total);
+    }
+
+    /// Add another commit's counts into this one and refresh the percentages.
+    /// The per-file breakdown is not merged.
+    pub fn accumulate(&mut self, other: &CommitStats) {
+        self.human_additions += other.human_additions;
+        self.mixed_additions += other.mixed_additions;
+        self.ai_additions += other.ai_additions;
+        self.ai_accepted += other.ai_accepted;
+        self.total_ai_additions += other.total_ai_additions;
+        self.total_ai_deletions += other.total_ai_deletions;
+        self.time_waiting_for_ai += other.time_waiting_for_ai;
+        self.git_diff_deleted_lines += other.git_diff_deleted_lines;
+        self.git_diff_added_lines += other.git_diff_added_lines;
+        self.ai_deletions += other.ai_deletions;
+        self.human_deletions += other.human_deletions;
+        self.ai_chars += other.ai_chars;
+        self.human_chars += other.human_chars;
+        self.ai_accepted_verbatim += other.ai_accepted_verbatim;
+        self.ai_accepted_modified += other.ai_accepted_modified;
+
+        for (tool_model, other_tool) in &other.tool_model_breakdown {
+            let tool = self
+                .tool_model_breakdown
+                .entry(tool_model.clone())
+                .or_default();
+            tool.ai_additions += other_tool.ai_additions;
+            tool.mixed_additions += other_tool.mixed_additions;
+            tool.ai_accepted += other_tool.ai_accepted;
+            tool.total_ai_additions += other_tool.total_ai_additions;
+            tool.total_ai_deletions += other_tool.total_ai_deletions;
+            tool.time_waiting_for_ai += other_tool.time_waiting_for_ai;
+        }
+        for (tool, additions) in &other.ai_by_tool {
+            *self.ai_by_tool.entry(tool.clone()).or_default() += additions;
+        }
+        for (session, additions) in &other.ai_by_session {
+            *self.ai_by_session.entry(session.clone()).or_default() += additions;
+        }
+
+        self.compute_percentages();
+    }
+}
+
+/// `a + b` sums two stats the same way `accumulate` does.
+impl std::ops::Add<&CommitStats> for CommitStats {
+    type Output = CommitStats;
+
+    fn add(mut self, other: &CommitStats) -> CommitStats {
+        self.accumulate(other);
+        self
+    }
+}
+
+impl std::ops::Add for CommitStats {
+    type Output = CommitStats;
+
+    fn add(self, other: CommitStats) -> CommitStats {
+        self + &other
+    }
+}
+
+impl std::ops::AddAssign<&CommitStats> for CommitStats {
+    fn add_assign(&mut self, other: &CommitStats) {
+        self.accumulate(other);
+    }
+}
+
+impl std::ops::AddAssign for CommitStats {
+    fn add_assign(&mut self, other: CommitStats) {
+        self.accumulate(&other);
+    }
+}
+
+impl<'a> std::iter::Sum<&'a CommitStats> for CommitStats {
+    fn sum<I: Iterator<Item = &'a CommitStats>>(iter: I) -> CommitStats {
+        iter.fold(CommitStats::default(), |total, stats| total + stats)
+    }
+}
+
+impl std::iter::Sum for CommitStats {
+    fn sum<I: Iterator<Item = CommitStats>>(iter: I) -> CommitStats {
+        iter.fold(CommitStats::default(), |total, stats| total + stats)
+    }
+}
+
+pub(crate) fn rounded_percentage(part: u32, total: u32) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    ((part as f64 / total as f64) * 10_000.0).round() / 100.0
+}
+
+/// Limits that make `git-ai stats` exit nonzero, so CI can fail a change with too much
+/// AI-generated code.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StatsThresholds {
+    /// Fail when `ai_percentage` is above this value
+    pub max_ai_percentage: Option<f64>,
+    /// Fail when fewer human lines than this were added
+    pub min_human_lines: Option<u32>,
+}
+
+impl StatsThresholds {
+    /// Describe each limit the stats break; empty when they all pass.
+    pub fn violations(&self, stats: &CommitStats) -> Vec<String> {
+        let mut violations = Vec::new();
+        if let Some(max) = self.max_ai_percentage
+            && stats.ai_percentage > max
+        {
+            violations.push(format!(
+                "AI percentage {}% exceeds the maximum of {}%",
+                stats.ai_percentage, max
+            ));
+        }
+        if let Some(min) = self.min_human_lines
+            && stats.human_additions < min
+        {
+            violations.push(format!(
+                "{} human lines added, below the minimum of {}",
+                stats.human_additions, min
+            ));
+        }
+        violations
+    }
+}
+
+/// Output format for `git-ai stats`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatsOutputFormat {
+    #[default]
+    Terminal,
+    Json,
+    Csv,
+    /// One JSON object per line, written as each commit's stats are ready
+    Ndjson,
+    /// Bordered table with one row per commit, or per file with `--per-file`
+    Table,
 }
 
 pub fn stats_command(
     repo: &Repository,
     commit_sha: Option<&str>,
-    json: bool,
+    format: StatsOutputFormat,
+    options: CommitStatsOptions,
     ignore_patterns: &[String],
-) -> Result<(), GitAiError> {
+) -> Result<CommitStats, GitAiError> {
     let (target, refname) = if let Some(sha) = commit_sha {
         // Validate that the commit exists using revparse_single
         match repo.revparse_single(sha) {
@@ -80,18 +278,260 @@ pub fn stats_command(
         target, refname
     ));
 
-    let stats = stats_for_commit_stats(repo, &target, ignore_patterns)?;
+    let stats = compute_commit_stats_with_options(
+        repo,
+        &repo.find_commit(target.clone())?,
+        ignore_patterns,
+        &options,
+    )?;
 
-    if json {
-        let json_str = serde_json::to_string(&stats)?;
-        println!("{}", json_str);
-    } else {
-        write_stats_to_terminal(&stats, true);
+    stats_profile::time(StatsPhase::Serialization, || {
+        write_stats_output(&target, &stats, format, &options)
+    })?;
+
+    Ok(stats)
+}
+
+pub fn write_stats_output(
+    target: &str,
+    stats: &CommitStats,
+    format: StatsOutputFormat,
+    options: &CommitStatsOptions,
+) -> Result<(), GitAiError> {
+    match format {
+        StatsOutputFormat::Json => {
+            let json_str = serde_json::to_string(stats)?;
+            println!("{}", json_str);
+        }
+        StatsOutputFormat::Ndjson => {
+            println!("{}", write_stats_ndjson_line(target, stats)?);
+        }
+        StatsOutputFormat::Csv if options.per_file => {
+            print!(
+                "{}",
+                write_file_stats_to_csv(&[(target.to_string(), stats.clone())])
+            );
+        }
+        StatsOutputFormat::Csv => {
+            print!(
+                "{}",
+                write_stats_to_csv(&[(target.to_string(), stats.clone())])?
+            );
+        }
+        StatsOutputFormat::Table => {
+            print!(
+                "{}",
+                write_stats_to_table(&[(target.to_string(), stats.clone())], options.per_file)
+            );
+        }
+        StatsOutputFormat::Terminal => {
+            write_stats_to_terminal(stats, true);
+            if options.per_file {
+                write_file_stats_to_terminal(&stats.files, true);
+            }
+            if options.by_session {
+                write_session_stats_to_terminal(&stats.ai_by_session, true);
+            }
+        }
     }
 
     Ok(())
 }
 
+/// One NDJSON line: the commit's stats with its SHA added as `commit`
+#[derive(Serialize)]
+struct NdjsonStatsLine<'a> {
+    commit: &'a str,
+    #[serde(flatten)]
+    stats: &'a CommitStats,
+}
+
+/// Render one commit's stats as a single line of JSON, without the trailing newline.
+pub fn write_stats_ndjson_line(commit: &str, stats: &CommitStats) -> Result<String, GitAiError> {
+    Ok(serde_json::to_string(&NdjsonStatsLine { commit, stats })?)
+}
+
+/// CSV columns, in order. Every public `CommitStats` field gets a column, prefixed by the commit SHA.
+pub const CSV_COLUMNS: &[&str] = &[
+    "commit",
+    "human_additions",
+    "mixed_additions",
+    "ai_additions",
+    "ai_accepted",
+    "total_ai_additions",
+    "total_ai_deletions",
+    "time_waiting_for_ai",
+    "git_diff_deleted_lines",
+    "git_diff_added_lines",
+    "ai_deletions",
+    "human_deletions",
+    "ai_chars",
+    "human_chars",
+    "ai_accepted_verbatim",
+    "ai_accepted_modified",
+    "ai_percentage",
+    "human_percentage",
+    "tool_model_breakdown",
+    "ai_by_tool",
+    "ai_by_session",
+];
+
+/// Render stats as RFC 4180 CSV: one header row followed by one row per commit.
+/// `tool_model_breakdown`, `ai_by_tool` and `ai_by_session` are embedded as JSON objects in
+/// single quoted cells.
+pub fn write_stats_to_csv(rows: &[(String, CommitStats)]) -> Result<String, GitAiError> {
+    let mut output = String::new();
+    output.push_str(&CSV_COLUMNS.join(","));
+    output.push_str("\r\n");
+
+    for (commit_sha, stats) in rows {
+        let fields = [
+            escape_csv_field(commit_sha),
+            stats.human_additions.to_string(),
+            stats.mixed_additions.to_string(),
+            stats.ai_additions.to_string(),
+            stats.ai_accepted.to_string(),
+            stats.total_ai_additions.to_string(),
+            stats.total_ai_deletions.to_string(),
+            stats.time_waiting_for_ai.to_string(),
+            stats.git_diff_deleted_lines.to_string(),
+            stats.git_diff_added_lines.to_string(),
+            stats.ai_deletions.to_string(),
+            stats.human_deletions.to_string(),
+            stats.ai_chars.to_string(),
+            stats.human_chars.to_string(),
+            stats.ai_accepted_verbatim.to_string(),
+            stats.ai_accepted_modified.to_string(),
+            stats.ai_percentage.to_string(),
+            stats.human_percentage.to_string(),
+            escape_csv_field(&serde_json::to_string(&stats.tool_model_breakdown)?),
+            escape_csv_field(&serde_json::to_string(&stats.ai_by_tool)?),
+            escape_csv_field(&serde_json::to_string(&stats.ai_by_session)?),
+        ];
+        output.push_str(&fields.join(","));
+        output.push_str("\r\n");
+    }
+
+    Ok(output)
+}
+
+/// CSV columns for the `--per-file` breakdown, one row per (commit, file) pair.
+pub const FILE_CSV_COLUMNS: &[&str] = &[
+    "commit",
+    "file_path",
+    "human_additions",
+    "ai_additions",
+    "ai_accepted",
+];
+
+/// Render per-file stats as RFC 4180 CSV, quoting paths so UTF-8 names survive import.
+pub fn write_file_stats_to_csv(rows: &[(String, CommitStats)]) -> String {
+    let mut output = String::new();
+    output.push_str(&FILE_CSV_COLUMNS.join(","));
+    output.push_str("\r\n");
+
+    for (commit_sha, stats) in rows {
+        for file in &stats.files {
+            let fields = [
+                escape_csv_field(commit_sha),
+                escape_csv_field(&file.file_path),
+                file.human_additions.to_string(),
+                file.ai_additions.to_string(),
+                file.ai_accepted.to_string(),
+            ];
+            output.push_str(&fields.join(","));
+            output.push_str("\r\n");
+        }
+    }
+
+    output
+}
+
+/// Render stats as a bordered table with one row per commit, or one row per file when
+/// `per_file` is set. Columns are sized by display width, so CJK paths keep the borders aligned.
+pub fn write_stats_to_table(rows: &[(String, CommitStats)], per_file: bool) -> String {
+    let mut header = vec!["Commit", "AI+", "Human+", "AI%"];
+    if per_file {
+        header.insert(1, "Path");
+    }
+    let mut cells: Vec<Vec<String>> = vec![header.iter().map(|h| h.to_string()).collect()];
+    for (commit_sha, stats) in rows {
+        let short_sha: String = commit_sha.chars().take(7).collect();
+        if per_file {
+            for file in &stats.files {
+                let total = file.ai_additions + file.human_additions;
+                cells.push(vec![
+                    short_sha.clone(),
+                    file.file_path.clone(),
+                    file.ai_additions.to_string(),
+                    file.human_additions.to_string(),
+                    format!("{:.1}%", rounded_percentage(file.ai_additions, total)),
+                ]);
+            }
+        } else {
+            cells.push(vec![
+                short_sha,
+                stats.ai_additions.to_string(),
+                stats.human_additions.to_string(),
+                format!("{:.1}%", stats.ai_percentage),
+            ]);
+        }
+    }
+
+    let mut widths = vec![0; header.len()];
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(UnicodeWidthStr::width(cell.as_str()));
+        }
+    }
+    // Only the commit and path columns are text; the counts read better right-aligned
+    let text_columns = if per_file { 2 } else { 1 };
+
+    let border = format!(
+        "+{}+\n",
+        widths
+            .iter()
+            .map(|width| "-".repeat(width + 2))
+            .collect::<Vec<_>>()
+            .join("+")
+    );
+    let mut output = border.clone();
+    for (index, row) in cells.iter().enumerate() {
+        let padded: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(column, (cell, width))| {
+                let padding = " ".repeat(width - UnicodeWidthStr::width(cell.as_str()));
+                if column < text_columns || index == 0 {
+                    format!(" {}{} ", cell, padding)
+                } else {
+                    format!(" {}{} ", padding, cell)
+                }
+            })
+            .collect();
+        output.push_str(&format!("|{}|\n", padded.join("|")));
+        if index == 0 {
+            output.push_str(&border);
+        }
+    }
+    output.push_str(&border);
+    output
+}
+
+/// Quote a CSV field per RFC 4180 when it contains a delimiter, quote, line break, or any
+/// non-ASCII character (so UTF-8 paths survive spreadsheet imports untouched).
+pub fn escape_csv_field(field: &str) -> String {
+    let needs_quotes = field
+        .chars()
+        .any(|c| matches!(c, ',' | '"' | '\r' | '\n') || !c.is_ascii());
+    if needs_quotes {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
 pub fn write_stats_to_terminal(stats: &CommitStats, print: bool) -> String {
     let mut output = String::new();
 
@@ -103,9 +543,7 @@ pub fn write_stats_to_terminal(stats: &CommitStats, print: bool) -> String {
         // Show gray bar for deletion-only commit
         let mut progress_bar = String::new();
         progress_bar.push_str("you  ");
-        progress_bar.push_str("\x1b[90m"); // Gray color
-        progress_bar.push_str(&" ".repeat(bar_width)); // Gray bar
-        progress_bar.push_str("\x1b[0m"); // Reset color
+        progress_bar.push_str(&paint("90", &" ".repeat(bar_width))); // Gray bar
         progress_bar.push_str(" ai");
 
         output.push_str(&progress_bar);
@@ -115,7 +553,8 @@ pub fn write_stats_to_terminal(stats: &CommitStats, print: bool) -> String {
         }
 
         // Show "(no additions)" message below the bar
-        let no_additions_msg = format!("     \x1b[90m{:^40}\x1b[0m", "(no additions)");
+        let no_additions_msg =
+            format!("     {}", paint("90", &format!("{:^40}", "(no additions)")));
         output.push_str(&no_additions_msg);
         output.push('\n');
         if print {
@@ -283,8 +722,14 @@ pub fn write_stats_to_terminal(stats: &CommitStats, print: bool) -> String {
         };
 
         let ai_acceptance_str = format!(
-            "     \x1b[90m{:.0}% AI code accepted{}\x1b[0m",
-            _ai_acceptance_percentage, waiting_time_str
+            "     {}",
+            paint(
+                "90",
+                &format!(
+                    "{:.0}% AI code accepted{}",
+                    _ai_acceptance_percentage, waiting_time_str
+                )
+            )
         );
         output.push_str(&ai_acceptance_str);
         output.push('\n');
@@ -295,6 +740,185 @@ pub fn write_stats_to_terminal(stats: &CommitStats, print: bool) -> String {
     output
 }
 
+pub fn write_file_stats_to_terminal(files: &[FileStats], print: bool) -> String {
+    let mut output = String::new();
+    if files.is_empty() {
+        return output;
+    }
+
+    let header = format!("     {:>6} {:>6} {:>8}  file", "ai", "human", "accepted");
+    output.push_str(&header);
+    output.push('\n');
+    if print {
+        println!("{}", header);
+    }
+
+    for file in files {
+        let row = format!(
+            "     {:>6} {:>6} {:>8}  {}",
+            file.ai_additions, file.human_additions, file.ai_accepted, file.file_path
+        );
+        output.push_str(&row);
+        output.push('\n');
+        if print {
+            println!("{}", row);
+        }
+    }
+    output
+}
+
+pub fn write_session_stats_to_terminal(sessions: &BTreeMap<String, u32>, print: bool) -> String {
+    let mut output = String::new();
+    if sessions.is_empty() {
+        return output;
+    }
+
+    let header = format!("     {:>6}  session", "ai");
+    output.push_str(&header);
+    output.push('\n');
+    if print {
+        println!("{}", header);
+    }
+
+    for (session, additions) in sessions {
+        let row = format!("     {:>6}  {}", additions, session);
+        output.push_str(&row);
+        output.push('\n');
+        if print {
+            println!("{}", row);
+        }
+    }
+    output
+}
+
+/// Directory prefix of `file_path` made of at most `depth` components. Files at the root of the
+/// repository are grouped under `.`.
+pub fn directory_prefix(file_path: &str, depth: usize) -> String {
+    let mut components: Vec<&str> = file_path.split('/').collect();
+    components.pop();
+    components.truncate(depth);
+    if components.is_empty() {
+        ".".to_string()
+    } else {
+        components.join("/")
+    }
+}
+
+/// Sum a commit's per-file stats by directory prefix, keeping `depth` path components
+pub fn stats_by_directory(files: &[FileStats], depth: usize) -> BTreeMap<String, CommitStats> {
+    let mut directories: BTreeMap<String, CommitStats> = BTreeMap::new();
+    for file in files {
+        let stats = directories
+            .entry(directory_prefix(&file.file_path, depth))
+            .or_default();
+        stats.ai_additions += file.ai_additions;
+        stats.ai_accepted += file.ai_accepted;
+        stats.human_additions += file.human_additions;
+        stats.git_diff_added_lines += file.ai_additions + file.human_additions;
+    }
+    for stats in directories.values_mut() {
+        stats.compute_percentages();
+    }
+    directories
+}
+
+/// A file ranked by `top_files`, with the share of its added lines that AI wrote
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TopFile {
+    pub file_path: String,
+    pub ai_additions: u32,
+    pub human_additions: u32,
+    pub ai_percentage: f64,
+}
+
+/// The `n` files with the most AI additions, summed over the per-file breakdowns of one or
+/// more commits. Ties go to the path in alphabetical order; files without AI lines are left out.
+pub fn top_files<'a>(
+    breakdowns: impl IntoIterator<Item = &'a [FileStats]>,
+    n: usize,
+) -> Vec<TopFile> {
+    let mut by_path: BTreeMap<&str, (u32, u32)> = BTreeMap::new();
+    for files in breakdowns {
+        for file in files {
+            let (ai, human) = by_path.entry(file.file_path.as_str()).or_default();
+            *ai += file.ai_additions;
+            *human += file.human_additions;
+        }
+    }
+
+    let mut ranked: Vec<TopFile> = by_path
+        .into_iter()
+        .filter(|(_, (ai, _))| *ai > 0)
+        .map(|(file_path, (ai, human))| TopFile {
+            file_path: file_path.to_string(),
+            ai_additions: ai,
+            human_additions: human,
+            ai_percentage: rounded_percentage(ai, ai + human),
+        })
+        .collect();
+    // The map iterates paths in order and the sort is stable, so ties stay alphabetical
+    ranked.sort_by(|a, b| b.ai_additions.cmp(&a.ai_additions));
+    ranked.truncate(n);
+    ranked
+}
+
+pub fn write_top_files_to_terminal(files: &[TopFile], print: bool) -> String {
+    let mut output = String::new();
+    if files.is_empty() {
+        return output;
+    }
+
+    let header = format!("     {:>6} {:>6} {:>7}  file", "ai", "human", "ai %");
+    output.push_str(&header);
+    output.push('\n');
+    if print {
+        println!("{}", header);
+    }
+
+    for file in files {
+        let row = format!(
+            "     {:>6} {:>6} {:>6.1}%  {}",
+            file.ai_additions, file.human_additions, file.ai_percentage, file.file_path
+        );
+        output.push_str(&row);
+        output.push('\n');
+        if print {
+            println!("{}", row);
+        }
+    }
+    output
+}
+
+pub fn write_directory_stats_to_terminal(
+    directories: &BTreeMap<String, CommitStats>,
+    print: bool,
+) -> String {
+    let mut output = String::new();
+    if directories.is_empty() {
+        return output;
+    }
+
+    let header = format!("     {:>6} {:>6} {:>7}  directory", "ai", "human", "ai %");
+    output.push_str(&header);
+    output.push('\n');
+    if print {
+        println!("{}", header);
+    }
+
+    for (directory, stats) in directories {
+        let row = format!(
+            "     {:>6} {:>6} {:>6.1}%  {}",
+            stats.ai_additions, stats.human_additions, stats.ai_percentage, directory
+        );
+        output.push_str(&row);
+        output.push('\n');
+        if print {
+            println!("{}", row);
+        }
+    }
+    output
+}
+
 #[allow(dead_code)]
 pub fn write_stats_to_markdown(stats: &CommitStats) -> String {
     let mut output = String::new();
@@ -463,6 +1087,17 @@ pub fn stats_from_authorship_log(
         total_ai_deletions: 0,
         time_waiting_for_ai: 0,
         tool_model_breakdown: BTreeMap::new(),
+        ai_by_tool: BTreeMap::new(),
+        ai_by_session: BTreeMap::new(),
+        ai_deletions: 0,
+        human_deletions: 0,
+        ai_chars: 0,
+        human_chars: 0,
+        ai_accepted_verbatim: 0,
+        ai_accepted_modified: 0,
+        ai_percentage: 0.0,
+        human_percentage: 0.0,
+        files: Vec::new(),
         git_diff_deleted_lines,
         git_diff_added_lines,
     };
@@ -513,85 +1148,921 @@ pub fn stats_from_authorship_log(
     // AI additions are the sum of mixed and accepted lines.
     commit_stats.ai_additions = commit_stats.mixed_additions + commit_stats.ai_accepted;
 
-    // Calculate ai_additions for each tool following the same contract: ai_additions = ai_accepted + mixed_additions
-    for tool_stats in commit_stats.tool_model_breakdown.values_mut() {
-        tool_stats.ai_additions = tool_stats.ai_accepted + tool_stats.mixed_additions;
+    // Calculate ai_additions for each tool following the same contract: ai_additions = ai_accepted + mixed_additions
+    for tool_stats in commit_stats.tool_model_breakdown.values_mut() {
+        tool_stats.ai_additions = tool_stats.ai_accepted + tool_stats.mixed_additions;
+    }
+
+    // Roll tool::model entries up per tool. Mixed additions were capped at the commit level above,
+    // so hand out that budget in key order; accepted lines whose prompt is missing go to "unknown".
+    let mut mixed_budget = commit_stats.mixed_additions;
+    let mut attributed = 0u32;
+    for (tool_model, tool_stats) in &commit_stats.tool_model_breakdown {
+        let tool = tool_model.split("::").next().unwrap_or(tool_model);
+        let mixed = tool_stats.mixed_additions.min(mixed_budget);
+        mixed_budget -= mixed;
+        let additions = tool_stats.ai_accepted + mixed;
+        if additions > 0 {
+            *commit_stats.ai_by_tool.entry(tool.to_string()).or_default() += additions;
+            attributed += additions;
+        }
+    }
+    let unattributed = commit_stats.ai_additions.saturating_sub(attributed);
+    if unattributed > 0 {
+        *commit_stats
+            .ai_by_tool
+            .entry("unknown".to_string())
+            .or_default() += unattributed;
+    }
+
+    // Human additions are the difference between total git diff and AI accepted lines (ensure non-negative)
+    // This includes mixed lines (AI-generated but human-edited) as human additions
+    commit_stats.human_additions = std::cmp::max(
+        0,
+        git_diff_added_lines.saturating_sub(commit_stats.ai_accepted),
+    );
+
+    commit_stats.compute_percentages();
+
+    commit_stats
+}
+
+/// Which commits of a range are summed by the per-commit stats outputs, and whether merge
+/// commits count lines of their own.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Like `git log -p`, merges show no diff: the lines they bring in are counted on the commits
+    /// that wrote them, so merge commits are left out.
+    #[default]
+    SkipMerges,
+    /// Every commit, with each merge also counted against its first parent
+    IncludeMerges,
+    /// Only the first-parent chain, like `git log --first-parent`, with each merge counted
+    /// against its first parent so a merged branch counts once, as the merge
+    FirstParent,
+}
+
+impl MergeStrategy {
+    /// `git rev-list` options that select the commits to sum.
+    pub fn rev_list_args(self) -> &'static [&'static str] {
+        match self {
+            MergeStrategy::SkipMerges => &["--no-merges"],
+            MergeStrategy::IncludeMerges => &[],
+            MergeStrategy::FirstParent => &["--first-parent"],
+        }
+    }
+}
+
+/// Optional, more expensive parts of the per-commit stats computation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CommitStatsOptions {
+    /// Fill in `CommitStats::files` with a per-file breakdown
+    pub per_file: bool,
+    /// Classify deleted lines as AI or human by blaming them in the parent commit
+    pub deletions: bool,
+    /// Treat lines that only differ in whitespace as unchanged, like `git diff -w`
+    pub ignore_whitespace: bool,
+    /// Diff with this algorithm instead of the repository's `diff.algorithm`
+    pub diff_algorithm: Option<DiffAlgorithm>,
+    /// Count AI and human characters of added lines, reading each changed file at the commit
+    pub chars: bool,
+    /// Count an unattested added line as AI when an earlier commit attested the exact same line
+    /// as AI in the same file, such as a deleted AI line a human types back in
+    pub attribute_identical_to_prior: bool,
+    /// Fill in `CommitStats::ai_by_session` from the session ids recorded on the prompts
+    pub by_session: bool,
+    /// Split `ai_accepted` into lines committed as suggested and lines edited since, reading
+    /// each changed file at the commit
+    pub verbatim: bool,
+}
+
+impl CommitStatsOptions {
+    /// These options with the deletion, character and verbatim breakdowns turned on. They blame
+    /// deleted lines and read each changed file, so `git-ai stats` always computes them for a
+    /// single commit but ranges and `git-ai log` only with `--breakdowns`.
+    pub fn with_breakdowns(self) -> CommitStatsOptions {
+        CommitStatsOptions {
+            deletions: true,
+            chars: true,
+            verbatim: true,
+            ..self
+        }
+    }
+}
+
+/// Stats for `commit` as `git-ai stats <commit>` reports them.
+///
+/// Added and deleted lines come from the diff against the first parent, leaving out files
+/// matched by the repository's default ignore patterns (lockfiles, `linguist-generated`
+/// paths, `.git-ai-ignore`). Added lines attested in the commit's authorship log count as AI
+/// and the rest as human; a commit without a log counts every added line as human. Deleted
+/// lines, characters and verbatim/modified AI lines are filled in as well, which reads the
+/// changed files at the commit and blames deleted lines in its parent.
+///
+/// ```
+/// use git_ai::authorship::stats::compute_commit_stats;
+/// use git_ai::git::repository::find_repository_in_path;
+/// use std::process::Command;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// let git = |args: &[&str]| {
+///     let status = Command::new("git")
+///         .args(args)
+///         .current_dir(dir.path())
+///         .status()
+///         .unwrap();
+///     assert!(status.success());
+/// };
+/// git(&["init", "--quiet"]);
+/// git(&["config", "user.name", "Test User"]);
+/// git(&["config", "user.email", "test@example.com"]);
+/// std::fs::write(dir.path().join("README.md"), "# Repo\nhello\n").unwrap();
+/// git(&["add", "README.md"]);
+/// git(&["commit", "--quiet", "-m", "Initial"]);
+///
+/// let repo = find_repository_in_path(dir.path().to_str().unwrap()).unwrap();
+/// let commit = repo.revparse_single("HEAD").unwrap().peel_to_commit().unwrap();
+/// let stats = compute_commit_stats(&repo, &commit).unwrap();
+///
+/// // Nothing attests the lines to a prompt, so both are human
+/// assert_eq!(stats.git_diff_added_lines, 2);
+/// assert_eq!(stats.human_additions, 2);
+/// assert_eq!(stats.ai_additions, 0);
+/// ```
+pub fn compute_commit_stats(repo: &Repository, commit: &Commit) -> Result<CommitStats, GitAiError> {
+    compute_commit_stats_with_options(
+        repo,
+        commit,
+        &effective_ignore_patterns(repo, &[], &[]),
+        &CommitStatsOptions::default(),
+    )
+}
+
+/// `compute_commit_stats` with explicit ignore patterns and the optional breakdowns in
+/// `options`, as used by `git-ai stats` with flags.
+pub fn compute_commit_stats_with_options(
+    repo: &Repository,
+    commit: &Commit,
+    ignore_patterns: &[String],
+    options: &CommitStatsOptions,
+) -> Result<CommitStats, GitAiError> {
+    stats_for_commit_stats_with_options(
+        repo,
+        &commit.id(),
+        ignore_patterns,
+        &options.with_breakdowns(),
+    )
+}
+
+pub fn stats_for_commit_stats(
+    repo: &Repository,
+    commit_sha: &str,
+    ignore_patterns: &[String],
+) -> Result<CommitStats, GitAiError> {
+    stats_for_commit_stats_with_options(
+        repo,
+        commit_sha,
+        ignore_patterns,
+        &CommitStatsOptions::default(),
+    )
+}
+
+/// Compute stats for each commit with `options` on a pool of `jobs` threads (0 picks one per
+/// CPU). Commits are independent of each other, and results keep the order of `commit_shas`.
+pub fn stats_for_commits(
+    repo: &Repository,
+    commit_shas: &[String],
+    ignore_patterns: &[String],
+    options: &CommitStatsOptions,
+    jobs: usize,
+) -> Result<Vec<CommitStats>, GitAiError> {
+    stats_for_commits_with_progress(
+        repo,
+        commit_shas,
+        ignore_patterns,
+        options,
+        jobs,
+        &|_, _| {},
+    )
+}
+
+/// Like `stats_for_commits`, calling `progress(done, total)` as each commit finishes. With
+/// several jobs, commits finish out of order, so `done` only counts them.
+pub fn stats_for_commits_with_progress(
+    repo: &Repository,
+    commit_shas: &[String],
+    ignore_patterns: &[String],
+    options: &CommitStatsOptions,
+    jobs: usize,
+    progress: &(dyn Fn(usize, usize) + Sync),
+) -> Result<Vec<CommitStats>, GitAiError> {
+    let total = commit_shas.len();
+    let done = AtomicUsize::new(0);
+    let stats_for = |sha: &String| {
+        let stats = stats_for_commit_stats_with_options(repo, sha, ignore_patterns, options);
+        progress(done.fetch_add(1, Ordering::Relaxed) + 1, total);
+        stats
+    };
+
+    if jobs == 1 || commit_shas.len() <= 1 {
+        return commit_shas.iter().map(stats_for).collect();
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map_err(|e| GitAiError::Generic(format!("Failed to start stats thread pool: {}", e)))?;
+    pool.install(|| commit_shas.par_iter().map(stats_for).collect())
+}
+
+/// Compute stats for each commit and pass each result to `emit` as soon as it is ready, so a
+/// large range is never held in memory. With several jobs, commits are emitted out of order.
+/// Stops at the first error from either the stats or `emit`.
+pub fn stream_stats_for_commits(
+    repo: &Repository,
+    commit_shas: &[String],
+    ignore_patterns: &[String],
+    options: &CommitStatsOptions,
+    jobs: usize,
+    emit: &(dyn Fn(&str, CommitStats) -> Result<(), GitAiError> + Sync),
+) -> Result<(), GitAiError> {
+    let emit_for = |sha: &String| {
+        emit(
+            sha,
+            stats_for_commit_stats_with_options(repo, sha, ignore_patterns, options)?,
+        )
+    };
+
+    if jobs == 1 || commit_shas.len() <= 1 {
+        return commit_shas.iter().try_for_each(emit_for);
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map_err(|e| GitAiError::Generic(format!("Failed to start stats thread pool: {}", e)))?;
+    pool.install(|| commit_shas.par_iter().try_for_each(emit_for))
+}
+
+pub fn stats_for_commit_stats_with_options(
+    repo: &Repository,
+    commit_sha: &str,
+    ignore_patterns: &[String],
+    options: &CommitStatsOptions,
+) -> Result<CommitStats, GitAiError> {
+    let commit_obj = repo.revparse_single(commit_sha)?.peel_to_commit()?;
+    let parent_count = commit_obj.parent_count()?;
+    let is_merge_commit = parent_count > 1;
+    if is_merge_commit {
+        // Merge stats blame the lines the merge brings in, so reuse them from the stats cache
+        let cache = StatsCache::for_repo(repo);
+        let note_blob_oid = note_blob_oids_for_commits(repo, &[commit_sha.to_string()])
+            .unwrap_or_default()
+            .remove(commit_sha);
+        let key = StatsCache::key(note_blob_oid.as_deref(), ignore_patterns, options);
+        if let Some(stats) = cache.get(commit_sha, &key) {
+            return Ok(stats);
+        }
+
+        let first_parent = commit_obj.parent(0)?.id();
+        let stats = merge_commit_stats(repo, &first_parent, commit_sha, ignore_patterns, options)?;
+        cache.put(commit_sha, &key, &stats);
+        return Ok(stats);
+    }
+
+    let from_ref = if parent_count == 0 {
+        "4b825dc642cb6eb9a060e54bf8d69288fbee4904".to_string()
+    } else {
+        commit_obj.parent(0)?.id()
+    };
+
+    // Step 1: get the diff between this commit and its parent ON refname (if more than one parent)
+    // If initial than everything is additions
+    // We want the count here git shows +111 -55
+    let (git_diff_added_lines, git_diff_deleted_lines) =
+        stats_profile::time(StatsPhase::Diff, || {
+            get_git_diff_stats(
+                repo,
+                commit_sha,
+                ignore_patterns,
+                options.ignore_whitespace,
+                options.diff_algorithm,
+            )
+        })?;
+
+    // Step 2: get the authorship log for this commit. A commit picked with `cherry-pick -x`
+    // outside the hooks has no note yet, so carry one over from its source.
+    let authorship_log = stats_profile::time(StatsPhase::Notes, || {
+        get_authorship(repo, commit_sha).or_else(|| {
+            recover_cherry_pick_authorship(repo, commit_sha).unwrap_or_else(|e| {
+                debug_log(&format!(
+                    "Failed to recover cherry-pick authorship for {}: {}",
+                    commit_sha, e
+                ));
+                None
+            })
+        })
+    });
+
+    // Step 3: get line numbers added by this specific commit, then intersect with attestations.
+    // This keeps accepted stats scoped to the target commit while avoiding expensive blame traversal.
+    let added_lines_by_file = stats_profile::time(StatsPhase::Diff, || {
+        added_lines_for_stats(repo, &from_ref, commit_sha, ignore_patterns, options)
+    })?;
+
+    stats_profile::time(StatsPhase::AttributionMatch, || {
+        let authorship_log = if options.attribute_identical_to_prior && parent_count == 1 {
+            attest_lines_identical_to_prior(
+                repo,
+                &from_ref,
+                commit_sha,
+                authorship_log,
+                &added_lines_by_file,
+            )?
+        } else {
+            authorship_log
+        };
+
+        // Step 4: derive accepted lines directly from note attestations for lines added in this commit.
+        let (ai_accepted, ai_accepted_by_tool) =
+            accepted_lines_from_attestations(authorship_log.as_ref(), &added_lines_by_file);
+
+        // Step 5: Calculate stats from authorship log
+        let mut stats = stats_from_authorship_log(
+            authorship_log.as_ref(),
+            git_diff_added_lines,
+            git_diff_deleted_lines,
+            ai_accepted,
+            &ai_accepted_by_tool,
+        );
+
+        // Step 6: the breakdowns the options ask for. Root commits have nothing to delete.
+        add_optional_breakdowns(
+            &mut stats,
+            repo,
+            commit_sha,
+            (parent_count == 1).then_some(from_ref.as_str()),
+            ignore_patterns,
+            options,
+            authorship_log.as_ref(),
+            &added_lines_by_file,
+        )?;
+
+        Ok(stats)
+    })
+}
+
+/// Lines each file gained between `from_ref` and `commit_sha`, keyed by NFC path (as
+/// attestations are) and sorted. Ignored files are left out, and so are symlinks and
+/// submodules, which diff as one line holding a target path or commit id rather than code.
+fn added_lines_for_stats(
+    repo: &Repository,
+    from_ref: &str,
+    commit_sha: &str,
+    ignore_patterns: &[String],
+    options: &CommitStatsOptions,
+) -> Result<HashMap<String, Vec<u32>>, GitAiError> {
+    let mut added_lines_by_file = repo.diff_added_lines_following_renames(
+        from_ref,
+        commit_sha,
+        options.ignore_whitespace,
+        options.diff_algorithm,
+    )?;
+    let links = repo.diff_link_paths(from_ref, commit_sha)?;
+    let ignore_matcher = build_ignore_matcher(ignore_patterns);
+    added_lines_by_file.retain(|file_path, _| {
+        !should_ignore_file_with_matcher(file_path, &ignore_matcher) && !links.contains(file_path)
+    });
+    let mut added_lines_by_file: HashMap<String, Vec<u32>> = added_lines_by_file
+        .into_iter()
+        .map(|(file_path, lines)| (nfc_path(&file_path).into_owned(), lines))
+        .collect();
+    for lines in added_lines_by_file.values_mut() {
+        lines.sort_unstable();
+        lines.dedup();
+    }
+    Ok(added_lines_by_file)
+}
+
+/// Fill in the per-file, per-session, deletion, character and verbatim breakdowns that
+/// `options` asks for, from the attestations covering the added lines. Deleted lines were
+/// authored in earlier commits, so they are classified from the attestations reachable from
+/// `deletions_from`; without it no deletions are counted.
+#[allow(clippy::too_many_arguments)]
+fn add_optional_breakdowns(
+    stats: &mut CommitStats,
+    repo: &Repository,
+    commit_sha: &str,
+    deletions_from: Option<&str>,
+    ignore_patterns: &[String],
+    options: &CommitStatsOptions,
+    attestations: Option<&AuthorshipLog>,
+    added_lines_by_file: &HashMap<String, Vec<u32>>,
+) -> Result<(), GitAiError> {
+    if options.per_file {
+        stats.files = file_stats_from_attestations(attestations, added_lines_by_file);
+    }
+
+    if options.by_session {
+        stats.ai_by_session = session_stats_from_attestations(attestations, added_lines_by_file);
+    }
+
+    if options.deletions
+        && let Some(from_ref) = deletions_from
+    {
+        let deleted = diff_deleted_stats(
+            repo,
+            from_ref,
+            commit_sha,
+            ignore_patterns,
+            options.ignore_whitespace,
+            options.diff_algorithm,
+        )?;
+        stats.ai_deletions = deleted.ai_deletions;
+        stats.human_deletions = deleted.human_deletions;
+    }
+
+    if options.chars {
+        let (ai_chars, human_chars) =
+            char_stats_from_attestations(repo, commit_sha, attestations, added_lines_by_file)?;
+        stats.ai_chars = ai_chars;
+        stats.human_chars = human_chars;
+    }
+
+    if options.verbatim {
+        let (verbatim, modified) =
+            verbatim_stats_from_attestations(repo, commit_sha, attestations, added_lines_by_file)?;
+        stats.ai_accepted_verbatim = verbatim;
+        stats.ai_accepted_modified = modified;
+    }
+
+    Ok(())
+}
+
+/// Attest the unattested added lines whose text matches, character for character, a line an
+/// earlier commit attested as AI in the same file. The newest such line decides which prompt is
+/// credited. Blank lines never match.
+fn attest_lines_identical_to_prior(
+    repo: &Repository,
+    parent_sha: &str,
+    commit_sha: &str,
+    authorship_log: Option<AuthorshipLog>,
+    added_lines_by_file: &HashMap<String, Vec<u32>>,
+) -> Result<Option<AuthorshipLog>, GitAiError> {
+    let mut log = authorship_log.clone().unwrap_or_else(AuthorshipLog::new);
+    let mut attested_any = false;
+
+    for (file_path, added_lines) in added_lines_by_file {
+        let attestation = log.file_attestation(file_path);
+        let unattested: Vec<u32> = added_lines
+            .iter()
+            .copied()
+            .filter(|line| {
+                !attestation.is_some_and(|file| {
+                    file.entries
+                        .iter()
+                        .any(|entry| entry.line_ranges.iter().any(|range| range.contains(*line)))
+                })
+            })
+            .collect();
+        if unattested.is_empty() {
+            continue;
+        }
+
+        let prior = prior_ai_lines(repo, parent_sha, file_path)?;
+        if prior.is_empty() {
+            continue;
+        }
+        let Ok(content) = repo.get_file_content(file_path, commit_sha) else {
+            continue;
+        };
+        let content = String::from_utf8_lossy(&content);
+        let lines: Vec<&str> = content.lines().collect();
+
+        let mut new_lines_by_prompt: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for line in unattested {
+            let Some(text) = lines.get(line.saturating_sub(1) as usize) else {
+                continue;
+            };
+            if let Some((prompt_hash, record)) = prior.get(text.trim_end_matches('\r')) {
+                new_lines_by_prompt
+                    .entry(prompt_hash.clone())
+                    .or_default()
+                    .push(line);
+                log.metadata
+                    .prompts
+                    .entry(prompt_hash.clone())
+                    .or_insert_with(|| copied_prompt_record(record));
+            }
+        }
+
+        for (prompt_hash, new_lines) in new_lines_by_prompt {
+            attested_any = true;
+            log.get_or_create_file(file_path)
+                .add_entry(AttestationEntry::new(
+                    prompt_hash,
+                    LineRange::compress_lines(&new_lines),
+                ));
+        }
+    }
+
+    Ok(if attested_any {
+        Some(log)
+    } else {
+        authorship_log
+    })
+}
+
+/// Text of every non-blank line that a commit up to `from_ref` attested as AI in `file_path`,
+/// mapped to the prompt that wrote it
+fn prior_ai_lines(
+    repo: &Repository,
+    from_ref: &str,
+    file_path: &str,
+) -> Result<HashMap<String, (String, PromptRecord)>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("log".to_string());
+    args.push("--format=%H".to_string());
+    args.push(from_ref.to_string());
+    args.push("--".to_string());
+    args.push(file_path.to_string());
+    let output = exec_git_with_profile(&args, InternalGitProfile::General)?;
+    let stdout = String::from_utf8(output.stdout)?;
+
+    let mut prior = HashMap::new();
+    // Newest commits come first, so the latest prompt to write a line wins
+    for sha in stdout.lines() {
+        let Some(log) = get_authorship(repo, sha) else {
+            continue;
+        };
+        let Some(file) = log.file_attestation(file_path) else {
+            continue;
+        };
+        let Ok(content) = repo.get_file_content(file_path, sha) else {
+            continue;
+        };
+        let content = String::from_utf8_lossy(&content);
+        let lines: Vec<&str> = content.lines().collect();
+
+        for entry in &file.entries {
+            let Some(record) = log.metadata.prompts.get(&entry.hash) else {
+                continue;
+            };
+            for range in &entry.line_ranges {
+                let (start, end) = match range {
+                    LineRange::Single(line) => (*line, *line),
+                    LineRange::Range(start, end) => (*start, *end),
+                };
+                for line in start..=end {
+                    let Some(text) = lines.get(line.saturating_sub(1) as usize) else {
+                        continue;
+                    };
+                    let text = text.trim_end_matches('\r');
+                    if text.trim().is_empty() {
+                        continue;
+                    }
+                    prior
+                        .entry(text.to_string())
+                        .or_insert_with(|| (entry.hash.clone(), record.clone()));
+                }
+            }
+        }
+    }
+
+    Ok(prior)
+}
+
+/// Split the characters of added lines between AI and human. Lines attested as a whole count
+/// entirely as AI; other lines count the characters their partial-line attestations cover.
+/// Line terminators are not counted.
+fn char_stats_from_attestations(
+    repo: &Repository,
+    commit_sha: &str,
+    authorship_log: Option<&crate::authorship::authorship_log_serialization::AuthorshipLog>,
+    added_lines_by_file: &HashMap<String, Vec<u32>>,
+) -> Result<(u32, u32), GitAiError> {
+    let mut ai_chars = 0u32;
+    let mut human_chars = 0u32;
+
+    for (file_path, added_lines) in added_lines_by_file {
+        if added_lines.is_empty() {
+            continue;
+        }
+        let Ok(content) = repo.get_file_content(file_path, commit_sha) else {
+            continue;
+        };
+        let content = String::from_utf8_lossy(&content);
+        let lines: Vec<&str> = content.lines().collect();
+
+        let attestation = authorship_log.and_then(|log| log.file_attestation(file_path));
+
+        for &line in added_lines {
+            let Some(text) = lines.get(line.saturating_sub(1) as usize) else {
+                continue;
+            };
+            let line_chars = text.trim_end_matches('\r').chars().count() as u32;
+
+            let ai_line_chars = match attestation {
+                Some(file)
+                    if file.entries.iter().any(|entry| {
+                        entry.line_ranges.iter().any(|range| range.contains(line))
+                    }) =>
+                {
+                    line_chars
+                }
+                Some(file) => file
+                    .entries
+                    .iter()
+                    .flat_map(|entry| entry.char_ranges.iter())
+                    .filter(|range| range.line == line)
+                    .map(|range| range.len())
+                    .sum::<u32>()
+                    .min(line_chars),
+                None => 0,
+            };
+            ai_chars += ai_line_chars;
+            human_chars += line_chars - ai_line_chars;
+        }
+    }
+
+    Ok((ai_chars, human_chars))
+}
+
+/// Split the accepted AI lines of a commit into lines whose text is one the prompt suggested and
+/// lines edited since. Prompts recorded without suggestions have nothing to compare against, so
+/// their lines count as verbatim.
+fn verbatim_stats_from_attestations(
+    repo: &Repository,
+    commit_sha: &str,
+    authorship_log: Option<&crate::authorship::authorship_log_serialization::AuthorshipLog>,
+    added_lines_by_file: &HashMap<String, Vec<u32>>,
+) -> Result<(u32, u32), GitAiError> {
+    let mut verbatim = 0u32;
+    let mut modified = 0u32;
+
+    let Some(log) = authorship_log else {
+        return Ok((0, 0));
+    };
+
+    for file_attestation in &log.attestations {
+        let Some(added_lines) =
+            added_lines_by_file.get(nfc_path(&file_attestation.file_path).as_ref())
+        else {
+            continue;
+        };
+        let mut content: Option<String> = None;
+
+        for entry in &file_attestation.entries {
+            let accepted: Vec<u32> = added_lines
+                .iter()
+                .copied()
+                .filter(|line| entry.line_ranges.iter().any(|range| range.contains(*line)))
+                .collect();
+            if accepted.is_empty() {
+                continue;
+            }
+
+            let suggestions = log
+                .metadata
+                .prompts
+                .get(&entry.hash)
+                .map(|prompt| prompt.suggested_line_hashes.as_slice())
+                .unwrap_or_default();
+            if suggestions.is_empty() {
+                verbatim += accepted.len() as u32;
+                continue;
+            }
+
+            if content.is_none() {
+                let bytes = repo.get_file_content(&file_attestation.file_path, commit_sha)?;
+                content = Some(String::from_utf8_lossy(&bytes).into_owned());
+            }
+            let lines: Vec<&str> = content.as_deref().unwrap_or_default().lines().collect();
+            for line in accepted {
+                let text = lines.get(line.saturating_sub(1) as usize).unwrap_or(&"");
+                if suggestions
+                    .binary_search(&suggested_line_hash(text))
+                    .is_ok()
+                {
+                    verbatim += 1;
+                } else {
+                    modified += 1;
+                }
+            }
+        }
     }
 
-    // Human additions are the difference between total git diff and AI accepted lines (ensure non-negative)
-    // This includes mixed lines (AI-generated but human-edited) as human additions
-    commit_stats.human_additions = std::cmp::max(
-        0,
-        git_diff_added_lines.saturating_sub(commit_stats.ai_accepted),
-    );
-
-    commit_stats
+    Ok((verbatim, modified))
 }
 
-pub fn stats_for_commit_stats(
+/// Stats for a merge commit, measured against its first parent.
+///
+/// A merge usually has no authorship log of its own; the lines it brings in were attested on the
+/// merged branch. Blaming those lines at the merge commit walks back into the branch commits, so
+/// their attestations are reconciled into the merge's stats instead of being treated as human.
+/// Merges over the post-commit stats cost limit are not blamed.
+fn merge_commit_stats(
     repo: &Repository,
+    first_parent: &str,
     commit_sha: &str,
     ignore_patterns: &[String],
+    options: &CommitStatsOptions,
 ) -> Result<CommitStats, GitAiError> {
-    let commit_obj = repo.revparse_single(commit_sha)?.peel_to_commit()?;
+    let ((git_diff_added_lines, git_diff_deleted_lines), added_lines_by_file) =
+        stats_profile::time(StatsPhase::Diff, || {
+            Ok::<_, GitAiError>((
+                get_git_diff_stats_between(
+                    repo,
+                    first_parent,
+                    commit_sha,
+                    ignore_patterns,
+                    options.ignore_whitespace,
+                    options.diff_algorithm,
+                )?,
+                added_lines_for_stats(repo, first_parent, commit_sha, ignore_patterns, options)?,
+            ))
+        })?;
+
+    stats_profile::time(StatsPhase::AttributionMatch, || {
+        let authorship_log = get_authorship(repo, commit_sha);
+
+        // Blaming is as costly as the post-commit stats, so merges too large for those only
+        // count the lines attested by their own note
+        let estimate = StatsCostEstimate::from_added_lines(&added_lines_by_file);
+        let attestations = if should_skip_expensive_post_commit_stats(&estimate) {
+            debug_log(&format!(
+                "Skipping blame for large merge commit {}: {:?}",
+                commit_sha, estimate
+            ));
+            None
+        } else {
+            Some(blamed_attestations(repo, commit_sha, &added_lines_by_file))
+        };
+        let attestations = attestations.as_ref().or(authorship_log.as_ref());
 
-    // Step 1: get the diff between this commit and its parent ON refname (if more than one parent)
-    // If initial than everything is additions
-    // We want the count here git shows +111 -55
-    let (git_diff_added_lines, git_diff_deleted_lines) =
-        get_git_diff_stats(repo, commit_sha, ignore_patterns)?;
+        let (ai_accepted, ai_accepted_by_tool) =
+            accepted_lines_from_attestations(attestations, &added_lines_by_file);
 
-    // Step 2: get the authorship log for this commit
-    let authorship_log = get_authorship(repo, commit_sha);
+        let mut stats = stats_from_authorship_log(
+            authorship_log.as_ref(),
+            git_diff_added_lines,
+            git_diff_deleted_lines,
+            ai_accepted,
+            &ai_accepted_by_tool,
+        );
+        add_optional_breakdowns(
+            &mut stats,
+            repo,
+            commit_sha,
+            Some(first_parent),
+            ignore_patterns,
+            options,
+            attestations,
+            &added_lines_by_file,
+        )?;
+        Ok(stats)
+    })
+}
 
-    // Step 3: get line numbers added by this specific commit, then intersect with attestations.
-    // This keeps accepted stats scoped to the target commit while avoiding expensive blame traversal.
-    let parent_count = commit_obj.parent_count()?;
-    let is_merge_commit = parent_count > 1;
-    let mut added_lines_by_file: HashMap<String, Vec<u32>> = if is_merge_commit {
-        HashMap::new()
-    } else {
-        let from_ref = if parent_count == 0 {
-            "4b825dc642cb6eb9a060e54bf8d69288fbee4904".to_string()
-        } else {
-            commit_obj.parent(0)?.id()
+/// Attestations for the given added lines, recovered by blaming them at `commit_sha` and
+/// keeping the lines whose introducing commit attested them to a prompt. Lines that cannot be
+/// blamed stay unattested and count as human.
+fn blamed_attestations(
+    repo: &Repository,
+    commit_sha: &str,
+    added_lines_by_file: &HashMap<String, Vec<u32>>,
+) -> AuthorshipLog {
+    let mut log = AuthorshipLog::new();
+
+    for (file_path, lines) in added_lines_by_file {
+        if lines.is_empty() {
+            continue;
+        }
+
+        let mut blame_options = GitAiBlameOptions::default();
+        #[allow(clippy::field_reassign_with_default)]
+        {
+            blame_options.newest_commit = Some(commit_sha.to_string());
+            blame_options.line_ranges = lines_to_ranges(lines);
+            blame_options.no_output = true;
+            blame_options.use_prompt_hashes_as_names = true;
+        }
+        let Ok((line_authors, prompt_records)) = repo.blame(file_path, &blame_options) else {
+            continue;
         };
-        repo.diff_added_lines(&from_ref, commit_sha, None)?
-    };
-    let ignore_matcher = build_ignore_matcher(ignore_patterns);
-    added_lines_by_file
-        .retain(|file_path, _| !should_ignore_file_with_matcher(file_path, &ignore_matcher));
-    for lines in added_lines_by_file.values_mut() {
-        lines.sort_unstable();
-        lines.dedup();
+
+        let mut lines_by_prompt: BTreeMap<&str, Vec<u32>> = BTreeMap::new();
+        for line in lines {
+            if let Some(prompt_hash) = line_authors.get(line)
+                && prompt_records.contains_key(prompt_hash)
+            {
+                lines_by_prompt
+                    .entry(prompt_hash.as_str())
+                    .or_default()
+                    .push(*line);
+            }
+        }
+        for (prompt_hash, prompt_lines) in lines_by_prompt {
+            log.get_or_create_file(file_path)
+                .add_entry(AttestationEntry::new(
+                    prompt_hash.to_string(),
+                    LineRange::compress_lines(&prompt_lines),
+                ));
+        }
+        for (prompt_hash, record) in prompt_records {
+            log.metadata.prompts.entry(prompt_hash).or_insert(record);
+        }
     }
 
-    // Step 4: derive accepted lines directly from note attestations for lines added in this commit.
-    let (ai_accepted, ai_accepted_by_tool) = accepted_lines_from_attestations(
-        authorship_log.as_ref(),
-        &added_lines_by_file,
-        is_merge_commit,
-    );
+    log
+}
 
-    // Step 5: Calculate stats from authorship log
-    Ok(stats_from_authorship_log(
-        authorship_log.as_ref(),
-        git_diff_added_lines,
-        git_diff_deleted_lines,
-        ai_accepted,
-        &ai_accepted_by_tool,
-    ))
+/// Break down added lines per file, counting those covered by an attestation as AI.
+fn file_stats_from_attestations(
+    authorship_log: Option<&crate::authorship::authorship_log_serialization::AuthorshipLog>,
+    added_lines_by_file: &HashMap<String, Vec<u32>>,
+) -> Vec<FileStats> {
+    let mut files: BTreeMap<&str, FileStats> = added_lines_by_file
+        .iter()
+        .map(|(file_path, lines)| {
+            (
+                file_path.as_str(),
+                FileStats {
+                    file_path: file_path.clone(),
+                    human_additions: lines.len() as u32,
+                    ..Default::default()
+                },
+            )
+        })
+        .collect();
+
+    if let Some(log) = authorship_log {
+        for file_attestation in &log.attestations {
+            let (Some(added_lines), Some(file_stats)) = (
+                added_lines_by_file.get(nfc_path(&file_attestation.file_path).as_ref()),
+                files.get_mut(nfc_path(&file_attestation.file_path).as_ref()),
+            ) else {
+                continue;
+            };
+
+            let accepted = file_attestation
+                .entries
+                .iter()
+                .flat_map(|entry| entry.line_ranges.iter())
+                .map(|line_range| line_range_overlap_len(line_range, added_lines))
+                .sum::<u32>();
+            file_stats.ai_accepted += accepted;
+            file_stats.ai_additions += accepted;
+            file_stats.human_additions = file_stats.human_additions.saturating_sub(accepted);
+        }
+    }
+
+    files.into_values().collect()
 }
 
-fn accepted_lines_from_attestations(
+/// Accepted AI lines grouped by the session id on their prompt. Lines whose prompt has no
+/// session, or is missing from the log, are grouped under "unknown".
+fn session_stats_from_attestations(
     authorship_log: Option<&crate::authorship::authorship_log_serialization::AuthorshipLog>,
     added_lines_by_file: &HashMap<String, Vec<u32>>,
-    is_merge_commit: bool,
-) -> (u32, BTreeMap<String, u32>) {
-    if is_merge_commit {
-        return (0, BTreeMap::new());
+) -> BTreeMap<String, u32> {
+    let mut sessions: BTreeMap<String, u32> = BTreeMap::new();
+    let Some(log) = authorship_log else {
+        return sessions;
+    };
+
+    for file_attestation in &log.attestations {
+        let Some(added_lines) =
+            added_lines_by_file.get(nfc_path(&file_attestation.file_path).as_ref())
+        else {
+            continue;
+        };
+        for entry in &file_attestation.entries {
+            let accepted = entry
+                .line_ranges
+                .iter()
+                .map(|line_range| line_range_overlap_len(line_range, added_lines))
+                .sum::<u32>();
+            if accepted == 0 {
+                continue;
+            }
+            let session = log
+                .metadata
+                .prompts
+                .get(&entry.hash)
+                .and_then(|prompt| prompt.session_id.clone())
+                .unwrap_or_else(|| "unknown".to_string());
+            *sessions.entry(session).or_default() += accepted;
+        }
     }
 
+    sessions
+}
+
+fn accepted_lines_from_attestations(
+    authorship_log: Option<&crate::authorship::authorship_log_serialization::AuthorshipLog>,
+    added_lines_by_file: &HashMap<String, Vec<u32>>,
+) -> (u32, BTreeMap<String, u32>) {
     let mut total_ai_accepted = 0u32;
     let mut per_tool_model = BTreeMap::new();
 
@@ -600,7 +2071,9 @@ fn accepted_lines_from_attestations(
     };
 
     for file_attestation in &log.attestations {
-        let Some(added_lines) = added_lines_by_file.get(&file_attestation.file_path) else {
+        let Some(added_lines) =
+            added_lines_by_file.get(nfc_path(&file_attestation.file_path).as_ref())
+        else {
             continue;
         };
 
@@ -642,60 +2115,151 @@ fn line_range_overlap_len(range: &LineRange, added_lines: &[u32]) -> u32 {
 }
 
 /// Get git diff statistics between commit and its parent
+///
+/// Renames are detected so that moving a file only counts the lines that actually changed.
+/// With `ignore_whitespace`, lines that only differ in whitespace are not counted.
+/// `diff_algorithm` overrides the repository's `diff.algorithm`.
 pub fn get_git_diff_stats(
     repo: &Repository,
     commit_sha: &str,
     ignore_patterns: &[String],
+    ignore_whitespace: bool,
+    diff_algorithm: Option<DiffAlgorithm>,
 ) -> Result<(u32, u32), GitAiError> {
-    // Use git show --numstat to get diff statistics
+    // Use git show --numstat to get diff statistics (-z keeps UTF-8 paths unquoted)
     let mut args = repo.global_args_for_exec();
     args.push("show".to_string());
+    args.push("--raw".to_string());
     args.push("--numstat".to_string());
+    args.push("-z".to_string());
+    args.push("--find-renames".to_string());
+    if ignore_whitespace {
+        args.push("-w".to_string());
+    }
+    if let Some(algorithm) = diff_algorithm {
+        args.push(algorithm.git_arg());
+    }
     args.push("--format=".to_string()); // No format, just the numstat
     args.push(commit_sha.to_string());
 
-    let output = exec_git_with_profile(&args, InternalGitProfile::NumstatParse)?;
+    let output = exec_git_with_profile(&args, InternalGitProfile::RawDiffParse)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    Ok(parse_numstat_z(&stdout, ignore_patterns))
+}
+
+/// Get git diff statistics between two arbitrary revisions, with the same whitespace and
+/// algorithm options as [`get_git_diff_stats`]
+pub fn get_git_diff_stats_between(
+    repo: &Repository,
+    from_ref: &str,
+    to_ref: &str,
+    ignore_patterns: &[String],
+    ignore_whitespace: bool,
+    diff_algorithm: Option<DiffAlgorithm>,
+) -> Result<(u32, u32), GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("diff".to_string());
+    args.push("--raw".to_string());
+    args.push("--numstat".to_string());
+    args.push("-z".to_string());
+    args.push("--find-renames".to_string());
+    if ignore_whitespace {
+        args.push("-w".to_string());
+    }
+    if let Some(algorithm) = diff_algorithm {
+        args.push(algorithm.git_arg());
+    }
+    args.push(from_ref.to_string());
+    args.push(to_ref.to_string());
+
+    let output = exec_git_with_profile(&args, InternalGitProfile::RawDiffParse)?;
     let stdout = String::from_utf8_lossy(&output.stdout);
 
+    Ok(parse_numstat_z(&stdout, ignore_patterns))
+}
+
+/// Sum added and deleted lines from `--raw --numstat -z` output, skipping binary, symlink,
+/// submodule and ignored files.
+fn parse_numstat_z(stdout: &str, ignore_patterns: &[String]) -> (u32, u32) {
     let mut added_lines = 0u32;
     let mut deleted_lines = 0u32;
     let ignore_matcher = build_ignore_matcher(ignore_patterns);
-
-    // Parse numstat output
-    for line in stdout.lines() {
-        if line.trim().is_empty() {
+    let mut links: HashSet<&str> = HashSet::new();
+
+    // Raw records come first: ":old_mode new_mode old_oid new_oid status\0path\0", with two
+    // paths for renames and copies. They are only used to find symlinks and gitlinks, whose
+    // single "line" is the link target or the submodule commit.
+    // Numstat records follow: "added\tdeleted\tfilename\0", or for renames
+    // "added\tdeleted\t\0old_path\0new_path\0"
+    let mut records = stdout.split('\0');
+    while let Some(record) = records.next() {
+        let record = record.trim_start_matches('\n');
+        if let Some(raw) = record.strip_prefix(':') {
+            let fields: Vec<&str> = raw.split(' ').collect();
+            if fields
+                .get(4)
+                .is_some_and(|status| status.starts_with('R') || status.starts_with('C'))
+            {
+                records.next();
+            }
+            let Some(path) = records.next() else {
+                break;
+            };
+            let (old_mode, new_mode) = (
+                fields.first().copied().unwrap_or_default(),
+                fields.get(1).copied().unwrap_or_default(),
+            );
+            let mode = if new_mode == "000000" {
+                old_mode
+            } else {
+                new_mode
+            };
+            if mode == SYMLINK_MODE || mode == GITLINK_MODE {
+                links.insert(path);
+            }
             continue;
         }
 
-        // Skip the commit message lines (they don't start with numbers)
-        if !line.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+        let parts: Vec<&str> = record.splitn(3, '\t').collect();
+        if parts.len() < 3 {
             continue;
         }
 
-        // Parse numstat format: "added\tdeleted\tfilename"
-        let parts: Vec<&str> = line.split('\t').collect();
-        if parts.len() >= 3 {
-            // Check if this file should be ignored
-            let filename = parts[2];
-            if should_ignore_file_with_matcher(filename, &ignore_matcher) {
-                continue;
+        let filename = if parts[2].is_empty() {
+            let _old_path = records.next();
+            match records.next() {
+                Some(new_path) => new_path,
+                None => break,
             }
+        } else {
+            parts[2]
+        };
 
-            // Parse added lines
-            if let Ok(added) = parts[0].parse::<u32>() {
-                added_lines += added;
-            }
+        // Skip binary files (numstat reports "-" for both counts)
+        if !parts[0].chars().next().is_some_and(|c| c.is_ascii_digit()) {
+            continue;
+        }
 
-            // Parse deleted lines (handle "-" for binary files)
-            if parts[1] != "-"
-                && let Ok(deleted) = parts[1].parse::<u32>()
-            {
-                deleted_lines += deleted;
-            }
+        // Check if this file should be ignored
+        if should_ignore_file_with_matcher(filename, &ignore_matcher) || links.contains(filename) {
+            continue;
+        }
+
+        // Parse added lines
+        if let Ok(added) = parts[0].parse::<u32>() {
+            added_lines += added;
+        }
+
+        // Parse deleted lines (handle "-" for binary files)
+        if parts[1] != "-"
+            && let Ok(deleted) = parts[1].parse::<u32>()
+        {
+            deleted_lines += deleted;
         }
     }
 
-    Ok((added_lines, deleted_lines))
+    (added_lines, deleted_lines)
 }
 
 /// Calculate time waiting for AI from transcript messages
@@ -764,6 +2328,9 @@ mod tests {
 
     #[test]
     fn test_terminal_stats_display() {
+        // The snapshots keep the colors, whatever stdout is while the tests run
+        crate::utils::set_color_override(true);
+
         // Test with mixed human/AI stats
         let stats = CommitStats {
             human_additions: 50,
@@ -776,6 +2343,7 @@ mod tests {
             total_ai_additions: 100,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ..Default::default()
         };
 
         let mixed_output = write_stats_to_terminal(&stats, true);
@@ -793,6 +2361,7 @@ mod tests {
             total_ai_additions: 100,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ..Default::default()
         };
 
         let ai_only_output = write_stats_to_terminal(&ai_stats, true);
@@ -810,6 +2379,7 @@ mod tests {
             total_ai_additions: 0,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ..Default::default()
         };
 
         let human_only_output = write_stats_to_terminal(&human_stats, true);
@@ -827,6 +2397,7 @@ mod tests {
             total_ai_additions: 100,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ..Default::default()
         };
 
         let minimal_human_output = write_stats_to_terminal(&minimal_human_stats, true);
@@ -844,6 +2415,7 @@ mod tests {
             total_ai_additions: 0,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ..Default::default()
         };
 
         let deletion_only_output = write_stats_to_terminal(&deletion_only_stats, true);
@@ -864,6 +2436,7 @@ mod tests {
             total_ai_additions: 100,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ..Default::default()
         };
 
         let mixed_output = write_stats_to_markdown(&stats);
@@ -881,6 +2454,7 @@ mod tests {
             total_ai_additions: 100,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ..Default::default()
         };
 
         let ai_only_output = write_stats_to_markdown(&ai_stats);
@@ -898,6 +2472,7 @@ mod tests {
             total_ai_additions: 0,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ..Default::default()
         };
 
         let human_only_output = write_stats_to_markdown(&human_stats);
@@ -915,6 +2490,7 @@ mod tests {
             total_ai_additions: 100,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ..Default::default()
         };
 
         let minimal_human_output = write_stats_to_markdown(&minimal_human_stats);
@@ -932,6 +2508,7 @@ mod tests {
             total_ai_additions: 0,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ..Default::default()
         };
 
         let deletion_only_output = write_stats_to_markdown(&deletion_only_stats);
@@ -1281,53 +2858,7 @@ mod tests {
     #[test]
     fn test_accepted_lines_no_authorship_log() {
         let added_lines: HashMap<String, Vec<u32>> = HashMap::new();
-        let (accepted, per_tool) = accepted_lines_from_attestations(None, &added_lines, false);
-        assert_eq!(accepted, 0);
-        assert!(per_tool.is_empty());
-    }
-
-    #[test]
-    fn test_accepted_lines_merge_commit() {
-        // Even with a real authorship log, merge commits should short-circuit to (0, empty)
-        let mut log = crate::authorship::authorship_log_serialization::AuthorshipLog::new();
-        let agent_id = crate::authorship::working_log::AgentId {
-            tool: "cursor".to_string(),
-            id: "session_1".to_string(),
-            model: "claude-3-sonnet".to_string(),
-        };
-        let hash = crate::authorship::authorship_log_serialization::generate_short_hash(
-            &agent_id.id,
-            &agent_id.tool,
-        );
-        log.metadata.prompts.insert(
-            hash.clone(),
-            crate::authorship::authorship_log::PromptRecord {
-                agent_id,
-                human_author: None,
-                messages: vec![],
-                total_additions: 5,
-                total_deletions: 0,
-                accepted_lines: 5,
-                overriden_lines: 0,
-                messages_url: None,
-            },
-        );
-
-        let mut file_att = crate::authorship::authorship_log_serialization::FileAttestation::new(
-            "foo.rs".to_string(),
-        );
-        file_att.add_entry(
-            crate::authorship::authorship_log_serialization::AttestationEntry::new(
-                hash,
-                vec![crate::authorship::authorship_log::LineRange::Range(1, 3)],
-            ),
-        );
-        log.attestations.push(file_att);
-
-        let mut added_lines: HashMap<String, Vec<u32>> = HashMap::new();
-        added_lines.insert("foo.rs".to_string(), vec![1, 2, 3]);
-
-        let (accepted, per_tool) = accepted_lines_from_attestations(Some(&log), &added_lines, true);
+        let (accepted, per_tool) = accepted_lines_from_attestations(None, &added_lines);
         assert_eq!(accepted, 0);
         assert!(per_tool.is_empty());
     }
@@ -1355,6 +2886,8 @@ mod tests {
                 accepted_lines: 3,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -1373,8 +2906,7 @@ mod tests {
         let mut added_lines: HashMap<String, Vec<u32>> = HashMap::new();
         added_lines.insert("bar.rs".to_string(), vec![1, 2, 3]);
 
-        let (accepted, per_tool) =
-            accepted_lines_from_attestations(Some(&log), &added_lines, false);
+        let (accepted, per_tool) = accepted_lines_from_attestations(Some(&log), &added_lines);
         assert_eq!(accepted, 0);
         assert!(per_tool.is_empty());
     }
@@ -1402,6 +2934,8 @@ mod tests {
                 accepted_lines: 3,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -1419,8 +2953,7 @@ mod tests {
         let mut added_lines: HashMap<String, Vec<u32>> = HashMap::new();
         added_lines.insert("foo.rs".to_string(), vec![1, 2, 3]);
 
-        let (accepted, per_tool) =
-            accepted_lines_from_attestations(Some(&log), &added_lines, false);
+        let (accepted, per_tool) = accepted_lines_from_attestations(Some(&log), &added_lines);
         assert_eq!(accepted, 3);
 
         // Verify per-tool breakdown contains the right key
@@ -1468,7 +3001,7 @@ mod tests {
     }
 
     #[test]
-    fn test_stats_for_merge_commit_skips_ai_acceptance() {
+    fn test_stats_for_merge_commit_reconciles_branch_attestations() {
         let tmp_repo = TmpRepo::new().unwrap();
 
         tmp_repo.write_file("test.txt", "base\n", true).unwrap();
@@ -1501,8 +3034,28 @@ mod tests {
         let merge_sha = tmp_repo.get_head_commit_sha().unwrap();
         let stats = stats_for_commit_stats(tmp_repo.gitai_repo(), &merge_sha, &[]).unwrap();
 
-        assert_eq!(stats.ai_accepted, 0);
-        assert_eq!(stats.ai_additions, stats.mixed_additions);
+        // Measured against the first parent, the merge brings in the AI-authored feature line.
+        assert_eq!(stats.git_diff_added_lines, 1);
+        assert_eq!(stats.ai_accepted, 1);
+        assert_eq!(stats.ai_additions, 1);
+        assert_eq!(stats.human_additions, 0);
+
+        // The breakdowns requested through the options are computed for merges too
+        let options = CommitStatsOptions {
+            per_file: true,
+            chars: true,
+            deletions: true,
+            ..Default::default()
+        };
+        let stats =
+            stats_for_commit_stats_with_options(tmp_repo.gitai_repo(), &merge_sha, &[], &options)
+                .unwrap();
+        assert_eq!(stats.files.len(), 1);
+        assert_eq!(stats.files[0].file_path, "test.txt");
+        assert_eq!(stats.files[0].ai_accepted, 1);
+        assert_eq!(stats.ai_chars, "feature line".len() as u32);
+        assert_eq!(stats.human_chars, 0);
+        assert_eq!((stats.ai_deletions, stats.human_deletions), (0, 0));
     }
 
     #[test]
@@ -1649,6 +3202,37 @@ mod tests {
         assert_eq!(calculate_waiting_time(&transcript), 0);
     }
 
+    #[test]
+    fn test_stats_thresholds_boundaries() {
+        let stats = CommitStats {
+            ai_additions: 4,
+            human_additions: 1,
+            ai_percentage: 80.0,
+            human_percentage: 20.0,
+            ..Default::default()
+        };
+
+        assert!(StatsThresholds::default().violations(&stats).is_empty());
+
+        let at_limit = StatsThresholds {
+            max_ai_percentage: Some(80.0),
+            min_human_lines: Some(1),
+        };
+        assert!(at_limit.violations(&stats).is_empty());
+
+        let over_limit = StatsThresholds {
+            max_ai_percentage: Some(79.9),
+            min_human_lines: Some(2),
+        };
+        assert_eq!(
+            over_limit.violations(&stats),
+            vec![
+                "AI percentage 80% exceeds the maximum of 79.9%".to_string(),
+                "1 human lines added, below the minimum of 2".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn test_stats_command_nonexistent_commit() {
         let tmp_repo = TmpRepo::new().unwrap();
@@ -1660,7 +3244,8 @@ mod tests {
         let result = stats_command(
             tmp_repo.gitai_repo(),
             Some("0000000000000000000000000000000000000000"),
-            false,
+            StatsOutputFormat::Terminal,
+            CommitStatsOptions::default(),
             &[],
         );
         assert!(result.is_err());
@@ -1679,7 +3264,13 @@ mod tests {
         let head_sha = tmp_repo.get_head_commit_sha().unwrap();
 
         // Should succeed with json output
-        let result = stats_command(tmp_repo.gitai_repo(), Some(&head_sha), true, &[]);
+        let result = stats_command(
+            tmp_repo.gitai_repo(),
+            Some(&head_sha),
+            StatsOutputFormat::Json,
+            CommitStatsOptions::default(),
+            &[],
+        );
         assert!(result.is_ok());
     }
 
@@ -1694,10 +3285,29 @@ mod tests {
         tmp_repo.commit_with_message("Commit").unwrap();
 
         // No SHA provided should default to HEAD
-        let result = stats_command(tmp_repo.gitai_repo(), None, false, &[]);
+        let result = stats_command(
+            tmp_repo.gitai_repo(),
+            None,
+            StatsOutputFormat::Terminal,
+            CommitStatsOptions::default(),
+            &[],
+        );
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_parse_numstat_z_skips_symlinks() {
+        let output = ":000000 120000 0000000 1111111 A\0link\0\
+                      :160000 160000 5555555 6666666 M\0vendor/lib\0\
+                      :100644 100644 2222222 3333333 M\0src/main.rs\0\
+                      :100644 100644 4444444 4444444 R100\0old.rs\0new.rs\0\
+                      1\t0\tlink\0\
+                      1\t1\tvendor/lib\0\
+                      3\t1\tsrc/main.rs\0\
+                      0\t0\t\0old.rs\0new.rs\0";
+        assert_eq!(parse_numstat_z(output, &[]), (3, 1));
+    }
+
     #[test]
     fn test_get_git_diff_stats_binary_files() {
         let tmp_repo = TmpRepo::new().unwrap();
@@ -1724,7 +3334,7 @@ mod tests {
         let head_sha = tmp_repo.get_head_commit_sha().unwrap();
 
         // Binary files should be handled (shown as "-" in numstat)
-        let result = get_git_diff_stats(tmp_repo.gitai_repo(), &head_sha, &[]);
+        let result = get_git_diff_stats(tmp_repo.gitai_repo(), &head_sha, &[], false, None);
         assert!(result.is_ok());
     }
 
@@ -1743,6 +3353,156 @@ mod tests {
         assert_eq!(stats.time_waiting_for_ai, 0);
     }
 
+    #[test]
+    fn test_stats_percentages() {
+        // Pure AI commit
+        let stats = stats_from_authorship_log(None, 10, 0, 10, &BTreeMap::new());
+        assert_eq!(stats.ai_percentage, 100.0);
+        assert_eq!(stats.human_percentage, 0.0);
+
+        // Pure human commit
+        let stats = stats_from_authorship_log(None, 10, 0, 0, &BTreeMap::new());
+        assert_eq!(stats.ai_percentage, 0.0);
+        assert_eq!(stats.human_percentage, 100.0);
+
+        // Empty commit reports zeros rather than NaN
+        let stats = stats_from_authorship_log(None, 0, 0, 0, &BTreeMap::new());
+        assert_eq!(stats.ai_percentage, 0.0);
+        assert_eq!(stats.human_percentage, 0.0);
+
+        // Rounded to two decimals
+        let stats = stats_from_authorship_log(None, 3, 0, 1, &BTreeMap::new());
+        assert_eq!(stats.ai_percentage, 33.33);
+        assert_eq!(stats.human_percentage, 66.67);
+    }
+
+    #[test]
+    fn test_stats_accumulate() {
+        let mut total = CommitStats::default();
+        let mut first = CommitStats {
+            human_additions: 1,
+            ai_additions: 3,
+            ai_accepted: 3,
+            git_diff_added_lines: 4,
+            ..Default::default()
+        };
+        first.ai_by_tool.insert("cursor".to_string(), 3);
+        let mut second = CommitStats {
+            human_additions: 2,
+            ai_additions: 2,
+            ai_accepted: 1,
+            mixed_additions: 1,
+            git_diff_added_lines: 4,
+            git_diff_deleted_lines: 1,
+            ..Default::default()
+        };
+        second.ai_by_tool.insert("cursor".to_string(), 2);
+
+        total.accumulate(&first);
+        total.accumulate(&second);
+
+        assert_eq!(total.human_additions, 3);
+        assert_eq!(total.ai_additions, 5);
+        assert_eq!(total.ai_accepted, 4);
+        assert_eq!(total.mixed_additions, 1);
+        assert_eq!(total.git_diff_added_lines, 8);
+        assert_eq!(total.git_diff_deleted_lines, 1);
+        assert_eq!(total.ai_by_tool.get("cursor"), Some(&5));
+        assert_eq!(total.ai_percentage, 62.5);
+        assert_eq!(total.human_percentage, 37.5);
+    }
+
+    #[test]
+    fn test_stats_add_sums_every_field() {
+        let first = CommitStats {
+            human_additions: 1,
+            mixed_additions: 2,
+            ai_additions: 3,
+            ai_accepted: 4,
+            total_ai_additions: 5,
+            total_ai_deletions: 6,
+            time_waiting_for_ai: 7,
+            git_diff_deleted_lines: 8,
+            git_diff_added_lines: 9,
+            tool_model_breakdown: BTreeMap::from([(
+                "cursor::gpt-4".to_string(),
+                ToolModelHeadlineStats {
+                    ai_additions: 3,
+                    mixed_additions: 2,
+                    ai_accepted: 1,
+                    total_ai_additions: 5,
+                    total_ai_deletions: 6,
+                    time_waiting_for_ai: 7,
+                },
+            )]),
+            ai_by_tool: BTreeMap::from([("cursor".to_string(), 3)]),
+            ai_deletions: 10,
+            human_deletions: 11,
+            ai_chars: 12,
+            human_chars: 13,
+            ..Default::default()
+        };
+
+        let doubled = first.clone() + &first;
+        assert_eq!(doubled.human_additions, 2);
+        assert_eq!(doubled.mixed_additions, 4);
+        assert_eq!(doubled.ai_additions, 6);
+        assert_eq!(doubled.ai_accepted, 8);
+        assert_eq!(doubled.total_ai_additions, 10);
+        assert_eq!(doubled.total_ai_deletions, 12);
+        assert_eq!(doubled.time_waiting_for_ai, 14);
+        assert_eq!(doubled.git_diff_deleted_lines, 16);
+        assert_eq!(doubled.git_diff_added_lines, 18);
+        assert_eq!(doubled.ai_deletions, 20);
+        assert_eq!(doubled.human_deletions, 22);
+        assert_eq!(doubled.ai_chars, 24);
+        assert_eq!(doubled.human_chars, 26);
+        assert_eq!(doubled.ai_by_tool.get("cursor"), Some(&6));
+        let tool = &doubled.tool_model_breakdown["cursor::gpt-4"];
+        assert_eq!(
+            (
+                tool.ai_additions,
+                tool.mixed_additions,
+                tool.ai_accepted,
+                tool.total_ai_additions,
+                tool.total_ai_deletions,
+                tool.time_waiting_for_ai
+            ),
+            (6, 4, 2, 10, 12, 14)
+        );
+    }
+
+    #[test]
+    fn test_stats_sum_recomputes_percentages() {
+        let stats = [
+            CommitStats {
+                ai_additions: 1,
+                ai_percentage: 100.0,
+                ..Default::default()
+            },
+            CommitStats {
+                human_additions: 2,
+                human_percentage: 100.0,
+                ..Default::default()
+            },
+        ];
+
+        let total: CommitStats = stats.iter().sum();
+        assert_eq!(total.ai_percentage, 33.33);
+        assert_eq!(total.human_percentage, 66.67);
+
+        let mut owned: CommitStats = stats.into_iter().sum();
+        owned += CommitStats {
+            ai_additions: 1,
+            ..Default::default()
+        };
+        assert_eq!(owned.ai_percentage, 50.0);
+        assert_eq!(owned.human_percentage, 50.0);
+
+        let empty: CommitStats = std::iter::empty::<CommitStats>().sum();
+        assert_eq!(empty.ai_percentage, 0.0);
+        assert_eq!(empty.human_percentage, 0.0);
+    }
     #[test]
     #[ignore] // Implementation-specific capping behavior differs from test expectations
     fn test_stats_from_authorship_log_mixed_cap() {
@@ -1770,6 +3530,8 @@ mod tests {
                 accepted_lines: 0,
                 overriden_lines: 100, // Unrealistically high
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -1782,6 +3544,86 @@ mod tests {
         assert_eq!(stats.human_additions, 0); // 10 - 5 accepted = 5, but mixed takes it
     }
 
+    #[test]
+    fn test_escape_csv_field() {
+        assert_eq!(escape_csv_field("plain.txt"), "plain.txt");
+        assert_eq!(escape_csv_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv_field("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(escape_csv_field("中文文件.txt"), "\"中文文件.txt\"");
+        assert_eq!(escape_csv_field("🚀rocket.rs"), "\"🚀rocket.rs\"");
+    }
+
+    #[test]
+    fn test_write_stats_to_csv() {
+        let stats = CommitStats {
+            human_additions: 3,
+            mixed_additions: 1,
+            ai_additions: 5,
+            ai_accepted: 4,
+            total_ai_additions: 6,
+            total_ai_deletions: 2,
+            time_waiting_for_ai: 10,
+            git_diff_deleted_lines: 1,
+            git_diff_added_lines: 7,
+            tool_model_breakdown: BTreeMap::new(),
+            ai_by_tool: BTreeMap::from([("cursor".to_string(), 5)]),
+            ai_accepted_verbatim: 3,
+            ai_accepted_modified: 1,
+            ..Default::default()
+        };
+
+        let csv = write_stats_to_csv(&[("abc123".to_string(), stats)]).unwrap();
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], CSV_COLUMNS.join(","));
+        assert_eq!(
+            lines[1],
+            "abc123,3,1,5,4,6,2,10,1,7,0,0,0,0,3,1,0,0,{},\"{\"\"cursor\"\":5}\",{}"
+        );
+        assert_eq!(lines[2], "");
+    }
+
+    #[test]
+    fn test_directory_prefix_truncates_to_depth() {
+        assert_eq!(directory_prefix("src/authorship/stats.rs", 1), "src");
+        assert_eq!(
+            directory_prefix("src/authorship/stats.rs", 2),
+            "src/authorship"
+        );
+        assert_eq!(
+            directory_prefix("src/authorship/stats.rs", 5),
+            "src/authorship"
+        );
+        assert_eq!(directory_prefix("src/模块/文件.rs", 2), "src/模块");
+        assert_eq!(directory_prefix("README.md", 1), ".");
+    }
+
+    #[test]
+    fn test_top_files_sums_commits_and_ranks_by_ai_lines() {
+        let file = |path: &str, ai: u32, human: u32| FileStats {
+            file_path: path.to_string(),
+            ai_additions: ai,
+            human_additions: human,
+            ai_accepted: ai,
+        };
+        let first = vec![
+            file("a.rs", 2, 2),
+            file("b.rs", 3, 0),
+            file("docs.md", 0, 9),
+        ];
+        let second = vec![file("a.rs", 2, 0), file("c.rs", 3, 1)];
+
+        let ranked = top_files([first.as_slice(), second.as_slice()], 2);
+        let summary: Vec<(&str, u32, f64)> = ranked
+            .iter()
+            .map(|f| (f.file_path.as_str(), f.ai_additions, f.ai_percentage))
+            .collect();
+        assert_eq!(summary, vec![("a.rs", 4, 66.67), ("b.rs", 3, 100.0)]);
+
+        // Files without AI lines are never ranked
+        assert_eq!(top_files([first.as_slice()], 10).len(), 2);
+    }
+
     #[test]
     fn test_line_range_overlap_edge_cases() {
         use crate::authorship::authorship_log::LineRange;
@@ -0,0 +1,208 @@
+use crate::authorship::authorship_log_serialization::{
+    AUTHORSHIP_LOG_FORMAT_VERSION, AUTHORSHIP_LOG_VERSION, GIT_AI_VERSION,
+};
+use crate::authorship::stats::{CommitStats, CommitStatsOptions};
+use crate::git::repository::Repository;
+use crate::utils::debug_log;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+/// Bump when the layout of cache entries changes. Entries are also tied to the git-ai version
+/// that wrote them, so changes to how `CommitStats` are computed take effect on upgrade.
+const STATS_CACHE_VERSION: u32 = 2;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedStats {
+    authorship_log_version: String,
+    /// Entries written before the format version existed deserialize as 0 and are recomputed
+    #[serde(default)]
+    authorship_log_format_version: u32,
+    cache_version: u32,
+    /// Entries written before the git-ai version was recorded deserialize as empty and are
+    /// recomputed
+    #[serde(default)]
+    git_ai_version: String,
+    key: String,
+    stats: CommitStats,
+}
+
+/// On-disk cache of per-commit stats under `.git/ai/cache/stats/<sha>.json`.
+///
+/// A commit's diff never changes, but its authorship note, the ignore patterns and the options
+/// the stats were computed with can, so each entry also records a key derived from all three and
+/// is only used while that key still matches.
+#[derive(Debug, Clone)]
+pub struct StatsCache {
+    dir: PathBuf,
+}
+
+impl StatsCache {
+    pub fn for_repo(repo: &Repository) -> StatsCache {
+        StatsCache {
+            dir: repo.path().join("ai").join("cache").join("stats"),
+        }
+    }
+
+    pub fn entry_path(&self, commit_sha: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", commit_sha))
+    }
+
+    /// Key for a commit given the blob OID of its authorship note (if any), the ignore patterns
+    /// and the stats options. Entries are tied to the git-ai version, so the options' `Debug`
+    /// form is stable enough to key on.
+    pub fn key(
+        note_blob_oid: Option<&str>,
+        ignore_patterns: &[String],
+        options: &CommitStatsOptions,
+    ) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(note_blob_oid.unwrap_or("-").as_bytes());
+        for pattern in ignore_patterns {
+            hasher.update(b"\0");
+            hasher.update(pattern.as_bytes());
+        }
+        hasher.update(b"\0");
+        hasher.update(format!("{:?}", options).as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    pub fn get(&self, commit_sha: &str, key: &str) -> Option<CommitStats> {
+        let contents = fs::read_to_string(self.entry_path(commit_sha)).ok()?;
+        let cached: CachedStats = serde_json::from_str(&contents).ok()?;
+        if cached.authorship_log_version != AUTHORSHIP_LOG_VERSION
+            || cached.authorship_log_format_version != AUTHORSHIP_LOG_FORMAT_VERSION
+            || cached.cache_version != STATS_CACHE_VERSION
+            || cached.git_ai_version != GIT_AI_VERSION
+            || cached.key != key
+        {
+            return None;
+        }
+        Some(cached.stats)
+    }
+
+    /// Store stats for a commit. Failures are only logged since the cache is an optimization.
+    pub fn put(&self, commit_sha: &str, key: &str, stats: &CommitStats) {
+        let cached = CachedStats {
+            authorship_log_version: AUTHORSHIP_LOG_VERSION.to_string(),
+            authorship_log_format_version: AUTHORSHIP_LOG_FORMAT_VERSION,
+            cache_version: STATS_CACHE_VERSION,
+            git_ai_version: GIT_AI_VERSION.to_string(),
+            key: key.to_string(),
+            stats: stats.clone(),
+        };
+        let result = serde_json::to_string(&cached)
+            .map_err(std::io::Error::other)
+            .and_then(|json| {
+                fs::create_dir_all(&self.dir)?;
+                fs::write(self.entry_path(commit_sha), json)
+            });
+        if let Err(e) = result {
+            debug_log(&format!(
+                "Failed to write stats cache for {}: {}",
+                commit_sha, e
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_in(dir: &std::path::Path) -> StatsCache {
+        StatsCache {
+            dir: dir.join("stats"),
+        }
+    }
+
+    #[test]
+    fn test_stats_cache_round_trip_and_key_mismatch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = cache_in(tmp.path());
+        let key = StatsCache::key(Some("abc"), &[], &CommitStatsOptions::default());
+        let stats = CommitStats {
+            ai_additions: 3,
+            human_additions: 1,
+            ..Default::default()
+        };
+
+        assert!(cache.get("deadbeef", &key).is_none());
+        cache.put("deadbeef", &key, &stats);
+
+        let cached = cache.get("deadbeef", &key).unwrap();
+        assert_eq!(cached.ai_additions, 3);
+        assert_eq!(cached.human_additions, 1);
+
+        // A rewritten note or different ignore patterns must not reuse the entry
+        assert!(
+            cache
+                .get(
+                    "deadbeef",
+                    &StatsCache::key(Some("abd"), &[], &CommitStatsOptions::default())
+                )
+                .is_none()
+        );
+        assert!(
+            cache
+                .get(
+                    "deadbeef",
+                    &StatsCache::key(
+                        Some("abc"),
+                        &["*.lock".to_string()],
+                        &CommitStatsOptions::default()
+                    )
+                )
+                .is_none()
+        );
+        // Nor do stats computed with other options
+        assert!(
+            cache
+                .get(
+                    "deadbeef",
+                    &StatsCache::key(
+                        Some("abc"),
+                        &[],
+                        &CommitStatsOptions::default().with_breakdowns()
+                    )
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_stats_cache_ignores_other_log_versions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = cache_in(tmp.path());
+        let key = StatsCache::key(None, &[], &CommitStatsOptions::default());
+        cache.put("deadbeef", &key, &CommitStats::default());
+
+        let path = cache.entry_path("deadbeef");
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::write(
+            &path,
+            contents.replace(AUTHORSHIP_LOG_VERSION, "authorship/2.0.0"),
+        )
+        .unwrap();
+
+        assert!(cache.get("deadbeef", &key).is_none());
+    }
+
+    #[test]
+    fn test_stats_cache_ignores_entries_from_other_git_ai_versions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cache = cache_in(tmp.path());
+        let key = StatsCache::key(None, &[], &CommitStatsOptions::default());
+        cache.put("deadbeef", &key, &CommitStats::default());
+        assert!(cache.get("deadbeef", &key).is_some());
+
+        let path = cache.entry_path("deadbeef");
+        let mut entry: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        entry["git_ai_version"] = serde_json::Value::from("0.0.1");
+        fs::write(&path, entry.to_string()).unwrap();
+
+        assert!(cache.get("deadbeef", &key).is_none());
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// The collector `git-ai stats --profile` installs for the rest of the process. Until it is
+/// installed, `time` runs its closure without touching the clock.
+static PROFILE: OnceLock<StatsProfile> = OnceLock::new();
+
+/// Phases of the stats pipeline that `--profile` reports on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StatsPhase {
+    /// Diffing each commit against its parent (or the range ends) for added and deleted lines
+    Diff,
+    /// Reading and parsing authorship notes
+    Notes,
+    /// Intersecting added lines with attestations, blaming deletions and counting characters
+    AttributionMatch,
+    /// Rendering the stats as JSON, CSV, tables or terminal output
+    Serialization,
+}
+
+impl StatsPhase {
+    pub const ALL: [StatsPhase; 4] = [
+        StatsPhase::Diff,
+        StatsPhase::Notes,
+        StatsPhase::AttributionMatch,
+        StatsPhase::Serialization,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatsPhase::Diff => "diff",
+            StatsPhase::Notes => "notes",
+            StatsPhase::AttributionMatch => "attribution match",
+            StatsPhase::Serialization => "serialization",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct PhaseTiming {
+    total: Duration,
+    calls: u32,
+}
+
+/// Wall-clock time spent in each phase, summed over every commit. With several jobs the
+/// phases of different commits overlap, so the totals can exceed the elapsed time.
+#[derive(Debug, Default)]
+pub struct StatsProfile {
+    phases: Mutex<BTreeMap<StatsPhase, PhaseTiming>>,
+}
+
+impl StatsProfile {
+    pub fn record(&self, phase: StatsPhase, elapsed: Duration) {
+        let mut phases = self.phases.lock().unwrap();
+        let timing = phases.entry(phase).or_default();
+        timing.total += elapsed;
+        timing.calls += 1;
+    }
+
+    /// One line per phase, in pipeline order, including phases that never ran
+    pub fn report(&self) -> String {
+        let phases = self.phases.lock().unwrap();
+        let mut output = String::from("stats profile:\n");
+        for phase in StatsPhase::ALL {
+            let timing = phases.get(&phase).copied().unwrap_or_default();
+            output.push_str(&format!(
+                "  {:<18} {:>10.2}ms  {:>6} call(s)\n",
+                phase.name(),
+                timing.total.as_secs_f64() * 1000.0,
+                timing.calls
+            ));
+        }
+        output
+    }
+}
+
+/// Start collecting timings for the rest of the process
+pub fn enable() -> &'static StatsProfile {
+    PROFILE.get_or_init(StatsProfile::default)
+}
+
+/// The collector installed by `enable`, if any
+pub fn enabled() -> Option<&'static StatsProfile> {
+    PROFILE.get()
+}
+
+/// Run `f`, adding its wall-clock time to `phase` when profiling is enabled
+pub fn time<T>(phase: StatsPhase, f: impl FnOnce() -> T) -> T {
+    let Some(profile) = PROFILE.get() else {
+        return f();
+    };
+    let start = Instant::now();
+    let result = f();
+    profile.record(phase, start.elapsed());
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_lists_every_phase_in_order() {
+        let profile = StatsProfile::default();
+        profile.record(StatsPhase::AttributionMatch, Duration::from_millis(3));
+        profile.record(StatsPhase::AttributionMatch, Duration::from_millis(2));
+
+        let report = profile.report();
+        let positions: Vec<usize> = StatsPhase::ALL
+            .iter()
+            .map(|phase| report.find(phase.name()).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(report.contains("attribution match"));
+        assert!(report.contains("5.00ms"));
+        assert!(report.contains("2 call(s)"));
+    }
+}
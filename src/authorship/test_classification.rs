@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use crate::authorship::ignore::only_matching_patterns;
+use crate::authorship::stats::CommitStats;
+use crate::error::GitAiError;
+use crate::git::repository::Repository;
+
+/// Repository git config key overriding the default test path patterns (comma-separated globs)
+pub const TEST_PATTERNS_CONFIG_KEY: &str = "git-ai.testPatterns";
+
+const DEFAULT_TEST_PATTERNS: &[&str] = &["**/tests/**", "*_test.*", "test_*"];
+
+/// Stats split between paths matching the test patterns and everything else
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClassifiedStats {
+    pub test: CommitStats,
+    pub production: CommitStats,
+}
+
+/// Globs that mark a path as test code: `user_patterns` when given, else `git-ai.testPatterns`,
+/// else the defaults. Globs match the full path or the file name, like ignore patterns.
+pub fn test_patterns(repo: &Repository, user_patterns: &[String]) -> Vec<String> {
+    if !user_patterns.is_empty() {
+        return user_patterns.to_vec();
+    }
+    if let Ok(Some(value)) = repo.config_get_str(TEST_PATTERNS_CONFIG_KEY) {
+        let configured: Vec<String> = value
+            .split(',')
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .map(str::to_string)
+            .collect();
+        if !configured.is_empty() {
+            return configured;
+        }
+    }
+    DEFAULT_TEST_PATTERNS
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .collect()
+}
+
+/// Run `stats_for` once per bucket. Test stats only see paths matching `test_patterns`, and
+/// production stats see the rest; `ignore_patterns` still apply to both.
+pub fn classify_test_stats(
+    test_patterns: &[String],
+    ignore_patterns: &[String],
+    stats_for: impl Fn(&[String]) -> Result<CommitStats, GitAiError>,
+) -> Result<ClassifiedStats, GitAiError> {
+    let mut test_only = only_matching_patterns(test_patterns);
+    test_only.extend(ignore_patterns.iter().cloned());
+
+    let mut production_only = ignore_patterns.to_vec();
+    production_only.extend(test_patterns.iter().cloned());
+
+    Ok(ClassifiedStats {
+        test: stats_for(&test_only)?,
+        production: stats_for(&production_only)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::ignore::should_ignore_file;
+
+    #[test]
+    fn test_buckets_partition_paths() {
+        let patterns: Vec<String> = DEFAULT_TEST_PATTERNS
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let classified = classify_test_stats(&patterns, &["*.lock".to_string()], |patterns| {
+            let kept = [
+                "src/lib.rs",
+                "tests/stats.rs",
+                "crates/a/tests/it.rs",
+                "src/parser_test.go",
+                "test_utils.py",
+                "Cargo.lock",
+            ]
+            .iter()
+            .filter(|path| !should_ignore_file(path, patterns))
+            .count();
+            Ok(CommitStats {
+                ai_additions: kept as u32,
+                ..Default::default()
+            })
+        })
+        .unwrap();
+
+        // Four test paths, one production path, and the lock file in neither
+        assert_eq!(classified.test.ai_additions, 4);
+        assert_eq!(classified.production.ai_additions, 1);
+    }
+}
@@ -1,12 +1,14 @@
 use crate::authorship::attribution_tracker::{
-    Attribution, LineAttribution, line_attributions_to_attributions,
+    Attribution, LineAttribution, line_attributions_to_attributions, line_author_char_runs,
 };
-use crate::authorship::authorship_log::{LineRange, PromptRecord};
+use crate::authorship::authorship_log::{LineRange, PromptRecord, suggested_line_hash};
+use crate::authorship::authorship_log_serialization::CharRange;
 use crate::authorship::working_log::CheckpointKind;
 use crate::commands::blame::{GitAiBlameOptions, OLDEST_AI_BLAME_DATE};
 use crate::error::GitAiError;
 use crate::git::repository::Repository;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::utils::{is_binary_content, nfc_path};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -278,6 +280,35 @@ impl VirtualAttributions {
         self.file_contents.get(file_path)
     }
 
+    /// Prompts of the AI-attributed lines in the latest snapshot of `file_path`, keyed by the
+    /// line's text and in line order. Matching by text keeps edits made since the last
+    /// checkpoint that shift lines from losing their attribution; each attributed line should
+    /// be matched at most once.
+    pub fn ai_line_prompts_by_text(&self, file_path: &str) -> HashMap<&str, VecDeque<&str>> {
+        let mut prompts_by_text: HashMap<&str, VecDeque<&str>> = HashMap::new();
+        let (Some(content), Some(attributions)) = (
+            self.get_file_content(file_path),
+            self.get_line_attributions(file_path),
+        ) else {
+            return prompts_by_text;
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        for attribution in attributions {
+            if !self.prompts.contains_key(&attribution.author_id) {
+                continue;
+            }
+            for line in attribution.start_line..=attribution.end_line {
+                if let Some(text) = lines.get((line as usize).saturating_sub(1)) {
+                    prompts_by_text
+                        .entry(*text)
+                        .or_default()
+                        .push_back(&attribution.author_id);
+                }
+            }
+        }
+        prompts_by_text
+    }
+
     /// Get a reference to the repository
     pub fn repo(&self) -> &Repository {
         &self.repo
@@ -309,10 +340,16 @@ impl VirtualAttributions {
         // Track additions and deletions per session_id for metrics
         let mut session_additions: HashMap<String, u32> = HashMap::new();
         let mut session_deletions: HashMap<String, u32> = HashMap::new();
+        // Hashes of the lines each session suggested, before any later edits
+        let mut session_suggestions: HashMap<String, BTreeSet<String>> = HashMap::new();
 
         // Add prompts from INITIAL attributions
         // These are uncommitted prompts, so we use an empty string as the commit_sha
         for (prompt_id, prompt_record) in &initial_attributions.prompts {
+            session_suggestions
+                .entry(prompt_id.clone())
+                .or_default()
+                .extend(prompt_record.suggested_line_hashes.iter().cloned());
             prompts
                 .entry(prompt_id.clone())
                 .or_insert_with(BTreeMap::new)
@@ -339,6 +376,8 @@ impl VirtualAttributions {
 
         // Collect attributions from all checkpoints (later checkpoints override earlier ones)
         for checkpoint in &checkpoints {
+            let mut suggesting_author = None;
+
             // Add prompts from checkpoint
             if let Some(agent_id) = &checkpoint.agent_id {
                 let author_id =
@@ -362,6 +401,8 @@ impl VirtualAttributions {
                     accepted_lines: 0,
                     overriden_lines: 0,
                     messages_url: None,
+                    session_id: checkpoint.session_id.clone(),
+                    suggested_line_hashes: Vec::new(),
                 };
 
                 prompts
@@ -374,6 +415,7 @@ impl VirtualAttributions {
                     checkpoint.line_stats.additions;
                 *session_deletions.entry(author_id.clone()).or_insert(0) +=
                     checkpoint.line_stats.deletions;
+                suggesting_author = Some(author_id);
             }
 
             // Collect attributions from checkpoint entries
@@ -384,6 +426,24 @@ impl VirtualAttributions {
                     continue;
                 }
 
+                // In the agent's own checkpoint, its lines are still exactly as it suggested them
+                if let Some(author_id) = &suggesting_author
+                    && let Ok(content) = working_log.get_file_version(&entry.blob_sha)
+                {
+                    let suggestions = session_suggestions.entry(author_id.clone()).or_default();
+                    let lines: Vec<&str> = content.lines().collect();
+                    for line_attr in &entry.line_attributions {
+                        if &line_attr.author_id != author_id {
+                            continue;
+                        }
+                        for line in line_attr.start_line..=line_attr.end_line {
+                            if let Some(text) = lines.get(line.saturating_sub(1) as usize) {
+                                suggestions.insert(suggested_line_hash(text));
+                            }
+                        }
+                    }
+                }
+
                 // Get the latest file content from working directory
                 if let Ok(workdir) = repo.workdir() {
                     let abs_path = workdir.join(&entry.file);
@@ -424,6 +484,13 @@ impl VirtualAttributions {
             &session_additions,
             &session_deletions,
         );
+        for (session_id, suggestions) in session_suggestions {
+            if let Some(commits) = prompts.get_mut(&session_id) {
+                for prompt_record in commits.values_mut() {
+                    prompt_record.suggested_line_hashes = suggestions.iter().cloned().collect();
+                }
+            }
+        }
 
         Ok(VirtualAttributions {
             repo,
@@ -624,16 +691,27 @@ fn collect_committed_hunks(
 
         for (file_path, lines) in added_lines {
             if !lines.is_empty() {
-                committed_hunks.insert(file_path, LineRange::compress_lines(&lines));
+                committed_hunks.insert(
+                    nfc_path(&file_path).into_owned(),
+                    LineRange::compress_lines(&lines),
+                );
             }
         }
         return Ok(committed_hunks);
     }
 
-    // Use git diff to get added lines directly
-    let added_lines = repo.diff_added_lines(parent_sha, commit_sha, pathspecs)?;
+    // Follow renames so lines a renamed file carries over keep their earlier attestations and
+    // only the lines edited on the way count as committed here. Pathspecs are applied afterwards
+    // because limiting the diff to the new paths would hide the renames' old sides.
+    let added_lines =
+        repo.diff_added_lines_following_renames(parent_sha, commit_sha, false, None)?;
 
     for (file_path, lines) in added_lines {
+        // Working log entries store paths in NFC
+        let file_path = nfc_path(&file_path).into_owned();
+        if pathspecs.is_some_and(|paths| !paths.contains(&file_path)) {
+            continue;
+        }
         if !lines.is_empty() {
             committed_hunks.insert(file_path, LineRange::compress_lines(&lines));
         }
@@ -810,9 +888,70 @@ impl VirtualAttributions {
         // Remove files with no unstaged hunks
         unstaged_hunks.retain(|_, ranges| !ranges.is_empty());
 
+        // Links this commit added or changed; their diff "line" is a link target or, for
+        // submodules, a commit id
+        let committed_links = repo.diff_link_paths(
+            if parent_sha == "initial" {
+                "4b825dc642cb6eb9a060e54bf8d69288fbee4904"
+            } else {
+                parent_sha
+            },
+            commit_sha,
+        )?;
+
         // Process each file
-        for (file_path, (_, line_attrs)) in &self.attributions {
-            if line_attrs.is_empty() {
+        for (file_path, (char_attrs, line_attrs)) in &self.attributions {
+            // A submodule pointer is not authored in this repository
+            if line_attrs.is_empty() || committed_links.gitlinks.contains(file_path) {
+                continue;
+            }
+
+            // Binary files and symlinks have no lines to attest, so when this commit changed one
+            // it is attested as a whole to the prompts that touched it
+            let is_symlink = committed_links.symlinks.contains(file_path);
+            let binary_changed = || {
+                self.file_contents
+                    .get(file_path)
+                    .is_some_and(|content| is_binary_content(content.as_bytes()))
+                    .then(|| repo.get_file_content(file_path, commit_sha).ok())
+                    .flatten()
+                    .filter(|content| is_binary_content(content))
+                    .map(|content| {
+                        parent_sha == "initial"
+                            || repo
+                                .get_file_content(file_path, parent_sha)
+                                .ok()
+                                .is_none_or(|parent_content| parent_content != content)
+                    })
+            };
+            let whole_file_changed = if is_symlink {
+                // Deleted links show up in the diff too, but there is nothing left to attest
+                Some(repo.get_file_content(file_path, commit_sha).is_ok())
+            } else {
+                binary_changed()
+            };
+            if let Some(changed) = whole_file_changed {
+                let mut authors: Vec<&String> = line_attrs
+                    .iter()
+                    .map(|line_attr| &line_attr.author_id)
+                    .filter(|author_id| *author_id != CheckpointKind::Human.to_str())
+                    .collect();
+                authors.sort();
+                authors.dedup();
+
+                if changed && !authors.is_empty() {
+                    let file_attestation = authorship_log.get_or_create_file(file_path);
+                    file_attestation.is_symlink = is_symlink;
+                    file_attestation.is_binary = !is_symlink;
+                    for author_id in authors {
+                        file_attestation.add_entry(
+                            crate::authorship::authorship_log_serialization::AttestationEntry::new(
+                                author_id.clone(),
+                                Vec::new(),
+                            ),
+                        );
+                    }
+                }
                 continue;
             }
 
@@ -830,6 +969,8 @@ impl VirtualAttributions {
             // so we need to convert to commit coordinates before comparing with committed hunks
             let mut committed_lines_map: StdHashMap<String, Vec<u32>> = StdHashMap::new();
             let mut uncommitted_lines_map: StdHashMap<String, Vec<u32>> = StdHashMap::new();
+            // Committed lines a human edit flipped away from AI, as (workdir line, commit line)
+            let mut overridden_lines: Vec<(u32, u32)> = Vec::new();
 
             // Get the committed hunks for this file (if any) - these are in commit coordinates
             let file_committed_hunks = committed_hunks.get(file_path);
@@ -869,6 +1010,10 @@ impl VirtualAttributions {
                                 .entry(line_attr.author_id.clone())
                                 .or_default()
                                 .push(commit_line_num);
+
+                            if line_attr.overrode.is_some() {
+                                overridden_lines.push((workdir_line_num, commit_line_num));
+                            }
                         }
                         // Note: Lines that are neither unstaged nor in committed_hunks are lines that
                         // already existed in the parent commit. They are discarded (not added to uncommitted).
@@ -876,6 +1021,35 @@ impl VirtualAttributions {
                 }
             }
 
+            // Keep the AI-written characters of those lines as partial-line attestations
+            let mut committed_chars_map: StdHashMap<String, Vec<CharRange>> = StdHashMap::new();
+            if !overridden_lines.is_empty()
+                && let Some(content) = self.file_contents.get(file_path)
+            {
+                let workdir_lines: Vec<u32> = overridden_lines
+                    .iter()
+                    .map(|(workdir, _)| *workdir)
+                    .collect();
+                let runs_by_line = line_author_char_runs(char_attrs, content, &workdir_lines);
+                for (workdir_line_num, commit_line_num) in &overridden_lines {
+                    let Some(runs) = runs_by_line.get(workdir_line_num) else {
+                        continue;
+                    };
+                    for (start, end, author_id) in runs {
+                        if *author_id != CheckpointKind::Human.to_str() {
+                            committed_chars_map
+                                .entry(author_id.clone())
+                                .or_default()
+                                .push(CharRange {
+                                    line: *commit_line_num,
+                                    start: *start,
+                                    end: *end,
+                                });
+                        }
+                    }
+                }
+            }
+
             // Add committed attributions to authorship log
             if !committed_lines_map.is_empty() {
                 // Create attestation entries from committed lines
@@ -928,16 +1102,30 @@ impl VirtualAttributions {
                         ));
                     }
 
+                    let char_ranges = committed_chars_map.remove(&author_id).unwrap_or_default();
                     let entry =
                         crate::authorship::authorship_log_serialization::AttestationEntry::new(
                             author_id, ranges,
-                        );
+                        )
+                        .with_char_ranges(char_ranges);
 
                     let file_attestation = authorship_log.get_or_create_file(file_path);
                     file_attestation.add_entry(entry);
                 }
             }
 
+            // Prompts whose only contribution to this file is part of human-edited lines
+            for (author_id, char_ranges) in committed_chars_map {
+                let entry = crate::authorship::authorship_log_serialization::AttestationEntry::new(
+                    author_id,
+                    Vec::new(),
+                )
+                .with_char_ranges(char_ranges);
+                authorship_log
+                    .get_or_create_file(file_path)
+                    .add_entry(entry);
+            }
+
             // Add uncommitted attributions to INITIAL
             if !uncommitted_lines_map.is_empty() {
                 // Convert the map into line attributions
@@ -1016,8 +1204,6 @@ impl VirtualAttributions {
     ///
     /// This is useful for retroactively generating authorship logs from working logs
     /// where we know the commit has landed and don't care about uncommitted work.
-    // only being used by stats-delta in a fork
-    #[allow(dead_code)]
     pub fn to_authorship_log_index_only(
         &self,
         repo: &Repository,
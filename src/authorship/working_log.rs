@@ -1,6 +1,8 @@
 use crate::authorship::attribution_tracker::{Attribution, LineAttribution};
 use crate::authorship::authorship_log_serialization::GIT_AI_VERSION;
 use crate::authorship::transcript::AiTranscript;
+use crate::utils::nfc_path;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
@@ -31,7 +33,8 @@ impl WorkingLogEntry {
         line_attributions: Vec<LineAttribution>,
     ) -> Self {
         Self {
-            file,
+            // Stored in NFC to match the paths git and the authorship logs use
+            file: nfc_path(&file).into_owned(),
             blob_sha,
             attributions,
             line_attributions,
@@ -39,7 +42,7 @@ impl WorkingLogEntry {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct AgentId {
     pub tool: String, // e.g., "cursor", "windsurf"
     pub id: String,   // id in their domain
@@ -118,6 +121,9 @@ pub struct Checkpoint {
     pub api_version: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub git_ai_version: Option<String>,
+    /// Session id an agent wrapper set in `GIT_AI_SESSION_ID` when this checkpoint was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
 }
 
 impl Checkpoint {
@@ -144,6 +150,7 @@ impl Checkpoint {
             line_stats: CheckpointLineStats::default(),
             api_version: CHECKPOINT_API_VERSION.to_string(),
             git_ai_version: Some(GIT_AI_VERSION.to_string()),
+            session_id: None,
         }
     }
 }
@@ -1,7 +1,9 @@
 use crate::auth::CredentialStore;
 use crate::authorship::authorship_log::PromptRecord;
 use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::deleted_lines::{deleted_lines, print_deleted_lines};
 use crate::authorship::prompt_utils::enrich_prompt_messages;
+use crate::authorship::stats::get_git_diff_stats;
 use crate::authorship::working_log::CheckpointKind;
 use crate::error::GitAiError;
 use crate::git::refs::get_reference_as_authorship_log_v3;
@@ -11,7 +13,7 @@ use crate::git::repository::{exec_git, exec_git_stdin};
 use crate::utils::normalize_to_posix;
 use chrono::{DateTime, FixedOffset, TimeZone, Utc};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io::{self, IsTerminal, Write};
 use std::sync::LazyLock;
@@ -99,6 +101,8 @@ pub struct GitAiBlameOptions {
     // Color options
     pub color_lines: bool,
     pub color_by_age: bool,
+    /// `--color`/`--no-color`; `None` leaves it to `NO_COLOR` and the terminal
+    pub color: Option<bool>,
 
     // Progress options
     pub progress: bool,
@@ -146,6 +150,15 @@ pub struct GitAiBlameOptions {
     // When true, a single git blame hunk may be split into multiple hunks
     // if different lines were authored by different humans working with AI
     pub split_hunks_by_ai_author: bool,
+
+    // List the lines deleted by the commits in this revision range instead of blaming
+    pub show_deleted: Option<String>,
+
+    // Attribute uncommitted lines from the working log, as the next commit would
+    pub working_tree: bool,
+
+    // Annotate each hunk with the AI and human deletions of the commit that introduced it
+    pub with_churn: bool,
 }
 
 impl Default for GitAiBlameOptions {
@@ -176,6 +189,7 @@ impl Default for GitAiBlameOptions {
             no_ignore_revs_file: false,
             color_lines: false,
             color_by_age: false,
+            color: None,
             progress: false,
             date_format: None,
             contents_file: None,
@@ -191,8 +205,56 @@ impl Default for GitAiBlameOptions {
             mark_unknown: false,
             show_prompt: false,
             split_hunks_by_ai_author: true,
+            show_deleted: None,
+            working_tree: false,
+            with_churn: false,
+        }
+    }
+}
+
+/// Lines a commit deleted, split by the AI deletions recorded in its authorship log
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CommitChurn {
+    pub ai_deletions: u32,
+    pub human_deletions: u32,
+}
+
+/// The churn of `commit_sha`: AI deletions are summed over the prompts in its authorship log
+/// and the rest of the lines its diff deletes are counted as human. Commits without a log have
+/// no AI deletions.
+pub fn commit_churn(repo: &Repository, commit_sha: &str) -> Result<CommitChurn, GitAiError> {
+    let ai_deletions = get_reference_as_authorship_log_v3(repo, commit_sha)
+        .map(|log| {
+            log.metadata
+                .prompts
+                .values()
+                .map(|prompt| prompt.total_deletions)
+                .sum()
+        })
+        .unwrap_or(0);
+    let (_, deleted) = get_git_diff_stats(repo, commit_sha, &[], false, None)?;
+    Ok(CommitChurn {
+        ai_deletions,
+        human_deletions: deleted.saturating_sub(ai_deletions),
+    })
+}
+
+/// Churn of every commit that introduced one of `blame_hunks`, skipping uncommitted lines
+fn churn_by_commit(
+    repo: &Repository,
+    blame_hunks: &[BlameHunk],
+) -> Result<BTreeMap<String, CommitChurn>, GitAiError> {
+    let mut churn = BTreeMap::new();
+    for hunk in blame_hunks {
+        if hunk.commit_sha.bytes().all(|b| b == b'0') || churn.contains_key(&hunk.commit_sha) {
+            continue;
         }
+        churn.insert(
+            hunk.commit_sha.clone(),
+            commit_churn(repo, &hunk.commit_sha)?,
+        );
     }
+    Ok(churn)
 }
 
 impl Repository {
@@ -262,11 +324,21 @@ impl Repository {
                 .to_string()
         };
 
+        if let Some(ref revision_range) = options.show_deleted {
+            let deleted = deleted_lines(self, &relative_file_path, revision_range)?;
+            if options.json {
+                println!("{}", serde_json::to_string(&deleted)?);
+            } else if !options.no_output {
+                print_deleted_lines(&deleted);
+            }
+            return Ok((HashMap::new(), HashMap::new()));
+        }
+
         // For JSON output, default to HEAD to exclude uncommitted changes
         // and use prompt hashes as names so we can correlate with prompt_records
         let options = if options.json {
             let mut opts = options.clone();
-            if opts.newest_commit.is_none() {
+            if opts.newest_commit.is_none() && !opts.working_tree {
                 opts.newest_commit = Some("HEAD".to_string());
             }
             opts.use_prompt_hashes_as_names = true;
@@ -279,6 +351,18 @@ impl Repository {
             options.clone()
         };
 
+        // A bare repository has no working tree to read from, so blame defaults to HEAD there
+        let options = if options.newest_commit.is_none()
+            && options.contents_data.is_none()
+            && self.is_bare_repository()?
+        {
+            let mut opts = options;
+            opts.newest_commit = Some("HEAD".to_string());
+            opts
+        } else {
+            options
+        };
+
         // Read file content from one of:
         // 1. Provided contents_data (from --contents flag)
         // 2. A specific commit
@@ -359,22 +443,43 @@ impl Repository {
         }
 
         // Step 2: Overlay AI authorship information
-        let (line_authors, prompt_records, authorship_logs, prompt_commits) =
+        let (mut line_authors, mut prompt_records, authorship_logs, prompt_commits) =
             overlay_ai_authorship(self, &all_blame_hunks, &relative_file_path, &options)?;
+        if options.working_tree {
+            overlay_working_tree_authorship(
+                self,
+                &all_blame_hunks,
+                &relative_file_path,
+                &lines,
+                &options,
+                &mut line_authors,
+                &mut prompt_records,
+            )?;
+        }
 
         if options.no_output {
             return Ok((line_authors, prompt_records));
         }
 
+        let churn = if options.with_churn {
+            churn_by_commit(self, &all_blame_hunks)?
+        } else {
+            BTreeMap::new()
+        };
+
         // Output based on format
         if options.json {
             output_json_format(
                 self,
+                &all_blame_hunks,
                 &line_authors,
                 &prompt_records,
                 &authorship_logs,
                 &prompt_commits,
                 &relative_file_path,
+                &lines,
+                &line_ranges,
+                &churn,
             )?;
         } else if options.porcelain || options.line_porcelain {
             output_porcelain_format(
@@ -403,6 +508,7 @@ impl Repository {
                 &lines,
                 &line_ranges,
                 &options,
+                &churn,
             )?;
         }
 
@@ -809,7 +915,7 @@ impl Repository {
 }
 
 #[allow(clippy::type_complexity)]
-fn overlay_ai_authorship(
+pub(crate) fn overlay_ai_authorship(
     repo: &Repository,
     blame_hunks: &[BlameHunk],
     file_path: &str,
@@ -934,6 +1040,59 @@ fn overlay_ai_authorship(
     ))
 }
 
+/// Attribute the lines git blames as not committed yet from the working log, the same data
+/// the next commit's authorship log is built from. A line is AI when its text matches a line
+/// the working log attributes to a prompt; the rest stay as git reports them.
+fn overlay_working_tree_authorship(
+    repo: &Repository,
+    blame_hunks: &[BlameHunk],
+    file_path: &str,
+    lines: &[&str],
+    options: &GitAiBlameOptions,
+    line_authors: &mut HashMap<u32, String>,
+    prompt_records: &mut HashMap<String, PromptRecord>,
+) -> Result<(), GitAiError> {
+    let uncommitted_lines: Vec<u32> = blame_hunks
+        .iter()
+        .filter(|hunk| hunk.commit_sha.bytes().all(|b| b == b'0'))
+        .flat_map(|hunk| hunk.range.0..=hunk.range.1)
+        .collect();
+    if uncommitted_lines.is_empty() {
+        return Ok(());
+    }
+
+    let head_sha = repo.head()?.target()?;
+    let working_va = VirtualAttributions::from_just_working_log(repo.clone(), head_sha, None)?;
+    let mut ai_prompts = working_va.ai_line_prompts_by_text(file_path);
+
+    for line_num in uncommitted_lines {
+        let prompt = lines
+            .get((line_num as usize).saturating_sub(1))
+            .and_then(|text| ai_prompts.get_mut(text))
+            .and_then(|prompts| prompts.pop_front())
+            .and_then(|prompt_hash| {
+                let record = working_va.prompts().get(prompt_hash)?.values().next()?;
+                Some((prompt_hash, record))
+            });
+        match prompt {
+            Some((prompt_hash, record)) => {
+                let author = if options.use_prompt_hashes_as_names {
+                    prompt_hash.to_string()
+                } else {
+                    record.agent_id.tool.clone()
+                };
+                line_authors.insert(line_num, author);
+                prompt_records.insert(prompt_hash.to_string(), record.clone());
+            }
+            None if options.return_human_authors_as_human => {
+                line_authors.insert(line_num, CheckpointKind::Human.to_str().to_string());
+            }
+            None => {}
+        }
+    }
+    Ok(())
+}
+
 /// Metadata about user's auth state and git identity
 #[derive(Debug, Serialize)]
 struct BlameMetadata {
@@ -941,12 +1100,32 @@ struct BlameMetadata {
     current_user: Option<String>,
 }
 
+/// Whether a blamed line is attributed to an AI prompt or to a human
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BlameAuthorType {
+    Ai,
+    Human,
+}
+
+/// One entry per blamed line, for consumers that don't want to expand the `lines` ranges
+#[derive(Debug, Serialize)]
+struct JsonBlameLine {
+    line_number: u32,
+    content: String,
+    author_type: BlameAuthorType,
+    commit_sha: String,
+}
+
 /// JSON output structure for blame
 #[derive(Debug, Serialize)]
 struct JsonBlameOutput {
     lines: std::collections::BTreeMap<String, String>,
+    line_details: Vec<JsonBlameLine>,
     prompts: HashMap<String, PromptRecordWithOtherFiles>,
     metadata: BlameMetadata,
+    /// Deletions of each blamed commit, keyed by SHA (only with `--with-churn`)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    churn: BTreeMap<String, CommitChurn>,
 }
 
 /// Read model that patches PromptRecord with other_files and commits fields
@@ -990,13 +1169,18 @@ fn get_files_for_prompt_hash(
     file_vec
 }
 
+#[allow(clippy::too_many_arguments)]
 fn output_json_format(
     repo: &Repository,
+    blame_hunks: &[BlameHunk],
     line_authors: &HashMap<u32, String>,
     prompt_records: &HashMap<String, PromptRecord>,
     authorship_logs: &[AuthorshipLog],
     prompt_commits: &HashMap<String, Vec<String>>,
     current_file: &str,
+    lines: &[&str],
+    line_ranges: &[(u32, u32)],
+    churn: &BTreeMap<String, CommitChurn>,
 ) -> Result<(), GitAiError> {
     // Filter to only AI lines (where author is a prompt_id in prompt_records)
     let mut ai_lines: Vec<(u32, String)> = line_authors
@@ -1091,11 +1275,19 @@ fn output_json_format(
 
     let output = JsonBlameOutput {
         lines: lines_map,
+        line_details: json_line_details(
+            blame_hunks,
+            line_authors,
+            prompt_records,
+            lines,
+            line_ranges,
+        ),
         prompts: filtered_prompts,
         metadata: BlameMetadata {
             is_logged_in,
             current_user,
         },
+        churn: churn.clone(),
     };
 
     let json_str = serde_json::to_string_pretty(&output)
@@ -1105,6 +1297,39 @@ fn output_json_format(
     Ok(())
 }
 
+fn json_line_details(
+    blame_hunks: &[BlameHunk],
+    line_authors: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+    lines: &[&str],
+    line_ranges: &[(u32, u32)],
+) -> Vec<JsonBlameLine> {
+    let mut details = Vec::new();
+    for (start_line, end_line) in line_ranges {
+        for line_num in *start_line..=*end_line {
+            let commit_sha = blame_hunks
+                .iter()
+                .find(|hunk| line_num >= hunk.range.0 && line_num <= hunk.range.1)
+                .map(|hunk| hunk.commit_sha.clone())
+                .unwrap_or_default();
+            let author_type = match line_authors.get(&line_num) {
+                Some(author) if prompt_records.contains_key(author) => BlameAuthorType::Ai,
+                _ => BlameAuthorType::Human,
+            };
+            details.push(JsonBlameLine {
+                line_number: line_num,
+                content: lines
+                    .get((line_num - 1) as usize)
+                    .unwrap_or(&"")
+                    .to_string(),
+                author_type,
+                commit_sha,
+            });
+        }
+    }
+    details
+}
+
 fn output_porcelain_format(
     repo: &Repository,
     _line_authors: &HashMap<u32, String>,
@@ -1309,6 +1534,7 @@ fn output_incremental_format(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn output_default_format(
     repo: &Repository,
     line_authors: &HashMap<u32, String>,
@@ -1317,6 +1543,7 @@ fn output_default_format(
     lines: &[&str],
     line_ranges: &[(u32, u32)],
     options: &GitAiBlameOptions,
+    churn: &BTreeMap<String, CommitChurn>,
 ) -> Result<(), GitAiError> {
     let mut output = String::new();
 
@@ -1335,6 +1562,19 @@ fn output_default_format(
         }
     }
 
+    // Churn is shown on the first line of each hunk, padded so the line numbers stay aligned
+    let churn_display = |hunk: &BlameHunk, line_num: u32| match churn.get(&hunk.commit_sha) {
+        Some(c) if line_num == hunk.range.0 => {
+            format!("-{} AI -{} human", c.ai_deletions, c.human_deletions)
+        }
+        _ => String::new(),
+    };
+    let churn_width = line_to_hunk
+        .iter()
+        .map(|(line_num, hunk)| churn_display(hunk, *line_num).len())
+        .max()
+        .unwrap_or(0);
+
     // Calculate the maximum line number width for proper padding
     let max_line_num = lines.len() as u32;
     let line_num_width = max_line_num.to_string().len();
@@ -1402,7 +1642,15 @@ fn output_default_format(
                 let author = line_authors.get(&line_num).unwrap_or(&hunk.original_author);
 
                 // Format date according to options
-                let date_str = format_blame_date(hunk.author_time, &hunk.author_tz, options);
+                let mut date_str = format_blame_date(hunk.author_time, &hunk.author_tz, options);
+                if options.with_churn {
+                    date_str = format!(
+                        "{} {:<width$}",
+                        date_str,
+                        churn_display(hunk, line_num),
+                        width = churn_width
+                    );
+                }
 
                 // Handle different output formats based on flags
                 let author_display = if options.suppress_author {
@@ -1610,9 +1858,13 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
     while i < args.len() {
         match args[i].as_str() {
             // Line range options
-            "-L" => {
+            // --range is a spelled-out alias for -L, restricting output to a slice of the file
+            "-L" | "--range" => {
                 if i + 1 >= args.len() {
-                    return Err(GitAiError::Generic("Missing argument for -L".to_string()));
+                    return Err(GitAiError::Generic(format!(
+                        "Missing argument for {}",
+                        args[i]
+                    )));
                 }
                 let range_str = &args[i + 1];
                 if let Some((start, end)) = parse_line_range(range_str) {
@@ -1626,6 +1878,19 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 i += 2;
             }
 
+            arg if arg.starts_with("--range=") => {
+                let range_str = &arg["--range=".len()..];
+                if let Some((start, end)) = parse_line_range(range_str) {
+                    options.line_ranges.push((start, end));
+                } else {
+                    return Err(GitAiError::Generic(format!(
+                        "Invalid line range: {}",
+                        range_str
+                    )));
+                }
+                i += 1;
+            }
+
             // Output format options
             "--porcelain" => {
                 options.porcelain = true;
@@ -1758,6 +2023,18 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 options.color_by_age = true;
                 i += 1;
             }
+            "--color" | "--color=always" => {
+                options.color = Some(true);
+                i += 1;
+            }
+            "--no-color" | "--color=never" => {
+                options.color = Some(false);
+                i += 1;
+            }
+            "--color=auto" => {
+                options.color = None;
+                i += 1;
+            }
 
             // Progress options
             "--progress" => {
@@ -1853,6 +2130,21 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 i += 1;
             }
 
+            "--show-deleted" => {
+                if i + 1 >= args.len() {
+                    return Err(GitAiError::Generic(
+                        "Missing argument for --show-deleted".to_string(),
+                    ));
+                }
+                options.show_deleted = Some(args[i + 1].clone());
+                i += 2;
+            }
+
+            arg if arg.starts_with("--show-deleted=") => {
+                options.show_deleted = Some(arg["--show-deleted=".len()..].to_string());
+                i += 1;
+            }
+
             // Mark unknown authorship
             "--mark-unknown" => {
                 options.mark_unknown = true;
@@ -1865,6 +2157,16 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 i += 1;
             }
 
+            "--working-tree" => {
+                options.working_tree = true;
+                i += 1;
+            }
+
+            "--with-churn" => {
+                options.with_churn = true;
+                i += 1;
+            }
+
             // File path (non-option argument)
             arg if !arg.starts_with('-') => {
                 if file_path.is_none() {
@@ -1887,6 +2189,27 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
     let file_path =
         file_path.ok_or_else(|| GitAiError::Generic("No file path specified".to_string()))?;
 
+    if options.working_tree
+        && (options.newest_commit.is_some()
+            || options.contents_data.is_some()
+            || options.contents_file.is_some())
+    {
+        return Err(GitAiError::Generic(
+            "--working-tree blames the file as it is on disk and cannot be combined with a revision or --contents".to_string(),
+        ));
+    }
+
+    if options.with_churn
+        && (options.porcelain
+            || options.line_porcelain
+            || options.incremental
+            || options.show_deleted.is_some())
+    {
+        return Err(GitAiError::Generic(
+            "--with-churn cannot be combined with --porcelain, --line-porcelain, --incremental or --show-deleted".to_string(),
+        ));
+    }
+
     Ok((file_path, options))
 }
 
@@ -1,5 +1,6 @@
 use crate::authorship::attribution_tracker::{
     Attribution, AttributionTracker, INITIAL_ATTRIBUTION_TS, LineAttribution,
+    reattribute_terminated_last_line,
 };
 use crate::authorship::authorship_log::PromptRecord;
 use crate::authorship::authorship_log_serialization::generate_short_hash;
@@ -16,7 +17,7 @@ use crate::error::GitAiError;
 use crate::git::repo_storage::{PersistedWorkingLog, RepoStorage};
 use crate::git::repository::Repository;
 use crate::git::status::{EntryKind, StatusCode};
-use crate::utils::{debug_log, normalize_to_posix};
+use crate::utils::{debug_log, nfc_path, normalize_to_posix};
 use futures::stream::{self, StreamExt};
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
@@ -41,10 +42,39 @@ struct PreviousFileState {
 
 use crate::authorship::working_log::AgentId;
 
+/// Set by `git-ai checkpoint --max-file-size=<bytes>`, overriding the configured limit
+static MAX_FILE_SIZE_OVERRIDE: std::sync::OnceLock<u64> = std::sync::OnceLock::new();
+
+/// Use `bytes` as the per-file size limit for the rest of the process
+pub fn set_max_file_size_override(bytes: u64) {
+    let _ = MAX_FILE_SIZE_OVERRIDE.set(bytes);
+}
+
+/// Files larger than this are attributed as a whole instead of line by line
+fn max_file_size() -> u64 {
+    match MAX_FILE_SIZE_OVERRIDE.get() {
+        Some(bytes) => *bytes,
+        None => Config::get().max_file_size(),
+    }
+}
+
 /// Emit at most one `agent_usage` metric per prompt every 2.5 minutes.
 /// This is half of the server-side bucketing window.
 const AGENT_USAGE_MIN_INTERVAL_SECS: u64 = 150;
 
+/// Set by agent wrappers to tag the AI checkpoints they record with a session id
+const SESSION_ID_ENV: &str = "GIT_AI_SESSION_ID";
+/// Set by agent wrappers to override the tool name their preset reports. The tool is part of
+/// the prompt hash attestations point at, so it can only be set here and not at commit time
+const TOOL_ENV: &str = "GIT_AI_TOOL";
+
+fn non_empty_env(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
 /// Build EventAttributes with repo metadata.
 /// Reused for both AgentUsage and Checkpoint events.
 fn build_checkpoint_attrs(
@@ -405,8 +435,13 @@ pub fn run(
             && let Some(agent_run) = &agent_run_result
         {
             checkpoint.transcript = Some(agent_run.transcript.clone().unwrap_or_default());
-            checkpoint.agent_id = Some(agent_run.agent_id.clone());
+            let mut agent_id = agent_run.agent_id.clone();
+            if let Some(tool) = non_empty_env(TOOL_ENV) {
+                agent_id.tool = tool;
+            }
+            checkpoint.agent_id = Some(agent_id);
             checkpoint.agent_metadata = agent_run.agent_metadata.clone();
+            checkpoint.session_id = non_empty_env(SESSION_ID_ENV);
         }
         debug_log(&format!(
             "[BENCHMARK] Checkpoint creation took {:?}",
@@ -903,13 +938,17 @@ fn get_checkpoint_entry_for_file(
     let feature_flag_inter_commit_move = Config::get().get_feature_flags().inter_commit_move;
 
     let file_start = Instant::now();
+    // Earlier entries and authorship logs store their paths in NFC
+    let stored_path = nfc_path(&file_path);
     let initial_attrs_for_file = initial_attributions
-        .get(&file_path)
+        .get(stored_path.as_ref())
         .cloned()
         .unwrap_or_default();
 
-    let previous_state = previous_file_state_by_file.get(&file_path).cloned();
-    let has_prior_ai_edits = ai_touched_files.contains(&file_path);
+    let previous_state = previous_file_state_by_file
+        .get(stored_path.as_ref())
+        .cloned();
+    let has_prior_ai_edits = ai_touched_files.contains(stored_path.as_ref());
 
     // Pre-commit fast path:
     // If this file has no prior AI attribution and no INITIAL attribution,
@@ -926,6 +965,38 @@ fn get_checkpoint_entry_for_file(
         .read_current_file_content(&file_path)
         .unwrap_or_default();
 
+    // Diffing very large files line by line is slow and their per-line attributions bloat the
+    // working log, so attribute the whole file to this checkpoint's author instead.
+    let limit = max_file_size();
+    if current_content.len() as u64 > limit {
+        let previous_content = if let Some(state) = previous_state.as_ref() {
+            working_log
+                .get_file_version(&state.blob_sha)
+                .unwrap_or_default()
+        } else {
+            get_previous_content_from_head(&repo, &file_path, head_tree_id.as_ref())
+        };
+
+        if current_content == previous_content {
+            return Ok(None);
+        }
+
+        debug_log(&format!(
+            "{} is {} bytes, over the {} byte limit; recording file-level attribution",
+            file_path,
+            current_content.len(),
+            limit
+        ));
+        return Ok(Some(make_file_level_entry(
+            &file_path,
+            &file_content_hash,
+            &author_id,
+            &previous_content,
+            &current_content,
+            ts,
+        )));
+    }
+
     // Non-pre-commit fast path:
     // Preserve existing `git-ai checkpoint` behavior for human-only files by writing an
     // attribution-empty entry while still capturing line stats.
@@ -1296,6 +1367,13 @@ fn make_entry_for_file(
         author_id,
         ts,
     )?;
+    let new_attributions = reattribute_terminated_last_line(
+        previous_content,
+        content,
+        new_attributions,
+        author_id,
+        ts,
+    );
     debug_log(&format!(
         "[BENCHMARK]   update_attributions for {} took {:?}",
         file_path,
@@ -1336,6 +1414,52 @@ fn make_entry_for_file(
     Ok((entry, line_stats))
 }
 
+/// Attribute all of `content` to `author_id` without diffing it against `previous_content`.
+/// Line stats are estimated from the change in line counts.
+fn make_file_level_entry(
+    file_path: &str,
+    blob_sha: &str,
+    author_id: &str,
+    previous_content: &str,
+    content: &str,
+    ts: u128,
+) -> (WorkingLogEntry, FileLineStats) {
+    let attributions = vec![Attribution::new(
+        0,
+        content.len(),
+        author_id.to_string(),
+        ts,
+    )];
+    let line_attributions =
+        crate::authorship::attribution_tracker::attributions_to_line_attributions(
+            &attributions,
+            content,
+        );
+
+    let count_lines = |text: &str| {
+        let lines = text.lines().count() as u32;
+        let sloc = text.lines().filter(|line| !line.trim().is_empty()).count() as u32;
+        (lines, sloc)
+    };
+    let (previous_lines, previous_sloc) = count_lines(previous_content);
+    let (lines, sloc) = count_lines(content);
+    let line_stats = FileLineStats {
+        additions: lines.saturating_sub(previous_lines),
+        deletions: previous_lines.saturating_sub(lines),
+        additions_sloc: sloc.saturating_sub(previous_sloc),
+        deletions_sloc: previous_sloc.saturating_sub(sloc),
+    };
+
+    let entry = WorkingLogEntry::new(
+        file_path.to_string(),
+        blob_sha.to_string(),
+        attributions,
+        line_attributions,
+    );
+
+    (entry, line_stats)
+}
+
 /// Compute line statistics for a single file by diffing previous and current content
 fn compute_file_line_stats(previous_content: &str, current_content: &str) -> FileLineStats {
     let mut stats = FileLineStats::default();
@@ -110,6 +110,11 @@ fn print_config_help() {
     eprintln!("  include_prompts_in_repositories  Repos to include for prompt storage (array)");
     eprintln!("  default_prompt_storage       Fallback storage mode for non-included repos");
     eprintln!("  quiet                        Suppress chart output after commits (bool)");
+    eprintln!("  authorship_signing_key_path  Ed25519 key file used to sign authorship logs");
+    eprintln!("  generated_marker             Header marker for --exclude-generated (@generated)");
+    eprintln!("  max_file_size                Bytes above which files are attributed as a whole");
+    eprintln!("  percentage_decimals          Decimal places of percentages in reports (1)");
+    eprintln!("  authorship_log_format        Encoding of new authorship logs (text/binary)");
     eprintln!();
     eprintln!("Repository Patterns:");
     eprintln!("  For exclude/allow/exclude_prompts_in_repositories, you can provide:");
@@ -304,6 +309,33 @@ fn show_all_config() -> Result<(), String> {
 
     effective_config.insert("quiet".to_string(), Value::Bool(runtime_config.is_quiet()));
 
+    if let Some(path) = runtime_config.authorship_signing_key_path() {
+        effective_config.insert(
+            "authorship_signing_key_path".to_string(),
+            Value::String(path.to_string()),
+        );
+    }
+
+    effective_config.insert(
+        "generated_marker".to_string(),
+        Value::String(runtime_config.generated_marker().to_string()),
+    );
+
+    effective_config.insert(
+        "max_file_size".to_string(),
+        Value::from(runtime_config.max_file_size()),
+    );
+
+    effective_config.insert(
+        "percentage_decimals".to_string(),
+        Value::from(runtime_config.percentage_decimals()),
+    );
+
+    effective_config.insert(
+        "authorship_log_format".to_string(),
+        Value::String(runtime_config.authorship_log_format().as_str().to_string()),
+    );
+
     // Feature flags - show effective flags with defaults applied
     let flags_value = serde_json::to_value(runtime_config.get_feature_flags())
         .unwrap_or_else(|_| Value::Object(serde_json::Map::new()));
@@ -392,6 +424,16 @@ fn get_config_value(key: &str) -> Result<(), String> {
                 }
             }
             "quiet" => Value::Bool(runtime_config.is_quiet()),
+            "authorship_signing_key_path" => match runtime_config.authorship_signing_key_path() {
+                Some(path) => Value::String(path.to_string()),
+                None => Value::Null,
+            },
+            "generated_marker" => Value::String(runtime_config.generated_marker().to_string()),
+            "max_file_size" => Value::from(runtime_config.max_file_size()),
+            "percentage_decimals" => Value::from(runtime_config.percentage_decimals()),
+            "authorship_log_format" => {
+                Value::String(runtime_config.authorship_log_format().as_str().to_string())
+            }
             _ => return Err(format!("Unknown config key: {}", key)),
         };
 
@@ -553,6 +595,50 @@ fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String
                 crate::config::save_file_config(&file_config)?;
                 eprintln!("[quiet]: {}", bool_value);
             }
+            "authorship_signing_key_path" => {
+                file_config.authorship_signing_key_path = Some(value.to_string());
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[authorship_signing_key_path]: {}", value);
+            }
+            "generated_marker" => {
+                file_config.generated_marker = Some(value.to_string());
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[generated_marker]: {}", value);
+            }
+            "max_file_size" => {
+                let bytes = value.parse::<u64>().map_err(|_| {
+                    "Invalid max_file_size value. Expected a number of bytes".to_string()
+                })?;
+                file_config.max_file_size = Some(bytes);
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[max_file_size]: {}", bytes);
+            }
+            "percentage_decimals" => {
+                let decimals = value
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|d| *d <= crate::config::MAX_PERCENTAGE_DECIMALS)
+                    .ok_or_else(|| {
+                        format!(
+                            "Invalid percentage_decimals value. Expected a number from 0 to {}",
+                            crate::config::MAX_PERCENTAGE_DECIMALS
+                        )
+                    })?;
+                file_config.percentage_decimals = Some(decimals);
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[percentage_decimals]: {}", decimals);
+            }
+            "authorship_log_format" => {
+                if crate::config::AuthorshipLogFormat::from_str(value).is_none() {
+                    return Err(
+                        "Invalid authorship_log_format value. Expected 'text' or 'binary'"
+                            .to_string(),
+                    );
+                }
+                file_config.authorship_log_format = Some(value.to_string());
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[authorship_log_format]: {}", value);
+            }
             _ => return Err(format!("Unknown config key: {}", key)),
         }
 
@@ -724,6 +810,41 @@ fn unset_config_value(key: &str) -> Result<(), String> {
                     eprintln!("- [quiet]: {}", v);
                 }
             }
+            "authorship_signing_key_path" => {
+                let old_value = file_config.authorship_signing_key_path.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [authorship_signing_key_path]: {}", v);
+                }
+            }
+            "generated_marker" => {
+                let old_value = file_config.generated_marker.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [generated_marker]: {}", v);
+                }
+            }
+            "max_file_size" => {
+                let old_value = file_config.max_file_size.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [max_file_size]: {}", v);
+                }
+            }
+            "percentage_decimals" => {
+                let old_value = file_config.percentage_decimals.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [percentage_decimals]: {}", v);
+                }
+            }
+            "authorship_log_format" => {
+                let old_value = file_config.authorship_log_format.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [authorship_log_format]: {}", v);
+                }
+            }
             _ => return Err(format!("Unknown config key: {}", key)),
         }
 
@@ -11,7 +11,7 @@ use crate::commands::search::{
     SearchResult, search_by_commit, search_by_commit_range, search_by_file, search_by_pattern,
     search_by_prompt_id,
 };
-use crate::error::GitAiError;
+use crate::error::{GitAiError, exit_code};
 use crate::git::find_repository_in_path;
 use crate::git::repository::{InternalGitProfile, Repository, exec_git, exec_git_with_profile};
 use std::collections::{BTreeMap, HashSet};
@@ -518,7 +518,7 @@ pub fn handle_continue(args: &[String]) {
     // Check for empty results
     if result.prompts.is_empty() {
         eprintln!("No AI prompt history found for the specified context.");
-        std::process::exit(2);
+        std::process::exit(exit_code::NO_RESULTS);
     }
 
     // Gather all session context
@@ -1386,6 +1386,8 @@ mod tests {
             accepted_lines: 0,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         }
     }
 
@@ -2,9 +2,9 @@ use crate::authorship::authorship_log::{LineRange, PromptRecord};
 use crate::commands::blame::GitAiBlameOptions;
 use crate::error::GitAiError;
 use crate::git::repository::{InternalGitProfile, Repository, exec_git_with_profile};
+use crate::utils::{color_enabled, set_color_override};
 use serde::{Deserialize, Serialize, Serializer};
 use std::collections::{BTreeMap, HashMap};
-use std::io::IsTerminal;
 
 // ============================================================================
 // Data Structures
@@ -80,15 +80,19 @@ pub enum Attribution {
 // ============================================================================
 
 pub fn handle_diff(repo: &Repository, args: &[String]) -> Result<(), GitAiError> {
-    if args.is_empty() {
+    if !args.iter().any(|arg| !arg.starts_with("--")) {
         eprintln!("Error: diff requires a commit or commit range argument");
-        eprintln!("Usage: git-ai diff <commit>");
+        eprintln!("Usage: git-ai diff <commit> [--json] [--color|--no-color]");
         eprintln!("       git-ai diff <commit1>..<commit2>");
         std::process::exit(1);
     }
 
     let (spec, format) = parse_diff_args(args)?;
-    let output = execute_diff(repo, spec, format)?;
+    if let Some(color) = parse_color_arg(args)? {
+        set_color_override(color);
+    }
+    let use_color = color_enabled();
+    let output = execute_diff(repo, spec, format, use_color)?;
     print!("{}", output);
 
     Ok(())
@@ -99,7 +103,10 @@ pub fn handle_diff(repo: &Repository, args: &[String]) -> Result<(), GitAiError>
 // ============================================================================
 
 pub fn parse_diff_args(args: &[String]) -> Result<(DiffSpec, DiffFormat), GitAiError> {
-    let arg = &args[0];
+    let arg = args
+        .iter()
+        .find(|arg| !arg.starts_with("--"))
+        .ok_or_else(|| GitAiError::Generic("diff requires a commit or commit range".to_string()))?;
 
     let format = if args.iter().any(|arg| arg == "--json") {
         DiffFormat::Json
@@ -126,6 +133,27 @@ pub fn parse_diff_args(args: &[String]) -> Result<(DiffSpec, DiffFormat), GitAiE
     Ok((DiffSpec::SingleCommit(arg.to_string()), format))
 }
 
+/// `--color`/`--color=always` forces colored output and `--no-color`/`--color=never` turns it
+/// off. `None` means neither was given (or `--color=auto`), so color follows the terminal.
+pub fn parse_color_arg(args: &[String]) -> Result<Option<bool>, GitAiError> {
+    let mut color = None;
+    for arg in args {
+        color = match arg.as_str() {
+            "--color" | "--color=always" => Some(true),
+            "--no-color" | "--color=never" => Some(false),
+            "--color=auto" => None,
+            other if other.starts_with("--color=") => {
+                return Err(GitAiError::Generic(format!(
+                    "Invalid --color value: {}",
+                    &other["--color=".len()..]
+                )));
+            }
+            _ => continue,
+        };
+    }
+    Ok(color)
+}
+
 // ============================================================================
 // Core Execution Logic
 // ============================================================================
@@ -134,6 +162,7 @@ pub fn execute_diff(
     repo: &Repository,
     spec: DiffSpec,
     format: DiffFormat,
+    use_color: bool,
 ) -> Result<String, GitAiError> {
     // Resolve commits to get from/to SHAs
     let (from_commit, to_commit) = match spec {
@@ -165,7 +194,7 @@ pub fn execute_diff(
                 .map_err(|e| GitAiError::Generic(format!("Failed to serialize JSON: {}", e)))?
         }
         DiffFormat::GitCompatibleTerminal => {
-            format_annotated_diff(repo, &from_commit, &to_commit, &attributions)?
+            format_annotated_diff(repo, &from_commit, &to_commit, &attributions, use_color)?
         }
     };
 
@@ -255,11 +284,22 @@ pub fn get_diff_with_line_numbers(
 fn parse_diff_hunks(diff_text: &str) -> Result<Vec<DiffHunk>, GitAiError> {
     let mut hunks = Vec::new();
     let mut current_file = String::new();
+    let mut old_file: Option<String> = None;
+    let mut in_header = false;
 
     for line in diff_text.lines() {
-        if let Some(path_opt) = parse_new_file_path_from_plus_header_line(line) {
-            current_file = path_opt.unwrap_or_default();
+        if line.starts_with("diff --git ") {
+            in_header = true;
+            old_file = None;
+        } else if in_header && let Some(path_opt) = parse_old_file_path_from_minus_header_line(line)
+        {
+            old_file = path_opt;
+        } else if in_header && let Some(path_opt) = parse_new_file_path_from_plus_header_line(line)
+        {
+            // A deleted file only has its old path
+            current_file = path_opt.or_else(|| old_file.clone()).unwrap_or_default();
         } else if line.starts_with("@@ ") {
+            in_header = false;
             // Hunk header
             if let Some(hunk) = parse_hunk_line(line, &current_file)? {
                 hunks.push(hunk);
@@ -271,7 +311,7 @@ fn parse_diff_hunks(diff_text: &str) -> Result<Vec<DiffHunk>, GitAiError> {
 }
 
 fn normalize_diff_path_token(path: &str) -> String {
-    let unescaped = crate::utils::unescape_git_path(path.trim_end());
+    let unescaped = crate::git::gitutil::decode_git_path(path.trim_end());
     let prefixes = ["a/", "b/", "c/", "w/", "i/", "o/"];
     for prefix in prefixes {
         if let Some(stripped) = unescaped.strip_prefix(prefix) {
@@ -289,6 +329,14 @@ fn parse_new_file_path_from_plus_header_line(line: &str) -> Option<Option<String
     Some(Some(normalize_diff_path_token(raw)))
 }
 
+fn parse_old_file_path_from_minus_header_line(line: &str) -> Option<Option<String>> {
+    let raw = line.strip_prefix("--- ")?;
+    if raw.trim_end() == "/dev/null" {
+        return Some(None);
+    }
+    Some(Some(normalize_diff_path_token(raw)))
+}
+
 fn parse_hunk_line(line: &str, file_path: &str) -> Result<Option<DiffHunk>, GitAiError> {
     // Parse hunk header format: @@ -old_start,old_count +new_start,new_count @@
     // Also handles: @@ -old_start +new_start,new_count @@ (single line deletion)
@@ -366,90 +414,110 @@ pub fn overlay_diff_attributions(
 ) -> Result<HashMap<DiffLineKey, Attribution>, GitAiError> {
     let mut attributions = HashMap::new();
 
-    // Group added lines by file
-    let mut lines_by_file: HashMap<String, Vec<u32>> = HashMap::new();
+    // Group added and deleted lines by file
+    let mut added_by_file: HashMap<String, Vec<u32>> = HashMap::new();
+    let mut deleted_by_file: HashMap<String, Vec<u32>> = HashMap::new();
     for hunk in hunks {
         if !hunk.added_lines.is_empty() {
-            lines_by_file
+            added_by_file
                 .entry(hunk.file_path.clone())
                 .or_default()
                 .extend(&hunk.added_lines);
         }
-    }
-
-    // For each file, call blame with the appropriate line ranges
-    for (file_path, mut lines) in lines_by_file {
-        // Sort and convert to contiguous ranges for efficient -L format
-        lines.sort_unstable();
-        lines.dedup();
-        let line_ranges = lines_to_ranges(&lines);
-
-        if line_ranges.is_empty() {
-            continue;
+        if !hunk.deleted_lines.is_empty() {
+            deleted_by_file
+                .entry(hunk.file_path.clone())
+                .or_default()
+                .extend(&hunk.deleted_lines);
         }
+    }
 
-        // Build blame options
+    // Added lines are blamed within the diffed range
+    for (file_path, lines) in added_by_file {
         let mut options = GitAiBlameOptions::default();
         #[allow(clippy::field_reassign_with_default)]
         {
             options.oldest_commit = Some(from_commit.to_string());
             options.newest_commit = Some(to_commit.to_string());
-            options.line_ranges = line_ranges;
-            options.no_output = true;
         }
+        blame_diff_lines(
+            repo,
+            &file_path,
+            lines,
+            options,
+            LineSide::New,
+            &mut attributions,
+        );
+    }
 
-        // Call blame to get attributions
-        let blame_result = repo.blame(&file_path, &options);
-
-        match blame_result {
-            Ok((line_authors, prompt_records)) => {
-                // Map blame results to Attribution enum
-                for line in &lines {
-                    if let Some(author) = line_authors.get(line) {
-                        // Check if this author is an AI tool by looking up in prompt_records
-                        let attribution = if prompt_records
-                            .values()
-                            .any(|pr| &pr.agent_id.tool == author)
-                        {
-                            Attribution::Ai(author.clone())
-                        } else {
-                            Attribution::Human(author.clone())
-                        };
-
-                        let key = DiffLineKey {
-                            file: file_path.clone(),
-                            line: *line,
-                            side: LineSide::New,
-                        };
-                        attributions.insert(key, attribution);
-                    } else {
-                        // No blame data for this line
-                        let key = DiffLineKey {
-                            file: file_path.clone(),
-                            line: *line,
-                            side: LineSide::New,
-                        };
-                        attributions.insert(key, Attribution::NoData);
-                    }
-                }
-            }
-            Err(_) => {
-                // Blame failed, mark all lines as NoData
-                for line in &lines {
-                    let key = DiffLineKey {
-                        file: file_path.clone(),
-                        line: *line,
-                        side: LineSide::New,
-                    };
-                    attributions.insert(key, Attribution::NoData);
-                }
-            }
+    // Deleted lines were written before the range, so they are blamed as of its start
+    for (file_path, lines) in deleted_by_file {
+        let mut options = GitAiBlameOptions::default();
+        #[allow(clippy::field_reassign_with_default)]
+        {
+            options.newest_commit = Some(from_commit.to_string());
         }
+        blame_diff_lines(
+            repo,
+            &file_path,
+            lines,
+            options,
+            LineSide::Old,
+            &mut attributions,
+        );
     }
 
     Ok(attributions)
 }
 
+/// Blame one side of a file's diffed lines and record an attribution for each of them.
+fn blame_diff_lines(
+    repo: &Repository,
+    file_path: &str,
+    mut lines: Vec<u32>,
+    mut options: GitAiBlameOptions,
+    side: LineSide,
+    attributions: &mut HashMap<DiffLineKey, Attribution>,
+) {
+    // Sort and convert to contiguous ranges for efficient -L format
+    lines.sort_unstable();
+    lines.dedup();
+    let line_ranges = lines_to_ranges(&lines);
+
+    if line_ranges.is_empty() {
+        return;
+    }
+
+    options.line_ranges = line_ranges;
+    options.no_output = true;
+
+    // Lines blame cannot place are marked NoData
+    let (line_authors, prompt_records) = repo.blame(file_path, &options).unwrap_or_default();
+
+    for line in lines {
+        let attribution = match line_authors.get(&line) {
+            // Check if this author is an AI tool by looking up in prompt_records
+            Some(author)
+                if prompt_records
+                    .values()
+                    .any(|pr| &pr.agent_id.tool == author) =>
+            {
+                Attribution::Ai(author.clone())
+            }
+            Some(author) => Attribution::Human(author.clone()),
+            None => Attribution::NoData,
+        };
+        attributions.insert(
+            DiffLineKey {
+                file: file_path.to_string(),
+                line,
+                side: side.clone(),
+            },
+            attribution,
+        );
+    }
+}
+
 /// Convert a sorted list of line numbers to contiguous ranges
 /// e.g., [1, 2, 3, 5, 6, 10] -> [(1, 3), (5, 6), (10, 10)]
 fn lines_to_ranges(lines: &[u32]) -> Vec<(u32, u32)> {
@@ -558,6 +626,7 @@ fn get_diff_split_by_file(
     let mut file_diffs: HashMap<String, String> = HashMap::new();
     let mut current_file = String::new();
     let mut current_diff = String::new();
+    let mut old_file: Option<String> = None;
 
     for line in diff_text.lines() {
         if line.starts_with("diff --git") {
@@ -567,8 +636,16 @@ fn get_diff_split_by_file(
             }
             current_diff = format!("{}\n", line);
             current_file.clear();
+            old_file = None;
+        } else if current_file.is_empty()
+            && let Some(path_opt) = parse_old_file_path_from_minus_header_line(line)
+        {
+            old_file = path_opt;
+            current_diff.push_str(line);
+            current_diff.push('\n');
         } else if let Some(path_opt) = parse_new_file_path_from_plus_header_line(line) {
-            current_file = path_opt.unwrap_or_default();
+            // A deleted file only has its old path
+            current_file = path_opt.or_else(|| old_file.clone()).unwrap_or_default();
             current_diff.push_str(line);
             current_diff.push('\n');
         } else {
@@ -684,6 +761,7 @@ pub fn format_annotated_diff(
     from_commit: &str,
     to_commit: &str,
     attributions: &HashMap<DiffLineKey, Attribution>,
+    use_color: bool,
 ) -> Result<String, GitAiError> {
     // Execute git diff with normal context
     let mut args = repo.global_args_for_exec();
@@ -697,12 +775,10 @@ pub fn format_annotated_diff(
     let diff_text = String::from_utf8(output.stdout)
         .map_err(|e| GitAiError::Generic(format!("Failed to parse diff output: {}", e)))?;
 
-    // Check if we should use colors
-    let use_color = std::io::stdout().is_terminal();
-
     // Parse and annotate diff
     let mut result = String::new();
     let mut current_file = String::new();
+    let mut old_file: Option<String> = None;
     let mut old_line_num = 0u32;
     let mut new_line_num = 0u32;
 
@@ -711,14 +787,17 @@ pub fn format_annotated_diff(
             // Diff header
             result.push_str(&format_line(line, LineType::DiffHeader, use_color, None));
             current_file.clear();
+            old_file = None;
             old_line_num = 0;
             new_line_num = 0;
         } else if line.starts_with("index ") {
             result.push_str(&format_line(line, LineType::DiffHeader, use_color, None));
-        } else if line.starts_with("--- ") {
+        } else if let Some(path_opt) = parse_old_file_path_from_minus_header_line(line) {
+            old_file = path_opt;
             result.push_str(&format_line(line, LineType::DiffHeader, use_color, None));
         } else if let Some(path_opt) = parse_new_file_path_from_plus_header_line(line) {
-            current_file = path_opt.unwrap_or_default();
+            // A deleted file only has its old path
+            current_file = path_opt.or_else(|| old_file.clone()).unwrap_or_default();
             result.push_str(&format_line(line, LineType::DiffHeader, use_color, None));
         } else if line.starts_with("@@ ") {
             // Hunk header - update line counters
@@ -1015,6 +1094,58 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_diff_args_skips_flags() {
+        let args = vec!["--color".to_string(), "abc123".to_string()];
+        let (spec, _format) = parse_diff_args(&args).unwrap();
+        assert!(matches!(spec, DiffSpec::SingleCommit(sha) if sha == "abc123"));
+    }
+
+    #[test]
+    fn test_parse_color_arg() {
+        let args = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(parse_color_arg(&args(&["HEAD"])).unwrap(), None);
+        assert_eq!(
+            parse_color_arg(&args(&["HEAD", "--color"])).unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            parse_color_arg(&args(&["--color=always", "HEAD"])).unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            parse_color_arg(&args(&["HEAD", "--no-color"])).unwrap(),
+            Some(false)
+        );
+        // The last flag wins, like git
+        assert_eq!(
+            parse_color_arg(&args(&["--color", "--color=never"])).unwrap(),
+            Some(false)
+        );
+        assert_eq!(
+            parse_color_arg(&args(&["--color", "--color=auto"])).unwrap(),
+            None
+        );
+        assert!(parse_color_arg(&args(&["--color=sometimes"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_diff_hunks_keys_deleted_file_by_old_path() {
+        let diff = "diff --git a/gone.rs b/gone.rs\n\
+deleted file mode 100644\n\
+--- a/gone.rs\n\
++++ /dev/null\n\
+@@ -1,2 +0,0 @@\n\
+-fn a() {}\n\
+-fn b() {}\n";
+
+        let hunks = parse_diff_hunks(diff).unwrap();
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].file_path, "gone.rs");
+        assert_eq!(hunks[0].deleted_lines, vec![1, 2]);
+    }
+
     #[test]
     fn test_parse_hunk_line_basic() {
         let line = "@@ -10,3 +15,5 @@ fn main() {";
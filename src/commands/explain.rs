@@ -0,0 +1,378 @@
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::get_authorship;
+use crate::git::repository::{Repository, exec_git};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+const USAGE: &str = "Usage: git-ai explain <file>:<line> [<revision>] [--json]";
+
+#[derive(Debug, Clone, Default)]
+pub struct ExplainOptions {
+    pub file: String,
+    pub line: u32,
+    pub revision: Option<String>,
+    pub json: bool,
+}
+
+/// One commit in the history of a line, as recorded by its authorship log
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProvenanceStep {
+    pub commit_sha: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// Author date as a Unix timestamp
+    pub timestamp: i64,
+    pub summary: String,
+    /// Path and line number of the line in this commit
+    pub file_path: String,
+    pub line: u32,
+    pub content: String,
+    /// "introduced" for the commit that added the line, "edited" for later changes to it
+    pub change: &'static str,
+    /// "ai", "human", or "unknown" when the commit has no authorship log
+    pub author_type: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_hash: Option<String>,
+}
+
+pub fn handle_explain(args: &[String]) {
+    let mut options = match parse_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("{}", USAGE);
+            std::process::exit(1);
+        }
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+    options.file = repo_relative_path(&repo, &options.file);
+
+    let steps = match explain_line(&repo, &options) {
+        Ok(steps) => steps,
+        Err(e) => {
+            eprintln!("Explain failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if options.json {
+        match serde_json::to_string(&steps) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize provenance: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        print!("{}", format_provenance(&options, &steps));
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<ExplainOptions, String> {
+    let mut options = ExplainOptions::default();
+    let mut target: Option<&str> = None;
+
+    for arg in args {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            arg if arg.starts_with('-') => {
+                return Err(format!("Unknown explain argument: {}", arg));
+            }
+            arg if target.is_none() => target = Some(arg),
+            arg => {
+                if options.revision.is_some() {
+                    return Err("explain accepts at most one revision".to_string());
+                }
+                options.revision = Some(arg.to_string());
+            }
+        }
+    }
+
+    let target = target.ok_or_else(|| "explain requires <file>:<line>".to_string())?;
+    let (file, line) = target
+        .rsplit_once(':')
+        .ok_or_else(|| format!("Expected <file>:<line>, got {}", target))?;
+    options.line = match line.parse::<u32>() {
+        Ok(line) if line > 0 && !file.is_empty() => line,
+        _ => return Err(format!("Expected <file>:<line>, got {}", target)),
+    };
+    options.file = file.to_string();
+
+    Ok(options)
+}
+
+/// Paths are given relative to the current directory, while git runs from the repository root
+fn repo_relative_path(repo: &Repository, file: &str) -> String {
+    let relative = std::env::current_dir()
+        .and_then(|dir| dir.canonicalize())
+        .ok()
+        .and_then(|dir| {
+            dir.join(file)
+                .strip_prefix(repo.canonical_workdir())
+                .ok()
+                .map(|path| path.to_string_lossy().to_string())
+        });
+    relative.unwrap_or_else(|| file.to_string())
+}
+
+/// Every commit that changed the line, newest first, following it back through edits and
+/// renames with `git log -L`. The line is attributed using each commit's own authorship log.
+pub fn explain_line(
+    repo: &Repository,
+    options: &ExplainOptions,
+) -> Result<Vec<ProvenanceStep>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("log".to_string());
+    args.push("--no-color".to_string());
+    args.push(format!(
+        "--format={}%H%x1f%an%x1f%ae%x1f%at%x1f%s",
+        COMMIT_MARKER
+    ));
+    args.push(format!(
+        "-L{},{}:{}",
+        options.line, options.line, options.file
+    ));
+    args.push(options.revision.as_deref().unwrap_or("HEAD").to_string());
+    let output = exec_git(&args)?;
+    let commits = parse_line_log(&String::from_utf8_lossy(&output.stdout));
+
+    let mut foreign_prompts_cache = HashMap::new();
+    let count = commits.len();
+    let mut steps = Vec::with_capacity(count);
+    for (index, commit) in commits.into_iter().enumerate() {
+        let mut step = ProvenanceStep {
+            commit_sha: commit.sha,
+            author_name: commit.author_name,
+            author_email: commit.author_email,
+            timestamp: commit.timestamp,
+            summary: commit.summary,
+            file_path: commit.file_path,
+            line: commit.line,
+            content: commit.content,
+            change: if index + 1 == count {
+                "introduced"
+            } else {
+                "edited"
+            },
+            author_type: "unknown",
+            tool: None,
+            model: None,
+            session_id: None,
+            prompt_hash: None,
+        };
+
+        if let Some(log) = get_authorship(repo, &step.commit_sha) {
+            match log.get_line_attribution(
+                repo,
+                &step.file_path,
+                step.line,
+                &mut foreign_prompts_cache,
+            ) {
+                Some((_, prompt_hash, prompt)) => {
+                    step.author_type = "ai";
+                    step.prompt_hash = prompt_hash;
+                    if let Some(prompt) = prompt {
+                        step.tool = Some(prompt.agent_id.tool);
+                        step.model = Some(prompt.agent_id.model).filter(|model| !model.is_empty());
+                        step.session_id = prompt.session_id;
+                    }
+                }
+                None => step.author_type = "human",
+            }
+        }
+        steps.push(step);
+    }
+
+    Ok(steps)
+}
+
+/// Starts each commit header in the `git log -L` output
+const COMMIT_MARKER: &str = "explain\x1f";
+
+#[derive(Debug, Default, PartialEq)]
+struct LineLogCommit {
+    sha: String,
+    author_name: String,
+    author_email: String,
+    timestamp: i64,
+    summary: String,
+    file_path: String,
+    line: u32,
+    content: String,
+}
+
+/// Split `git log -L` output into commits, reading where the line sits in each from the
+/// post-image side of its hunk
+fn parse_line_log(output: &str) -> Vec<LineLogCommit> {
+    let mut commits: Vec<LineLogCommit> = Vec::new();
+    // Post-image line number of the next hunk line, while inside a hunk
+    let mut next_line: Option<u32> = None;
+
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix(COMMIT_MARKER) {
+            let fields: Vec<&str> = header.splitn(5, '\x1f').collect();
+            if let [sha, author_name, author_email, timestamp, summary] = fields[..] {
+                commits.push(LineLogCommit {
+                    sha: sha.to_string(),
+                    author_name: author_name.to_string(),
+                    author_email: author_email.to_string(),
+                    timestamp: timestamp.parse().unwrap_or(0),
+                    summary: summary.to_string(),
+                    ..Default::default()
+                });
+            }
+            next_line = None;
+            continue;
+        }
+        let Some(commit) = commits.last_mut() else {
+            continue;
+        };
+
+        if let Some(path) = line.strip_prefix("+++ ") {
+            let path = path.trim_matches('"');
+            commit.file_path = path.strip_prefix("b/").unwrap_or(path).to_string();
+        } else if let Some(hunk) = line.strip_prefix("@@ ") {
+            // "@@ -a,b +c,d @@", where ",d" is left out for a single line
+            next_line = hunk
+                .split_whitespace()
+                .find_map(|part| part.strip_prefix('+'))
+                .and_then(|range| range.split(',').next())
+                .and_then(|start| start.parse().ok());
+            if let Some(start) = next_line {
+                commit.line = start;
+            }
+        } else if let Some(current) = next_line {
+            // Only the tracked line is shown, so the first post-image line is the one
+            if let Some(content) = line.strip_prefix('+').or_else(|| line.strip_prefix(' ')) {
+                if commit.content.is_empty() && current == commit.line {
+                    commit.content = content.to_string();
+                }
+                next_line = Some(current + 1);
+            }
+        }
+    }
+
+    commits
+}
+
+/// Human-readable chain, newest commit first
+pub fn format_provenance(options: &ExplainOptions, steps: &[ProvenanceStep]) -> String {
+    let mut output = format!("{}:{}\n", options.file, options.line);
+    if steps.is_empty() {
+        output.push_str("  no commit changed this line\n");
+        return output;
+    }
+
+    for (index, step) in steps.iter().enumerate() {
+        let date = DateTime::<Utc>::from_timestamp(step.timestamp, 0)
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let mut attribution = step.author_type.to_string();
+        if let Some(tool) = &step.tool {
+            let mut details = vec![tool.clone()];
+            details.extend(step.model.clone());
+            if let Some(session_id) = &step.session_id {
+                details.push(format!("session {}", session_id));
+            }
+            attribution = format!("{} ({})", attribution, details.join(", "));
+        }
+        output.push_str(&format!(
+            "  {} {} {} <{}>  {} {}  {}\n",
+            &step.commit_sha[..step.commit_sha.len().min(7)],
+            date,
+            step.author_name,
+            step.author_email,
+            step.change,
+            attribution,
+            step.summary
+        ));
+        output.push_str(&format!(
+            "      {}:{}  {}\n",
+            step.file_path, step.line, step.content
+        ));
+        // Steps are newest first, so the older one is next
+        if let Some(older) = steps.get(index + 1)
+            && older.author_type != step.author_type
+        {
+            output.push_str(&format!(
+                "      attribution changed from {} to {}\n",
+                older.author_type, step.author_type
+            ));
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&args(&["src/lib.rs:12", "main", "--json"])).unwrap();
+        assert_eq!(options.file, "src/lib.rs");
+        assert_eq!(options.line, 12);
+        assert_eq!(options.revision.as_deref(), Some("main"));
+        assert!(options.json);
+
+        assert!(parse_args(&[]).is_err());
+        assert!(parse_args(&args(&["src/lib.rs"])).is_err());
+        assert!(parse_args(&args(&["src/lib.rs:0"])).is_err());
+        assert!(parse_args(&args(&[":3"])).is_err());
+        assert!(parse_args(&args(&["a.rs:1", "HEAD", "HEAD~1"])).is_err());
+        assert!(parse_args(&args(&["a.rs:1", "--verbose"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_line_log_follows_line_through_hunks() {
+        let output = [
+            "explain\x1fbbb\x1fBob\x1fbob@example.com\x1f200\x1fEdit b",
+            "",
+            "diff --git a/src/lib.rs b/src/lib.rs",
+            "--- a/src/lib.rs",
+            "+++ b/src/lib.rs",
+            "@@ -2,1 +3,1 @@",
+            "-fn b() {}",
+            "+fn b() { todo!() }",
+            "explain\x1faaa\x1fAlice\x1falice@example.com\x1f100\x1fAdd lib",
+            "",
+            "diff --git a/src/lib.rs b/src/lib.rs",
+            "--- /dev/null",
+            "+++ b/src/lib.rs",
+            "@@ -0,0 +2 @@",
+            "+fn b() {}",
+        ]
+        .join("\n");
+
+        let commits = parse_line_log(&output);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].sha, "bbb");
+        assert_eq!(commits[0].file_path, "src/lib.rs");
+        assert_eq!(commits[0].line, 3);
+        assert_eq!(commits[0].content, "fn b() { todo!() }");
+        assert_eq!(commits[1].author_email, "alice@example.com");
+        assert_eq!(commits[1].timestamp, 100);
+        assert_eq!(commits[1].line, 2);
+        assert_eq!(commits[1].content, "fn b() {}");
+    }
+}
@@ -1,8 +1,24 @@
+use crate::authorship::author_map::AuthorMap;
 use crate::authorship::authorship_log_serialization::generate_short_hash;
-use crate::authorship::ignore::effective_ignore_patterns;
+use crate::authorship::ignore::{
+    effective_ignore_patterns, generated_file_patterns, only_matching_patterns,
+};
 use crate::authorship::internal_db::InternalDatabase;
+use crate::authorship::language::{
+    LanguageClassifier, stats_by_language, write_language_stats_to_terminal,
+};
+use crate::authorship::multi_repo::aggregate_repos;
+use crate::authorship::net_authorship::{net_authorship, print_net_authorship_stats};
 use crate::authorship::range_authorship;
-use crate::authorship::stats::stats_command;
+use crate::authorship::stats::{
+    CommitStats, CommitStatsOptions, MergeStrategy, StatsOutputFormat, StatsThresholds,
+    compute_commit_stats_with_options, stats_by_directory, stats_command,
+    stats_for_commit_stats_with_options, stats_for_commits_with_progress, stream_stats_for_commits,
+    top_files, write_directory_stats_to_terminal, write_stats_ndjson_line, write_stats_output,
+    write_stats_to_csv, write_stats_to_table, write_stats_to_terminal, write_top_files_to_terminal,
+};
+use crate::authorship::stats_profile::{self, StatsPhase, StatsProfile};
+use crate::authorship::test_classification::{classify_test_stats, test_patterns};
 use crate::authorship::working_log::{AgentId, CheckpointKind};
 use crate::commands;
 use crate::commands::checkpoint_agent::agent_presets::{
@@ -11,13 +27,16 @@ use crate::commands::checkpoint_agent::agent_presets::{
 };
 use crate::commands::checkpoint_agent::agent_v1_preset::AgentV1Preset;
 use crate::commands::checkpoint_agent::opencode_preset::OpenCodePreset;
+use crate::commands::progress::CommitProgress;
 use crate::config;
+use crate::error::exit_code;
 use crate::git::find_repository;
 use crate::git::find_repository_in_path;
-use crate::git::repository::{CommitRange, group_files_by_repository};
+use crate::git::refs::commits_missing_authorship_notes;
+use crate::git::repository::{CommitRange, DiffAlgorithm, group_files_by_repository};
 use crate::observability::wrapper_performance_targets::log_performance_for_checkpoint;
 use crate::observability::{self, log_message};
-use crate::utils::is_interactive_terminal;
+use crate::utils::{is_interactive_terminal, set_color_override};
 use std::env;
 use std::io::IsTerminal;
 use std::io::Read;
@@ -65,9 +84,33 @@ pub fn handle_git_ai(args: &[String]) {
         "status" => {
             commands::status::handle_status(&args[1..]);
         }
+        "log" => {
+            commands::log::handle_log(&args[1..]);
+        }
         "show" => {
             commands::show::handle_show(&args[1..]);
         }
+        "report" => {
+            commands::report::handle_report(&args[1..]);
+        }
+        "verify" => {
+            commands::verify::handle_verify(&args[1..]);
+        }
+        "trend" => {
+            commands::trend::handle_trend(&args[1..]);
+        }
+        "watch" => {
+            commands::watch::handle_watch(&args[1..]);
+        }
+        "schema" => {
+            commands::schema::handle_schema(&args[1..]);
+        }
+        "leaderboard" => {
+            commands::leaderboard::handle_leaderboard(&args[1..]);
+        }
+        "explain" => {
+            commands::explain::handle_explain(&args[1..]);
+        }
         "checkpoint" => {
             handle_checkpoint(&args[1..]);
         }
@@ -88,6 +131,24 @@ pub fn handle_git_ai(args: &[String]) {
             println!("{}", config.git_cmd());
             std::process::exit(0);
         }
+        "install-hooks" | "install" if args[1..].iter().any(|arg| arg == "--uninstall") => {
+            let uninstall_args: Vec<String> = args[1..]
+                .iter()
+                .filter(|arg| *arg != "--uninstall")
+                .cloned()
+                .collect();
+            match commands::install_hooks::run_uninstall(&uninstall_args) {
+                Ok(statuses) => {
+                    if let Ok(statuses_value) = serde_json::to_value(&statuses) {
+                        log_message("uninstall-hooks", "info", Some(statuses_value));
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Uninstall hooks failed: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
         "install-hooks" | "install" => match commands::install_hooks::run(&args[1..]) {
             Ok(statuses) => {
                 if let Ok(statuses_value) = serde_json::to_value(&statuses) {
@@ -113,9 +174,21 @@ pub fn handle_git_ai(args: &[String]) {
         "git-hooks" => {
             handle_git_hooks(&args[1..]);
         }
+        "init" => {
+            commands::init::handle_init(&args[1..]);
+        }
+        "migrate-storage" => {
+            commands::migrate_storage::handle_migrate_storage(&args[1..]);
+        }
         "squash-authorship" => {
             commands::squash_authorship::handle_squash_authorship(&args[1..]);
         }
+        "replay" => {
+            commands::replay::handle_replay(&args[1..]);
+        }
+        "record" => {
+            commands::record::handle_record(&args[1..]);
+        }
         "ci" => {
             commands::ci_handlers::handle_ci(&args[1..]);
         }
@@ -187,15 +260,104 @@ fn print_help() {
     );
     eprintln!("    --show-working-log          Display current working log");
     eprintln!("    --reset                     Reset working log");
+    eprintln!("    --max-file-size=<bytes>     Attribute larger files as a whole (default 1 MiB)");
     eprintln!("    mock_ai [pathspecs...]      Test preset accepting optional file pathspecs");
     eprintln!("  blame <file>       Git blame with AI authorship overlay");
+    eprintln!("                     Exits 1 in a repository with no commits yet");
+    eprintln!("    --range <L1,L2>       Only blame lines L1 through L2, like git blame -L");
+    eprintln!("    --show-deleted <range> List lines deleted in <range> and who wrote them");
+    eprintln!("    --working-tree        Attribute uncommitted lines from pending checkpoints");
+    eprintln!("    --with-churn          Show AI and human deletions of each hunk's commit");
+    eprintln!("    --color|--no-color    Force terminal colors on or off");
     eprintln!("  diff <commit|range>  Show diff with AI authorship annotations");
     eprintln!("    <commit>              Diff from commit's parent to commit");
     eprintln!("    <commit1>..<commit2>  Diff between two commits");
-    eprintln!("  stats [commit]     Show AI authorship statistics for a commit");
+    eprintln!("    --json                Output annotations and prompts as JSON");
+    eprintln!("    --color[=<when>]      Color output: always, never or auto (default)");
+    eprintln!("    --no-color            Same as --color=never");
+    eprintln!("  explain <file>:<line> [rev]");
+    eprintln!("                     Show every commit that changed a line and who wrote it");
+    eprintln!("    --json                Output the chain as a JSON array, newest first");
+    eprintln!("  stats [commit|range]  Show AI authorship statistics for a commit");
+    eprintln!("                     Reports all zeros in a repository with no commits yet");
+    eprintln!("    <commit1>..<commit2>  Aggregate the net changes across a range");
     eprintln!("    --json                 Output in JSON format");
+    eprintln!("    --csv                  Output in CSV format (one row per commit)");
+    eprintln!("    --format=ndjson        Stream one JSON object per commit, one per line");
+    eprintln!("    --format=table         Output an aligned table (one row per commit)");
+    eprintln!("    --per-file             Break down additions per file");
+    eprintln!("    --by-session           Group AI additions by GIT_AI_SESSION_ID");
+    eprintln!("    --by-language          Break down additions per language as a map");
+    eprintln!("    --group-by-dir[=<n>]   Break down additions per directory, <n> levels deep");
+    eprintln!("    --top-files <n>        List the <n> files with the most AI lines");
+    eprintln!("    --net                  Count AI vs human lines surviving in the final tree");
+    eprintln!("    --baseline <rev>       Like --net, leaving out lines that existed at <rev>");
+    eprintln!("    --fail-on-missing-attestation");
+    eprintln!("                           Exit 4 listing commits with no authorship log");
+    eprintln!("    --exclude-generated    Skip generated files (header marker or known paths)");
+    eprintln!("    --ignore-whitespace    Ignore whitespace-only changes (like git diff -w)");
+    eprintln!("    --diff-algorithm <name>");
+    eprintln!("                           Diff with myers, minimal, patience or histogram");
+    eprintln!("    --attribute-identical-to-prior");
+    eprintln!("                           Count re-typed copies of earlier AI lines as AI");
+    eprintln!("    --file <glob>          Count only paths matching the glob (repeatable)");
+    eprintln!("    --classify-tests       Split stats into test and production code");
+    eprintln!("    --test-pattern <glob>  Treat matching paths as tests (repeatable)");
+    eprintln!("    --author-email <email> Sum only commits by this author (requires a range)");
+    eprintln!("    --committer-email <email>");
+    eprintln!("                           Sum only commits by this committer (requires a range)");
+    eprintln!("    --author-map <file>    Also match aliases from `alias = canonical` email lines");
+    eprintln!("    --since <date>         Sum only commits committed after this date");
+    eprintln!("    --until <date>         Sum only commits committed before this date");
+    eprintln!("    --include-merges       Also count merges against their first parent when");
+    eprintln!("                           summing commits (default: skip merges, like git log -p)");
+    eprintln!("    --first-parent         Sum only the first-parent chain, merges included");
+    eprintln!("    --relative-to <branch> Sum the commits since the merge base with <branch>");
+    eprintln!("    --repos <path>...      Sum the history of several repositories as JSON");
+    eprintln!("    --max-ai-percentage <n>");
+    eprintln!("                           Exit 3 when the AI percentage is above n");
+    eprintln!("    --min-human-lines <n>  Exit 3 when fewer than n human lines were added");
+    eprintln!("    --breakdowns           Also compute deletions, characters and verbatim lines");
+    eprintln!("                           for each commit of a range (always on for one commit)");
+    eprintln!("    --jobs <n>             Threads for range stats (default: per CPU)");
+    eprintln!("    --profile              Print time spent per phase to stderr");
+    eprintln!("    --color|--no-color     Force terminal colors on or off (default: on for a TTY");
+    eprintln!("                           unless NO_COLOR is set)");
+    eprintln!("  log [rev|range]    Show AI vs human additions per commit");
+    eprintln!("    --max-count <n>        Limit the number of commits shown");
+    eprintln!("    --no-merges            Skip merge commits");
+    eprintln!("    --first-parent         Follow only the first parent of merges");
+    eprintln!("    --author-email <email> Only show commits by this author");
+    eprintln!("    --committer-email <email>");
+    eprintln!("                           Only show commits by this committer");
+    eprintln!("    --breakdowns           Also compute deletions, characters and verbatim lines");
+    eprintln!("    --json                 Output as a JSON array");
+    eprintln!("  report [rev|range] Report AI vs human regions for added lines");
+    eprintln!("    --format=annotations   JSON array of regions for PR check annotations");
+    eprintln!("    --format=markdown      Per-file AI/human table with a summary line");
+    eprintln!("    --format=html          Standalone HTML page with a weekly chart and file table");
+    eprintln!(
+        "    --locale <locale>      Decimal separator for markdown/HTML percentages (e.g. de)"
+    );
+    eprintln!("  verify [rev|range] Check stored authorship logs against their commits");
+    eprintln!("    --verify-signatures    Also check Ed25519 signatures on authorship logs");
+    eprintln!("    --public-key <path>    Hex-encoded public key to verify signatures with");
+    eprintln!("  trend [rev|range]  AI percentage per time bucket as JSON");
+    eprintln!("    --by=week|month        Bucket commits by ISO week (default) or month");
+    eprintln!("  leaderboard [rev|range] AI percentage and lines added per committer");
+    eprintln!("    --by=committer|author  Group by committer (default) or author email");
+    eprintln!("    --author-map <file>    Merge alias emails using `alias = canonical` lines");
+    eprintln!("    --json                 Output as a JSON array");
+    eprintln!("    --anonymize            Replace committer emails with short hashes");
+    eprintln!("  schema [name]      Print JSON Schemas for git-ai's JSON output");
+    eprintln!("                          Names: commit-stats, authorship-metadata");
     eprintln!("  status             Show uncommitted AI authorship status (debug)");
     eprintln!("    --json                 Output in JSON format");
+    eprintln!("  watch              Print live AI/human line counts for unstaged changes");
+    eprintln!("    --json                 Output one JSON object per update");
+    eprintln!(
+        "    --debounce <ms>        Wait for edits to settle before recounting (default 300)"
+    );
     eprintln!("  show <rev|range>   Display authorship logs for a revision or range");
     eprintln!("  show-prompt <id>   Display a prompt record by its ID");
     eprintln!("    --commit <rev>        Look in a specific commit only");
@@ -217,16 +379,41 @@ fn print_help() {
     eprintln!("    --add <key> <value>   Add to array or upsert into object");
     eprintln!("    unset <key>           Remove config value (reverts to default)");
     eprintln!("  install-hooks      Install git hooks for AI authorship tracking");
+    eprintln!(
+        "    --repo                 Also install post-commit hooks in the current repository,"
+    );
+    eprintln!("                           chaining to any hooks it already has");
+    eprintln!("    --uninstall            Remove the hooks instead (same as uninstall-hooks)");
     eprintln!("  uninstall-hooks    Remove git-ai hooks from all detected tools");
+    eprintln!("    --repo                 Also remove the current repository's post-commit hooks");
     eprintln!("  git-hooks ensure   Ensure repo-local git-ai hooks are installed/healed");
     eprintln!("  git-hooks remove   Remove repo-local git-ai hooks and restore local hooksPath");
     eprintln!("  ci                 Continuous integration utilities");
     eprintln!("    github                 GitHub CI helpers");
+    eprintln!("  init               Set up git-ai for this repository; safe to re-run");
+    eprintln!("    --hooks                Also install repo-local git-ai hooks");
+    eprintln!("    --storage <notes|sidecar>");
+    eprintln!("                           Store authorship logs in this backend");
+    eprintln!("  migrate-storage <notes|sidecar>");
+    eprintln!("                     Copy authorship logs to a storage backend and switch to it");
+    eprintln!("    notes                  Git notes under refs/notes/ai (default)");
+    eprintln!("    sidecar                Local files under .git/ai/authorship");
     eprintln!("  squash-authorship  Generate authorship log for squashed commits");
     eprintln!(
         "    <base_branch> <new_sha> <old_sha>  Required: base branch, new commit SHA, old commit SHA"
     );
     eprintln!("    --dry-run             Show what would be done without making changes");
+    eprintln!("  replay <old-range> <new-range>");
+    eprintln!("                     Carry authorship logs over to commits rewritten without hooks");
+    eprintln!("  record --commit <rev>");
+    eprintln!("                     Write a commit's authorship log from its recorded checkpoints");
+    eprintln!("    --force               Rewrite a log the commit already has");
+    eprintln!("  record --from-telemetry <file.csv>");
+    eprintln!("                     Checkpoint AI edits from path,start_line,end_line,tool rows");
+    eprintln!("  record --from-markers <path>...");
+    eprintln!("                     Checkpoint lines between git-ai:begin and git-ai:end comments");
+    eprintln!("  record --from-ide-log <file>");
+    eprintln!("                     Checkpoint AI edits from <path>:<start>[-<end>] log entries");
     eprintln!("  git-path           Print the path to the underlying git executable");
     eprintln!("  upgrade            Check for updates and install if available");
     eprintln!("    --force               Reinstall latest version even if already up to date");
@@ -267,7 +454,15 @@ fn print_help() {
     eprintln!("  version, -v, --version     Print the git-ai version");
     eprintln!("  help, -h, --help           Show this help message");
     eprintln!();
-    std::process::exit(0);
+    eprintln!("Exit codes:");
+    eprintln!("  0  Success");
+    eprintln!("  1  Error (invalid arguments, git failure, unreadable data)");
+    eprintln!("  2  search/continue found no prompt history");
+    eprintln!("  3  stats broke --max-ai-percentage or --min-human-lines");
+    eprintln!("  4  stats --fail-on-missing-attestation found commits without an authorship log");
+    eprintln!("  5  verify found authorship logs that do not match their commits");
+    eprintln!();
+    std::process::exit(exit_code::SUCCESS);
 }
 
 fn handle_checkpoint(args: &[String]) {
@@ -318,6 +513,16 @@ fn handle_checkpoint(args: &[String]) {
                     std::process::exit(0);
                 }
             }
+            arg if arg.starts_with("--max-file-size=") => {
+                match arg["--max-file-size=".len()..].parse::<u64>() {
+                    Ok(bytes) => commands::checkpoint::set_max_file_size_override(bytes),
+                    Err(_) => {
+                        eprintln!("Error: --max-file-size requires a number of bytes");
+                        std::process::exit(0);
+                    }
+                }
+                i += 1;
+            }
 
             _ => {
                 i += 1;
@@ -962,9 +1167,26 @@ fn handle_ai_blame(args: &[String]) {
         }
     };
 
+    if let Some(color) = options.color {
+        set_color_override(color);
+    }
+
+    // Git blame has no commit to walk from until the first one is made
+    if repo.head_is_unborn().unwrap_or(false) {
+        eprintln!(
+            "Cannot blame {}: the repository has no commits yet",
+            file_path
+        );
+        std::process::exit(1);
+    }
+
+    // Paths in a bare repository are tree paths, and there are no worktree files to look at
+    let is_bare = repo.is_bare_repository().unwrap_or(false);
+
     // Auto-detect ignore-revs-file if not explicitly provided, not disabled via --no-ignore-revs-file,
     // and git version supports --ignore-revs-file (git >= 2.23)
-    if options.ignore_revs_file.is_none()
+    if !is_bare
+        && options.ignore_revs_file.is_none()
         && !options.no_ignore_revs_file
         && repo.git_supports_ignore_revs_file()
     {
@@ -1006,7 +1228,7 @@ fn handle_ai_blame(args: &[String]) {
         std::process::exit(1);
     }
 
-    let file_path = if !std::path::Path::new(&file_path).is_absolute() {
+    let file_path = if !is_bare && !std::path::Path::new(&file_path).is_absolute() {
         let current_dir_path = std::path::PathBuf::from(&current_dir);
         current_dir_path
             .join(&file_path)
@@ -1042,6 +1264,12 @@ fn handle_ai_diff(args: &[String]) {
 }
 
 fn handle_stats(args: &[String]) {
+    // Aggregating across repositories does not need the current directory to be one
+    if args.iter().any(|arg| arg == "--repos") {
+        handle_multi_repo_stats(args);
+        return;
+    }
+
     // Find the git repository
     let repo = match find_repository(&Vec::<String>::new()) {
         Ok(repo) => repo,
@@ -1050,11 +1278,50 @@ fn handle_stats(args: &[String]) {
             std::process::exit(1);
         }
     };
+    match commands::diff::parse_color_arg(args) {
+        Ok(Some(color)) => set_color_override(color),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+
     // Parse stats-specific arguments
     let mut json_output = false;
+    let mut csv_output = false;
+    let mut ndjson_output = false;
+    let mut table_output = false;
+    let mut per_file = false;
+    let mut by_session = false;
+    let mut by_language = false;
+    let mut group_by_dir: Option<usize> = None;
+    let mut top_files_count: Option<usize> = None;
+    let mut ignore_whitespace = false;
+    let mut diff_algorithm: Option<DiffAlgorithm> = None;
+    let mut attribute_identical_to_prior = false;
+    let mut net = false;
+    let mut baseline: Option<String> = None;
+    let mut fail_on_missing_attestation = false;
+    let mut exclude_generated = false;
+    let mut profile = false;
+    let mut classify_tests = false;
+    let mut test_globs: Vec<String> = Vec::new();
+    let mut author_email: Option<String> = None;
+    let mut committer_email: Option<String> = None;
+    let mut author_map_path: Option<String> = None;
+    let mut since: Option<String> = None;
+    let mut until: Option<String> = None;
+    let mut merge_strategy: Option<MergeStrategy> = None;
+    let mut jobs: usize = 0;
+    let mut breakdowns = false;
+    let mut thresholds = StatsThresholds::default();
     let mut commit_sha = None;
     let mut commit_range: Option<CommitRange> = None;
+    let mut range_arg: Option<String> = None;
+    let mut relative_to: Option<String> = None;
     let mut ignore_patterns: Vec<String> = Vec::new();
+    let mut file_globs: Vec<String> = Vec::new();
 
     let mut i = 0;
     while i < args.len() {
@@ -1063,6 +1330,269 @@ fn handle_stats(args: &[String]) {
                 json_output = true;
                 i += 1;
             }
+            "--csv" => {
+                csv_output = true;
+                i += 1;
+            }
+            arg if arg == "--format" || arg.starts_with("--format=") => {
+                let value = match args[i].split_once('=') {
+                    Some((_, value)) => value.to_string(),
+                    None if i + 1 < args.len() => {
+                        i += 1;
+                        args[i].clone()
+                    }
+                    None => {
+                        eprintln!("--format requires a value");
+                        std::process::exit(1);
+                    }
+                };
+                match value.as_str() {
+                    "json" => json_output = true,
+                    "csv" => csv_output = true,
+                    "ndjson" => ndjson_output = true,
+                    "table" => table_output = true,
+                    other => {
+                        eprintln!(
+                            "Invalid --format value: {} (expected json, csv, ndjson or table)",
+                            other
+                        );
+                        std::process::exit(1);
+                    }
+                }
+                i += 1;
+            }
+            "--per-file" => {
+                per_file = true;
+                i += 1;
+            }
+            "--by-session" => {
+                by_session = true;
+                i += 1;
+            }
+            "--by-language" => {
+                by_language = true;
+                i += 1;
+            }
+            "--group-by-dir" => {
+                group_by_dir = Some(1);
+                i += 1;
+            }
+            arg if arg.starts_with("--group-by-dir=") => {
+                let value = &arg["--group-by-dir=".len()..];
+                group_by_dir = match value.parse::<usize>() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => {
+                        eprintln!("Invalid --group-by-dir depth: {}", value);
+                        std::process::exit(1);
+                    }
+                };
+                i += 1;
+            }
+            arg if arg == "--top-files" || arg.starts_with("--top-files=") => {
+                let value = match arg.strip_prefix("--top-files=") {
+                    Some(value) => value.to_string(),
+                    None => {
+                        if i + 1 >= args.len() {
+                            eprintln!("--top-files requires a count");
+                            std::process::exit(1);
+                        }
+                        i += 1;
+                        args[i].clone()
+                    }
+                };
+                top_files_count = match value.parse::<usize>() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => {
+                        eprintln!("Invalid --top-files count: {}", value);
+                        std::process::exit(1);
+                    }
+                };
+                i += 1;
+            }
+            "--ignore-whitespace" => {
+                ignore_whitespace = true;
+                i += 1;
+            }
+            "--diff-algorithm" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--diff-algorithm requires a value");
+                    std::process::exit(1);
+                }
+                diff_algorithm = Some(parse_diff_algorithm(&args[i + 1]));
+                i += 2;
+            }
+            arg if arg.starts_with("--diff-algorithm=") => {
+                diff_algorithm = Some(parse_diff_algorithm(&arg["--diff-algorithm=".len()..]));
+                i += 1;
+            }
+            "--attribute-identical-to-prior" => {
+                attribute_identical_to_prior = true;
+                i += 1;
+            }
+            "--net" => {
+                net = true;
+                i += 1;
+            }
+            // The first-parent chain already counts its merges, so it wins over --include-merges
+            "--include-merges" => {
+                if merge_strategy.is_none() {
+                    merge_strategy = Some(MergeStrategy::IncludeMerges);
+                }
+                i += 1;
+            }
+            "--first-parent" => {
+                merge_strategy = Some(MergeStrategy::FirstParent);
+                i += 1;
+            }
+            "--relative-to" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--relative-to requires a branch");
+                    std::process::exit(1);
+                }
+                relative_to = Some(args[i + 1].clone());
+                i += 2;
+            }
+            arg if arg.starts_with("--relative-to=") => {
+                relative_to = Some(arg["--relative-to=".len()..].to_string());
+                i += 1;
+            }
+            "--fail-on-missing-attestation" => {
+                fail_on_missing_attestation = true;
+                i += 1;
+            }
+            "--exclude-generated" => {
+                exclude_generated = true;
+                i += 1;
+            }
+            "--profile" => {
+                profile = true;
+                i += 1;
+            }
+            // Applied before the loop by `parse_color_arg`
+            arg if arg == "--color" || arg == "--no-color" || arg.starts_with("--color=") => {
+                i += 1;
+            }
+            "--baseline" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--baseline requires a revision");
+                    std::process::exit(1);
+                }
+                baseline = Some(args[i + 1].clone());
+                i += 2;
+            }
+            arg if arg.starts_with("--baseline=") => {
+                baseline = Some(arg["--baseline=".len()..].to_string());
+                i += 1;
+            }
+            "--classify-tests" => {
+                classify_tests = true;
+                i += 1;
+            }
+            "--test-pattern" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--test-pattern requires a glob");
+                    std::process::exit(1);
+                }
+                test_globs.push(args[i + 1].clone());
+                i += 2;
+            }
+            arg if arg.starts_with("--test-pattern=") => {
+                test_globs.push(arg["--test-pattern=".len()..].to_string());
+                i += 1;
+            }
+            "--author-email" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--author-email requires a value");
+                    std::process::exit(1);
+                }
+                author_email = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--committer-email" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--committer-email requires a value");
+                    std::process::exit(1);
+                }
+                committer_email = Some(args[i + 1].clone());
+                i += 2;
+            }
+            "--author-map" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--author-map requires a file");
+                    std::process::exit(1);
+                }
+                author_map_path = Some(args[i + 1].clone());
+                i += 2;
+            }
+            arg if arg.starts_with("--author-map=") => {
+                author_map_path = Some(arg["--author-map=".len()..].to_string());
+                i += 1;
+            }
+            "--since" | "--until" => {
+                if i + 1 >= args.len() {
+                    eprintln!("{} requires a value", args[i]);
+                    std::process::exit(1);
+                }
+                if args[i] == "--since" {
+                    since = Some(args[i + 1].clone());
+                } else {
+                    until = Some(args[i + 1].clone());
+                }
+                i += 2;
+            }
+            arg if arg.starts_with("--since=") => {
+                since = Some(arg["--since=".len()..].to_string());
+                i += 1;
+            }
+            arg if arg.starts_with("--until=") => {
+                until = Some(arg["--until=".len()..].to_string());
+                i += 1;
+            }
+            "--breakdowns" => {
+                breakdowns = true;
+                i += 1;
+            }
+            "--jobs" | "-j" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--jobs requires a value");
+                    std::process::exit(1);
+                }
+                jobs = match args[i + 1].parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => {
+                        eprintln!("Invalid --jobs value: {}", args[i + 1]);
+                        std::process::exit(1);
+                    }
+                };
+                i += 2;
+            }
+            "--max-ai-percentage" | "--min-human-lines" => {
+                if i + 1 >= args.len() {
+                    eprintln!("{} requires a value", args[i]);
+                    std::process::exit(1);
+                }
+                parse_stats_threshold(&mut thresholds, &args[i], &args[i + 1]);
+                i += 2;
+            }
+            arg if arg.starts_with("--max-ai-percentage=")
+                || arg.starts_with("--min-human-lines=") =>
+            {
+                let (flag, value) = arg.split_once('=').unwrap();
+                parse_stats_threshold(&mut thresholds, flag, value);
+                i += 1;
+            }
+            "--file" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--file requires a glob");
+                    std::process::exit(1);
+                }
+                file_globs.push(args[i + 1].clone());
+                i += 2;
+            }
+            arg if arg.starts_with("--file=") => {
+                file_globs.push(arg["--file=".len()..].to_string());
+                i += 1;
+            }
             "--ignore" => {
                 // Collect all arguments after --ignore until we hit another flag or commit SHA
                 // This supports shell glob expansion: `--ignore *.lock` expands to `--ignore Cargo.lock package.lock`
@@ -1107,6 +1637,7 @@ fn handle_stats(args: &[String]) {
                             ) {
                                 Ok(range) => {
                                     commit_range = Some(range);
+                                    range_arg = Some(arg.clone());
                                 }
                                 Err(e) => {
                                     eprintln!("Failed to create commit range: {}", e);
@@ -1129,41 +1660,777 @@ fn handle_stats(args: &[String]) {
         }
     }
 
-    let effective_patterns = effective_ignore_patterns(&repo, &ignore_patterns, &[]);
+    // Scope the stats to what the branch added since it forked from `relative_to`, like a PR
+    if let Some(branch) = relative_to {
+        if commit_range.is_some() {
+            eprintln!("--relative-to can't be combined with a commit range");
+            std::process::exit(1);
+        }
+        let tip = commit_sha.take().unwrap_or_else(|| "HEAD".to_string());
+        let base = match repo.merge_base(branch.clone(), tip.clone()) {
+            Ok(base) => base,
+            Err(e) => {
+                eprintln!(
+                    "Failed to find the merge base of {} and {}: {}",
+                    branch, tip, e
+                );
+                std::process::exit(1);
+            }
+        };
+        match CommitRange::new_infer_refname(&repo, base.clone(), tip.clone(), None) {
+            Ok(range) => {
+                commit_range = Some(range);
+                range_arg = Some(format!("{}..{}", base, tip));
+            }
+            Err(e) => {
+                eprintln!("Failed to create commit range: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
 
-    // Handle commit range if detected
-    if let Some(range) = commit_range {
-        match range_authorship::range_authorship(range, false, &effective_patterns) {
+    if [json_output, csv_output, ndjson_output, table_output]
+        .iter()
+        .filter(|set| **set)
+        .count()
+        > 1
+    {
+        eprintln!("--json, --csv, --format=ndjson and --format=table are mutually exclusive");
+        std::process::exit(1);
+    }
+    let format = if json_output {
+        StatsOutputFormat::Json
+    } else if csv_output {
+        StatsOutputFormat::Csv
+    } else if ndjson_output {
+        StatsOutputFormat::Ndjson
+    } else if table_output {
+        StatsOutputFormat::Table
+    } else {
+        StatsOutputFormat::Terminal
+    };
+
+    if per_file && commit_range.is_some() {
+        eprintln!("--per-file is only supported for a single commit");
+        std::process::exit(1);
+    }
+
+    if by_language && (commit_range.is_some() || csv_output || ndjson_output || table_output) {
+        eprintln!("--by-language needs a single commit and JSON or terminal output");
+        std::process::exit(1);
+    }
+
+    if group_by_dir.is_some()
+        && (commit_range.is_some() || csv_output || ndjson_output || table_output)
+    {
+        eprintln!("--group-by-dir needs a single commit and JSON or terminal output");
+        std::process::exit(1);
+    }
+
+    if group_by_dir.is_some() && by_language {
+        eprintln!("--group-by-dir cannot be combined with --by-language");
+        std::process::exit(1);
+    }
+
+    if top_files_count.is_some()
+        && (csv_output
+            || ndjson_output
+            || table_output
+            || per_file
+            || by_language
+            || group_by_dir.is_some()
+            || classify_tests
+            || author_email.is_some()
+            || committer_email.is_some()
+            || since.is_some()
+            || until.is_some())
+    {
+        eprintln!("--top-files can only be combined with --json, --file, --ignore and thresholds");
+        std::process::exit(1);
+    }
+
+    if by_session && commit_range.is_some() {
+        eprintln!("--by-session is only supported for a single commit");
+        std::process::exit(1);
+    }
+
+    if ignore_whitespace && commit_range.is_some() {
+        eprintln!("--ignore-whitespace is only supported for a single commit");
+        std::process::exit(1);
+    }
+
+    if diff_algorithm.is_some() && commit_range.is_some() {
+        eprintln!("--diff-algorithm is only supported for a single commit");
+        std::process::exit(1);
+    }
+
+    if attribute_identical_to_prior && commit_range.is_some() {
+        eprintln!("--attribute-identical-to-prior is only supported for a single commit");
+        std::process::exit(1);
+    }
+
+    // A freshly initialized repository has nothing to count yet, so it reports all zeros
+    if commit_sha.is_none() && commit_range.is_none() && repo.head_is_unborn().unwrap_or(false) {
+        let stats = CommitStats::default();
+        let options = CommitStatsOptions {
+            per_file,
+            by_session,
+            ..Default::default()
+        };
+        if let Err(e) = write_stats_output("HEAD", &stats, format, &options) {
+            eprintln!("Stats failed: {}", e);
+            std::process::exit(1);
+        }
+        enforce_stats_thresholds(&thresholds, &stats);
+        return;
+    }
+
+    let mut effective_patterns = only_matching_patterns(&file_globs);
+    effective_patterns.extend(effective_ignore_patterns(&repo, &ignore_patterns, &[]));
+
+    // Generated files are recognized by their header, so the scan covers the counted commits
+    if exclude_generated {
+        let (base, commit) = match commit_range.as_ref() {
+            Some(range) => (Some(range.start_oid.as_str()), range.end_oid.clone()),
+            None => (
+                None,
+                commit_sha.clone().unwrap_or_else(|| "HEAD".to_string()),
+            ),
+        };
+        let marker = config::Config::get().generated_marker();
+        match generated_file_patterns(&repo, base, &commit, marker) {
+            Ok(patterns) => effective_patterns.extend(patterns),
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let _profile_report = profile.then(|| StatsProfileReport(stats_profile::enable()));
+
+    // Strict mode refuses to count the lines of unattested commits as human
+    if fail_on_missing_attestation {
+        let shas = match commit_range.as_ref() {
+            Some(range) => range.all_commits(),
+            None => match repo.revparse_single(commit_sha.as_deref().unwrap_or("HEAD")) {
+                Ok(commit) => vec![commit.id()],
+                Err(e) => {
+                    eprintln!("Stats failed: {}", e);
+                    exit_stats(1);
+                }
+            },
+        };
+        match commits_missing_authorship_notes(&repo, &shas) {
+            Ok(missing) if missing.is_empty() => {}
+            Ok(missing) => {
+                eprintln!("{} commit(s) have no authorship log:", missing.len());
+                for sha in &missing {
+                    let summary = repo
+                        .find_commit(sha.clone())
+                        .and_then(|commit| commit.summary())
+                        .unwrap_or_default();
+                    eprintln!("  {} {}", &sha[..7.min(sha.len())], summary);
+                }
+                exit_stats(exit_code::MISSING_ATTESTATION);
+            }
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                exit_stats(1);
+            }
+        }
+    }
+
+    // A baseline counts the lines surviving from after it, so it is net mode with a fixed base
+    if baseline.is_some() {
+        if commit_range.is_some() {
+            eprintln!("--baseline takes a single commit, not a range");
+            exit_stats(1);
+        }
+        net = true;
+    }
+
+    // Net mode blames the final tree once instead of summing what each commit added
+    if net {
+        if csv_output
+            || table_output
+            || per_file
+            || ignore_whitespace
+            || diff_algorithm.is_some()
+            || attribute_identical_to_prior
+            || author_email.is_some()
+            || committer_email.is_some()
+            || since.is_some()
+            || until.is_some()
+            || merge_strategy.is_some()
+            || top_files_count.is_some()
+        {
+            eprintln!(
+                "--net and --baseline can only be combined with --json, --file, --ignore and thresholds"
+            );
+            exit_stats(1);
+        }
+        let (commit, base) = match commit_range.as_ref() {
+            Some(range) => (range.end_oid.clone(), Some(range.start_oid.as_str())),
+            None => (
+                commit_sha.clone().unwrap_or_else(|| "HEAD".to_string()),
+                baseline.as_deref(),
+            ),
+        };
+        match net_authorship(&repo, &commit, base, &effective_patterns) {
+            Ok(stats) => {
+                if json_output || ndjson_output {
+                    println!("{}", serde_json::to_string(&stats).unwrap());
+                } else {
+                    print_net_authorship_stats(&stats);
+                }
+                enforce_stats_thresholds(&thresholds, &stats.as_commit_stats());
+            }
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                exit_stats(1);
+            }
+        }
+        return;
+    }
+
+    // Files are ranked from the per-file breakdown of every commit in the range
+    if let Some(n) = top_files_count {
+        let shas = match commit_range.as_ref() {
+            Some(range) => Ok(range
+                .commits_with_rev_list_args(merge_strategy.unwrap_or_default().rev_list_args())),
+            None => repo
+                .revparse_single(commit_sha.as_deref().unwrap_or("HEAD"))
+                .map(|commit| vec![commit.id()]),
+        };
+        let options = CommitStatsOptions {
+            per_file: true,
+            ignore_whitespace,
+            diff_algorithm,
+            attribute_identical_to_prior,
+            ..Default::default()
+        };
+        let result = shas.and_then(|shas| {
+            shas.iter()
+                .map(|sha| {
+                    stats_for_commit_stats_with_options(&repo, sha, &effective_patterns, &options)
+                })
+                .collect::<Result<Vec<CommitStats>, _>>()
+        });
+        match result {
             Ok(stats) => {
+                let ranked = top_files(stats.iter().map(|s| s.files.as_slice()), n);
+                if json_output {
+                    println!("{}", serde_json::to_string(&ranked).unwrap());
+                } else {
+                    write_top_files_to_terminal(&ranked, true);
+                }
+                enforce_stats_thresholds(&thresholds, &stats.iter().sum::<CommitStats>());
+            }
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                exit_stats(1);
+            }
+        }
+        return;
+    }
+
+    // Languages and directories come from the per-file breakdown of the commit
+    if by_language || group_by_dir.is_some() {
+        let result = repo
+            .revparse_single(commit_sha.as_deref().unwrap_or("HEAD"))
+            .and_then(|commit| {
+                let options = CommitStatsOptions {
+                    per_file: true,
+                    ignore_whitespace,
+                    diff_algorithm,
+                    attribute_identical_to_prior,
+                    ..Default::default()
+                };
+                stats_for_commit_stats_with_options(
+                    &repo,
+                    &commit.id(),
+                    &effective_patterns,
+                    &options,
+                )
+            });
+        match result {
+            Ok(stats) => {
+                if let Some(depth) = group_by_dir {
+                    let directories = stats_by_directory(&stats.files, depth);
+                    if json_output {
+                        println!("{}", serde_json::to_string(&directories).unwrap());
+                    } else {
+                        write_directory_stats_to_terminal(&directories, true);
+                    }
+                } else {
+                    let languages =
+                        stats_by_language(&stats.files, &LanguageClassifier::new(&repo));
+                    if json_output {
+                        println!("{}", serde_json::to_string(&languages).unwrap());
+                    } else {
+                        write_language_stats_to_terminal(&languages, true);
+                    }
+                }
+                enforce_stats_thresholds(&thresholds, &stats);
+            }
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                exit_stats(1);
+            }
+        }
+        return;
+    }
+
+    // Test and production code are counted separately by running the stats once per bucket
+    if classify_tests {
+        if net
+            || csv_output
+            || ndjson_output
+            || table_output
+            || per_file
+            || author_email.is_some()
+            || committer_email.is_some()
+            || since.is_some()
+            || until.is_some()
+            || merge_strategy.is_some()
+        {
+            eprintln!("--classify-tests can only be combined with --json, --file and --ignore");
+            exit_stats(1);
+        }
+        let patterns = test_patterns(&repo, &test_globs);
+        let result =
+            classify_test_stats(
+                &patterns,
+                &effective_patterns,
+                |bucket_patterns| match commit_range.as_ref() {
+                    Some(range) => {
+                        range_authorship::range_authorship(range.clone(), false, bucket_patterns)
+                            .map(|stats| stats.range_stats)
+                    }
+                    None => {
+                        let commit = repo
+                            .revparse_single(commit_sha.as_deref().unwrap_or("HEAD"))?
+                            .peel_to_commit()?;
+                        let options = CommitStatsOptions {
+                            ignore_whitespace,
+                            diff_algorithm,
+                            attribute_identical_to_prior,
+                            ..Default::default()
+                        };
+                        compute_commit_stats_with_options(&repo, &commit, bucket_patterns, &options)
+                    }
+                },
+            );
+        match result {
+            Ok(classified) => {
                 if json_output {
-                    let json_str = serde_json::to_string(&stats).unwrap();
-                    println!("{}", json_str);
+                    println!("{}", serde_json::to_string(&classified).unwrap());
                 } else {
-                    range_authorship::print_range_authorship_stats(&stats);
+                    println!("Test code");
+                    write_stats_to_terminal(&classified.test, true);
+                    println!("Production code");
+                    write_stats_to_terminal(&classified.production, true);
                 }
+                enforce_stats_thresholds(
+                    &thresholds,
+                    &[classified.test, classified.production].iter().sum(),
+                );
+            }
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                exit_stats(1);
+            }
+        }
+        return;
+    }
+
+    if author_map_path.is_some() && author_email.is_none() && committer_email.is_none() {
+        eprintln!("--author-map requires --author-email or --committer-email");
+        exit_stats(1);
+    }
+
+    // Filtering by author, committer or date, or choosing how merges count, sums the selected
+    // commits rather than squashing the whole range. A date window bounds the walk on its own, so
+    // it may start from any revision.
+    let date_filtered = since.is_some() || until.is_some();
+    if author_email.is_some()
+        || committer_email.is_some()
+        || date_filtered
+        || merge_strategy.is_some()
+    {
+        if !date_filtered && range_arg.is_none() {
+            eprintln!(
+                "--author-email, --committer-email, --include-merges and --first-parent require a commit range"
+            );
+            exit_stats(1);
+        }
+        let merge_strategy = merge_strategy.unwrap_or_default();
+        let author_map = match author_map_path.as_deref() {
+            Some(path) => match AuthorMap::load(std::path::Path::new(path)) {
+                Ok(map) => Some(map),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    exit_stats(1);
+                }
+            },
+            None => None,
+        };
+        let log_options = commands::log::LogOptions {
+            revision: range_arg.or(commit_sha),
+            no_merges: merge_strategy == MergeStrategy::SkipMerges,
+            first_parent: merge_strategy == MergeStrategy::FirstParent,
+            author_email,
+            committer_email,
+            author_map,
+            since,
+            until,
+            jobs,
+            breakdowns,
+            ..Default::default()
+        };
+        let entries = match commands::log::log_entries(&repo, &log_options, &effective_patterns) {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                exit_stats(1);
+            }
+        };
+
+        if format == StatsOutputFormat::Csv || format == StatsOutputFormat::Table {
+            let rows: Vec<(String, CommitStats)> = entries
+                .into_iter()
+                .map(|entry| (entry.commit_sha, entry.stats))
+                .collect();
+            let output = stats_profile::time(StatsPhase::Serialization, || {
+                if format == StatsOutputFormat::Table {
+                    Ok(write_stats_to_table(&rows, false))
+                } else {
+                    write_stats_to_csv(&rows)
+                }
+            });
+            match output {
+                Ok(output) => print!("{}", output),
+                Err(e) => {
+                    eprintln!("Stats failed: {}", e);
+                    exit_stats(1);
+                }
+            }
+            enforce_stats_thresholds(
+                &thresholds,
+                &rows.iter().map(|(_, s)| s).sum::<CommitStats>(),
+            );
+            return;
+        }
+
+        if format == StatsOutputFormat::Ndjson {
+            for entry in &entries {
+                match stats_profile::time(StatsPhase::Serialization, || {
+                    write_stats_ndjson_line(&entry.commit_sha, &entry.stats)
+                }) {
+                    Ok(line) => println!("{}", line),
+                    Err(e) => {
+                        eprintln!("Stats failed: {}", e);
+                        exit_stats(1);
+                    }
+                }
+            }
+            enforce_stats_thresholds(
+                &thresholds,
+                &entries
+                    .iter()
+                    .map(|entry| &entry.stats)
+                    .sum::<CommitStats>(),
+            );
+            return;
+        }
+
+        let total: CommitStats = entries.iter().map(|entry| &entry.stats).sum();
+        stats_profile::time(StatsPhase::Serialization, || {
+            if json_output {
+                println!("{}", serde_json::to_string(&total).unwrap());
+            } else {
+                write_stats_to_terminal(&total, true);
+            }
+        });
+        enforce_stats_thresholds(&thresholds, &total);
+        return;
+    }
+
+    // Rows of a range skip the breakdowns that blame deleted lines and read changed files
+    // unless asked for
+    let row_options = if breakdowns {
+        CommitStatsOptions::default().with_breakdowns()
+    } else {
+        CommitStatsOptions::default()
+    };
+
+    // NDJSON over a range streams one line per commit as soon as its stats are computed
+    if let Some(range) = commit_range.as_ref()
+        && format == StatsOutputFormat::Ndjson
+    {
+        let shas = range.commits_with_rev_list_args(MergeStrategy::default().rev_list_args());
+        // Holding the lock while printing keeps lines from parallel jobs whole
+        let total = std::sync::Mutex::new((CommitStats::default(), 0));
+        let progress = CommitProgress::new(true);
+        let result = stream_stats_for_commits(
+            &repo,
+            &shas,
+            &effective_patterns,
+            &row_options,
+            jobs,
+            &|sha, stats| {
+                let line = stats_profile::time(StatsPhase::Serialization, || {
+                    write_stats_ndjson_line(sha, &stats)
+                })?;
+                let mut total = total.lock().unwrap();
+                progress.println(&line);
+                total.0.accumulate(&stats);
+                total.1 += 1;
+                progress.update(total.1, shas.len());
+                Ok(())
+            },
+        );
+        progress.finish();
+        if let Err(e) = result {
+            eprintln!("Stats failed: {}", e);
+            exit_stats(1);
+        }
+        enforce_stats_thresholds(&thresholds, &total.into_inner().unwrap().0);
+        return;
+    }
+
+    // CSV and tables over a range emit one row per commit rather than the squashed range totals
+    if let Some(range) = commit_range.as_ref()
+        && (format == StatsOutputFormat::Csv || format == StatsOutputFormat::Table)
+    {
+        let shas = range.commits_with_rev_list_args(MergeStrategy::default().rev_list_args());
+        // CSV is machine-readable too, but it goes to stdout and the bar to stderr
+        let progress = CommitProgress::new(true);
+        let result = stats_for_commits_with_progress(
+            &repo,
+            &shas,
+            &effective_patterns,
+            &row_options,
+            jobs,
+            &|done, total| progress.update(done, total),
+        );
+        progress.finish();
+        let rows: Vec<(String, CommitStats)> = match result {
+            Ok(stats) => shas.into_iter().zip(stats).collect(),
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                exit_stats(1);
+            }
+        };
+        let output = stats_profile::time(StatsPhase::Serialization, || {
+            if format == StatsOutputFormat::Table {
+                Ok(write_stats_to_table(&rows, false))
+            } else {
+                write_stats_to_csv(&rows)
+            }
+        });
+        match output {
+            Ok(output) => print!("{}", output),
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                exit_stats(1);
+            }
+        }
+        enforce_stats_thresholds(
+            &thresholds,
+            &rows.iter().map(|(_, s)| s).sum::<CommitStats>(),
+        );
+        return;
+    }
+
+    // Handle commit range if detected
+    if let Some(range) = commit_range {
+        let progress = CommitProgress::new(!json_output);
+        let result = range_authorship::range_authorship_with_progress(
+            range,
+            false,
+            &effective_patterns,
+            jobs,
+            &|done, total| progress.update(done, total),
+        );
+        progress.finish();
+        match result {
+            Ok(stats) => {
+                stats_profile::time(StatsPhase::Serialization, || {
+                    if json_output {
+                        let json_str = serde_json::to_string(&stats).unwrap();
+                        println!("{}", json_str);
+                    } else {
+                        range_authorship::print_range_authorship_stats(&stats);
+                    }
+                });
+                enforce_stats_thresholds(&thresholds, &stats.range_stats);
             }
             Err(e) => {
                 eprintln!("Range authorship failed: {}", e);
-                std::process::exit(1);
+                exit_stats(1);
             }
         }
         return;
     }
 
-    if let Err(e) = stats_command(
+    match stats_command(
         &repo,
         commit_sha.as_deref(),
-        json_output,
+        format,
+        CommitStatsOptions {
+            per_file,
+            ignore_whitespace,
+            diff_algorithm,
+            attribute_identical_to_prior,
+            by_session,
+            ..Default::default()
+        },
         &effective_patterns,
     ) {
-        match e {
-            crate::error::GitAiError::Generic(msg) if msg.starts_with("No commit found:") => {
-                eprintln!("{}", msg);
+        Ok(stats) => enforce_stats_thresholds(&thresholds, &stats),
+        Err(e) => {
+            match e {
+                crate::error::GitAiError::Generic(msg) if msg.starts_with("No commit found:") => {
+                    eprintln!("{}", msg);
+                }
+                _ => {
+                    eprintln!("Stats failed: {}", e);
+                }
             }
+            exit_stats(1);
+        }
+    }
+}
+
+fn parse_stats_threshold(thresholds: &mut StatsThresholds, flag: &str, value: &str) {
+    if flag == "--max-ai-percentage" {
+        match value.parse::<f64>() {
+            Ok(max) if (0.0..=100.0).contains(&max) => thresholds.max_ai_percentage = Some(max),
             _ => {
-                eprintln!("Stats failed: {}", e);
+                eprintln!("Invalid --max-ai-percentage value: {}", value);
+                std::process::exit(1);
             }
         }
+    } else {
+        match value.parse::<u32>() {
+            Ok(min) => thresholds.min_human_lines = Some(min),
+            Err(_) => {
+                eprintln!("Invalid --min-human-lines value: {}", value);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Prints the `--profile` timings to stderr once `handle_stats` returns
+struct StatsProfileReport(&'static StatsProfile);
+
+impl Drop for StatsProfileReport {
+    fn drop(&mut self) {
+        eprint!("{}", self.0.report());
+    }
+}
+
+/// Exit from `handle_stats`. `process::exit` skips `StatsProfileReport`'s drop, so the
+/// `--profile` timings are printed here, including when a threshold fails.
+fn exit_stats(code: i32) -> ! {
+    if let Some(profile) = stats_profile::enabled() {
+        eprint!("{}", profile.report());
+    }
+    std::process::exit(code);
+}
+
+fn parse_diff_algorithm(name: &str) -> DiffAlgorithm {
+    DiffAlgorithm::from_name(name).unwrap_or_else(|| {
+        eprintln!(
+            "Invalid --diff-algorithm: {} (expected myers, minimal, patience or histogram)",
+            name
+        );
+        std::process::exit(1);
+    })
+}
+
+/// Exit nonzero after the stats were printed if they break any of the thresholds.
+fn enforce_stats_thresholds(thresholds: &StatsThresholds, stats: &CommitStats) {
+    let violations = thresholds.violations(stats);
+    for violation in &violations {
+        eprintln!("Threshold failed: {}", violation);
+    }
+    if !violations.is_empty() {
+        exit_stats(exit_code::THRESHOLD_EXCEEDED);
+    }
+}
+
+fn handle_multi_repo_stats(args: &[String]) {
+    let mut paths: Vec<String> = Vec::new();
+    let mut since: Option<String> = None;
+    let mut until: Option<String> = None;
+    let mut ignore_patterns: Vec<String> = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--repos" => {
+                i += 1;
+                while i < args.len() && !args[i].starts_with("--") {
+                    paths.push(args[i].clone());
+                    i += 1;
+                }
+            }
+            "--ignore" => {
+                i += 1;
+                while i < args.len() && !args[i].starts_with("--") {
+                    ignore_patterns.push(args[i].clone());
+                    i += 1;
+                }
+            }
+            "--since" | "--until" => {
+                if i + 1 >= args.len() {
+                    eprintln!("{} requires a value", args[i]);
+                    std::process::exit(1);
+                }
+                if args[i] == "--since" {
+                    since = Some(args[i + 1].clone());
+                } else {
+                    until = Some(args[i + 1].clone());
+                }
+                i += 2;
+            }
+            arg if arg.starts_with("--since=") => {
+                since = Some(arg["--since=".len()..].to_string());
+                i += 1;
+            }
+            arg if arg.starts_with("--until=") => {
+                until = Some(arg["--until=".len()..].to_string());
+                i += 1;
+            }
+            // Multi-repo stats are always printed as JSON
+            "--json" => {
+                i += 1;
+            }
+            other => {
+                eprintln!("Unsupported argument with --repos: {}", other);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if paths.is_empty() {
+        eprintln!("--repos requires at least one repository path");
+        std::process::exit(1);
+    }
+
+    let aggregate = aggregate_repos(&paths, since.as_deref(), until.as_deref(), &ignore_patterns);
+    for repo in &aggregate.repos {
+        if let Some(error) = &repo.error {
+            eprintln!("Skipping {}: {}", repo.path, error);
+        }
+    }
+    println!("{}", serde_json::to_string(&aggregate).unwrap());
+
+    if aggregate.repos.iter().all(|repo| repo.error.is_some()) {
         std::process::exit(1);
     }
 }
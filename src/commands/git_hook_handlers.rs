@@ -794,9 +794,27 @@ fn select_forward_target_for_repo(
         }
     }
 
+    // Without any hooksPath git runs hooks from `<git-dir>/hooks`; chain to those instead of
+    // silently shadowing them once core.hooksPath points at the managed directory.
+    if let Some(default_hooks) = default_hooks_dir_with_user_hooks(repo) {
+        return (
+            ForwardMode::RepoLocal,
+            Some(default_hooks.to_string_lossy().to_string()),
+            None,
+        );
+    }
+
     (ForwardMode::None, None, None)
 }
 
+fn default_hooks_dir_with_user_hooks(repo: &Repository) -> Option<PathBuf> {
+    let hooks_dir = repo.common_dir().join(GIT_HOOKS_DIR_NAME);
+    let has_user_hook = CORE_GIT_HOOK_NAMES
+        .iter()
+        .any(|hook_name| hooks_dir.join(hook_name).is_file());
+    has_user_hook.then_some(hooks_dir)
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct EnsureRepoHooksReport {
     pub changed: bool,
@@ -18,10 +18,12 @@ pub fn pre_stash_hook(
         None => return, // Implicit push, nothing to capture
     };
 
-    if subcommand == "pop" || subcommand == "apply" {
-        // Capture the stash SHA BEFORE git runs (pop will delete it)
+    if subcommand == "pop" || subcommand == "apply" || subcommand == "branch" {
+        // Capture the stash SHA BEFORE git runs (pop will delete it).
+        // `stash branch` takes the new branch name before the stash reference.
+        let ref_position = if subcommand == "branch" { 2 } else { 1 };
         let stash_ref = parsed_args
-            .pos_command(1)
+            .pos_command(ref_position)
             .unwrap_or_else(|| "stash@{0}".to_string());
 
         if let Ok(stash_sha) = resolve_stash_to_sha(repository, &stash_ref) {
@@ -79,8 +81,8 @@ pub fn post_stash_hook(
         if let Err(e) = save_stash_authorship_log(repository, &pathspecs) {
             debug_log(&format!("Failed to save stash authorship log: {}", e));
         }
-    } else if subcommand == "pop" || subcommand == "apply" {
-        // Stash was applied - restore attributions from git note
+    } else if subcommand == "pop" || subcommand == "apply" || subcommand == "branch" {
+        // Stash was applied (on a new branch for `stash branch`) - restore attributions from git note
         // Use the stash SHA we captured in pre-hook (before Git deleted it)
         let stash_sha = match &command_hooks_context.stash_sha {
             Some(sha) => sha.clone(),
@@ -153,7 +155,7 @@ fn save_stash_authorship_log(repo: &Repository, pathspecs: &[String]) -> Result<
 
     // Save as git note at refs/notes/ai-stash
     let json = authorship_log
-        .serialize_to_string()
+        .serialize_for_storage()
         .map_err(|e| GitAiError::Generic(format!("Failed to serialize authorship log: {}", e)))?;
     save_stash_note(repo, &stash_sha, &json)?;
 
@@ -296,7 +298,12 @@ fn read_stash_note(repo: &Repository, stash_sha: &str) -> Result<String, GitAiEr
 fn resolve_stash_to_sha(repo: &Repository, stash_ref: &str) -> Result<String, GitAiError> {
     let mut args = repo.global_args_for_exec();
     args.push("rev-parse".to_string());
-    args.push(stash_ref.to_string());
+    // Git accepts a bare index like `git stash pop 1` as shorthand for stash@{1}
+    if !stash_ref.is_empty() && stash_ref.chars().all(|c| c.is_ascii_digit()) {
+        args.push(format!("stash@{{{}}}", stash_ref));
+    } else {
+        args.push(stash_ref.to_string());
+    }
 
     let output = exec_git(&args)?;
 
@@ -0,0 +1,136 @@
+use crate::commands::git_hook_handlers::{ensure_repo_hooks_installed, mark_repo_hooks_enabled};
+use crate::config::{FileConfig, config_file_path_public, save_file_config};
+use crate::error::GitAiError;
+use crate::git::attestation_storage::{AttestationStorage, configured_storage};
+use crate::git::find_repository;
+use crate::git::refs::migrate_attestation_storage;
+use crate::git::repository::Repository;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InitOptions {
+    pub hooks: bool,
+    pub storage: Option<AttestationStorage>,
+}
+
+pub fn handle_init(args: &[String]) {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("Usage: git-ai init [--hooks] [--storage <notes|sidecar>]");
+            std::process::exit(1);
+        }
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match init(&repo, &options) {
+        Ok(steps) => {
+            for step in steps {
+                println!("{}", step);
+            }
+        }
+        Err(e) => {
+            eprintln!("Init failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<InitOptions, String> {
+    let mut options = InitOptions::default();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--hooks" => options.hooks = true,
+            "--storage" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--storage requires a value".to_string())?;
+                options.storage = Some(value.parse()?);
+            }
+            arg if arg.starts_with("--storage=") => {
+                options.storage = Some(arg["--storage=".len()..].parse()?);
+            }
+            other => return Err(format!("Unknown init argument: {}", other)),
+        }
+        i += 1;
+    }
+
+    Ok(options)
+}
+
+/// Set up git-ai for `repo`, returning one line per step saying what changed. Every step
+/// leaves existing setup alone, so running init again only reports that everything is ok.
+pub fn init(repo: &Repository, options: &InitOptions) -> Result<Vec<String>, GitAiError> {
+    let mut steps = Vec::new();
+
+    // An existing config file is never rewritten, so settings made since are kept
+    let config_path = config_file_path_public()
+        .ok_or_else(|| GitAiError::Generic("Could not determine config file path".to_string()))?;
+    if config_path.exists() {
+        steps.push(format!("config file ok: {}", config_path.display()));
+    } else {
+        save_file_config(&FileConfig::default()).map_err(GitAiError::Generic)?;
+        steps.push(format!("config file created: {}", config_path.display()));
+    }
+
+    // Switching backends copies the existing authorship logs, like migrate-storage
+    let current = configured_storage(repo);
+    match options.storage {
+        Some(target) if target != current => {
+            let copied = migrate_attestation_storage(repo, target)?;
+            steps.push(format!(
+                "storage set: {} (copied {} authorship log(s) from {})",
+                target, copied, current
+            ));
+        }
+        _ => steps.push(format!("storage ok: {}", current)),
+    }
+
+    if options.hooks {
+        let report = ensure_repo_hooks_installed(repo, false)?;
+        mark_repo_hooks_enabled(repo)?;
+        let status = if report.changed { "updated" } else { "ok" };
+        steps.push(format!(
+            "repo hooks {}: {}",
+            status,
+            report.managed_hooks_path.display()
+        ));
+    }
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(&[]).unwrap(), InitOptions::default());
+
+        let options = parse_args(&args(&["--hooks", "--storage", "sidecar"])).unwrap();
+        assert!(options.hooks);
+        assert_eq!(options.storage, Some(AttestationStorage::Sidecar));
+
+        let options = parse_args(&args(&["--storage=notes"])).unwrap();
+        assert_eq!(options.storage, Some(AttestationStorage::Notes));
+
+        assert!(parse_args(&args(&["--storage"])).is_err());
+        assert!(parse_args(&args(&["--storage", "files"])).is_err());
+        assert!(parse_args(&args(&["--force"])).is_err());
+    }
+}
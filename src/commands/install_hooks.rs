@@ -1,5 +1,9 @@
 use crate::commands::flush_metrics_db::spawn_background_metrics_db_flush;
+use crate::commands::git_hook_handlers::{
+    ensure_repo_hooks_installed, mark_repo_hooks_enabled, remove_repo_hooks,
+};
 use crate::error::GitAiError;
+use crate::git::find_repository;
 use crate::mdm::agents::get_all_installers;
 use crate::mdm::git_client_installer::GitClientInstallerParams;
 use crate::mdm::git_clients::get_all_git_client_installers;
@@ -9,6 +13,9 @@ use crate::mdm::spinner::{Spinner, print_diff};
 use crate::mdm::utils::{get_current_binary_path, git_shim_path};
 use std::collections::HashMap;
 
+/// Status key for the repo-local git hooks of the repository install-hooks runs in
+const REPO_HOOKS_ID: &str = "git-repo";
+
 /// Installation status for a tool
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InstallStatus {
@@ -106,6 +113,7 @@ pub fn run(args: &[String]) -> Result<HashMap<String, String>, GitAiError> {
     // Parse flags
     let mut dry_run = false;
     let mut verbose = false;
+    let mut repo_hooks = false;
     for arg in args {
         if arg == "--dry-run" || arg == "--dry-run=true" {
             dry_run = true;
@@ -113,6 +121,9 @@ pub fn run(args: &[String]) -> Result<HashMap<String, String>, GitAiError> {
         if arg == "--verbose" || arg == "-v" {
             verbose = true;
         }
+        if arg == "--repo" {
+            repo_hooks = true;
+        }
     }
 
     // Get absolute path to the current binary
@@ -120,7 +131,7 @@ pub fn run(args: &[String]) -> Result<HashMap<String, String>, GitAiError> {
     let params = HookInstallerParams { binary_path };
 
     // Run async operations with smol and convert result
-    let statuses = smol::block_on(async_run_install(&params, dry_run, verbose))?;
+    let statuses = smol::block_on(async_run_install(&params, dry_run, verbose, repo_hooks))?;
 
     // Spawn background processes to flush metrics
     crate::observability::spawn_background_flush();
@@ -134,6 +145,7 @@ pub fn run_uninstall(args: &[String]) -> Result<HashMap<String, String>, GitAiEr
     // Parse flags
     let mut dry_run = false;
     let mut verbose = false;
+    let mut repo_hooks = false;
     for arg in args {
         if arg == "--dry-run" || arg == "--dry-run=true" {
             dry_run = true;
@@ -141,6 +153,9 @@ pub fn run_uninstall(args: &[String]) -> Result<HashMap<String, String>, GitAiEr
         if arg == "--verbose" || arg == "-v" {
             verbose = true;
         }
+        if arg == "--repo" {
+            repo_hooks = true;
+        }
     }
 
     // Get absolute path to the current binary
@@ -148,7 +163,7 @@ pub fn run_uninstall(args: &[String]) -> Result<HashMap<String, String>, GitAiEr
     let params = HookInstallerParams { binary_path };
 
     // Run async operations with smol and convert result
-    let statuses = smol::block_on(async_run_uninstall(&params, dry_run, verbose))?;
+    let statuses = smol::block_on(async_run_uninstall(&params, dry_run, verbose, repo_hooks))?;
     Ok(to_hashmap(statuses))
 }
 
@@ -156,6 +171,7 @@ async fn async_run_install(
     params: &HookInstallerParams,
     dry_run: bool,
     verbose: bool,
+    repo_hooks: bool,
 ) -> Result<HashMap<String, InstallStatus>, GitAiError> {
     let mut any_checked = false;
     let mut has_changes = false;
@@ -377,6 +393,14 @@ async fn async_run_install(
         }
     }
 
+    // === Repository ===
+    if repo_hooks && let Some(result) = sync_current_repo_hooks(true, dry_run) {
+        any_checked = true;
+        has_changes |= result.status == InstallStatus::Installed;
+        statuses.insert(REPO_HOOKS_ID.to_string(), result.status);
+        detailed_results.push((REPO_HOOKS_ID.to_string(), result));
+    }
+
     if !any_checked {
         println!("No compatible IDEs or agent configurations detected. Nothing to install.");
     } else if has_changes && dry_run {
@@ -393,6 +417,62 @@ async fn async_run_install(
     Ok(statuses)
 }
 
+/// Install (or remove) the repo-local git hooks for `--repo` when run inside a non-bare
+/// repository, so commits made with plain git record authorship. Hooks the repo already had are chained to
+/// rather than replaced, and restored on removal.
+fn sync_current_repo_hooks(install: bool, dry_run: bool) -> Option<InstallResult> {
+    let repo = find_repository(&Vec::<String>::new()).ok()?;
+    if repo.is_bare_repository().unwrap_or(true) {
+        return None;
+    }
+
+    println!("\n\x1b[1mRepository\x1b[0m");
+    let spinner = Spinner::new(if install {
+        "git hooks: checking"
+    } else {
+        "git hooks: removing"
+    });
+    spinner.start();
+
+    let changed = if install {
+        ensure_repo_hooks_installed(&repo, dry_run).and_then(|report| {
+            if !dry_run {
+                mark_repo_hooks_enabled(&repo)?;
+            }
+            Ok(report.changed)
+        })
+    } else {
+        remove_repo_hooks(&repo, dry_run).map(|report| report.changed)
+    };
+
+    match changed {
+        Ok(true) => {
+            if dry_run {
+                spinner.pending("git hooks: Pending updates");
+            } else if install {
+                spinner.success("git hooks: Installed");
+            } else {
+                spinner.success("git hooks: Removed");
+            }
+            Some(InstallResult::installed())
+        }
+        Ok(false) => {
+            if install {
+                spinner.success("git hooks: Already up to date");
+            } else {
+                spinner.success("git hooks: Not installed");
+            }
+            Some(InstallResult::already_installed())
+        }
+        Err(e) => {
+            let error_msg = e.to_string();
+            spinner.error("git hooks: Failed to update");
+            eprintln!("  Error: {}", error_msg);
+            Some(InstallResult::failed(error_msg))
+        }
+    }
+}
+
 /// Emit metrics events for install-hooks results
 fn emit_install_hooks_metrics(results: &[(String, InstallResult)]) {
     use crate::metrics::{EventAttributes, InstallHooksValues};
@@ -418,6 +498,7 @@ async fn async_run_uninstall(
     params: &HookInstallerParams,
     dry_run: bool,
     verbose: bool,
+    repo_hooks: bool,
 ) -> Result<HashMap<String, InstallStatus>, GitAiError> {
     let mut any_checked = false;
     let mut has_changes = false;
@@ -587,6 +668,13 @@ async fn async_run_uninstall(
         }
     }
 
+    // === Repository ===
+    if repo_hooks && let Some(result) = sync_current_repo_hooks(false, dry_run) {
+        any_checked = true;
+        has_changes |= result.status == InstallStatus::Installed;
+        statuses.insert(REPO_HOOKS_ID.to_string(), result.status);
+    }
+
     if !any_checked {
         println!("No git-ai hooks found to uninstall.");
     } else if has_changes && dry_run {
@@ -0,0 +1,284 @@
+use crate::authorship::author_map::AuthorMap;
+use crate::authorship::ignore::effective_ignore_patterns;
+use crate::authorship::stats::CommitStats;
+use crate::commands::log::{LogEntry, LogOptions, log_entries};
+use crate::git::find_repository;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Which identity on a commit the leaderboard groups by. Rebases and cherry-picks keep the
+/// author but make whoever rewrote the commit its committer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeaderboardIdentity {
+    #[default]
+    Committer,
+    Author,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LeaderboardOptions {
+    pub revision: Option<String>,
+    pub json: bool,
+    pub anonymize: bool,
+    pub by: LeaderboardIdentity,
+    /// File of `alias = canonical` email pairs merging one person's identities
+    pub author_map: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LeaderboardEntry {
+    /// Committer email, or its hash with `--anonymize`. Set when grouping by committer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committer: Option<String>,
+    /// Author email, or its hash with `--anonymize`. Set when grouping by author.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    pub commits: u32,
+    pub ai_additions: u32,
+    pub human_additions: u32,
+    pub total_additions: u32,
+    pub ai_percentage: f64,
+}
+
+pub fn handle_leaderboard(args: &[String]) {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!(
+                "Usage: git-ai leaderboard [<revision>|<range>] [--by=committer|author] [--author-map <file>] [--json] [--anonymize]"
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let author_map = match options.author_map.as_deref() {
+        Some(path) => match AuthorMap::load(Path::new(path)) {
+            Ok(map) => map,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => AuthorMap::default(),
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Merge commits repeat the lines of the branch commits they bring in, so leave them out
+    let log_options = LogOptions {
+        revision: options.revision.clone(),
+        no_merges: true,
+        ..Default::default()
+    };
+    let ignore_patterns = effective_ignore_patterns(&repo, &[], &[]);
+    let entries = match log_entries(&repo, &log_options, &ignore_patterns) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Leaderboard failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let leaderboard = leaderboard(&entries, options.by, options.anonymize, &author_map);
+
+    if options.json {
+        match serde_json::to_string(&leaderboard) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize leaderboard: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        for entry in &leaderboard {
+            println!(
+                "{:>5.1}% ai  {:>6} lines  {:>4} commits  {}",
+                entry.ai_percentage,
+                entry.total_additions,
+                entry.commits,
+                entry.email()
+            );
+        }
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<LeaderboardOptions, String> {
+    let mut options = LeaderboardOptions::default();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--json" => options.json = true,
+            "--anonymize" => options.anonymize = true,
+            "--by" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--by requires a value".to_string())?;
+                options.by = parse_identity(value)?;
+            }
+            arg if arg.starts_with("--by=") => {
+                options.by = parse_identity(&arg["--by=".len()..])?;
+            }
+            "--author-map" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--author-map requires a file".to_string())?;
+                options.author_map = Some(value.clone());
+            }
+            arg if arg.starts_with("--author-map=") => {
+                options.author_map = Some(arg["--author-map=".len()..].to_string());
+            }
+            arg if arg.starts_with('-') => {
+                return Err(format!("Unknown leaderboard argument: {}", arg));
+            }
+            arg => {
+                if options.revision.is_some() {
+                    return Err("leaderboard accepts at most one revision or range".to_string());
+                }
+                options.revision = Some(arg.to_string());
+            }
+        }
+        i += 1;
+    }
+
+    Ok(options)
+}
+
+fn parse_identity(value: &str) -> Result<LeaderboardIdentity, String> {
+    match value {
+        "committer" => Ok(LeaderboardIdentity::Committer),
+        "author" => Ok(LeaderboardIdentity::Author),
+        other => Err(format!("Unsupported leaderboard identity: {}", other)),
+    }
+}
+
+impl LeaderboardEntry {
+    /// The email (or hash) the row was grouped by
+    pub fn email(&self) -> &str {
+        self.committer
+            .as_deref()
+            .or(self.author.as_deref())
+            .unwrap_or_default()
+    }
+}
+
+/// Short, stable stand-in for an email. Emails are compared case-insensitively, so the
+/// lowercased address is hashed.
+pub fn anonymize_email(email: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(email.to_lowercase().as_bytes());
+    let digest = format!("{:x}", hasher.finalize());
+    digest[..12].to_string()
+}
+
+/// Sum stats per committer (or author) email, highest AI percentage first. Ties go to the
+/// identity with more lines, then to the email (or hash) in alphabetical order. Aliases in
+/// `author_map` are counted under their canonical email.
+pub fn leaderboard(
+    entries: &[LogEntry],
+    by: LeaderboardIdentity,
+    anonymize: bool,
+    author_map: &AuthorMap,
+) -> Vec<LeaderboardEntry> {
+    let mut by_email: BTreeMap<String, (u32, CommitStats)> = BTreeMap::new();
+    for entry in entries {
+        let email = match by {
+            LeaderboardIdentity::Committer => &entry.committer_email,
+            LeaderboardIdentity::Author => &entry.author_email,
+        };
+        let (commits, stats) = by_email.entry(author_map.canonical(email)).or_default();
+        *commits += 1;
+        stats.accumulate(&entry.stats);
+    }
+
+    let mut leaderboard: Vec<LeaderboardEntry> = by_email
+        .into_iter()
+        .map(|(email, (commits, stats))| {
+            let email = if anonymize {
+                anonymize_email(&email)
+            } else {
+                email
+            };
+            let (committer, author) = match by {
+                LeaderboardIdentity::Committer => (Some(email), None),
+                LeaderboardIdentity::Author => (None, Some(email)),
+            };
+            LeaderboardEntry {
+                committer,
+                author,
+                commits,
+                ai_additions: stats.ai_additions,
+                human_additions: stats.human_additions,
+                total_additions: stats.ai_additions + stats.human_additions,
+                ai_percentage: stats.ai_percentage,
+            }
+        })
+        .collect();
+
+    leaderboard.sort_by(|a, b| {
+        b.ai_percentage
+            .total_cmp(&a.ai_percentage)
+            .then(b.total_additions.cmp(&a.total_additions))
+            .then_with(|| a.email().cmp(b.email()))
+    });
+
+    leaderboard
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&[]).unwrap();
+        assert!(options.revision.is_none());
+        assert!(!options.json);
+        assert!(!options.anonymize);
+        assert_eq!(options.by, LeaderboardIdentity::Committer);
+
+        let options = parse_args(&args(&["main..HEAD", "--json", "--anonymize"])).unwrap();
+        assert_eq!(options.revision.as_deref(), Some("main..HEAD"));
+        assert!(options.json);
+        assert!(options.anonymize);
+
+        let options = parse_args(&args(&["--by=author"])).unwrap();
+        assert_eq!(options.by, LeaderboardIdentity::Author);
+        let options = parse_args(&args(&["--by", "committer"])).unwrap();
+        assert_eq!(options.by, LeaderboardIdentity::Committer);
+        assert!(parse_args(&args(&["--by=reviewer"])).is_err());
+        assert!(parse_args(&args(&["--by"])).is_err());
+
+        let options = parse_args(&args(&["--author-map", "authors.txt"])).unwrap();
+        assert_eq!(options.author_map.as_deref(), Some("authors.txt"));
+        let options = parse_args(&args(&["--author-map=authors.txt"])).unwrap();
+        assert_eq!(options.author_map.as_deref(), Some("authors.txt"));
+        assert!(parse_args(&args(&["--author-map"])).is_err());
+
+        assert!(parse_args(&args(&["--by-author"])).is_err());
+        assert!(parse_args(&args(&["HEAD", "HEAD~1"])).is_err());
+    }
+
+    #[test]
+    fn test_anonymize_email_ignores_case() {
+        let hash = anonymize_email("Dev@Example.com");
+        assert_eq!(hash.len(), 12);
+        assert_eq!(hash, anonymize_email("dev@example.com"));
+        assert!(!hash.contains('@'));
+    }
+}
@@ -0,0 +1,366 @@
+use crate::authorship::author_map::AuthorMap;
+use crate::authorship::ignore::effective_ignore_patterns;
+use crate::authorship::stats::{CommitStats, CommitStatsOptions, stats_for_commits};
+use crate::authorship::stats_cache::StatsCache;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::note_blob_oids_for_commits;
+use crate::git::repository::{InternalGitProfile, Repository, exec_git_with_profile};
+use crate::utils::paint;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub commit_sha: String,
+    #[serde(default)]
+    pub author_email: String,
+    #[serde(default)]
+    pub committer_email: String,
+    /// Author date as a Unix timestamp
+    #[serde(default)]
+    pub timestamp: i64,
+    pub summary: String,
+    pub is_merge: bool,
+    pub stats: CommitStats,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    pub revision: Option<String>,
+    pub max_count: Option<usize>,
+    pub no_merges: bool,
+    /// Follow only the first parent of merges, like `git log --first-parent`
+    pub first_parent: bool,
+    pub author_email: Option<String>,
+    /// Matches the committer, which rebases and cherry-picks set to whoever rewrote the commit
+    pub committer_email: Option<String>,
+    /// Widens the email filters to every alias of the same identity
+    pub author_map: Option<AuthorMap>,
+    /// Git date strings bounding the commit date, passed to `git log --since`/`--until`
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub json: bool,
+    /// Threads used to compute stats for uncached commits (0 picks one per CPU)
+    pub jobs: usize,
+    /// Also compute the deletion, character and verbatim breakdowns of each commit
+    pub breakdowns: bool,
+}
+
+pub fn handle_log(args: &[String]) {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!(
+                "Usage: git-ai log [<revision>|<range>] [--max-count <n>] [--no-merges] [--author-email <email>] [--committer-email <email>] [--breakdowns] [--json]"
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let ignore_patterns = effective_ignore_patterns(&repo, &[], &[]);
+    let entries = match log_entries(&repo, &options, &ignore_patterns) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Log failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if options.json {
+        match serde_json::to_string(&entries) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize log: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        for entry in &entries {
+            println!("{}", format_log_line(entry));
+        }
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<LogOptions, String> {
+    let mut options = LogOptions::default();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--json" => {
+                options.json = true;
+            }
+            "--no-merges" => {
+                options.no_merges = true;
+            }
+            "--first-parent" => {
+                options.first_parent = true;
+            }
+            "--breakdowns" => {
+                options.breakdowns = true;
+            }
+            "--max-count" | "-n" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--max-count requires a value".to_string())?;
+                options.max_count = Some(parse_max_count(value)?);
+            }
+            arg if arg.starts_with("--max-count=") => {
+                options.max_count = Some(parse_max_count(&arg["--max-count=".len()..])?);
+            }
+            "--author-email" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--author-email requires a value".to_string())?;
+                options.author_email = Some(value.clone());
+            }
+            arg if arg.starts_with("--author-email=") => {
+                options.author_email = Some(arg["--author-email=".len()..].to_string());
+            }
+            "--committer-email" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--committer-email requires a value".to_string())?;
+                options.committer_email = Some(value.clone());
+            }
+            arg if arg.starts_with("--committer-email=") => {
+                options.committer_email = Some(arg["--committer-email=".len()..].to_string());
+            }
+            arg if arg.starts_with('-') => {
+                return Err(format!("Unknown log argument: {}", arg));
+            }
+            arg => {
+                if options.revision.is_some() {
+                    return Err("log accepts at most one revision or range".to_string());
+                }
+                options.revision = Some(arg.to_string());
+            }
+        }
+        i += 1;
+    }
+
+    Ok(options)
+}
+
+fn parse_max_count(value: &str) -> Result<usize, String> {
+    value
+        .parse::<usize>()
+        .map_err(|_| format!("Invalid --max-count value: {}", value))
+}
+
+/// Walk commits from the given revision (HEAD by default) backwards, computing stats for each.
+pub fn log_entries(
+    repo: &Repository,
+    options: &LogOptions,
+    ignore_patterns: &[String],
+) -> Result<Vec<LogEntry>, GitAiError> {
+    // Before the first commit there is no history to walk, which is not an error
+    if options.revision.is_none() && repo.head_is_unborn()? {
+        return Ok(Vec::new());
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.push("log".to_string());
+    args.push("--no-color".to_string());
+    args.push("--format=%H%x1f%P%x1f%ae%x1f%ce%x1f%at%x1f%s".to_string());
+    if let Some(max_count) = options.max_count {
+        args.push(format!("--max-count={}", max_count));
+    }
+    if options.no_merges {
+        args.push("--no-merges".to_string());
+    }
+    if options.first_parent {
+        args.push("--first-parent".to_string());
+    }
+    // --author and --committer match "Name <email>" as a regex, so anchor on the escaped address
+    if options.author_email.is_some() || options.committer_email.is_some() {
+        args.push("--regexp-ignore-case".to_string());
+    }
+    // Repeated --author and --committer options match any of their values
+    let identity_emails = |email: &str| match &options.author_map {
+        Some(map) => map.emails_of(email),
+        None => vec![email.to_string()],
+    };
+    if let Some(email) = &options.author_email {
+        for email in identity_emails(email) {
+            args.push(format!("--author=<{}>", regex::escape(&email)));
+        }
+    }
+    if let Some(email) = &options.committer_email {
+        for email in identity_emails(email) {
+            args.push(format!("--committer=<{}>", regex::escape(&email)));
+        }
+    }
+    if let Some(since) = &options.since {
+        args.push(format!("--since={}", since));
+    }
+    if let Some(until) = &options.until {
+        args.push(format!("--until={}", until));
+    }
+    args.push(options.revision.as_deref().unwrap_or("HEAD").to_string());
+    args.push("--".to_string());
+
+    let output = exec_git_with_profile(&args, InternalGitProfile::General)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let commits: Vec<(&str, &str, &str, &str, &str, &str)> = stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(6, '\x1f');
+            Some((
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+                fields.next()?,
+            ))
+        })
+        .collect();
+
+    let stats_options = if options.breakdowns {
+        CommitStatsOptions::default().with_breakdowns()
+    } else {
+        CommitStatsOptions::default()
+    };
+
+    // Stats for a commit only change when its note, the ignore patterns or the options do, so
+    // they are cached keyed on all three. A failed note lookup just means every commit is
+    // recomputed.
+    let cache = StatsCache::for_repo(repo);
+    let commit_shas: Vec<String> = commits.iter().map(|c| c.0.to_string()).collect();
+    let note_blob_oids = note_blob_oids_for_commits(repo, &commit_shas).unwrap_or_default();
+
+    let keys: Vec<String> = commits
+        .iter()
+        .map(|c| {
+            StatsCache::key(
+                note_blob_oids.get(c.0).map(String::as_str),
+                ignore_patterns,
+                &stats_options,
+            )
+        })
+        .collect();
+    let mut cached: Vec<Option<CommitStats>> = commits
+        .iter()
+        .zip(&keys)
+        .map(|(c, key)| cache.get(c.0, key))
+        .collect();
+
+    let missing: Vec<usize> = (0..commits.len())
+        .filter(|&i| cached[i].is_none())
+        .collect();
+    let missing_shas: Vec<String> = missing.iter().map(|&i| commit_shas[i].clone()).collect();
+    let computed = stats_for_commits(
+        repo,
+        &missing_shas,
+        ignore_patterns,
+        &stats_options,
+        options.jobs,
+    )?;
+    for (i, stats) in missing.into_iter().zip(computed) {
+        cache.put(&commit_shas[i], &keys[i], &stats);
+        cached[i] = Some(stats);
+    }
+
+    let mut entries = Vec::new();
+    for ((sha, parents, author_email, committer_email, timestamp, summary), stats) in
+        commits.into_iter().zip(cached)
+    {
+        // Merge commits are measured against their first parent, so they report the
+        // lines brought in from the merged branch.
+        let is_merge = parents.split_whitespace().count() > 1;
+
+        entries.push(LogEntry {
+            commit_sha: sha.to_string(),
+            author_email: author_email.to_string(),
+            committer_email: committer_email.to_string(),
+            timestamp: timestamp.parse().unwrap_or(0),
+            summary: summary.to_string(),
+            is_merge,
+            stats: stats.unwrap_or_default(),
+        });
+    }
+
+    Ok(entries)
+}
+
+pub fn format_log_line(entry: &LogEntry) -> String {
+    let short_sha = &entry.commit_sha[..entry.commit_sha.len().min(7)];
+    let merge_marker = if entry.is_merge { " (merge)" } else { "" };
+    format!(
+        "{} {} ai  +{:<5} human  {}{}",
+        short_sha,
+        paint("32", &format!("+{:<5}", entry.stats.ai_additions)),
+        entry.stats.human_additions,
+        entry.summary,
+        merge_marker
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args_defaults() {
+        let options = parse_args(&[]).unwrap();
+        assert!(options.revision.is_none());
+        assert!(options.max_count.is_none());
+        assert!(!options.no_merges);
+        assert!(!options.json);
+        assert!(!options.breakdowns);
+    }
+
+    #[test]
+    fn test_parse_args_all_flags() {
+        let options = parse_args(&args(&[
+            "main..feature",
+            "--max-count",
+            "5",
+            "--no-merges",
+            "--author-email",
+            "dev@example.com",
+            "--committer-email=ci@example.com",
+            "--breakdowns",
+            "--json",
+        ]))
+        .unwrap();
+        assert!(options.breakdowns);
+        assert_eq!(options.revision.as_deref(), Some("main..feature"));
+        assert_eq!(options.max_count, Some(5));
+        assert!(options.no_merges);
+        assert_eq!(options.author_email.as_deref(), Some("dev@example.com"));
+        assert_eq!(options.committer_email.as_deref(), Some("ci@example.com"));
+        assert!(options.json);
+
+        let options = parse_args(&args(&["--max-count=3"])).unwrap();
+        assert_eq!(options.max_count, Some(3));
+    }
+
+    #[test]
+    fn test_parse_args_rejects_invalid_input() {
+        assert!(parse_args(&args(&["--max-count", "abc"])).is_err());
+        assert!(parse_args(&args(&["--max-count"])).is_err());
+        assert!(parse_args(&args(&["--author-email"])).is_err());
+        assert!(parse_args(&args(&["--committer-email"])).is_err());
+        assert!(parse_args(&args(&["HEAD", "HEAD~1"])).is_err());
+        assert!(parse_args(&args(&["--bogus"])).is_err());
+    }
+}
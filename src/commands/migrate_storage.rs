@@ -0,0 +1,65 @@
+use crate::git::attestation_storage::AttestationStorage;
+use crate::git::find_repository;
+use crate::git::refs::migrate_attestation_storage;
+
+pub fn handle_migrate_storage(args: &[String]) {
+    let target = match parse_args(args) {
+        Ok(target) => target,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("Usage: git-ai migrate-storage <notes|sidecar>");
+            std::process::exit(1);
+        }
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match migrate_attestation_storage(&repo, target) {
+        Ok(count) => println!(
+            "Copied {} authorship log(s) to {} storage; git-ai.storage is now '{}'",
+            count, target, target
+        ),
+        Err(e) => {
+            eprintln!("Migration failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<AttestationStorage, String> {
+    match args {
+        [mode] => mode.parse(),
+        [] => Err("missing target storage mode".to_string()),
+        _ => Err("migrate-storage accepts exactly one storage mode".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse_args(&args(&["sidecar"])).unwrap(),
+            AttestationStorage::Sidecar
+        );
+        assert_eq!(
+            parse_args(&args(&["Notes"])).unwrap(),
+            AttestationStorage::Notes
+        );
+        assert!(parse_args(&args(&["committed"])).is_err());
+        assert!(parse_args(&[]).is_err());
+        assert!(parse_args(&args(&["notes", "sidecar"])).is_err());
+    }
+}
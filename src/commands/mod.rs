@@ -6,6 +6,7 @@ pub mod config;
 pub mod continue_session;
 pub mod diff;
 pub mod exchange_nonce;
+pub mod explain;
 pub mod flush_cas;
 pub mod flush_logs;
 pub mod flush_metrics_db;
@@ -13,12 +14,21 @@ pub mod git_ai_handlers;
 pub mod git_handlers;
 pub mod git_hook_handlers;
 pub mod hooks;
+pub mod init;
 pub mod install_hooks;
+pub mod leaderboard;
+pub mod log;
 pub mod login;
 pub mod logout;
+pub mod migrate_storage;
 pub mod personal_dashboard;
+pub mod progress;
 pub mod prompt_picker;
 pub mod prompts_db;
+pub mod record;
+pub mod replay;
+pub mod report;
+pub mod schema;
 pub mod search;
 pub mod share;
 pub mod share_tui;
@@ -27,4 +37,7 @@ pub mod show_prompt;
 pub mod squash_authorship;
 pub mod status;
 pub mod sync_prompts;
+pub mod trend;
 pub mod upgrade;
+pub mod verify;
+pub mod watch;
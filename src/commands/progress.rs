@@ -0,0 +1,45 @@
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::IsTerminal;
+
+/// "processed / total" bar on stderr for commands that walk many commits. It is only drawn when
+/// stderr is a terminal, so piped and machine-readable output stays clean.
+pub struct CommitProgress {
+    bar: Option<ProgressBar>,
+}
+
+impl CommitProgress {
+    pub fn new(enabled: bool) -> Self {
+        let bar = (enabled && std::io::stderr().is_terminal()).then(|| {
+            let bar = ProgressBar::new(0);
+            bar.set_style(
+                ProgressStyle::default_bar()
+                    .template("{bar:30} {pos}/{len} commits")
+                    .unwrap(),
+            );
+            bar
+        });
+        Self { bar }
+    }
+
+    pub fn update(&self, done: usize, total: usize) {
+        if let Some(bar) = &self.bar {
+            bar.set_length(total as u64);
+            bar.set_position(done as u64);
+        }
+    }
+
+    /// Print a line to stdout while the bar may be drawing. The bar is cleared for the write
+    /// and redrawn after, so the two never end up on the same terminal line.
+    pub fn println(&self, line: &str) {
+        match &self.bar {
+            Some(bar) => bar.suspend(|| println!("{}", line)),
+            None => println!("{}", line),
+        }
+    }
+
+    pub fn finish(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+    }
+}
@@ -6,6 +6,7 @@
 use crate::authorship::internal_db::InternalDatabase;
 use crate::authorship::transcript::AiTranscript;
 use crate::error::GitAiError;
+use crate::git::attestation_storage::{AttestationStorage, configured_storage, sidecar_list};
 use crate::git::find_repository_in_path;
 use crate::git::repository::{Repository, exec_git, exec_git_stdin};
 use chrono::{Local, TimeZone};
@@ -919,6 +920,16 @@ fn get_commits_with_notes_since(repo: &Repository, since_timestamp: i64) -> Vec<
     }
     let commit_set: HashSet<String> = commits_since.into_iter().collect();
 
+    if configured_storage(repo) == AttestationStorage::Sidecar {
+        return sidecar_list(repo)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(commit_sha, content)| {
+                commit_set.contains(commit_sha) && content.contains('{')
+            })
+            .collect();
+    }
+
     // Step 2: Get all notes mappings (note_blob_sha, commit_sha)
     let note_mappings = get_notes_list(&global_args);
 
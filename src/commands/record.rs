@@ -0,0 +1,304 @@
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::internal_db::InternalDatabase;
+use crate::authorship::post_commit::{
+    apply_prompt_storage_mode, attest_carried_over_lines, drop_ignored_attestations,
+};
+use crate::authorship::signing::sign_with_configured_key;
+use crate::authorship::virtual_attribution::VirtualAttributions;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::{get_authorship, notes_add};
+use crate::git::repository::Repository;
+use crate::sources::telemetry::load_telemetry;
+use crate::sources::{IdeLogSource, MarkerCommentSource, record_ai_source};
+use std::path::Path;
+
+const USAGE: &str = "Usage: git-ai record --commit <rev> [--force]\n       git-ai record --from-telemetry <file.csv>\n       git-ai record --from-markers <path>...\n       git-ai record --from-ide-log <file>";
+
+/// Where `record_authorship` found the data it wrote the log from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordSource {
+    /// The checkpoints recorded while the commit was being written
+    WorkingLog,
+    /// The checkpoints are gone: prompts the local database saved for the commit, with only
+    /// the lines the commit moved, copied or reverted from earlier prompts attested
+    BestEffort,
+    /// The checkpoints are gone and the commit already has a log, which was left as it is
+    KeptExisting,
+}
+
+pub fn handle_record(args: &[String]) {
+    let mut commit: Option<String> = None;
+    let mut force = false;
+    let mut telemetry: Option<String> = None;
+    let mut marker_paths: Vec<String> = Vec::new();
+    let mut ide_log: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--commit" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--commit requires a value");
+                    eprintln!("{}", USAGE);
+                    std::process::exit(1);
+                }
+                commit = Some(args[i + 1].clone());
+                i += 2;
+            }
+            arg if arg.starts_with("--commit=") => {
+                commit = Some(arg["--commit=".len()..].to_string());
+                i += 1;
+            }
+            "--force" => {
+                force = true;
+                i += 1;
+            }
+            "--from-telemetry" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--from-telemetry requires a file");
+                    eprintln!("{}", USAGE);
+                    std::process::exit(1);
+                }
+                telemetry = Some(args[i + 1].clone());
+                i += 2;
+            }
+            arg if arg.starts_with("--from-telemetry=") => {
+                telemetry = Some(arg["--from-telemetry=".len()..].to_string());
+                i += 1;
+            }
+            "--from-markers" => {
+                i += 1;
+                while i < args.len() && !args[i].starts_with("--") {
+                    marker_paths.push(args[i].clone());
+                    i += 1;
+                }
+                if marker_paths.is_empty() {
+                    eprintln!("--from-markers requires at least one path");
+                    eprintln!("{}", USAGE);
+                    std::process::exit(1);
+                }
+            }
+            "--from-ide-log" => {
+                if i + 1 >= args.len() {
+                    eprintln!("--from-ide-log requires a file");
+                    eprintln!("{}", USAGE);
+                    std::process::exit(1);
+                }
+                ide_log = Some(args[i + 1].clone());
+                i += 2;
+            }
+            arg if arg.starts_with("--from-ide-log=") => {
+                ide_log = Some(arg["--from-ide-log=".len()..].to_string());
+                i += 1;
+            }
+            other => {
+                eprintln!("Unknown record argument: {}", other);
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let from_sources = usize::from(telemetry.is_some())
+        + usize::from(!marker_paths.is_empty())
+        + usize::from(ide_log.is_some());
+    if from_sources > 1 {
+        eprintln!("Error: pass only one of --from-telemetry, --from-markers and --from-ide-log");
+        eprintln!("{}", USAGE);
+        std::process::exit(1);
+    }
+    if from_sources == 1 && (commit.is_some() || force) {
+        eprintln!("Error: --from-* options record the next commit and take no --commit or --force");
+        eprintln!("{}", USAGE);
+        std::process::exit(1);
+    }
+    if commit.is_none() && from_sources == 0 {
+        eprintln!("Error: record needs the commit to record");
+        eprintln!("{}", USAGE);
+        std::process::exit(1);
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let Some(commit) = commit else {
+        let (result, from) = if let Some(telemetry) = telemetry {
+            (record_telemetry(&repo, Path::new(&telemetry)), telemetry)
+        } else if let Some(ide_log) = ide_log {
+            (record_ide_log(&repo, Path::new(&ide_log)), ide_log)
+        } else {
+            (
+                record_markers(&repo, &marker_paths),
+                "marker comments".to_string(),
+            )
+        };
+        match result {
+            Ok(lines) => println!(
+                "Recorded {} AI lines from {} for the next commit",
+                lines, from
+            ),
+            Err(e) => {
+                eprintln!("Record failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    };
+
+    match record_authorship(&repo, &commit, force) {
+        Ok(RecordSource::WorkingLog) => {
+            println!("Recorded authorship log for {}", commit);
+        }
+        Ok(RecordSource::BestEffort) => {
+            eprintln!(
+                "Warning: the checkpoints for {} are gone; recorded a best-effort log from saved prompts and lines carried over from earlier commits",
+                commit
+            );
+            println!("Recorded authorship log for {}", commit);
+        }
+        Ok(RecordSource::KeptExisting) => {
+            eprintln!(
+                "Warning: the checkpoints for {} are gone; kept its existing authorship log",
+                commit
+            );
+        }
+        Err(e) => {
+            eprintln!("Record failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Write the authorship log of the commit `rev` names from the checkpoints recorded while it
+/// was being written, without rewriting history. Running it again gives the same log.
+///
+/// A commit that already has a log is only rewritten with `force`. The checkpoints are
+/// normally cleared once the post-commit hook has written the log, so without them this falls
+/// back to a best-effort log; see [`RecordSource`].
+pub fn record_authorship(
+    repo: &Repository,
+    rev: &str,
+    force: bool,
+) -> Result<RecordSource, GitAiError> {
+    let commit = repo.revparse_single(rev)?.peel_to_commit()?;
+    let commit_sha = commit.id();
+    let parent_sha = if commit.parent_count()? == 0 {
+        "initial".to_string()
+    } else {
+        commit.parent(0)?.id()
+    };
+
+    let existing = get_authorship(repo, &commit_sha);
+    if existing.is_some() && !force {
+        return Err(GitAiError::Generic(format!(
+            "{} already has an authorship log; pass --force to rewrite it",
+            commit_sha
+        )));
+    }
+
+    let (mut authorship_log, source) = match checkpoints_base(repo, &parent_sha) {
+        Some(base) => {
+            let human_author = commit.author().ok().map(|author| {
+                format!(
+                    "{} <{}>",
+                    author.name().unwrap_or_default(),
+                    author.email().unwrap_or_default()
+                )
+            });
+            let working_va =
+                VirtualAttributions::from_just_working_log(repo.clone(), base, human_author)?;
+            let authorship_log =
+                working_va.to_authorship_log_index_only(repo, &parent_sha, &commit_sha, None)?;
+            (authorship_log, RecordSource::WorkingLog)
+        }
+        None if existing.is_some() => return Ok(RecordSource::KeptExisting),
+        None => (saved_prompts_log(&commit_sha), RecordSource::BestEffort),
+    };
+
+    attest_carried_over_lines(repo, &parent_sha, &commit_sha, &mut authorship_log);
+    drop_ignored_attestations(repo, &mut authorship_log);
+    authorship_log.metadata.base_commit_sha = commit_sha.clone();
+    apply_prompt_storage_mode(repo, &mut authorship_log);
+
+    // Sign last so the signature covers the final attestations
+    if let Err(e) = sign_with_configured_key(&mut authorship_log) {
+        eprintln!("Warning: Failed to sign authorship log: {}", e);
+    }
+
+    let authorship_json = authorship_log
+        .serialize_for_storage()
+        .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
+    notes_add(repo, &commit_sha, &authorship_json)?;
+
+    Ok(source)
+}
+
+/// Checkpoint the AI edits an IDE's telemetry CSV lists, so the next commit attests them. Each
+/// `path,start_line,end_line,tool` row credits those lines of the working tree file to `tool`;
+/// lines no row lists stay human. Returns the number of lines credited.
+pub fn record_telemetry(repo: &Repository, csv_path: &Path) -> Result<usize, GitAiError> {
+    let mut recorded = 0;
+    for source in load_telemetry(csv_path)? {
+        recorded += record_ai_source(repo, &source, &source.file_paths())?;
+    }
+    Ok(recorded)
+}
+
+/// Checkpoint the lines between `git-ai:begin` and `git-ai:end` marker comments in
+/// `file_paths` (relative to the repository root) as AI, so the next commit attests them.
+/// Returns the number of lines credited.
+pub fn record_markers(repo: &Repository, file_paths: &[String]) -> Result<usize, GitAiError> {
+    record_ai_source(repo, &MarkerCommentSource::default(), file_paths)
+}
+
+/// Checkpoint the `<path>:<start>[-<end>]` entries an IDE appended to `log_path` as AI, so the
+/// next commit attests them. Returns the number of lines credited.
+pub fn record_ide_log(repo: &Repository, log_path: &Path) -> Result<usize, GitAiError> {
+    let source = IdeLogSource::new(log_path.to_path_buf());
+    let file_paths = source.file_paths().map_err(|e| {
+        GitAiError::Generic(format!(
+            "Failed to read IDE log {}: {}",
+            log_path.display(),
+            e
+        ))
+    })?;
+    record_ai_source(repo, &source, &file_paths)
+}
+
+/// The working log holding the checkpoints of the commit on top of `parent_sha`: the parent's
+/// own when the post-commit hook never consumed it, or the copy debug builds keep after it did.
+fn checkpoints_base(repo: &Repository, parent_sha: &str) -> Option<String> {
+    [parent_sha.to_string(), format!("old-{}", parent_sha)]
+        .into_iter()
+        .find(|base| {
+            repo.storage.has_working_log(base)
+                && repo
+                    .storage
+                    .working_log_for_base_commit(base)
+                    .read_all_checkpoints()
+                    .is_ok_and(|checkpoints| !checkpoints.is_empty())
+        })
+}
+
+/// A log holding the prompts the local database saved for `commit_sha`, with no attestations.
+fn saved_prompts_log(commit_sha: &str) -> AuthorshipLog {
+    let mut authorship_log = AuthorshipLog::new();
+    if let Ok(db) = InternalDatabase::global()
+        && let Ok(db_guard) = db.lock()
+        && let Ok(records) = db_guard.get_prompts_by_commit(commit_sha)
+    {
+        for record in records {
+            authorship_log
+                .metadata
+                .prompts
+                .insert(record.id.clone(), record.to_prompt_record());
+        }
+    }
+    authorship_log
+}
@@ -0,0 +1,78 @@
+use crate::authorship::rebase_authorship::rewrite_authorship_after_rebase_v2;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::commits_with_authorship_notes;
+use crate::git::repository::{Repository, exec_git};
+
+const USAGE: &str = "Usage: git-ai replay <old-range> <new-range>";
+
+pub fn handle_replay(args: &[String]) {
+    let (old_range, new_range) = match args {
+        [old_range, new_range] if old_range.contains("..") && new_range.contains("..") => {
+            (old_range, new_range)
+        }
+        _ => {
+            eprintln!("Error: replay takes the commit range before and after the rewrite");
+            eprintln!("{}", USAGE);
+            std::process::exit(1);
+        }
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match replay_authorship(&repo, old_range, new_range) {
+        Ok(replayed) => println!("Replayed authorship onto {} rewritten commit(s)", replayed),
+        Err(e) => {
+            eprintln!("Replay failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Carry the authorship logs of the commits in `old_range` over to the commits that a
+/// rebase made outside the hooks rewrote them into, matching lines by content. Squashed
+/// commits get the combined attribution of every original commit folded into them.
+/// Returns how many of the new commits have an authorship log afterwards.
+pub fn replay_authorship(
+    repo: &Repository,
+    old_range: &str,
+    new_range: &str,
+) -> Result<usize, GitAiError> {
+    let original_commits = commits_oldest_first(repo, old_range)?;
+    let new_commits = commits_oldest_first(repo, new_range)?;
+    let Some(original_head) = original_commits.last() else {
+        return Err(GitAiError::Generic(format!(
+            "{} contains no commits",
+            old_range
+        )));
+    };
+    if new_commits.is_empty() {
+        return Err(GitAiError::Generic(format!(
+            "{} contains no commits",
+            new_range
+        )));
+    }
+
+    rewrite_authorship_after_rebase_v2(repo, original_head, &original_commits, &new_commits, "")?;
+
+    Ok(commits_with_authorship_notes(repo, &new_commits)?.len())
+}
+
+fn commits_oldest_first(repo: &Repository, range: &str) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-list".to_string());
+    args.push("--reverse".to_string());
+    args.push(range.to_string());
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
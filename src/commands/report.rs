@@ -0,0 +1,642 @@
+use crate::authorship::diff_ai_accepted::lines_to_ranges;
+use crate::authorship::ignore::{
+    build_ignore_matcher, effective_ignore_patterns, should_ignore_file_with_matcher,
+};
+use crate::authorship::stats::{
+    CommitStatsOptions, FileStats, MergeStrategy, rounded_percentage,
+    stats_for_commit_stats_with_options,
+};
+use crate::commands::blame::{BlameAuthorType, GitAiBlameOptions};
+use crate::commands::trend::{TrendBucket, TrendOptions, TrendPeriod, trend_buckets};
+use crate::config::{Config, DEFAULT_PERCENTAGE_DECIMALS};
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::repository::{CommitRange, Repository};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+const EMPTY_TREE_HASH: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    #[default]
+    Annotations,
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    pub revision: Option<String>,
+    pub format: ReportFormat,
+    pub locale: Option<String>,
+}
+
+/// Languages whose reports write percentages with a decimal comma
+const DECIMAL_COMMA_LANGUAGES: &[&str] = &[
+    "bg", "ca", "cs", "da", "de", "el", "es", "fi", "fr", "hr", "hu", "id", "it", "lt", "lv", "nb",
+    "nl", "nn", "no", "pl", "pt", "ro", "ru", "sk", "sl", "sr", "sv", "tr", "uk", "vi",
+];
+
+/// How markdown and HTML reports write percentages. Annotations are JSON and keep plain numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PercentFormat {
+    pub decimals: u8,
+    pub decimal_comma: bool,
+}
+
+impl Default for PercentFormat {
+    fn default() -> Self {
+        PercentFormat {
+            decimals: DEFAULT_PERCENTAGE_DECIMALS,
+            decimal_comma: false,
+        }
+    }
+}
+
+impl PercentFormat {
+    /// The format for a locale such as `de`, `pt-BR` or `fr_FR.UTF-8`. Only the language
+    /// decides the separator; languages without a decimal comma get a point.
+    pub fn for_locale(locale: &str, decimals: u8) -> Result<Self, String> {
+        let language = locale
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if language.len() < 2 || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("Invalid locale: {}", locale));
+        }
+        Ok(PercentFormat {
+            decimals,
+            decimal_comma: DECIMAL_COMMA_LANGUAGES.contains(&language.as_str()),
+        })
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        let formatted = format!("{:.*}%", self.decimals as usize, value);
+        if self.decimal_comma {
+            formatted.replace('.', ",")
+        } else {
+            formatted
+        }
+    }
+}
+
+/// A run of consecutive added lines in one file with the same authorship.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AnnotationRegion {
+    pub path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub author_type: BlameAuthorType,
+}
+
+pub fn handle_report(args: &[String]) {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!(
+                "Usage: git-ai report [<revision>|<range>] [--format=annotations|markdown|html] [--locale <locale>]"
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let decimals = Config::get().percentage_decimals();
+    let percent_format = match options.locale.as_deref() {
+        Some(locale) => match PercentFormat::for_locale(locale, decimals) {
+            Ok(format) => format,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => PercentFormat {
+            decimals,
+            ..Default::default()
+        },
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let ignore_patterns = effective_ignore_patterns(&repo, &[], &[]);
+    if options.format == ReportFormat::Annotations {
+        let regions = match annotation_regions(&repo, options.revision.as_deref(), &ignore_patterns)
+        {
+            Ok(regions) => regions,
+            Err(e) => {
+                eprintln!("Report failed: {}", e);
+                std::process::exit(1);
+            }
+        };
+        match serde_json::to_string(&regions) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize report: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let files = match file_breakdown(&repo, options.revision.as_deref(), &ignore_patterns) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("Report failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match options.format {
+        ReportFormat::Annotations => {}
+        ReportFormat::Markdown => {
+            print!("{}", write_report_markdown(&files, &percent_format));
+        }
+        ReportFormat::Html => {
+            let trend_options = TrendOptions {
+                revision: options.revision.clone(),
+                by: TrendPeriod::Week,
+            };
+            let buckets = match trend_buckets(&repo, &trend_options, &ignore_patterns) {
+                Ok(buckets) => buckets,
+                Err(e) => {
+                    eprintln!("Report failed: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            print!("{}", write_report_html(&buckets, &files, &percent_format));
+        }
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<ReportOptions, String> {
+    let mut options = ReportOptions::default();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--format" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--format requires a value".to_string())?;
+                options.format = parse_format(value)?;
+            }
+            arg if arg.starts_with("--format=") => {
+                options.format = parse_format(&arg["--format=".len()..])?;
+            }
+            "--locale" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--locale requires a value".to_string())?;
+                options.locale = Some(value.clone());
+            }
+            arg if arg.starts_with("--locale=") => {
+                options.locale = Some(arg["--locale=".len()..].to_string());
+            }
+            arg if arg.starts_with('-') => {
+                return Err(format!("Unknown report argument: {}", arg));
+            }
+            arg => {
+                if options.revision.is_some() {
+                    return Err("report accepts at most one revision or range".to_string());
+                }
+                options.revision = Some(arg.to_string());
+            }
+        }
+        i += 1;
+    }
+
+    Ok(options)
+}
+
+fn parse_format(value: &str) -> Result<ReportFormat, String> {
+    match value {
+        "annotations" => Ok(ReportFormat::Annotations),
+        "markdown" => Ok(ReportFormat::Markdown),
+        "html" => Ok(ReportFormat::Html),
+        other => Err(format!("Unsupported report format: {}", other)),
+    }
+}
+
+/// Resolve a revision (defaults to HEAD) or `<from>..<to>` range to the pair of commits to diff.
+fn resolve_diff_endpoints(
+    repo: &Repository,
+    revision: Option<&str>,
+) -> Result<(String, String), GitAiError> {
+    let revision = revision.unwrap_or("HEAD");
+
+    if let Some((from, to)) = revision.split_once("..") {
+        let to = if to.is_empty() { "HEAD" } else { to };
+        let from_sha = repo.revparse_single(from)?.peel_to_commit()?.id();
+        let to_sha = repo.revparse_single(to)?.peel_to_commit()?.id();
+        return Ok((from_sha, to_sha));
+    }
+
+    let commit = repo.revparse_single(revision)?.peel_to_commit()?;
+    let from_sha = if commit.parent_count()? == 0 {
+        EMPTY_TREE_HASH.to_string()
+    } else {
+        commit.parent(0)?.id()
+    };
+    Ok((from_sha, commit.id()))
+}
+
+/// Classify every line added by the revision or range and coalesce them into regions.
+/// Regions are ordered by path, then by line number.
+pub fn annotation_regions(
+    repo: &Repository,
+    revision: Option<&str>,
+    ignore_patterns: &[String],
+) -> Result<Vec<AnnotationRegion>, GitAiError> {
+    let (from_sha, to_sha) = resolve_diff_endpoints(repo, revision)?;
+    let ignore_matcher = build_ignore_matcher(ignore_patterns);
+
+    let added_lines_by_file: BTreeMap<String, Vec<u32>> = repo
+        .diff_added_lines_following_renames(&from_sha, &to_sha, false, None)?
+        .into_iter()
+        .filter(|(file_path, _)| !should_ignore_file_with_matcher(file_path, &ignore_matcher))
+        .collect();
+
+    let mut regions = Vec::new();
+    for (file_path, mut lines) in added_lines_by_file {
+        lines.sort_unstable();
+        lines.dedup();
+        if lines.is_empty() {
+            continue;
+        }
+
+        let mut options = GitAiBlameOptions::default();
+        #[allow(clippy::field_reassign_with_default)]
+        {
+            options.newest_commit = Some(to_sha.clone());
+            options.line_ranges = lines_to_ranges(&lines);
+            options.no_output = true;
+            options.use_prompt_hashes_as_names = true;
+        }
+
+        let (line_authors, prompt_records) = repo.blame(&file_path, &options)?;
+
+        let classified = lines.iter().map(|line| {
+            let author_type = match line_authors.get(line) {
+                Some(author) if prompt_records.contains_key(author) => BlameAuthorType::Ai,
+                _ => BlameAuthorType::Human,
+            };
+            (*line, author_type)
+        });
+        regions.extend(coalesce_regions(&file_path, classified));
+    }
+
+    Ok(regions)
+}
+
+/// Merge consecutive lines with the same author type into a single region.
+fn coalesce_regions(
+    path: &str,
+    lines: impl IntoIterator<Item = (u32, BlameAuthorType)>,
+) -> Vec<AnnotationRegion> {
+    let mut regions: Vec<AnnotationRegion> = Vec::new();
+
+    for (line, author_type) in lines {
+        if let Some(last) = regions.last_mut()
+            && last.author_type == author_type
+            && last.end_line + 1 == line
+        {
+            last.end_line = line;
+            continue;
+        }
+        regions.push(AnnotationRegion {
+            path: path.to_string(),
+            start_line: line,
+            end_line: line,
+            author_type,
+        });
+    }
+
+    regions
+}
+
+/// Per-file AI and human added lines, computed like `git-ai stats` computes them: the
+/// commit's own breakdown for a revision, and the sum over the range's commits for a range.
+/// Merges are left out of ranges since they repeat the lines of the commits they bring in.
+/// Files are ordered by path.
+pub fn file_breakdown(
+    repo: &Repository,
+    revision: Option<&str>,
+    ignore_patterns: &[String],
+) -> Result<Vec<FileStats>, GitAiError> {
+    let (from_sha, to_sha) = resolve_diff_endpoints(repo, revision)?;
+    let commits = if revision.is_some_and(|revision| revision.contains("..")) {
+        CommitRange::new(repo, from_sha, to_sha, String::new())?
+            .commits_with_rev_list_args(MergeStrategy::default().rev_list_args())
+    } else {
+        vec![to_sha]
+    };
+
+    let options = CommitStatsOptions {
+        per_file: true,
+        ..Default::default()
+    };
+    let mut files: BTreeMap<String, FileStats> = BTreeMap::new();
+    for commit_sha in commits {
+        let stats =
+            stats_for_commit_stats_with_options(repo, &commit_sha, ignore_patterns, &options)?;
+        for file in stats.files {
+            if file.ai_additions + file.human_additions == 0 {
+                continue;
+            }
+            let total = files
+                .entry(file.file_path.clone())
+                .or_insert_with(|| FileStats {
+                    file_path: file.file_path.clone(),
+                    ..Default::default()
+                });
+            total.ai_additions += file.ai_additions;
+            total.human_additions += file.human_additions;
+            total.ai_accepted += file.ai_accepted;
+        }
+    }
+
+    Ok(files.into_values().collect())
+}
+
+/// Render the per-file breakdown as a markdown table with a total row, followed by a fenced
+/// one-line summary. Paths are written verbatim apart from escaping `|`.
+pub fn write_report_markdown(files: &[FileStats], percent_format: &PercentFormat) -> String {
+    let mut output = String::new();
+    output.push_str("| File | AI lines | Human lines | AI % | Human % |\n");
+    output.push_str("| --- | ---: | ---: | ---: | ---: |\n");
+
+    let mut ai_total = 0u32;
+    let mut human_total = 0u32;
+    for file in files {
+        let total = file.ai_additions + file.human_additions;
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            file.file_path.replace('|', "\\|"),
+            file.ai_additions,
+            file.human_additions,
+            percent_format.format(rounded_percentage(file.ai_additions, total)),
+            percent_format.format(rounded_percentage(file.human_additions, total)),
+        ));
+        ai_total += file.ai_additions;
+        human_total += file.human_additions;
+    }
+
+    let total = ai_total + human_total;
+    let ai_percentage = percent_format.format(rounded_percentage(ai_total, total));
+    let human_percentage = percent_format.format(rounded_percentage(human_total, total));
+    output.push_str(&format!(
+        "| **Total** | **{}** | **{}** | **{}** | **{}** |\n",
+        ai_total, human_total, ai_percentage, human_percentage
+    ));
+
+    output.push_str("\n```\n");
+    output.push_str(&format!(
+        "{} lines added across {} files: {} AI ({} lines), {} human ({} lines)\n",
+        total,
+        files.len(),
+        ai_percentage,
+        ai_total,
+        human_percentage,
+        human_total
+    ));
+    output.push_str("```\n");
+
+    output
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const HTML_CHART_HEIGHT: u32 = 200;
+const HTML_BAR_WIDTH: u32 = 32;
+const HTML_BAR_GAP: u32 = 16;
+const HTML_AI_COLOR: &str = "#7c3aed";
+const HTML_HUMAN_COLOR: &str = "#0ea5e9";
+
+/// Stacked AI/human bars, one per trend bucket, scaled to the busiest bucket.
+fn write_trend_svg(buckets: &[TrendBucket]) -> String {
+    let label_height = 40;
+    let width = HTML_BAR_GAP + buckets.len() as u32 * (HTML_BAR_WIDTH + HTML_BAR_GAP);
+    let max_total = buckets
+        .iter()
+        .map(|bucket| bucket.ai_additions + bucket.human_additions)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" role=\"img\" aria-label=\"AI vs human lines added per week\">\n",
+        width,
+        HTML_CHART_HEIGHT + label_height
+    );
+    for (index, bucket) in buckets.iter().enumerate() {
+        let x = HTML_BAR_GAP + index as u32 * (HTML_BAR_WIDTH + HTML_BAR_GAP);
+        let ai_height = bucket.ai_additions * HTML_CHART_HEIGHT / max_total;
+        let human_height = bucket.human_additions * HTML_CHART_HEIGHT / max_total;
+        let human_y = HTML_CHART_HEIGHT - human_height;
+        let ai_y = human_y - ai_height;
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"><title>{}: {} human lines</title></rect>\n",
+            x, human_y, HTML_BAR_WIDTH, human_height, HTML_HUMAN_COLOR,
+            escape_html(&bucket.bucket), bucket.human_additions
+        ));
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"><title>{}: {} AI lines</title></rect>\n",
+            x, ai_y, HTML_BAR_WIDTH, ai_height, HTML_AI_COLOR,
+            escape_html(&bucket.bucket), bucket.ai_additions
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" font-size=\"10\" text-anchor=\"middle\">{}</text>\n",
+            x + HTML_BAR_WIDTH / 2,
+            HTML_CHART_HEIGHT + 16,
+            escape_html(&bucket.bucket)
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Render a standalone HTML page: weekly AI/human bar chart as inline SVG, then the per-file
+/// table with a total row. Everything is inline so the file can be mailed or opened offline.
+pub fn write_report_html(
+    buckets: &[TrendBucket],
+    files: &[FileStats],
+    percent_format: &PercentFormat,
+) -> String {
+    let mut output = String::new();
+    output.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    output.push_str("<title>git-ai report</title>\n<style>\n");
+    output.push_str("body { font-family: sans-serif; margin: 2em; color: #111827; }\n");
+    output.push_str("table { border-collapse: collapse; }\n");
+    output.push_str("th, td { border: 1px solid #d1d5db; padding: 4px 8px; }\n");
+    output.push_str("td.num { text-align: right; }\n");
+    output.push_str(&format!(
+        ".ai {{ color: {}; }}\n.human {{ color: {}; }}\n",
+        HTML_AI_COLOR, HTML_HUMAN_COLOR
+    ));
+    output.push_str("</style>\n</head>\n<body>\n<h1>AI vs human code</h1>\n");
+
+    output.push_str("<h2>Lines added per week</h2>\n");
+    output.push_str(
+        "<p><span class=\"ai\">&#9632; AI</span> <span class=\"human\">&#9632; Human</span></p>\n",
+    );
+    output.push_str(&write_trend_svg(buckets));
+
+    output.push_str("<h2>Files</h2>\n<table>\n");
+    output.push_str("<tr><th>File</th><th>AI lines</th><th>Human lines</th><th>AI %</th><th>Human %</th></tr>\n");
+    let mut ai_total = 0u32;
+    let mut human_total = 0u32;
+    for file in files {
+        let total = file.ai_additions + file.human_additions;
+        output.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+            escape_html(&file.file_path),
+            file.ai_additions,
+            file.human_additions,
+            percent_format.format(rounded_percentage(file.ai_additions, total)),
+            percent_format.format(rounded_percentage(file.human_additions, total)),
+        ));
+        ai_total += file.ai_additions;
+        human_total += file.human_additions;
+    }
+    let total = ai_total + human_total;
+    output.push_str(&format!(
+        "<tr><th>Total</th><th class=\"num\">{}</th><th class=\"num\">{}</th><th class=\"num\">{}</th><th class=\"num\">{}</th></tr>\n",
+        ai_total,
+        human_total,
+        percent_format.format(rounded_percentage(ai_total, total)),
+        percent_format.format(rounded_percentage(human_total, total)),
+    ));
+    output.push_str("</table>\n</body>\n</html>\n");
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&[]).unwrap();
+        assert!(options.revision.is_none());
+        assert_eq!(options.format, ReportFormat::Annotations);
+
+        let options = parse_args(&args(&["main..HEAD", "--format=annotations"])).unwrap();
+        assert_eq!(options.revision.as_deref(), Some("main..HEAD"));
+
+        let options = parse_args(&args(&["--format", "markdown"])).unwrap();
+        assert_eq!(options.format, ReportFormat::Markdown);
+
+        let options = parse_args(&args(&["--format=html"])).unwrap();
+        assert_eq!(options.format, ReportFormat::Html);
+
+        assert!(parse_args(&args(&["--format", "sarif"])).is_err());
+        assert!(parse_args(&args(&["--format"])).is_err());
+        assert!(parse_args(&args(&["HEAD", "HEAD~1"])).is_err());
+
+        let options = parse_args(&args(&["--locale", "de", "--format=markdown"])).unwrap();
+        assert_eq!(options.locale.as_deref(), Some("de"));
+        assert!(parse_args(&args(&["--locale"])).is_err());
+    }
+
+    #[test]
+    fn test_coalesce_regions() {
+        let regions = coalesce_regions(
+            "a.rs",
+            vec![
+                (1, BlameAuthorType::Ai),
+                (2, BlameAuthorType::Ai),
+                (3, BlameAuthorType::Human),
+                (5, BlameAuthorType::Human),
+                (6, BlameAuthorType::Ai),
+            ],
+        );
+        let spans: Vec<(u32, u32, BlameAuthorType)> = regions
+            .iter()
+            .map(|r| (r.start_line, r.end_line, r.author_type))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (1, 2, BlameAuthorType::Ai),
+                (3, 3, BlameAuthorType::Human),
+                (5, 5, BlameAuthorType::Human),
+                (6, 6, BlameAuthorType::Ai),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_percent_format_for_locale() {
+        let german = PercentFormat::for_locale("de", 1).unwrap();
+        assert_eq!(german.format(81.5), "81,5%");
+        assert_eq!(
+            PercentFormat::for_locale("pt_BR.UTF-8", 2)
+                .unwrap()
+                .format(12.5),
+            "12,50%"
+        );
+        assert_eq!(
+            PercentFormat::for_locale("en-US", 0).unwrap().format(81.6),
+            "82%"
+        );
+        assert!(PercentFormat::for_locale("1", 1).is_err());
+    }
+
+    #[test]
+    fn test_write_report_markdown() {
+        let files = vec![
+            FileStats {
+                file_path: "a|b.rs".to_string(),
+                ai_additions: 3,
+                ai_accepted: 3,
+                human_additions: 1,
+            },
+            FileStats {
+                file_path: "c.rs".to_string(),
+                human_additions: 1,
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            write_report_markdown(&files, &PercentFormat::default()),
+            "| File | AI lines | Human lines | AI % | Human % |\n\
+             | --- | ---: | ---: | ---: | ---: |\n\
+             | a\\|b.rs | 3 | 1 | 75.0% | 25.0% |\n\
+             | c.rs | 0 | 1 | 0.0% | 100.0% |\n\
+             | **Total** | **3** | **2** | **60.0%** | **40.0%** |\n\
+             \n\
+             ```\n\
+             5 lines added across 2 files: 60.0% AI (3 lines), 40.0% human (2 lines)\n\
+             ```\n"
+        );
+    }
+}
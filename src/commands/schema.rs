@@ -0,0 +1,56 @@
+use crate::authorship::authorship_log_serialization::AuthorshipMetadata;
+use crate::authorship::stats::CommitStats;
+use schemars::schema::RootSchema;
+use schemars::schema_for;
+use std::collections::BTreeMap;
+
+/// Names accepted by `git-ai schema <name>`, in the order they are listed in the usage line.
+pub const SCHEMA_NAMES: &[&str] = &["commit-stats", "authorship-metadata"];
+
+pub fn handle_schema(args: &[String]) {
+    let output = match args {
+        [] => serde_json::to_string_pretty(&all_schemas()),
+        [name] => match schema_by_name(name) {
+            Some(schema) => serde_json::to_string_pretty(&schema),
+            None => {
+                eprintln!("Error: Unknown schema: {}", name);
+                eprintln!("Usage: git-ai schema [{}]", SCHEMA_NAMES.join("|"));
+                std::process::exit(1);
+            }
+        },
+        _ => {
+            eprintln!("Error: schema accepts at most one schema name");
+            eprintln!("Usage: git-ai schema [{}]", SCHEMA_NAMES.join("|"));
+            std::process::exit(1);
+        }
+    };
+
+    match output {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("Failed to serialize schema: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// JSON Schema for one of the JSON documents git-ai emits:
+/// - `commit-stats`: the output of `git-ai stats --json`
+/// - `authorship-metadata`: the JSON section below the `---` divider of an authorship note
+///
+/// The schemas are derived from the Rust types, so they change whenever a field does.
+pub fn schema_by_name(name: &str) -> Option<RootSchema> {
+    match name {
+        "commit-stats" => Some(schema_for!(CommitStats)),
+        "authorship-metadata" => Some(schema_for!(AuthorshipMetadata)),
+        _ => None,
+    }
+}
+
+/// Every schema keyed by name.
+pub fn all_schemas() -> BTreeMap<&'static str, RootSchema> {
+    SCHEMA_NAMES
+        .iter()
+        .filter_map(|name| schema_by_name(name).map(|schema| (*name, schema)))
+        .collect()
+}
@@ -7,7 +7,7 @@ use crate::authorship::authorship_log::{LineRange, PromptRecord};
 use crate::authorship::internal_db::InternalDatabase;
 use crate::authorship::prompt_utils::find_prompt_with_db_fallback;
 use crate::commands::blame::GitAiBlameOptions;
-use crate::error::GitAiError;
+use crate::error::{GitAiError, exit_code};
 use crate::git::find_repository_in_path;
 use crate::git::refs::get_authorship;
 use crate::git::repository::{Repository, exec_git};
@@ -476,7 +476,7 @@ pub fn handle_search(args: &[String]) {
             SearchMode::PromptId { prompt_id } => format!("prompt ID '{}'", prompt_id),
         };
         eprintln!("No AI prompt history found for {}", mode_desc);
-        std::process::exit(2);
+        std::process::exit(exit_code::NO_RESULTS);
     }
 
     // Format and output results
@@ -1440,6 +1440,8 @@ mod tests {
             accepted_lines: 0,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         }
     }
 
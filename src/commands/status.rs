@@ -516,6 +516,8 @@ mod tests {
                 accepted_lines: 0,
                 overriden_lines: 0,
                 messages_url: None,
+                session_id: None,
+                suggested_line_hashes: Vec::new(),
             },
         );
 
@@ -0,0 +1,187 @@
+use crate::authorship::ignore::effective_ignore_patterns;
+use crate::authorship::stats::CommitStats;
+use crate::commands::log::{LogOptions, log_entries};
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::repository::Repository;
+use chrono::{DateTime, Datelike, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrendPeriod {
+    #[default]
+    Week,
+    Month,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TrendOptions {
+    pub revision: Option<String>,
+    pub by: TrendPeriod,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrendBucket {
+    pub bucket: String,
+    pub ai_additions: u32,
+    pub human_additions: u32,
+    pub ai_percentage: f64,
+}
+
+pub fn handle_trend(args: &[String]) {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("Usage: git-ai trend [<revision>|<range>] [--by=week|month]");
+            std::process::exit(1);
+        }
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let ignore_patterns = effective_ignore_patterns(&repo, &[], &[]);
+    let buckets = match trend_buckets(&repo, &options, &ignore_patterns) {
+        Ok(buckets) => buckets,
+        Err(e) => {
+            eprintln!("Trend failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match serde_json::to_string(&buckets) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("Failed to serialize trend: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<TrendOptions, String> {
+    let mut options = TrendOptions::default();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--by" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--by requires a value".to_string())?;
+                options.by = parse_period(value)?;
+            }
+            arg if arg.starts_with("--by=") => {
+                options.by = parse_period(&arg["--by=".len()..])?;
+            }
+            arg if arg.starts_with('-') => {
+                return Err(format!("Unknown trend argument: {}", arg));
+            }
+            arg => {
+                if options.revision.is_some() {
+                    return Err("trend accepts at most one revision or range".to_string());
+                }
+                options.revision = Some(arg.to_string());
+            }
+        }
+        i += 1;
+    }
+
+    Ok(options)
+}
+
+fn parse_period(value: &str) -> Result<TrendPeriod, String> {
+    match value {
+        "week" => Ok(TrendPeriod::Week),
+        "month" => Ok(TrendPeriod::Month),
+        other => Err(format!("Unsupported trend period: {}", other)),
+    }
+}
+
+/// Label for the bucket containing the timestamp: ISO week (`2024-W02`) or month (`2024-01`), in UTC.
+/// Labels sort chronologically.
+pub fn bucket_label(timestamp: i64, by: TrendPeriod) -> String {
+    let date = DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .date_naive();
+    match by {
+        TrendPeriod::Week => {
+            let week = date.iso_week();
+            format!("{}-W{:02}", week.year(), week.week())
+        }
+        TrendPeriod::Month => format!("{}-{:02}", date.year(), date.month()),
+    }
+}
+
+/// Sum commit stats per time bucket, oldest bucket first.
+pub fn trend_buckets(
+    repo: &Repository,
+    options: &TrendOptions,
+    ignore_patterns: &[String],
+) -> Result<Vec<TrendBucket>, GitAiError> {
+    // Merge commits repeat the lines of the branch commits they bring in, so leave them out
+    let log_options = LogOptions {
+        revision: options.revision.clone(),
+        no_merges: true,
+        ..Default::default()
+    };
+
+    let mut totals: BTreeMap<String, CommitStats> = BTreeMap::new();
+    for entry in log_entries(repo, &log_options, ignore_patterns)? {
+        totals
+            .entry(bucket_label(entry.timestamp, options.by))
+            .or_default()
+            .accumulate(&entry.stats);
+    }
+
+    Ok(totals
+        .into_iter()
+        .map(|(bucket, stats)| TrendBucket {
+            bucket,
+            ai_additions: stats.ai_additions,
+            human_additions: stats.human_additions,
+            ai_percentage: stats.ai_percentage,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&[]).unwrap();
+        assert!(options.revision.is_none());
+        assert_eq!(options.by, TrendPeriod::Week);
+
+        let options = parse_args(&args(&["main..HEAD", "--by=month"])).unwrap();
+        assert_eq!(options.revision.as_deref(), Some("main..HEAD"));
+        assert_eq!(options.by, TrendPeriod::Month);
+
+        assert!(parse_args(&args(&["--by", "year"])).is_err());
+        assert!(parse_args(&args(&["--by"])).is_err());
+        assert!(parse_args(&args(&["HEAD", "HEAD~1"])).is_err());
+    }
+
+    #[test]
+    fn test_bucket_label() {
+        // 2024-01-07 is a Sunday (end of ISO week 1), 2024-01-08 starts week 2
+        assert_eq!(bucket_label(1704671999, TrendPeriod::Week), "2024-W01");
+        assert_eq!(bucket_label(1704672000, TrendPeriod::Week), "2024-W02");
+        // 2021-01-01 belongs to the last ISO week of 2020
+        assert_eq!(bucket_label(1609459200, TrendPeriod::Week), "2020-W53");
+        assert_eq!(bucket_label(1704672000, TrendPeriod::Month), "2024-01");
+    }
+}
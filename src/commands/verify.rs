@@ -0,0 +1,321 @@
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::signing::{
+    SignatureCheck, load_signing_key, load_verifying_key, verify_authorship_log_signature,
+};
+use crate::authorship::stats::get_git_diff_stats;
+use crate::config::Config;
+use crate::error::{GitAiError, exit_code};
+use crate::git::find_repository;
+use crate::git::refs::show_authorship_note;
+use crate::git::repository::{InternalGitProfile, Repository, exec_git_with_profile};
+use ed25519_dalek::VerifyingKey;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+const EMPTY_TREE_HASH: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+#[derive(Debug, Clone, Default)]
+pub struct VerifyOptions {
+    pub revision: Option<String>,
+    pub verify_signatures: bool,
+    /// Hex-encoded Ed25519 public key; defaults to the key derived from the configured signing key
+    pub public_key_path: Option<String>,
+}
+
+/// Outcome of checking a single commit's authorship log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitVerification {
+    /// The commit has no authorship note, so there is nothing to check.
+    NoLog,
+    Ok,
+    Failed(Vec<String>),
+}
+
+pub fn handle_verify(args: &[String]) {
+    let options = match parse_args(args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!(
+                "Usage: git-ai verify [<revision>|<range>] [--verify-signatures] [--public-key <path>]"
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let verifying_key = if options.verify_signatures {
+        match resolve_verifying_key(options.public_key_path.as_deref()) {
+            Ok(key) => Some(key),
+            Err(e) => {
+                eprintln!("Verify failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    let commits = match commits_to_verify(&repo, options.revision.as_deref()) {
+        Ok(commits) => commits,
+        Err(e) => {
+            eprintln!("Verify failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let mut failed = 0usize;
+    for sha in &commits {
+        let short_sha = &sha[..sha.len().min(7)];
+        match verify_commit(&repo, sha, verifying_key.as_ref()) {
+            Ok(CommitVerification::NoLog) => println!("- {} no authorship log", short_sha),
+            Ok(CommitVerification::Ok) => println!("✓ {}", short_sha),
+            Ok(CommitVerification::Failed(problems)) => {
+                failed += 1;
+                println!("✗ {}", short_sha);
+                for problem in problems {
+                    println!("    {}", problem);
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                println!("✗ {} {}", short_sha, e);
+            }
+        }
+    }
+
+    if failed > 0 {
+        eprintln!(
+            "{} of {} commits failed authorship verification",
+            failed,
+            commits.len()
+        );
+        std::process::exit(exit_code::VERIFICATION_FAILED);
+    }
+}
+
+pub fn parse_args(args: &[String]) -> Result<VerifyOptions, String> {
+    let mut options = VerifyOptions::default();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--verify-signatures" => {
+                options.verify_signatures = true;
+            }
+            "--public-key" => {
+                i += 1;
+                let value = args
+                    .get(i)
+                    .ok_or_else(|| "--public-key requires a value".to_string())?;
+                options.public_key_path = Some(value.clone());
+            }
+            arg if arg.starts_with("--public-key=") => {
+                options.public_key_path = Some(arg["--public-key=".len()..].to_string());
+            }
+            arg if arg.starts_with('-') => {
+                return Err(format!("Unknown verify argument: {}", arg));
+            }
+            arg => {
+                if options.revision.is_some() {
+                    return Err("verify accepts at most one revision or range".to_string());
+                }
+                options.revision = Some(arg.to_string());
+            }
+        }
+        i += 1;
+    }
+
+    if options.public_key_path.is_some() && !options.verify_signatures {
+        return Err("--public-key requires --verify-signatures".to_string());
+    }
+
+    Ok(options)
+}
+
+fn resolve_verifying_key(public_key_path: Option<&str>) -> Result<VerifyingKey, GitAiError> {
+    if let Some(path) = public_key_path {
+        return load_verifying_key(Path::new(path));
+    }
+    match Config::get().authorship_signing_key_path() {
+        Some(path) => Ok(load_signing_key(Path::new(path))?.verifying_key()),
+        None => Err(GitAiError::Generic(
+            "--verify-signatures needs --public-key or authorship_signing_key_path configured"
+                .to_string(),
+        )),
+    }
+}
+
+/// Commits to check, newest first. A plain revision (HEAD by default) checks just that commit.
+fn commits_to_verify(repo: &Repository, revision: Option<&str>) -> Result<Vec<String>, GitAiError> {
+    let revision = revision.unwrap_or("HEAD");
+    if !revision.contains("..") {
+        return Ok(vec![repo.revparse_single(revision)?.peel_to_commit()?.id()]);
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-list".to_string());
+    args.push(revision.to_string());
+    args.push("--".to_string());
+
+    let output = exec_git_with_profile(&args, InternalGitProfile::General)?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// Check a commit's stored authorship log against the lines the commit actually added, and
+/// against its signature when a verifying key is given.
+pub fn verify_commit(
+    repo: &Repository,
+    commit_sha: &str,
+    verifying_key: Option<&VerifyingKey>,
+) -> Result<CommitVerification, GitAiError> {
+    let Some(content) = show_authorship_note(repo, commit_sha) else {
+        return Ok(CommitVerification::NoLog);
+    };
+
+    let log = match AuthorshipLog::deserialize_from_string(&content) {
+        Ok(log) => log,
+        Err(e) => {
+            return Ok(CommitVerification::Failed(vec![format!(
+                "authorship log could not be parsed: {}",
+                e
+            )]));
+        }
+    };
+
+    // A merge's diff depends on which parent it is compared against, so only ordinary
+    // commits are checked against their added lines.
+    let commit = repo.revparse_single(commit_sha)?.peel_to_commit()?;
+    let added_lines: Option<HashMap<String, HashSet<u32>>> = match commit.parent_count()? {
+        0 | 1 => {
+            let from_sha = if commit.parent_count()? == 0 {
+                EMPTY_TREE_HASH.to_string()
+            } else {
+                commit.parent(0)?.id()
+            };
+            Some(
+                repo.diff_added_lines_following_renames(&from_sha, commit_sha, false, None)?
+                    .into_iter()
+                    .map(|(file_path, lines)| (file_path, lines.into_iter().collect()))
+                    .collect(),
+            )
+        }
+        _ => None,
+    };
+
+    let mut problems = Vec::new();
+    let mut attested_lines = 0u32;
+
+    if let Some(key) = verifying_key {
+        match verify_authorship_log_signature(&log, key)? {
+            SignatureCheck::Valid => {}
+            SignatureCheck::Unsigned => problems.push("authorship log is not signed".to_string()),
+            SignatureCheck::Invalid(reason) => problems.push(reason),
+        }
+    }
+
+    for file in &log.attestations {
+        let line_count = match repo.get_file_content(&file.file_path, commit_sha) {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).lines().count() as u32,
+            Err(_) => {
+                problems.push(format!(
+                    "{}: attested file does not exist in the commit",
+                    file.file_path
+                ));
+                continue;
+            }
+        };
+        let file_added_lines = added_lines
+            .as_ref()
+            .map(|added| added.get(&file.file_path).cloned().unwrap_or_default());
+
+        let mut file_lines = BTreeSet::new();
+        for entry in &file.entries {
+            if !log.metadata.prompts.contains_key(&entry.hash) {
+                problems.push(format!(
+                    "{}: attestation {} has no matching prompt record",
+                    file.file_path, entry.hash
+                ));
+            }
+            for line in entry.line_ranges.iter().flat_map(|range| range.expand()) {
+                if line == 0 || line > line_count {
+                    problems.push(format!(
+                        "{}: attested line {} is outside the file ({} lines)",
+                        file.file_path, line, line_count
+                    ));
+                } else if !file_lines.insert(line) {
+                    problems.push(format!(
+                        "{}: line {} is attested more than once",
+                        file.file_path, line
+                    ));
+                } else if let Some(file_added_lines) = &file_added_lines
+                    && !file_added_lines.contains(&line)
+                {
+                    problems.push(format!(
+                        "{}: attested line {} was not added by this commit",
+                        file.file_path, line
+                    ));
+                }
+            }
+        }
+        attested_lines += file_lines.len() as u32;
+    }
+
+    if added_lines.is_some() {
+        let (git_diff_added_lines, _) = get_git_diff_stats(repo, commit_sha, &[], false, None)?;
+        if attested_lines > git_diff_added_lines {
+            problems.push(format!(
+                "{} lines are attested but the commit only adds {}",
+                attested_lines, git_diff_added_lines
+            ));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(CommitVerification::Ok)
+    } else {
+        Ok(CommitVerification::Failed(problems))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(&[]).unwrap();
+        assert!(options.revision.is_none());
+        assert!(!options.verify_signatures);
+
+        let options = parse_args(&args(&[
+            "main..HEAD",
+            "--verify-signatures",
+            "--public-key",
+            "key.pub",
+        ]))
+        .unwrap();
+        assert_eq!(options.revision.as_deref(), Some("main..HEAD"));
+        assert!(options.verify_signatures);
+        assert_eq!(options.public_key_path.as_deref(), Some("key.pub"));
+
+        assert!(parse_args(&args(&["--bogus"])).is_err());
+        assert!(parse_args(&args(&["a", "b"])).is_err());
+        assert!(parse_args(&args(&["--public-key=key.pub"])).is_err());
+    }
+}
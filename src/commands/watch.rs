@@ -0,0 +1,184 @@
+use crate::authorship::ignore::{
+    IgnoreMatcher, build_ignore_matcher, effective_ignore_patterns, should_ignore_file_with_matcher,
+};
+use crate::authorship::virtual_attribution::VirtualAttributions;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::repository::Repository;
+use notify::{RecursiveMode, Watcher};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// AI and human lines among the unstaged changes in the working tree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct WatchCounts {
+    pub ai_lines: u32,
+    pub human_lines: u32,
+}
+
+pub fn handle_watch(args: &[String]) {
+    let mut json_output = false;
+    let mut debounce = DEFAULT_DEBOUNCE;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--json" => json_output = true,
+            arg if arg == "--debounce" || arg.starts_with("--debounce=") => {
+                let value = match arg.split_once('=') {
+                    Some((_, value)) => value.to_string(),
+                    None if i + 1 < args.len() => {
+                        i += 1;
+                        args[i].clone()
+                    }
+                    None => {
+                        eprintln!("--debounce requires a value in milliseconds");
+                        std::process::exit(1);
+                    }
+                };
+                debounce = match value.parse::<u64>() {
+                    Ok(ms) => Duration::from_millis(ms),
+                    Err(_) => {
+                        eprintln!("Invalid --debounce value: {}", value);
+                        std::process::exit(1);
+                    }
+                };
+            }
+            other => {
+                eprintln!("Unknown watch argument: {}", other);
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = run_watch(&repo, json_output, debounce) {
+        eprintln!("Watch failed: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Print the counts for the unstaged changes, then again whenever a save or checkpoint
+/// changes them. Bursts of events are coalesced until `debounce` passes without another.
+fn run_watch(repo: &Repository, json: bool, debounce: Duration) -> Result<(), GitAiError> {
+    let ignore_matcher = build_ignore_matcher(&effective_ignore_patterns(repo, &[], &[]));
+    let workdir = canonical(&repo.workdir()?);
+    let git_dir = canonical(repo.path());
+    let working_logs = canonical(&repo.storage.working_logs);
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            let _ = tx.send(event);
+        }
+    })
+    .map_err(|e| GitAiError::Generic(format!("Failed to start file watcher: {}", e)))?;
+    watcher
+        .watch(&workdir, RecursiveMode::Recursive)
+        .map_err(|e| {
+            GitAiError::Generic(format!("Failed to watch {}: {}", workdir.display(), e))
+        })?;
+    // Linked worktrees keep their working logs outside the working tree
+    if !working_logs.starts_with(&workdir) {
+        std::fs::create_dir_all(&working_logs)?;
+        watcher
+            .watch(&working_logs, RecursiveMode::Recursive)
+            .map_err(|e| GitAiError::Generic(format!("Failed to watch working logs: {}", e)))?;
+    }
+
+    let mut last_counts = None;
+    loop {
+        let counts = unstaged_counts(repo, &ignore_matcher)?;
+        if last_counts != Some(counts) {
+            print_counts(&counts, json)?;
+            last_counts = Some(counts);
+        }
+
+        // Edits in the working tree and new checkpoints change the counts; the rest of the
+        // git directory (index writes, object files, locks) does not
+        loop {
+            let event = rx
+                .recv()
+                .map_err(|_| GitAiError::Generic("File watcher stopped".to_string()))?;
+            if event
+                .paths
+                .iter()
+                .any(|path| path.starts_with(&working_logs) || !path.starts_with(&git_dir))
+            {
+                break;
+            }
+        }
+        while rx.recv_timeout(debounce).is_ok() {}
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn print_counts(counts: &WatchCounts, json: bool) -> Result<(), GitAiError> {
+    if json {
+        println!("{}", serde_json::to_string(counts)?);
+    } else {
+        let total = counts.ai_lines + counts.human_lines;
+        let ai_percentage = if total > 0 {
+            counts.ai_lines as f64 / total as f64 * 100.0
+        } else {
+            0.0
+        };
+        println!(
+            "ai +{:<6} human +{:<6} {:>5.1}% ai",
+            counts.ai_lines, counts.human_lines, ai_percentage
+        );
+    }
+    Ok(())
+}
+
+/// Count the AI and human lines added by unstaged changes, untracked files included.
+/// An added line is AI when its text matches a line the working log attributes to an AI
+/// prompt, so edits made since the last checkpoint that shift lines keep their attribution.
+pub fn unstaged_counts(
+    repo: &Repository,
+    ignore_matcher: &IgnoreMatcher,
+) -> Result<WatchCounts, GitAiError> {
+    let head_sha = repo.head()?.target()?;
+    let added = repo.diff_unstaged_added_lines()?;
+    let working_va = VirtualAttributions::from_just_working_log(repo.clone(), head_sha, None)?;
+    let workdir = repo.workdir()?;
+
+    let mut counts = WatchCounts::default();
+    for (file, lines) in &added {
+        if should_ignore_file_with_matcher(file, ignore_matcher) {
+            continue;
+        }
+        let content = std::fs::read(workdir.join(file))
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default();
+        let current: Vec<&str> = content.lines().collect();
+        let mut ai_prompts = working_va.ai_line_prompts_by_text(file);
+
+        for line in lines {
+            let text = current.get((*line as usize).saturating_sub(1)).copied();
+            match text
+                .and_then(|text| ai_prompts.get_mut(text))
+                .and_then(|prompts| prompts.pop_front())
+            {
+                Some(_) => counts.ai_lines += 1,
+                None => counts.human_lines += 1,
+            }
+        }
+    }
+    Ok(counts)
+}
@@ -16,6 +16,14 @@ use std::sync::RwLock;
 
 /// Default API base URL for comparison
 pub const DEFAULT_API_BASE_URL: &str = "https://usegitai.com";
+/// Header marker that flags a file as generated (see `generated_marker`)
+pub const DEFAULT_GENERATED_MARKER: &str = "@generated";
+/// Files above this many bytes are attributed as a whole when recorded (see `max_file_size`)
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024;
+/// Decimal places shown for percentages in reports (see `percentage_decimals`)
+pub const DEFAULT_PERCENTAGE_DECIMALS: u8 = 1;
+/// The most decimal places `percentage_decimals` accepts
+pub const MAX_PERCENTAGE_DECIMALS: u8 = 6;
 
 /// Prompt storage mode enum for type-safe handling
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -74,6 +82,11 @@ pub struct Config {
     default_prompt_storage: Option<String>,
     api_key: Option<String>,
     quiet: bool,
+    authorship_signing_key_path: Option<String>,
+    generated_marker: Option<String>,
+    max_file_size: Option<u64>,
+    percentage_decimals: Option<u8>,
+    authorship_log_format: AuthorshipLogFormat,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -106,6 +119,33 @@ impl UpdateChannel {
     }
 }
 
+/// Encoding used when writing authorship logs to notes. Reads accept either encoding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AuthorshipLogFormat {
+    /// Attestation text followed by pretty JSON metadata
+    #[default]
+    Text,
+    /// Length-prefixed binary encoding, armored as base64 text
+    Binary,
+}
+
+impl AuthorshipLogFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthorshipLogFormat::Text => "text",
+            AuthorshipLogFormat::Binary => "binary",
+        }
+    }
+
+    pub fn from_str(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "text" => Some(AuthorshipLogFormat::Text),
+            "binary" => Some(AuthorshipLogFormat::Binary),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Default)]
 pub struct FileConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -140,6 +180,16 @@ pub struct FileConfig {
     pub api_key: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub quiet: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorship_signing_key_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_marker: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_file_size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percentage_decimals: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorship_log_format: Option<String>,
 }
 
 static CONFIG: OnceLock<Config> = OnceLock::new();
@@ -380,6 +430,36 @@ impl Config {
         self.quiet
     }
 
+    /// Returns the path to the Ed25519 key used to sign authorship logs, if configured
+    pub fn authorship_signing_key_path(&self) -> Option<&str> {
+        self.authorship_signing_key_path.as_deref()
+    }
+
+    /// Returns the header marker that flags a file as generated for `--exclude-generated`
+    pub fn generated_marker(&self) -> &str {
+        self.generated_marker
+            .as_deref()
+            .unwrap_or(DEFAULT_GENERATED_MARKER)
+    }
+
+    /// Returns the size in bytes above which a recorded file gets one file-level attribution
+    /// instead of line-level ones
+    pub fn max_file_size(&self) -> u64 {
+        self.max_file_size.unwrap_or(DEFAULT_MAX_FILE_SIZE)
+    }
+
+    /// Returns the number of decimal places percentages get in markdown and HTML reports
+    pub fn percentage_decimals(&self) -> u8 {
+        self.percentage_decimals
+            .unwrap_or(DEFAULT_PERCENTAGE_DECIMALS)
+            .min(MAX_PERCENTAGE_DECIMALS)
+    }
+
+    /// Returns the encoding used when writing authorship logs
+    pub fn authorship_log_format(&self) -> AuthorshipLogFormat {
+        self.authorship_log_format
+    }
+
     /// Override feature flags for testing purposes.
     /// Only available when the `test-support` feature is enabled or in test mode.
     /// Must be `pub` to work with integration tests in the `tests/` directory.
@@ -577,6 +657,31 @@ fn build_config() -> Config {
     // Get quiet setting (defaults to false)
     let quiet = file_cfg.as_ref().and_then(|c| c.quiet).unwrap_or(false);
 
+    // Get authorship signing key path (signing is disabled when unset)
+    let authorship_signing_key_path = file_cfg
+        .as_ref()
+        .and_then(|c| c.authorship_signing_key_path.clone())
+        .filter(|s| !s.is_empty());
+
+    // Get generated-file marker (falls back to DEFAULT_GENERATED_MARKER)
+    let generated_marker = file_cfg
+        .as_ref()
+        .and_then(|c| c.generated_marker.clone())
+        .filter(|s| !s.is_empty());
+
+    // Get the recording size limit (falls back to DEFAULT_MAX_FILE_SIZE)
+    let max_file_size = file_cfg.as_ref().and_then(|c| c.max_file_size);
+
+    // Get report precision (falls back to DEFAULT_PERCENTAGE_DECIMALS)
+    let percentage_decimals = file_cfg.as_ref().and_then(|c| c.percentage_decimals);
+
+    // Get authorship log encoding (defaults to text)
+    let authorship_log_format = file_cfg
+        .as_ref()
+        .and_then(|c| c.authorship_log_format.as_deref())
+        .and_then(AuthorshipLogFormat::from_str)
+        .unwrap_or_default();
+
     #[cfg(any(test, feature = "test-support"))]
     {
         let mut config = Config {
@@ -596,6 +701,11 @@ fn build_config() -> Config {
             default_prompt_storage,
             api_key,
             quiet,
+            authorship_signing_key_path,
+            generated_marker,
+            max_file_size,
+            percentage_decimals,
+            authorship_log_format,
         };
         apply_test_config_patch(&mut config);
         config
@@ -619,6 +729,11 @@ fn build_config() -> Config {
         default_prompt_storage,
         api_key,
         quiet,
+        authorship_signing_key_path,
+        generated_marker,
+        max_file_size,
+        percentage_decimals,
+        authorship_log_format,
     }
 }
 
@@ -891,6 +1006,11 @@ mod tests {
             default_prompt_storage: None,
             api_key: None,
             quiet: false,
+            authorship_signing_key_path: None,
+            generated_marker: None,
+            max_file_size: None,
+            percentage_decimals: None,
+            authorship_log_format: AuthorshipLogFormat::Text,
         }
     }
 
@@ -998,6 +1118,11 @@ mod tests {
             default_prompt_storage: None,
             api_key: None,
             quiet: false,
+            authorship_signing_key_path: None,
+            generated_marker: None,
+            max_file_size: None,
+            percentage_decimals: None,
+            authorship_log_format: AuthorshipLogFormat::Text,
         }
     }
 
@@ -1114,6 +1239,11 @@ mod tests {
             default_prompt_storage: default_prompt_storage.map(|s| s.to_string()),
             api_key: None,
             quiet: false,
+            authorship_signing_key_path: None,
+            generated_marker: None,
+            max_file_size: None,
+            percentage_decimals: None,
+            authorship_log_format: AuthorshipLogFormat::Text,
         }
     }
 
@@ -1,4 +1,22 @@
 use std::fmt;
+use std::path::PathBuf;
+
+/// Exit codes of `git-ai` commands. CI scripts rely on these, so a code's meaning never changes
+/// and new conditions get new codes.
+pub mod exit_code {
+    /// The command succeeded.
+    pub const SUCCESS: i32 = 0;
+    /// The command failed: invalid arguments, a git failure, unreadable data.
+    pub const ERROR: i32 = 1;
+    /// `search` or `continue` found no prompt history.
+    pub const NO_RESULTS: i32 = 2;
+    /// `stats` broke `--max-ai-percentage` or `--min-human-lines`.
+    pub const THRESHOLD_EXCEEDED: i32 = 3;
+    /// `stats --fail-on-missing-attestation` found commits without an authorship log.
+    pub const MISSING_ATTESTATION: i32 = 4;
+    /// `verify` found authorship logs that do not match their commits.
+    pub const VERIFICATION_FAILED: i32 = 5;
+}
 
 #[derive(Debug)]
 pub enum GitAiError {
@@ -13,6 +31,14 @@ pub enum GitAiError {
     },
     /// Errors from  Gix
     GixError(String),
+    /// The directory is not inside a git repository
+    NotAGitRepo(PathBuf),
+    /// The commit has no authorship log
+    MissingAttestation(String),
+    /// `git diff` failed, with git's error output
+    DiffFailed(String),
+    /// A path that has to be passed to git as text is not valid UTF-8
+    Utf8Path(PathBuf),
     JsonError(serde_json::Error),
     Utf8Error(std::str::Utf8Error),
     FromUtf8Error(std::string::FromUtf8Error),
@@ -44,6 +70,14 @@ impl fmt::Display for GitAiError {
             GitAiError::SqliteError(e) => write!(f, "SQLite error: {}", e),
             GitAiError::Generic(e) => write!(f, "Generic error: {}", e),
             GitAiError::GixError(e) => write!(f, "Gix error: {}", e),
+            GitAiError::NotAGitRepo(path) => {
+                write!(f, "Not a git repository: {}", path.display())
+            }
+            GitAiError::MissingAttestation(commit) => {
+                write!(f, "No authorship log found for commit {}", commit)
+            }
+            GitAiError::DiffFailed(e) => write!(f, "Git diff failed: {}", e),
+            GitAiError::Utf8Path(path) => write!(f, "Path is not valid UTF-8: {}", path.display()),
         }
     }
 }
@@ -107,6 +141,12 @@ impl Clone for GitAiError {
             GitAiError::SqliteError(e) => GitAiError::Generic(format!("SQLite error: {}", e)),
             GitAiError::Generic(s) => GitAiError::Generic(s.clone()),
             GitAiError::GixError(e) => GitAiError::Generic(format!("Gix error: {}", e)),
+            GitAiError::NotAGitRepo(path) => GitAiError::NotAGitRepo(path.clone()),
+            GitAiError::MissingAttestation(commit) => {
+                GitAiError::MissingAttestation(commit.clone())
+            }
+            GitAiError::DiffFailed(e) => GitAiError::DiffFailed(e.clone()),
+            GitAiError::Utf8Path(path) => GitAiError::Utf8Path(path.clone()),
         }
     }
 }
@@ -307,6 +347,29 @@ mod tests {
         assert!(display.contains("Gix error"));
     }
 
+    #[test]
+    fn test_error_display_and_clone_keep_specific_variants() {
+        let err = GitAiError::NotAGitRepo(PathBuf::from("/tmp/plain"));
+        assert_eq!(format!("{}", err), "Not a git repository: /tmp/plain");
+        assert!(
+            matches!(err.clone(), GitAiError::NotAGitRepo(path) if path == PathBuf::from("/tmp/plain"))
+        );
+
+        let err = GitAiError::MissingAttestation("abc123".to_string());
+        assert!(format!("{}", err).contains("abc123"));
+        assert!(
+            matches!(err.clone(), GitAiError::MissingAttestation(commit) if commit == "abc123")
+        );
+
+        let err = GitAiError::DiffFailed("fatal: bad revision".to_string());
+        assert!(format!("{}", err).contains("bad revision"));
+        assert!(matches!(err.clone(), GitAiError::DiffFailed(_)));
+
+        let err = GitAiError::Utf8Path(PathBuf::from("dir"));
+        assert!(format!("{}", err).contains("not valid UTF-8"));
+        assert!(matches!(err.clone(), GitAiError::Utf8Path(_)));
+    }
+
     #[test]
     fn test_error_is_std_error() {
         let err = GitAiError::Generic("test".to_string());
@@ -0,0 +1,272 @@
+use crate::error::GitAiError;
+use crate::git::repository::{Repository, exec_git_stdin};
+use crate::utils::{LockFile, debug_log};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Repository git config key selecting where authorship logs are stored
+pub const STORAGE_CONFIG_KEY: &str = "git-ai.storage";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AttestationStorage {
+    /// Git notes under refs/notes/ai, synced with the remote on fetch and push
+    #[default]
+    Notes,
+    /// Plain files under .git/ai/authorship, never shared with remotes
+    Sidecar,
+}
+
+impl AttestationStorage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttestationStorage::Notes => "notes",
+            AttestationStorage::Sidecar => "sidecar",
+        }
+    }
+}
+
+impl std::str::FromStr for AttestationStorage {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            "notes" => Ok(AttestationStorage::Notes),
+            "sidecar" => Ok(AttestationStorage::Sidecar),
+            other => Err(format!("invalid attestation storage mode: '{}'", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for AttestationStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Storage backend configured for this repository via `git-ai.storage`, defaulting to notes
+pub fn configured_storage(repo: &Repository) -> AttestationStorage {
+    match repo.config_get_str(STORAGE_CONFIG_KEY) {
+        Ok(Some(value)) => value.parse().unwrap_or_else(|e| {
+            debug_log(&format!("{} {}, using notes", STORAGE_CONFIG_KEY, e));
+            AttestationStorage::Notes
+        }),
+        _ => AttestationStorage::Notes,
+    }
+}
+
+/// How long a writer waits for another process to finish writing authorship logs
+const WRITE_LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Serialize authorship log writes across processes, such as hooks of concurrent git commands.
+/// Hold the returned lock while writing; it is released when dropped. Times out with an error
+/// rather than writing unlocked.
+pub fn lock_attestation_writes(repo: &Repository) -> Result<LockFile, GitAiError> {
+    // Worktrees share notes and sidecar logs, so they share the lock too
+    let dir = repo.common_dir().join("ai");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("authorship.lock");
+    LockFile::acquire(&path, WRITE_LOCK_TIMEOUT).ok_or_else(|| {
+        GitAiError::Generic(format!(
+            "Timed out waiting for the authorship log lock at {}",
+            path.display()
+        ))
+    })
+}
+
+/// Sidecar logs live in the common git dir, so every worktree of the repository sees them
+fn sidecar_dir(repo: &Repository) -> PathBuf {
+    repo.common_dir().join("ai").join("authorship")
+}
+
+/// Sidecar files use the same <aa>/<bb...> fanout as notes to keep directories small
+fn sidecar_path(repo: &Repository, commit_sha: &str) -> PathBuf {
+    let dir = sidecar_dir(repo);
+    if commit_sha.len() <= 2 {
+        dir.join(commit_sha)
+    } else {
+        dir.join(&commit_sha[..2]).join(&commit_sha[2..])
+    }
+}
+
+/// Callers hold `lock_attestation_writes`. The log is written next to its final path and renamed
+/// into place, so readers never see a partly written log.
+pub fn sidecar_write(repo: &Repository, commit_sha: &str, content: &str) -> Result<(), GitAiError> {
+    let path = sidecar_path(repo, commit_sha);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, &path)?;
+    Ok(())
+}
+
+pub fn sidecar_read(repo: &Repository, commit_sha: &str) -> Option<String> {
+    fs::read_to_string(sidecar_path(repo, commit_sha))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Delete the sidecar log of a commit. A missing log is not an error.
+pub fn sidecar_remove(repo: &Repository, commit_sha: &str) -> Result<(), GitAiError> {
+    match fs::remove_file(sidecar_path(repo, commit_sha)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// List every (commit_sha, content) pair stored in the sidecar directory
+pub fn sidecar_list(repo: &Repository) -> Result<Vec<(String, String)>, GitAiError> {
+    let dir = sidecar_dir(repo);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for fanout in fs::read_dir(&dir)? {
+        let fanout = fanout?;
+        if !fanout.file_type()?.is_dir() {
+            continue;
+        }
+        let prefix = fanout.file_name().to_string_lossy().to_string();
+        for file in fs::read_dir(fanout.path())? {
+            let file = file?;
+            // Left behind by a writer that died before renaming it into place
+            if file.file_name().to_string_lossy().ends_with(".tmp") {
+                continue;
+            }
+            let commit_sha = format!("{}{}", prefix, file.file_name().to_string_lossy());
+            if let Some(content) = sidecar_read(repo, &commit_sha) {
+                entries.push((commit_sha, content));
+            }
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+/// Write sidecar logs into the object database and return commit SHA -> blob OID, so callers
+/// that batch-read note blobs work unchanged with the sidecar backend.
+pub fn sidecar_blob_oids_for_commits(
+    repo: &Repository,
+    commit_shas: &[String],
+) -> Result<HashMap<String, String>, GitAiError> {
+    let present: Vec<(&String, PathBuf)> = commit_shas
+        .iter()
+        .map(|sha| (sha, sidecar_path(repo, sha)))
+        .filter(|(_, path)| path.is_file())
+        .collect();
+    if present.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.push("hash-object".to_string());
+    args.push("-w".to_string());
+    args.push("--no-filters".to_string());
+    args.push("--stdin-paths".to_string());
+
+    let mut stdin_data = String::new();
+    for (_, path) in &present {
+        stdin_data.push_str(&path.to_string_lossy());
+        stdin_data.push('\n');
+    }
+
+    let output = exec_git_stdin(&args, stdin_data.as_bytes())?;
+    let stdout = String::from_utf8(output.stdout)?;
+    Ok(present
+        .into_iter()
+        .zip(stdout.lines())
+        .map(|((sha, _), oid)| (sha.clone(), oid.trim().to_string()))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::TmpRepo;
+
+    #[test]
+    fn test_parse_storage_mode() {
+        assert_eq!(
+            " Sidecar ".parse::<AttestationStorage>(),
+            Ok(AttestationStorage::Sidecar)
+        );
+        assert_eq!(
+            "notes".parse::<AttestationStorage>(),
+            Ok(AttestationStorage::Notes)
+        );
+        assert!("files".parse::<AttestationStorage>().is_err());
+        assert_eq!(AttestationStorage::default(), AttestationStorage::Notes);
+    }
+
+    #[test]
+    fn test_sidecar_write_read_and_list() {
+        let tmp_repo = TmpRepo::new().expect("Failed to create tmp repo");
+        let repo = tmp_repo.gitai_repo();
+        let sha = "abcdef0123456789abcdef0123456789abcdef01";
+
+        assert_eq!(sidecar_read(repo, sha), None);
+        sidecar_write(repo, sha, "{\"attestations\":[]}\n").unwrap();
+        assert!(
+            repo.path()
+                .join("ai/authorship/ab")
+                .join(&sha[2..])
+                .is_file()
+        );
+        assert_eq!(
+            sidecar_read(repo, sha).as_deref(),
+            Some("{\"attestations\":[]}")
+        );
+        assert_eq!(
+            sidecar_list(repo).unwrap(),
+            vec![(sha.to_string(), "{\"attestations\":[]}".to_string())]
+        );
+
+        let oids =
+            sidecar_blob_oids_for_commits(repo, &[sha.to_string(), "missing".to_string()]).unwrap();
+        assert_eq!(oids.len(), 1);
+        assert_eq!(oids[sha].len(), 40);
+    }
+
+    #[test]
+    fn test_sidecar_logs_are_shared_between_worktrees() {
+        let tmp_repo = TmpRepo::new().expect("Failed to create tmp repo");
+        tmp_repo.write_file("a.txt", "a\n", true).unwrap();
+        tmp_repo.commit_with_message("initial").unwrap();
+
+        let worktree_dir = tempfile::tempdir().unwrap();
+        let worktree_path = worktree_dir.path().join("linked");
+        tmp_repo
+            .git_command(&[
+                "worktree",
+                "add",
+                "--detach",
+                worktree_path.to_str().unwrap(),
+            ])
+            .unwrap();
+        let worktree_repo =
+            crate::git::repository::find_repository_in_path(worktree_path.to_str().unwrap())
+                .unwrap();
+        assert_ne!(worktree_repo.path(), tmp_repo.gitai_repo().path());
+
+        let sha = "abcdef0123456789abcdef0123456789abcdef01";
+        let _lock = lock_attestation_writes(&worktree_repo).unwrap();
+        sidecar_write(&worktree_repo, sha, "log").unwrap();
+
+        assert_eq!(
+            sidecar_read(tmp_repo.gitai_repo(), sha).as_deref(),
+            Some("log")
+        );
+        assert!(
+            tmp_repo
+                .gitai_repo()
+                .path()
+                .join("ai/authorship.lock")
+                .exists()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::authorship_log_serialization::{AuthorshipLog, BINARY_LOG_PREFIX};
 use crate::error::GitAiError;
 use crate::git::refs::{commits_with_authorship_notes, note_blob_oids_for_commits};
 #[cfg(test)]
@@ -178,6 +178,12 @@ fn extract_file_paths_from_note(content: &str, files: &mut HashSet<String>) {
                 files.insert(attestation.file_path);
             }
         }
+    } else if content.trim_start().starts_with(BINARY_LOG_PREFIX)
+        && let Ok(log) = AuthorshipLog::deserialize_from_string(content)
+    {
+        for attestation in log.attestations {
+            files.insert(attestation.file_path);
+        }
     }
 }
 
@@ -0,0 +1,642 @@
+/// Decode a path as printed by git back into the real filename.
+///
+/// Git quotes filenames containing non-ASCII characters (and some special characters)
+/// using C-style escaping with octal sequences. For example, a Chinese filename like
+/// "中文.txt" would appear as `"\344\270\255\346\226\207.txt"` in git output.
+///
+/// This function handles:
+/// - Quoted paths: removes surrounding quotes and unescapes content
+/// - Octal escapes: converts `\NNN` sequences back to UTF-8 bytes
+/// - Other escapes: `\\`, `\"`, `\n`, `\t`, `\a`, etc.
+/// - `core.quotepath=false` output: raw UTF-8, quoted or not, passes through
+/// - Unquoted paths: returned as-is
+///
+/// # Examples
+///
+/// ```
+/// use git_ai::git::gitutil::decode_git_path;
+///
+/// // Unquoted path - returned as-is
+/// assert_eq!(decode_git_path("simple.txt"), "simple.txt");
+///
+/// // Quoted path with spaces
+/// assert_eq!(decode_git_path("\"path with spaces.txt\""), "path with spaces.txt");
+///
+/// // Chinese characters encoded as octal
+/// assert_eq!(decode_git_path("\"\\344\\270\\255\\346\\226\\207.txt\""), "中文.txt");
+/// ```
+pub fn decode_git_path(raw: &str) -> String {
+    // If not quoted, return as-is
+    if raw.len() < 2 || !raw.starts_with('"') || !raw.ends_with('"') {
+        return raw.to_string();
+    }
+
+    // Remove surrounding quotes
+    let inner = &raw[1..raw.len() - 1];
+
+    // Parse escape sequences and collect bytes
+    let mut bytes: Vec<u8> = Vec::with_capacity(inner.len());
+    let mut chars = inner.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            // Regular character - encode as UTF-8
+            let mut buf = [0u8; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+
+        let escaped = match chars.peek() {
+            Some('\\') => Some(b'\\'),
+            Some('"') => Some(b'"'),
+            Some('a') => Some(0x07),
+            Some('b') => Some(0x08),
+            Some('f') => Some(0x0c),
+            Some('n') => Some(b'\n'),
+            Some('r') => Some(b'\r'),
+            Some('t') => Some(b'\t'),
+            Some('v') => Some(0x0b),
+            _ => None,
+        };
+        if let Some(byte) = escaped {
+            chars.next();
+            bytes.push(byte);
+            continue;
+        }
+
+        if chars.peek().is_some_and(|d| d.is_ascii_digit()) {
+            // Octal escape sequence: \NNN (1-3 octal digits)
+            let mut octal = String::new();
+            while octal.len() < 3
+                && let Some(&d) = chars.peek()
+                && ('0'..='7').contains(&d)
+            {
+                octal.push(d);
+                chars.next();
+            }
+            if let Ok(byte_val) = u8::from_str_radix(&octal, 8) {
+                bytes.push(byte_val);
+            }
+        } else {
+            // Unknown escape - keep the backslash
+            bytes.push(b'\\');
+        }
+    }
+
+    // Convert bytes to UTF-8 string
+    String::from_utf8(bytes).unwrap_or_else(|e| {
+        // If invalid UTF-8, try lossy conversion
+        String::from_utf8_lossy(e.as_bytes()).into_owned()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_git_path_mixed_ascii_and_octal() {
+        assert_eq!(
+            decode_git_path("\"src/\\344\\270\\255\\346\\226\\207/main.rs\""),
+            "src/中文/main.rs"
+        );
+        assert_eq!(
+            decode_git_path("\"docs/caf\\303\\251 menu.md\""),
+            "docs/café menu.md"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_emoji() {
+        assert_eq!(decode_git_path("\"\\360\\237\\232\\200.txt\""), "🚀.txt");
+        assert_eq!(
+            decode_git_path("\"notes/\\360\\237\\216\\211_party.md\""),
+            "notes/🎉_party.md"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_embedded_quotes_and_backslashes() {
+        assert_eq!(decode_git_path("\"say \\\"hi\\\".txt\""), "say \"hi\".txt");
+        assert_eq!(decode_git_path("\"dir\\\\file.txt\""), "dir\\file.txt");
+        assert_eq!(
+            decode_git_path("\"bell\\a form\\f vtab\\v back\\b\""),
+            "bell\x07 form\x0c vtab\x0b back\x08"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_already_unquoted() {
+        // core.quotepath=false prints non-ASCII names raw and unquoted
+        assert_eq!(decode_git_path("src/中文/main.rs"), "src/中文/main.rs");
+        assert_eq!(decode_git_path("🚀.txt"), "🚀.txt");
+        assert_eq!(decode_git_path("plain.txt"), "plain.txt");
+        // A lone quote character is not a quoted path
+        assert_eq!(decode_git_path("\""), "\"");
+    }
+
+    #[test]
+    fn test_decode_git_path_quotepath_off_still_quotes_special_characters() {
+        // With core.quotepath=false git only quotes names with special characters,
+        // leaving any UTF-8 inside the quotes as-is
+        assert_eq!(decode_git_path("\"中文\\tname.txt\""), "中文\tname.txt");
+        assert_eq!(decode_git_path("\"🚀 \\\"launch\\\"\""), "🚀 \"launch\"");
+    }
+
+    // =========================================================================
+    // Escaped path Tests
+    // =========================================================================
+
+    #[test]
+    fn test_decode_git_path_simple() {
+        // Unquoted path - no change
+        assert_eq!(decode_git_path("simple.txt"), "simple.txt");
+        assert_eq!(decode_git_path("path/to/file.rs"), "path/to/file.rs");
+    }
+
+    #[test]
+    fn test_decode_git_path_quoted_with_spaces() {
+        // Quoted path with spaces
+        assert_eq!(
+            decode_git_path("\"path with spaces.txt\""),
+            "path with spaces.txt"
+        );
+        assert_eq!(
+            decode_git_path("\"dir name/file name.txt\""),
+            "dir name/file name.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_chinese_characters() {
+        // Chinese characters "中文" encoded as octal: \344\270\255\346\226\207
+        assert_eq!(
+            decode_git_path("\"\\344\\270\\255\\346\\226\\207.txt\""),
+            "中文.txt"
+        );
+
+        // More complex Chinese filename: "中文文件.txt"
+        // 中 = \344\270\255, 文 = \346\226\207, 件 = \344\273\266
+        assert_eq!(
+            decode_git_path("\"\\344\\270\\255\\346\\226\\207\\346\\226\\207\\344\\273\\266.txt\""),
+            "中文文件.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_emoji_filenames() {
+        // Emoji "🚀" (rocket) = U+1F680 = \360\237\232\200 in octal UTF-8
+        assert_eq!(decode_git_path("\"\\360\\237\\232\\200.txt\""), "🚀.txt");
+
+        // Emoji "😀" (grinning face) = U+1F600 = \360\237\230\200 in octal UTF-8
+        assert_eq!(decode_git_path("\"\\360\\237\\230\\200.txt\""), "😀.txt");
+
+        // Mixed: "test_🎉_file.txt" where 🎉 = \360\237\216\211
+        assert_eq!(
+            decode_git_path("\"test_\\360\\237\\216\\211_file.txt\""),
+            "test_🎉_file.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_escaped_characters() {
+        // Escaped backslash
+        assert_eq!(
+            decode_git_path("\"path\\\\with\\\\slashes\""),
+            "path\\with\\slashes"
+        );
+
+        // Escaped quotes
+        assert_eq!(decode_git_path("\"file\\\"name.txt\""), "file\"name.txt");
+
+        // Escaped newline and tab
+        assert_eq!(decode_git_path("\"line1\\nline2\""), "line1\nline2");
+        assert_eq!(decode_git_path("\"col1\\tcol2\""), "col1\tcol2");
+    }
+
+    #[test]
+    fn test_decode_git_path_mixed_content() {
+        // Mix of ASCII, Chinese, and escapes
+        assert_eq!(
+            decode_git_path("\"src/\\344\\270\\255\\346\\226\\207/file.txt\""),
+            "src/中文/file.txt"
+        );
+    }
+
+    // =========================================================================
+    // Phase 1: CJK Extended Coverage Tests
+    // =========================================================================
+
+    #[test]
+    fn test_decode_git_path_japanese_hiragana() {
+        // Japanese Hiragana "ひらがな" = \343\201\262\343\202\211\343\201\214\343\201\252
+        assert_eq!(
+            decode_git_path("\"\\343\\201\\262\\343\\202\\211\\343\\201\\214\\343\\201\\252.txt\""),
+            "ひらがな.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_japanese_katakana() {
+        // Japanese Katakana "カタカナ" = \343\202\253\343\202\277\343\202\253\343\203\212
+        assert_eq!(
+            decode_git_path("\"\\343\\202\\253\\343\\202\\277\\343\\202\\253\\343\\203\\212.txt\""),
+            "カタカナ.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_korean_hangul() {
+        // Korean Hangul "한글" = \355\225\234\352\270\200
+        assert_eq!(
+            decode_git_path("\"\\355\\225\\234\\352\\270\\200.txt\""),
+            "한글.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_traditional_chinese() {
+        // Traditional Chinese "繁體" = \347\271\201\351\253\224
+        assert_eq!(
+            decode_git_path("\"\\347\\271\\201\\351\\253\\224.txt\""),
+            "繁體.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_mixed_cjk() {
+        // Mixed CJK: "日中韓" (Japanese, Chinese, Korean characters mixed)
+        // 日 = \346\227\245, 中 = \344\270\255, 韓 = \351\237\223
+        assert_eq!(
+            decode_git_path("\"\\346\\227\\245\\344\\270\\255\\351\\237\\223.txt\""),
+            "日中韓.txt"
+        );
+    }
+
+    // =========================================================================
+    // Phase 2: RTL Scripts Tests (Arabic, Hebrew, Persian, Urdu)
+    // =========================================================================
+
+    #[test]
+    fn test_decode_git_path_arabic() {
+        // Arabic "مرحبا" (marhaba = hello)
+        // م = \331\205, ر = \330\261, ح = \330\255, ب = \330\250, ا = \330\247
+        assert_eq!(
+            decode_git_path("\"\\331\\205\\330\\261\\330\\255\\330\\250\\330\\247.txt\""),
+            "مرحبا.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_hebrew() {
+        // Hebrew "שלום" (shalom = hello/peace)
+        // ש = \327\251, ל = \327\234, ו = \327\225, ם = \327\235
+        assert_eq!(
+            decode_git_path("\"\\327\\251\\327\\234\\327\\225\\327\\235.txt\""),
+            "שלום.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_persian() {
+        // Persian "فارسی" (farsi)
+        // ف = \331\201, ا = \330\247, ر = \330\261, س = \330\263, ی = \333\214
+        assert_eq!(
+            decode_git_path("\"\\331\\201\\330\\247\\330\\261\\330\\263\\333\\214.txt\""),
+            "فارسی.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_urdu() {
+        // Urdu "اردو" (urdu)
+        // ا = \330\247, ر = \330\261, د = \330\257, و = \331\210
+        assert_eq!(
+            decode_git_path("\"\\330\\247\\330\\261\\330\\257\\331\\210.txt\""),
+            "اردو.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_mixed_rtl_ltr() {
+        // Mixed RTL/LTR: "test_مرحبا_file" (ASCII + Arabic + ASCII)
+        assert_eq!(
+            decode_git_path("\"test_\\331\\205\\330\\261\\330\\255\\330\\250\\330\\247_file.txt\""),
+            "test_مرحبا_file.txt"
+        );
+    }
+
+    // =========================================================================
+    // Phase 3: Indic Scripts Tests (Hindi, Tamil, Bengali, Telugu, Gujarati)
+    // =========================================================================
+
+    #[test]
+    fn test_decode_git_path_hindi_devanagari() {
+        // Hindi "हिंदी" (Hindi in Devanagari script)
+        // ह = \340\244\271, ि = \340\244\277, ं = \340\244\202, द = \340\244\246, ी = \340\245\200
+        assert_eq!(
+            decode_git_path(
+                "\"\\340\\244\\271\\340\\244\\277\\340\\244\\202\\340\\244\\246\\340\\245\\200.txt\""
+            ),
+            "हिंदी.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_tamil() {
+        // Tamil "தமிழ்" (Tamil)
+        // த = \340\256\244, ம = \340\256\256, ி = \340\256\277, ழ = \340\256\264, ் = \340\257\215
+        assert_eq!(
+            decode_git_path(
+                "\"\\340\\256\\244\\340\\256\\256\\340\\256\\277\\340\\256\\264\\340\\257\\215.txt\""
+            ),
+            "தமிழ்.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_bengali() {
+        // Bengali "বাংলা" (Bangla)
+        // ব = \340\246\254, া = \340\246\276, ং = \340\246\202, ল = \340\246\262, া = \340\246\276
+        assert_eq!(
+            decode_git_path(
+                "\"\\340\\246\\254\\340\\246\\276\\340\\246\\202\\340\\246\\262\\340\\246\\276.txt\""
+            ),
+            "বাংলা.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_telugu() {
+        // Telugu "తెలుగు" (Telugu)
+        // త = \340\260\244, ె = \340\261\206, ల = \340\260\262, ు = \340\261\201, గ = \340\260\227, ు = \340\261\201
+        assert_eq!(
+            decode_git_path(
+                "\"\\340\\260\\244\\340\\261\\206\\340\\260\\262\\340\\261\\201\\340\\260\\227\\340\\261\\201.txt\""
+            ),
+            "తెలుగు.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_gujarati() {
+        // Gujarati "ગુજરાતી" (Gujarati)
+        // ગ = \340\252\227, ુ = \340\253\201, જ = \340\252\234, ર = \340\252\260, ા = \340\252\276, ત = \340\252\244, ી = \340\253\200
+        assert_eq!(
+            decode_git_path(
+                "\"\\340\\252\\227\\340\\253\\201\\340\\252\\234\\340\\252\\260\\340\\252\\276\\340\\252\\244\\340\\253\\200.txt\""
+            ),
+            "ગુજરાતી.txt"
+        );
+    }
+
+    // =========================================================================
+    // Phase 4: Southeast Asian Scripts Tests (Thai, Vietnamese, Khmer, Lao)
+    // =========================================================================
+
+    #[test]
+    fn test_decode_git_path_thai() {
+        // Thai "ไทย" (Thai)
+        // ไ = \340\271\204, ท = \340\270\227, ย = \340\270\242
+        assert_eq!(
+            decode_git_path("\"\\340\\271\\204\\340\\270\\227\\340\\270\\242.txt\""),
+            "ไทย.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_vietnamese() {
+        // Vietnamese "tiếng" with tone marks
+        // t = 't', i = 'i', ế = \341\272\277, n = 'n', g = 'g'
+        assert_eq!(decode_git_path("\"ti\\341\\272\\277ng.txt\""), "tiếng.txt");
+    }
+
+    #[test]
+    fn test_decode_git_path_khmer() {
+        // Khmer "ខ្មែរ" (Khmer)
+        // ខ = \341\236\201, ្ = \341\237\222, ម = \341\236\230, ែ = \341\237\202, រ = \341\236\232
+        assert_eq!(
+            decode_git_path(
+                "\"\\341\\236\\201\\341\\237\\222\\341\\236\\230\\341\\237\\202\\341\\236\\232.txt\""
+            ),
+            "ខ្មែរ.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_lao() {
+        // Lao "ລາວ" (Lao)
+        // ລ = \340\272\245, າ = \340\272\262, ວ = \340\272\247
+        assert_eq!(
+            decode_git_path("\"\\340\\272\\245\\340\\272\\262\\340\\272\\247.txt\""),
+            "ລາວ.txt"
+        );
+    }
+
+    // =========================================================================
+    // Phase 5: Cyrillic and Greek Scripts Tests
+    // =========================================================================
+
+    #[test]
+    fn test_decode_git_path_russian_cyrillic() {
+        // Russian "Русский" (Russian)
+        // Р = \320\240, у = \321\203, с = \321\201, к = \320\272, и = \320\270, й = \320\271
+        assert_eq!(
+            decode_git_path(
+                "\"\\320\\240\\321\\203\\321\\201\\321\\201\\320\\272\\320\\270\\320\\271.txt\""
+            ),
+            "Русский.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_ukrainian_cyrillic() {
+        // Ukrainian "Україна" (Ukraine)
+        // У = \320\243, к = \320\272, р = \321\200, а = \320\260, ї = \321\227, н = \320\275, а = \320\260
+        assert_eq!(
+            decode_git_path(
+                "\"\\320\\243\\320\\272\\321\\200\\320\\260\\321\\227\\320\\275\\320\\260.txt\""
+            ),
+            "Україна.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_greek() {
+        // Greek "Ελλάδα" (Greece)
+        // Ε = \316\225, λ = \316\273, λ = \316\273, ά = \316\254, δ = \316\264, α = \316\261
+        assert_eq!(
+            decode_git_path("\"\\316\\225\\316\\273\\316\\273\\316\\254\\316\\264\\316\\261.txt\""),
+            "Ελλάδα.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_greek_polytonic() {
+        // Greek polytonic "Ἑλληνική" (Hellenic with diacritics)
+        // Ἑ = \341\274\231, λ = \316\273, λ = \316\273, η = \316\267, ν = \316\275, ι = \316\271, κ = \316\272, ή = \316\256
+        assert_eq!(
+            decode_git_path(
+                "\"\\341\\274\\231\\316\\273\\316\\273\\316\\267\\316\\275\\316\\271\\316\\272\\316\\256.txt\""
+            ),
+            "Ἑλληνική.txt"
+        );
+    }
+
+    // =========================================================================
+    // Phase 6: Extended Emoji Tests (ZWJ, skin tones, flags)
+    // =========================================================================
+
+    #[test]
+    fn test_decode_git_path_emoji_skin_tone() {
+        // Emoji with skin tone modifier 👋🏽 = 👋 (U+1F44B) + 🏽 (U+1F3FD)
+        // 👋 = \360\237\221\213, 🏽 = \360\237\217\275
+        assert_eq!(
+            decode_git_path("\"\\360\\237\\221\\213\\360\\237\\217\\275.txt\""),
+            "👋🏽.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_emoji_zwj_sequence() {
+        // ZWJ emoji sequence: 👨‍💻 (man technologist) = man + ZWJ + laptop
+        // 👨 = \360\237\221\250, ZWJ = \342\200\215, 💻 = \360\237\222\273
+        assert_eq!(
+            decode_git_path("\"\\360\\237\\221\\250\\342\\200\\215\\360\\237\\222\\273.txt\""),
+            "👨‍💻.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_emoji_flag() {
+        // Flag emoji 🇯🇵 (Japan) = regional indicator J + regional indicator P
+        // 🇯 = \360\237\207\257, 🇵 = \360\237\207\265
+        assert_eq!(
+            decode_git_path("\"\\360\\237\\207\\257\\360\\237\\207\\265.txt\""),
+            "🇯🇵.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_multiple_emoji() {
+        // Multiple emoji: 🚀🎉 (rocket + party)
+        // 🚀 = \360\237\232\200, 🎉 = \360\237\216\211
+        assert_eq!(
+            decode_git_path("\"\\360\\237\\232\\200\\360\\237\\216\\211.txt\""),
+            "🚀🎉.txt"
+        );
+    }
+
+    // =========================================================================
+    // Phase 7: Special Unicode Characters Tests (math, currency, symbols)
+    // =========================================================================
+
+    #[test]
+    fn test_decode_git_path_math_symbols() {
+        // Math symbols: ∑ (summation) = \342\210\221
+        assert_eq!(decode_git_path("\"\\342\\210\\221.txt\""), "∑.txt");
+    }
+
+    #[test]
+    fn test_decode_git_path_currency_symbols() {
+        // Currency: € (euro) = \342\202\254
+        assert_eq!(decode_git_path("\"\\342\\202\\254.txt\""), "€.txt");
+    }
+
+    #[test]
+    fn test_decode_git_path_box_drawing() {
+        // Box drawing: ┌ (box drawings light down and right) = \342\224\214
+        assert_eq!(decode_git_path("\"\\342\\224\\214.txt\""), "┌.txt");
+    }
+
+    #[test]
+    fn test_decode_git_path_dingbats() {
+        // Dingbats: ✓ (check mark) = \342\234\223
+        assert_eq!(decode_git_path("\"\\342\\234\\223.txt\""), "✓.txt");
+    }
+
+    // =========================================================================
+    // Phase 8: Unicode Normalization Tests (NFC vs NFD)
+    // =========================================================================
+
+    #[test]
+    fn test_decode_git_path_nfc_precomposed() {
+        // NFC precomposed: é (U+00E9) = \303\251
+        assert_eq!(decode_git_path("\"caf\\303\\251.txt\""), "café.txt");
+    }
+
+    #[test]
+    fn test_decode_git_path_nfd_decomposed() {
+        // NFD decomposed: e + combining acute (U+0065 + U+0301) = e + \314\201
+        assert_eq!(
+            decode_git_path("\"cafe\\314\\201.txt\""),
+            "cafe\u{0301}.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_combining_diaeresis() {
+        // Combining diaeresis: i + ̈ (U+0069 + U+0308) = i + \314\210
+        assert_eq!(
+            decode_git_path("\"nai\\314\\210ve.txt\""),
+            "nai\u{0308}ve.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_angstrom() {
+        // Å (A with ring above, U+00C5) = \303\205
+        assert_eq!(
+            decode_git_path("\"\\303\\205ngstr\\303\\266m.txt\""),
+            "Ångström.txt"
+        );
+    }
+
+    // =========================================================================
+    // Phase 9: Escape Sequence Edge Cases
+    // =========================================================================
+
+    #[test]
+    fn test_decode_git_path_incomplete_octal() {
+        // Incomplete octal at end of string
+        assert_eq!(decode_git_path("\"file\\34\""), "file\x1c");
+        assert_eq!(decode_git_path("\"file\\3\""), "file\x03");
+    }
+
+    #[test]
+    fn test_decode_git_path_invalid_octal() {
+        // Invalid octal digit (8 and 9 are not valid octal)
+        assert_eq!(
+            decode_git_path("\"file\\389.txt\""),
+            "file\x038\u{0039}.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_backslash_only() {
+        // Backslash at end without following character
+        assert_eq!(decode_git_path("\"file\\\""), "file\\");
+    }
+
+    #[test]
+    fn test_decode_git_path_mixed_escapes() {
+        // Mix of different escape types
+        assert_eq!(
+            decode_git_path("\"path\\nwith\\ttab\\\\and\\344\\270\\255.txt\""),
+            "path\nwith\ttab\\and中.txt"
+        );
+    }
+
+    #[test]
+    fn test_decode_git_path_empty_quoted() {
+        // Empty quoted string
+        assert_eq!(decode_git_path("\"\""), "");
+    }
+
+    #[test]
+    fn test_decode_git_path_unmatched_quotes() {
+        // Unmatched quotes - returned as-is
+        assert_eq!(decode_git_path("\"unmatched"), "\"unmatched");
+        assert_eq!(decode_git_path("unmatched\""), "unmatched\"");
+    }
+}
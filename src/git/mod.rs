@@ -1,5 +1,7 @@
+pub mod attestation_storage;
 pub mod cli_parser;
 pub mod diff_tree_to_tree;
+pub mod gitutil;
 pub mod refs;
 pub mod repository;
 
@@ -1,10 +1,17 @@
 use crate::authorship::authorship_log_serialization::{AUTHORSHIP_LOG_VERSION, AuthorshipLog};
+use crate::authorship::rebase_authorship::batch_read_blob_contents;
 use crate::authorship::working_log::Checkpoint;
 use crate::error::GitAiError;
+use crate::git::attestation_storage::{
+    AttestationStorage, STORAGE_CONFIG_KEY, configured_storage, lock_attestation_writes,
+    sidecar_blob_oids_for_commits, sidecar_list, sidecar_read, sidecar_remove, sidecar_write,
+};
 use crate::git::repository::{Repository, exec_git, exec_git_stdin};
 use crate::utils::debug_log;
+use rayon::prelude::*;
 use serde_json;
 use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 // Modern refspecs without force to enable proper merging
 pub const AI_AUTHORSHIP_REFNAME: &str = "ai";
@@ -15,6 +22,11 @@ pub fn notes_add(
     commit_sha: &str,
     note_content: &str,
 ) -> Result<(), GitAiError> {
+    let _lock = lock_attestation_writes(repo)?;
+    if configured_storage(repo) == AttestationStorage::Sidecar {
+        return sidecar_write(repo, commit_sha, note_content);
+    }
+
     let mut args = repo.global_args_for_exec();
     args.push("notes".to_string());
     args.push("--ref=ai".to_string());
@@ -29,6 +41,24 @@ pub fn notes_add(
     Ok(())
 }
 
+/// Remove the authorship note of a commit. A commit without a note is left as is.
+pub fn notes_remove(repo: &Repository, commit_sha: &str) -> Result<(), GitAiError> {
+    let _lock = lock_attestation_writes(repo)?;
+    if configured_storage(repo) == AttestationStorage::Sidecar {
+        return sidecar_remove(repo, commit_sha);
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.push("notes".to_string());
+    args.push("--ref=ai".to_string());
+    args.push("remove".to_string());
+    args.push("--ignore-missing".to_string());
+    args.push(commit_sha.to_string());
+
+    exec_git(&args)?;
+    Ok(())
+}
+
 fn notes_path_for_object(oid: &str) -> String {
     if oid.len() <= 2 {
         oid.to_string()
@@ -70,6 +100,9 @@ pub fn note_blob_oids_for_commits(
     if commit_shas.is_empty() {
         return Ok(HashMap::new());
     }
+    if configured_storage(repo) == AttestationStorage::Sidecar {
+        return sidecar_blob_oids_for_commits(repo, commit_shas);
+    }
 
     let mut args = repo.global_args_for_exec();
     args.push("cat-file".to_string());
@@ -107,6 +140,19 @@ pub fn note_blob_oids_for_commits(
 }
 
 pub fn notes_add_batch(repo: &Repository, entries: &[(String, String)]) -> Result<(), GitAiError> {
+    let _lock = lock_attestation_writes(repo)?;
+    if configured_storage(repo) == AttestationStorage::Sidecar {
+        for (commit_sha, note_content) in entries {
+            sidecar_write(repo, commit_sha, note_content)?;
+        }
+        return Ok(());
+    }
+    git_notes_add_batch(repo, entries)
+}
+
+/// Write many notes under refs/notes/ai in one fast-import commit, regardless of the
+/// configured storage backend.
+fn git_notes_add_batch(repo: &Repository, entries: &[(String, String)]) -> Result<(), GitAiError> {
     if entries.is_empty() {
         return Ok(());
     }
@@ -185,6 +231,17 @@ pub fn notes_add_blob_batch(
     if entries.is_empty() {
         return Ok(());
     }
+    let _lock = lock_attestation_writes(repo)?;
+    if configured_storage(repo) == AttestationStorage::Sidecar {
+        let blob_oids: Vec<String> = entries.iter().map(|(_, oid)| oid.clone()).collect();
+        let contents = batch_read_blob_contents(repo, &blob_oids)?;
+        for (commit_sha, blob_oid) in entries {
+            if let Some(content) = contents.get(blob_oid) {
+                sidecar_write(repo, commit_sha, content)?;
+            }
+        }
+        return Ok(());
+    }
 
     let mut args = repo.global_args_for_exec();
     args.push("rev-parse".to_string());
@@ -259,6 +316,18 @@ pub enum CommitAuthorship {
 pub fn get_commits_with_notes_from_list(
     repo: &Repository,
     commit_shas: &[String],
+) -> Result<Vec<CommitAuthorship>, GitAiError> {
+    get_commits_with_notes_from_list_with_progress(repo, commit_shas, 1, &|_, _| {})
+}
+
+/// Like `get_commits_with_notes_from_list`, reading the notes on `jobs` threads (0 for one per
+/// CPU) and calling `progress(done, total)` after each commit. With several jobs, commits
+/// finish out of order, so `done` only counts them; the result keeps the order of `commit_shas`.
+pub fn get_commits_with_notes_from_list_with_progress(
+    repo: &Repository,
+    commit_shas: &[String],
+    jobs: usize,
+    progress: &(dyn Fn(usize, usize) + Sync),
 ) -> Result<Vec<CommitAuthorship>, GitAiError> {
     if commit_shas.is_empty() {
         return Ok(Vec::new());
@@ -301,34 +370,48 @@ pub fn get_commits_with_notes_from_list(
         }
     }
 
-    // Build the result Vec
-    let mut result = Vec::new();
-    for sha in commit_shas {
+    let total = commit_shas.len();
+    let done = AtomicUsize::new(0);
+    let authorship_for = |sha: &String| {
         let git_author = commit_authors
             .get(sha)
             .cloned()
             .unwrap_or_else(|| "Unknown".to_string());
 
         // Check if this commit has a note by trying to show it
-        if let Some(authorship_log) = get_authorship(repo, sha) {
-            result.push(CommitAuthorship::Log {
+        let entry = if let Some(authorship_log) = get_authorship(repo, sha) {
+            CommitAuthorship::Log {
                 sha: sha.clone(),
                 git_author,
                 authorship_log,
-            });
+            }
         } else {
-            result.push(CommitAuthorship::NoLog {
+            CommitAuthorship::NoLog {
                 sha: sha.clone(),
                 git_author,
-            });
-        }
+            }
+        };
+        progress(done.fetch_add(1, Ordering::Relaxed) + 1, total);
+        entry
+    };
+
+    if jobs == 1 || total <= 1 {
+        return Ok(commit_shas.iter().map(authorship_for).collect());
     }
 
-    Ok(result)
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map_err(|e| GitAiError::Generic(format!("Failed to start notes thread pool: {}", e)))?;
+    Ok(pool.install(|| commit_shas.par_iter().map(authorship_for).collect()))
 }
 
 // Show an authorship note and return its JSON content if found, or None if it doesn't exist.
 pub fn show_authorship_note(repo: &Repository, commit_sha: &str) -> Option<String> {
+    if configured_storage(repo) == AttestationStorage::Sidecar {
+        return sidecar_read(repo, commit_sha);
+    }
+
     let mut args = repo.global_args_for_exec();
     args.push("notes".to_string());
     args.push("--ref=ai".to_string());
@@ -358,6 +441,23 @@ pub fn commits_with_authorship_notes(
         .collect())
 }
 
+/// Return the commits of `commit_shas` that have no authorship note, in order. Merge commits
+/// are left out since their lines were attested on the branches they merge.
+pub fn commits_missing_authorship_notes(
+    repo: &Repository,
+    commit_shas: &[String],
+) -> Result<Vec<String>, GitAiError> {
+    let attested = commits_with_authorship_notes(repo, commit_shas)?;
+    let mut missing = Vec::new();
+    for sha in commit_shas {
+        if attested.contains(sha) || repo.find_commit(sha.clone())?.parent_count()? > 1 {
+            continue;
+        }
+        missing.push(sha.clone());
+    }
+    Ok(missing)
+}
+
 // Show an authorship note and return its JSON content if found, or None if it doesn't exist.
 pub fn get_authorship(repo: &Repository, commit_sha: &str) -> Option<AuthorshipLog> {
     let content = show_authorship_note(repo, commit_sha)?;
@@ -373,7 +473,7 @@ pub fn get_reference_as_working_log(
     commit_sha: &str,
 ) -> Result<Vec<Checkpoint>, GitAiError> {
     let content = show_authorship_note(repo, commit_sha)
-        .ok_or_else(|| GitAiError::Generic("No authorship note found".to_string()))?;
+        .ok_or_else(|| GitAiError::MissingAttestation(commit_sha.to_string()))?;
     let working_log = serde_json::from_str(&content)?;
     Ok(working_log)
 }
@@ -383,7 +483,7 @@ pub fn get_reference_as_authorship_log_v3(
     commit_sha: &str,
 ) -> Result<AuthorshipLog, GitAiError> {
     let content = show_authorship_note(repo, commit_sha)
-        .ok_or_else(|| GitAiError::Generic("No authorship note found".to_string()))?;
+        .ok_or_else(|| GitAiError::MissingAttestation(commit_sha.to_string()))?;
 
     // Try to deserialize as AuthorshipLog
     let mut authorship_log = match AuthorshipLog::deserialize_from_string(&content) {
@@ -451,6 +551,7 @@ pub fn ref_exists(repo: &Repository, ref_name: &str) -> bool {
 /// Merge notes from a source ref into refs/notes/ai
 /// Uses the 'ours' strategy to combine notes without data loss
 pub fn merge_notes_from_ref(repo: &Repository, source_ref: &str) -> Result<(), GitAiError> {
+    let _lock = lock_attestation_writes(repo)?;
     let mut args = repo.global_args_for_exec();
     args.push("notes".to_string());
     args.push(format!("--ref={}", AI_AUTHORSHIP_REFNAME));
@@ -483,6 +584,18 @@ pub fn copy_ref(repo: &Repository, source_ref: &str, dest_ref: &str) -> Result<(
 /// Search AI notes for a pattern and return matching commit SHAs ordered by commit date (newest first)
 /// Uses git grep to search through refs/notes/ai
 pub fn grep_ai_notes(repo: &Repository, pattern: &str) -> Result<Vec<String>, GitAiError> {
+    if configured_storage(repo) == AttestationStorage::Sidecar {
+        let re = regex::Regex::new(pattern)
+            .or_else(|_| regex::Regex::new(&regex::escape(pattern)))
+            .map_err(|e| GitAiError::Generic(format!("Invalid pattern: {}", e)))?;
+        let shas: Vec<String> = sidecar_list(repo)?
+            .into_iter()
+            .filter(|(_, content)| re.is_match(content))
+            .map(|(sha, _)| sha)
+            .collect();
+        return sort_by_commit_date(repo, shas);
+    }
+
     let mut args = repo.global_args_for_exec();
     args.push("--no-pager".to_string());
     args.push("grep".to_string());
@@ -508,15 +621,18 @@ pub fn grep_ai_notes(repo: &Repository, pattern: &str) -> Result<Vec<String>, Gi
         }
     }
 
-    // If we have multiple results, sort by commit date (newest first)
+    sort_by_commit_date(repo, shas.into_iter().collect())
+}
+
+/// Order commit SHAs by commit date, newest first
+fn sort_by_commit_date(repo: &Repository, shas: Vec<String>) -> Result<Vec<String>, GitAiError> {
     if shas.len() > 1 {
-        let sha_vec: Vec<String> = shas.into_iter().collect();
         let mut args = repo.global_args_for_exec();
         args.push("log".to_string());
         args.push("--format=%H".to_string());
         args.push("--date-order".to_string());
         args.push("--no-walk".to_string());
-        for sha in &sha_vec {
+        for sha in &shas {
             args.push(sha.clone());
         }
 
@@ -526,10 +642,77 @@ pub fn grep_ai_notes(repo: &Repository, pattern: &str) -> Result<Vec<String>, Gi
 
         Ok(stdout.lines().map(|s| s.to_string()).collect())
     } else {
-        Ok(shas.into_iter().collect())
+        Ok(shas)
     }
 }
 
+/// List every (commit_sha, note_content) pair under refs/notes/ai
+fn git_notes_list(repo: &Repository) -> Result<Vec<(String, String)>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("notes".to_string());
+    args.push(format!("--ref={}", AI_AUTHORSHIP_REFNAME));
+    args.push("list".to_string());
+
+    let output = match exec_git(&args) {
+        Ok(output) => output,
+        Err(GitAiError::GitCliError { code: Some(1), .. }) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let stdout = String::from_utf8(output.stdout)?;
+
+    // Parse notes list output: "<note_blob_sha> <commit_sha>"
+    let pairs: Vec<(String, String)> = stdout
+        .lines()
+        .filter_map(|line| {
+            let (blob_oid, commit_sha) = line.split_once(' ')?;
+            Some((commit_sha.trim().to_string(), blob_oid.to_string()))
+        })
+        .collect();
+    let blob_oids: Vec<String> = pairs.iter().map(|(_, oid)| oid.clone()).collect();
+    let contents = batch_read_blob_contents(repo, &blob_oids)?;
+
+    Ok(pairs
+        .into_iter()
+        .filter_map(|(commit_sha, blob_oid)| {
+            contents
+                .get(&blob_oid)
+                .map(|content| (commit_sha, content.trim().to_string()))
+        })
+        .collect())
+}
+
+/// Copy every authorship log from the other backend into `target` and point
+/// `git-ai.storage` at it. Logs already in `target` are overwritten by the copied ones; the
+/// source is left untouched so the switch can be undone. Returns the number of logs copied.
+pub fn migrate_attestation_storage(
+    repo: &Repository,
+    target: AttestationStorage,
+) -> Result<usize, GitAiError> {
+    let _lock = lock_attestation_writes(repo)?;
+    let entries = match target {
+        AttestationStorage::Notes => sidecar_list(repo)?,
+        AttestationStorage::Sidecar => git_notes_list(repo)?,
+    };
+
+    match target {
+        AttestationStorage::Notes => git_notes_add_batch(repo, &entries)?,
+        AttestationStorage::Sidecar => {
+            for (commit_sha, content) in &entries {
+                sidecar_write(repo, commit_sha, content)?;
+            }
+        }
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.push("config".to_string());
+    args.push("--local".to_string());
+    args.push(STORAGE_CONFIG_KEY.to_string());
+    args.push(target.as_str().to_string());
+    exec_git(&args)?;
+
+    Ok(entries.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
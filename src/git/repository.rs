@@ -9,6 +9,7 @@ use crate::git::repo_storage::RepoStorage;
 use crate::git::rewrite_log::RewriteLogEvent;
 use crate::git::status::MAX_PATHSPEC_ARGS;
 use crate::git::sync_authorship::{fetch_authorship_notes, push_authorship_notes};
+use crate::utils::debug_log;
 #[cfg(windows)]
 use crate::utils::is_interactive_terminal;
 
@@ -17,6 +18,7 @@ use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
 #[cfg(windows)]
 use crate::utils::CREATE_NO_WINDOW;
@@ -352,7 +354,8 @@ impl<'a> CommitRange<'a> {
 
     /// Create a new CommitRange with automatic refname inference.
     /// If refname is None, tries to find a single ref pointing to end_oid.
-    /// If exactly one ref is found, uses that. Otherwise falls back to current HEAD.
+    /// If exactly one ref is found, uses that. Otherwise falls back to current HEAD, or to
+    /// end_oid itself when HEAD (for instance a detached checkout) cannot reach it.
     pub fn new_infer_refname(
         repo: &'a Repository,
         start_oid: String,
@@ -390,10 +393,22 @@ impl<'a> CommitRange<'a> {
                 if refs.len() == 1 {
                     refs[0].clone()
                 } else {
-                    // Fall back to current HEAD
-                    match repo.head() {
+                    // Fall back to current HEAD, which is plain "HEAD" when detached
+                    let head = match repo.head() {
                         Ok(head_ref) => head_ref.name().unwrap_or("HEAD").to_string(),
                         Err(_) => "HEAD".to_string(),
+                    };
+                    // A detached checkout (as in CI) may sit off the range's history, and then
+                    // the range can only be checked against its own end commit
+                    let mut args = repo.global_args_for_exec();
+                    args.push("merge-base".to_string());
+                    args.push("--is-ancestor".to_string());
+                    args.push(resolved_end.clone());
+                    args.push(head.clone());
+                    if exec_git(&args).is_ok() {
+                        head
+                    } else {
+                        resolved_end.clone()
                     }
                 }
             }
@@ -490,6 +505,29 @@ impl<'a> CommitRange<'a> {
         }
     }
 
+    /// Like `all_commits`, with extra `git rev-list` options such as `--first-parent` or
+    /// `--no-merges` selecting which commits of the range are walked.
+    pub fn commits_with_rev_list_args(&self, rev_list_args: &[&str]) -> Vec<String> {
+        if rev_list_args.is_empty() || self.start_oid == self.end_oid {
+            return self.all_commits();
+        }
+
+        let mut args = self.repo.global_args_for_exec();
+        args.push("rev-list".to_string());
+        args.extend(rev_list_args.iter().map(|arg| arg.to_string()));
+        args.push(format!("{}..{}", self.start_oid, self.end_oid));
+
+        match exec_git(&args) {
+            Ok(output) => String::from_utf8(output.stdout)
+                .unwrap_or_default()
+                .lines()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
     pub fn all_commits(&self) -> Vec<String> {
         let mut commits = Vec::new();
         let itt = self.clone().into_iter();
@@ -1226,6 +1264,16 @@ impl Repository {
         self.git_dir.as_path()
     }
 
+    /// The git directory shared by all worktrees, as `git rev-parse --git-common-dir` reports
+    /// it. A linked worktree's git directory names it in its `commondir` file; every other
+    /// repository shares its own.
+    pub fn common_dir(&self) -> PathBuf {
+        match std::fs::read_to_string(self.git_dir.join("commondir")) {
+            Ok(contents) if !contents.trim().is_empty() => self.git_dir.join(contents.trim()),
+            _ => self.git_dir.clone(),
+        }
+    }
+
     // Get the path of the working directory for this repository.
     // If this repository is bare, then None is returned.
     pub fn workdir(&self) -> Result<PathBuf, GitAiError> {
@@ -1243,6 +1291,25 @@ impl Repository {
         Ok(value.trim() == "true")
     }
 
+    /// Returns true when HEAD has no commit yet, as in a freshly initialized repository.
+    pub fn head_is_unborn(&self) -> Result<bool, GitAiError> {
+        let mut args = self.global_args_for_exec();
+        args.push("rev-parse".to_string());
+        args.push("--verify".to_string());
+        args.push("--quiet".to_string());
+        args.push("HEAD^{commit}".to_string());
+        match exec_git(&args) {
+            Ok(_) => Ok(false),
+            // --quiet makes a missing HEAD exit 1 without printing anything
+            Err(GitAiError::GitCliError {
+                code: Some(1),
+                stderr,
+                ..
+            }) if stderr.trim().is_empty() => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Get the canonical (absolute, resolved) path of the working directory
     /// On Windows, this uses the \\?\ UNC prefix format for reliable path comparisons
     #[allow(dead_code)]
@@ -1995,6 +2062,56 @@ impl Repository {
         Ok(files)
     }
 
+    /// Paths the diff between two revisions leaves as symlinks (git mode `120000`) or gitlinks
+    /// (mode `160000`, submodule commits), plus links it deletes. A link's "content" is just a
+    /// target path or a commit id, so these are not counted as lines.
+    pub fn diff_link_paths(
+        &self,
+        from_ref: &str,
+        to_ref: &str,
+    ) -> Result<DiffLinkPaths, GitAiError> {
+        let mut args = self.global_args_for_exec();
+        args.push("diff-tree".to_string());
+        args.push("-r".to_string());
+        args.push("-z".to_string());
+        args.push("--no-renames".to_string());
+        args.push(from_ref.to_string());
+        args.push(to_ref.to_string());
+
+        let output = exec_git(&args)?;
+
+        // With -z, each entry is ":<old mode> <new mode> <old oid> <new oid> <status>\0<path>\0"
+        let mut links = DiffLinkPaths::default();
+        let mut fields = output.stdout.split(|&b| b == 0);
+        while let Some(meta) = fields.next() {
+            let Some(path) = fields.next() else {
+                break;
+            };
+            let meta = String::from_utf8_lossy(meta);
+            let mut modes = meta.trim_start_matches(':').split(' ');
+            let (Some(old_mode), Some(new_mode)) = (modes.next(), modes.next()) else {
+                continue;
+            };
+            let mode = if new_mode == "000000" {
+                old_mode
+            } else {
+                new_mode
+            };
+            let path = String::from_utf8_lossy(path).to_string();
+            match mode {
+                SYMLINK_MODE => {
+                    links.symlinks.insert(path);
+                }
+                GITLINK_MODE => {
+                    links.gitlinks.insert(path);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(links)
+    }
+
     /// Get added line ranges from git diff between two commits
     /// Returns a HashMap of file paths to vectors of added line numbers
     ///
@@ -2035,7 +2152,7 @@ impl Repository {
             false
         };
 
-        let output = exec_git_with_profile(&args, InternalGitProfile::PatchParse)?;
+        let output = exec_git_diff(&args, InternalGitProfile::PatchParse)?;
         let diff_output = String::from_utf8_lossy(&output.stdout);
 
         let mut result = parse_diff_added_lines(&diff_output)?;
@@ -2047,6 +2164,88 @@ impl Repository {
         Ok(result)
     }
 
+    /// Like `diff_added_lines`, but with git's rename detection enabled. Lines carried over
+    /// from a renamed file (`R100`, `R095`, ...) are not reported as added under the new path.
+    /// With `ignore_whitespace`, whitespace-only changes are not reported (`git diff -w`).
+    /// `diff_algorithm` overrides the repository's `diff.algorithm`.
+    pub fn diff_added_lines_following_renames(
+        &self,
+        from_ref: &str,
+        to_ref: &str,
+        ignore_whitespace: bool,
+        diff_algorithm: Option<DiffAlgorithm>,
+    ) -> Result<HashMap<String, Vec<u32>>, GitAiError> {
+        let mut args = self.global_args_for_exec();
+        args.push("diff".to_string());
+        args.push("-U0".to_string()); // Zero context lines
+        args.push("--no-color".to_string());
+        args.push("--find-renames".to_string());
+        if ignore_whitespace {
+            args.push("-w".to_string());
+        }
+        if let Some(algorithm) = diff_algorithm {
+            args.push(algorithm.git_arg());
+        }
+        args.push(from_ref.to_string());
+        args.push(to_ref.to_string());
+
+        let output = exec_git_diff(&args, InternalGitProfile::PatchParse)?;
+        let diff_output = String::from_utf8_lossy(&output.stdout);
+
+        parse_diff_added_lines(&diff_output)
+    }
+
+    /// Get deleted line numbers between two refs, in `from_ref` coordinates and keyed by the
+    /// path in `from_ref`. Renames are followed so moved lines are not reported as deleted.
+    pub fn diff_deleted_lines(
+        &self,
+        from_ref: &str,
+        to_ref: &str,
+        ignore_whitespace: bool,
+        diff_algorithm: Option<DiffAlgorithm>,
+    ) -> Result<HashMap<String, Vec<u32>>, GitAiError> {
+        let mut args = self.global_args_for_exec();
+        args.push("diff".to_string());
+        args.push("-U0".to_string()); // Zero context lines
+        args.push("--no-color".to_string());
+        args.push("--find-renames".to_string());
+        if ignore_whitespace {
+            args.push("-w".to_string());
+        }
+        if let Some(algorithm) = diff_algorithm {
+            args.push(algorithm.git_arg());
+        }
+        args.push(from_ref.to_string());
+        args.push(to_ref.to_string());
+
+        let output = exec_git_diff(&args, InternalGitProfile::PatchParse)?;
+        let diff_output = String::from_utf8_lossy(&output.stdout);
+
+        Ok(parse_diff_deleted_lines(&diff_output))
+    }
+
+    /// Deleted and added lines, with their content, of each file changed between two refs.
+    /// Line numbers are in `from_ref` coordinates for deleted lines and `to_ref` coordinates
+    /// for added lines. Added and deleted files only have lines on one side.
+    pub fn diff_line_changes(
+        &self,
+        from_ref: &str,
+        to_ref: &str,
+    ) -> Result<HashMap<String, FileLineChanges>, GitAiError> {
+        let mut args = self.global_args_for_exec();
+        args.push("diff".to_string());
+        args.push("-U0".to_string()); // Zero context lines
+        args.push("--no-color".to_string());
+        args.push("--no-renames".to_string());
+        args.push(from_ref.to_string());
+        args.push(to_ref.to_string());
+
+        let output = exec_git_diff(&args, InternalGitProfile::PatchParse)?;
+        let diff_output = String::from_utf8_lossy(&output.stdout);
+
+        Ok(parse_diff_line_changes(&diff_output))
+    }
+
     /// Get list of changed files between two refs using `git diff --name-only`
     /// Returns a Vec of file paths that differ between the two refs
     pub fn diff_changed_files(
@@ -2062,7 +2261,7 @@ impl Repository {
         args.push(from_ref.to_string());
         args.push(to_ref.to_string());
 
-        let output = exec_git_with_profile(&args, InternalGitProfile::RawDiffParse)?;
+        let output = exec_git_diff(&args, InternalGitProfile::RawDiffParse)?;
 
         // With -z, output is NUL-separated. The output may contain a trailing NUL.
         let files: Vec<String> = output
@@ -2075,6 +2274,53 @@ impl Repository {
         Ok(files)
     }
 
+    /// Files `to_ref` adds as copies of files in `from_ref`, as `(source path, copy path)`
+    /// pairs. Uses plain `-C`, so only files the diff also modifies are considered as sources;
+    /// `--find-copies-harder` would compare each added file against the whole parent tree.
+    pub fn diff_copied_files(
+        &self,
+        from_ref: &str,
+        to_ref: &str,
+    ) -> Result<Vec<(String, String)>, GitAiError> {
+        let mut args = self.global_args_for_exec();
+        args.push("diff-tree".to_string());
+        args.push("-r".to_string());
+        args.push("-z".to_string());
+        args.push("-C".to_string());
+        args.push(from_ref.to_string());
+        args.push(to_ref.to_string());
+
+        let output = exec_git(&args)?;
+
+        // With -z, each entry is ":<modes> <oids> <status>\0<path>\0", and renames and
+        // copies carry a second path: ":<modes> <oids> C<score>\0<source>\0<copy>\0"
+        let mut copies = Vec::new();
+        let mut fields = output.stdout.split(|&b| b == 0);
+        while let Some(meta) = fields.next() {
+            let meta = String::from_utf8_lossy(meta);
+            let Some(status) = meta.split(' ').next_back() else {
+                continue;
+            };
+            let Some(path) = fields.next() else {
+                break;
+            };
+            if !(status.starts_with('C') || status.starts_with('R')) {
+                continue;
+            }
+            let Some(copy_path) = fields.next() else {
+                break;
+            };
+            if status.starts_with('C') {
+                copies.push((
+                    String::from_utf8_lossy(path).to_string(),
+                    String::from_utf8_lossy(copy_path).to_string(),
+                ));
+            }
+        }
+
+        Ok(copies)
+    }
+
     /// Get added line ranges from git diff between a commit and the working directory
     /// Returns a HashMap of file paths to vectors of added line numbers
     ///
@@ -2112,7 +2358,7 @@ impl Repository {
             false
         };
 
-        let output = exec_git_with_profile(&args, InternalGitProfile::PatchParse)?;
+        let output = exec_git_diff(&args, InternalGitProfile::PatchParse)?;
         let diff_output = String::from_utf8_lossy(&output.stdout);
 
         let mut result = parse_diff_added_lines(&diff_output)?;
@@ -2162,7 +2408,7 @@ impl Repository {
             false
         };
 
-        let output = exec_git_with_profile(&args, InternalGitProfile::PatchParse)?;
+        let output = exec_git_diff(&args, InternalGitProfile::PatchParse)?;
         let diff_output = String::from_utf8_lossy(&output.stdout);
 
         let (mut all_added, mut pure_insertions) =
@@ -2176,6 +2422,40 @@ impl Repository {
         Ok((all_added, pure_insertions))
     }
 
+    /// Get added line numbers from the unstaged changes (index to working directory), with
+    /// every line of each untracked, non-ignored file counted as added.
+    pub fn diff_unstaged_added_lines(&self) -> Result<HashMap<String, Vec<u32>>, GitAiError> {
+        let mut args = self.global_args_for_exec();
+        args.push("diff".to_string());
+        args.push("-U0".to_string()); // Zero context lines
+        args.push("--no-color".to_string());
+        args.push("--no-renames".to_string());
+
+        let output = exec_git_diff(&args, InternalGitProfile::PatchParse)?;
+        let mut result = parse_diff_added_lines(&String::from_utf8_lossy(&output.stdout))?;
+
+        let mut args = self.global_args_for_exec();
+        args.push("ls-files".to_string());
+        args.push("--others".to_string());
+        args.push("--exclude-standard".to_string());
+        args.push("-z".to_string());
+        let output = exec_git(&args)?;
+        for path in output
+            .stdout
+            .split(|&b| b == 0)
+            .filter(|bytes| !bytes.is_empty())
+            .filter_map(|bytes| String::from_utf8(bytes.to_vec()).ok())
+        {
+            let Ok(content) = std::fs::read(self.workdir.join(&path)) else {
+                continue;
+            };
+            let line_count = String::from_utf8_lossy(&content).lines().count() as u32;
+            result.insert(path, (1..=line_count).collect());
+        }
+
+        Ok(result)
+    }
+
     pub fn fetch_branch(&self, branch_name: &str, remote_name: &str) -> Result<(), GitAiError> {
         let mut args = self.global_args_for_exec();
         args.push("fetch".to_string());
@@ -2195,7 +2475,14 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
     rev_parse_args.push("--is-bare-repository".to_string());
     rev_parse_args.push("--git-dir".to_string());
 
-    let rev_parse_output = exec_git(&rev_parse_args)?;
+    let rev_parse_output = exec_git(&rev_parse_args).map_err(|e| match e {
+        GitAiError::GitCliError { ref stderr, .. } if stderr.contains("not a git repository") => {
+            resolve_command_base_dir(global_args)
+                .map(GitAiError::NotAGitRepo)
+                .unwrap_or(e)
+        }
+        e => e,
+    })?;
     let rev_parse_stdout = String::from_utf8(rev_parse_output.stdout)?;
     let mut lines = rev_parse_stdout
         .lines()
@@ -2259,11 +2546,11 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
 
     // Ensure all internal git commands use a stable repository root consistently.
     let mut normalized_global_args = global_args.to_owned();
-    let command_root = if is_bare {
-        git_dir.display().to_string()
-    } else {
-        workdir.display().to_string()
-    };
+    let command_root_path = if is_bare { &git_dir } else { &workdir };
+    let command_root = command_root_path
+        .to_str()
+        .ok_or_else(|| GitAiError::Utf8Path(command_root_path.clone()))?
+        .to_string();
 
     if normalized_global_args.is_empty() {
         normalized_global_args = vec!["-C".to_string(), command_root];
@@ -2483,11 +2770,58 @@ pub fn exec_git(args: &[String]) -> Result<Output, GitAiError> {
     exec_git_with_profile(args, InternalGitProfile::General)
 }
 
+/// Run a `git diff` command, reporting a failure as `GitAiError::DiffFailed`
+fn exec_git_diff(args: &[String], profile: InternalGitProfile) -> Result<Output, GitAiError> {
+    exec_git_with_profile(args, profile).map_err(|e| match e {
+        GitAiError::GitCliError { stderr, .. } => GitAiError::DiffFailed(stderr.trim().to_string()),
+        e => e,
+    })
+}
+
+/// Times a git command is run when it keeps failing on a lock another git process holds
+const GIT_LOCK_ATTEMPTS: u32 = 5;
+/// Wait before the first retry, doubled before each later one
+const GIT_LOCK_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+/// Whether `stderr` is git failing to take a lock file (`index.lock`, a ref or config lock)
+/// that another git process holds. Git gives up before changing anything in that case, so the
+/// command can be run again once the other process is done.
+fn is_transient_git_error(stderr: &str) -> bool {
+    stderr.contains(".lock") && stderr.contains("File exists")
+}
+
+/// Run a git command through `run`, running it again with exponential backoff while it fails
+/// on lock contention. Other failures and the last attempt's result are returned as they are.
+fn retry_transient_git_errors(
+    mut run: impl FnMut() -> Result<Output, GitAiError>,
+) -> Result<Output, GitAiError> {
+    let mut delay = GIT_LOCK_RETRY_DELAY;
+    for _ in 1..GIT_LOCK_ATTEMPTS {
+        match run() {
+            Err(GitAiError::GitCliError { ref stderr, .. }) if is_transient_git_error(stderr) => {
+                debug_log(&format!(
+                    "git hit lock contention, retrying in {:?}: {}",
+                    delay,
+                    stderr.trim()
+                ));
+                std::thread::sleep(delay);
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+    run()
+}
+
 /// Helper to execute a git command with an explicit internal profile.
 pub fn exec_git_with_profile(
     args: &[String],
     profile: InternalGitProfile,
 ) -> Result<Output, GitAiError> {
+    retry_transient_git_errors(|| exec_git_once(args, profile))
+}
+
+fn exec_git_once(args: &[String], profile: InternalGitProfile) -> Result<Output, GitAiError> {
     // TODO Make sure to handle process signals, etc.
     let effective_args =
         args_with_internal_git_profile(&args_with_disabled_hooks_if_needed(args), profile);
@@ -2530,47 +2864,7 @@ pub fn exec_git_stdin_with_profile(
     stdin_data: &[u8],
     profile: InternalGitProfile,
 ) -> Result<Output, GitAiError> {
-    // TODO Make sure to handle process signals, etc.
-    let effective_args =
-        args_with_internal_git_profile(&args_with_disabled_hooks_if_needed(args), profile);
-    let mut cmd = Command::new(config::Config::get().git_cmd());
-    cmd.args(&effective_args)
-        .stdin(std::process::Stdio::piped())
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped());
-    sanitize_git_config_env(&mut cmd);
-    cmd.env_remove("GIT_EXTERNAL_DIFF");
-    cmd.env_remove("GIT_DIFF_OPTS");
-
-    #[cfg(windows)]
-    {
-        if !is_interactive_terminal() {
-            cmd.creation_flags(CREATE_NO_WINDOW);
-        }
-    }
-
-    let mut child = cmd.spawn().map_err(GitAiError::IoError)?;
-
-    if let Some(mut stdin) = child.stdin.take() {
-        use std::io::Write;
-        if let Err(e) = stdin.write_all(stdin_data) {
-            return Err(GitAiError::IoError(e));
-        }
-    }
-
-    let output = child.wait_with_output().map_err(GitAiError::IoError)?;
-
-    if !output.status.success() {
-        let code = output.status.code();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        return Err(GitAiError::GitCliError {
-            code,
-            stderr,
-            args: effective_args,
-        });
-    }
-
-    Ok(output)
+    retry_transient_git_errors(|| exec_git_stdin_with_env_once(args, &[], stdin_data, profile))
 }
 
 /// Helper to execute a git command with data provided on stdin and additional environment variables
@@ -2590,6 +2884,15 @@ pub fn exec_git_stdin_with_env_with_profile(
     env: &[(String, String)],
     stdin_data: &[u8],
     profile: InternalGitProfile,
+) -> Result<Output, GitAiError> {
+    retry_transient_git_errors(|| exec_git_stdin_with_env_once(args, env, stdin_data, profile))
+}
+
+fn exec_git_stdin_with_env_once(
+    args: &[String],
+    env: &[(String, String)],
+    stdin_data: &[u8],
+    profile: InternalGitProfile,
 ) -> Result<Output, GitAiError> {
     // TODO Make sure to handle process signals, etc.
     let effective_args =
@@ -2746,8 +3049,204 @@ fn parse_diff_added_lines_with_insertions(
     Ok((all_lines, insertion_lines))
 }
 
+/// Parses unified diff output into deleted line numbers per old file path.
+/// File headers are only recognised between `diff --git` and the first hunk, so deleted
+/// content lines that happen to start with `-- ` are not mistaken for headers.
+fn parse_diff_deleted_lines(diff_output: &str) -> HashMap<String, Vec<u32>> {
+    let mut result: HashMap<String, Vec<u32>> = HashMap::new();
+    let mut current_file: Option<String> = None;
+    let mut in_header = false;
+
+    for line in diff_output.lines() {
+        if line.starts_with("diff --git ") {
+            in_header = true;
+            current_file = None;
+        } else if in_header && let Some(raw) = line.strip_prefix("--- ") {
+            current_file = if raw.trim_end() == "/dev/null" {
+                None
+            } else {
+                Some(normalize_diff_path_token(raw))
+            };
+        } else if line.starts_with("@@ ") {
+            in_header = false;
+            if let Some(ref file) = current_file
+                && let Some(deleted_lines) = parse_hunk_header_deleted_lines(line)
+            {
+                result
+                    .entry(file.clone())
+                    .or_default()
+                    .extend(deleted_lines);
+            }
+        }
+    }
+
+    for lines in result.values_mut() {
+        lines.sort_unstable();
+        lines.dedup();
+    }
+
+    result
+}
+
+/// Git tree entry mode of a symlink
+pub const SYMLINK_MODE: &str = "120000";
+/// Git tree entry mode of a gitlink, the commit a submodule points at
+pub const GITLINK_MODE: &str = "160000";
+
+/// Line diff algorithm passed to git as `--diff-algorithm`. Different algorithms can place
+/// hunk boundaries differently, which moves the lines a diff reports as added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffAlgorithm {
+    Myers,
+    Minimal,
+    Patience,
+    Histogram,
+}
+
+impl DiffAlgorithm {
+    /// Parse a name as git's `diff.algorithm` accepts it.
+    pub fn from_name(name: &str) -> Option<DiffAlgorithm> {
+        match name {
+            "myers" | "default" => Some(DiffAlgorithm::Myers),
+            "minimal" => Some(DiffAlgorithm::Minimal),
+            "patience" => Some(DiffAlgorithm::Patience),
+            "histogram" => Some(DiffAlgorithm::Histogram),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiffAlgorithm::Myers => "myers",
+            DiffAlgorithm::Minimal => "minimal",
+            DiffAlgorithm::Patience => "patience",
+            DiffAlgorithm::Histogram => "histogram",
+        }
+    }
+
+    /// The git option selecting this algorithm.
+    pub fn git_arg(self) -> String {
+        format!("--diff-algorithm={}", self.as_str())
+    }
+}
+
+/// Symlinks and gitlinks changed by a diff. See `Repository::diff_link_paths`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffLinkPaths {
+    pub symlinks: HashSet<String>,
+    pub gitlinks: HashSet<String>,
+}
+
+impl DiffLinkPaths {
+    pub fn contains(&self, path: &str) -> bool {
+        self.symlinks.contains(path) || self.gitlinks.contains(path)
+    }
+}
+
+/// Deleted and added lines of one file, as `(line number, content)` pairs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileLineChanges {
+    pub deleted: Vec<(u32, String)>,
+    pub added: Vec<(u32, String)>,
+}
+
+/// Parses zero-context unified diff output into the deleted and added lines of each file.
+/// Headers are only recognised before the first hunk of a file, like in
+/// `parse_diff_deleted_lines`.
+fn parse_diff_line_changes(diff_output: &str) -> HashMap<String, FileLineChanges> {
+    let mut result: HashMap<String, FileLineChanges> = HashMap::new();
+    let mut old_file: Option<String> = None;
+    let mut current_file: Option<String> = None;
+    let mut in_header = false;
+    let mut old_line = 0;
+    let mut new_line = 0;
+
+    for line in diff_output.lines() {
+        if line.starts_with("diff --git ") {
+            in_header = true;
+            old_file = None;
+            current_file = None;
+        } else if in_header && let Some(raw) = line.strip_prefix("--- ") {
+            old_file = if raw.trim_end() == "/dev/null" {
+                None
+            } else {
+                Some(normalize_diff_path_token(raw))
+            };
+        } else if in_header && let Some(path_opt) = parse_new_file_path_from_plus_header_line(line)
+        {
+            // A deleted file has no new path, so its lines are filed under the old one
+            current_file = path_opt.or_else(|| old_file.clone());
+        } else if line.starts_with("@@ ") {
+            in_header = false;
+            if let Some((old_start, new_start)) = parse_hunk_header_starts(line) {
+                old_line = old_start;
+                new_line = new_start;
+            }
+        } else if in_header {
+            continue;
+        } else if let Some(ref file) = current_file {
+            if let Some(content) = line.strip_prefix('-') {
+                result
+                    .entry(file.clone())
+                    .or_default()
+                    .deleted
+                    .push((old_line, content.to_string()));
+                old_line += 1;
+            } else if let Some(content) = line.strip_prefix('+') {
+                result
+                    .entry(file.clone())
+                    .or_default()
+                    .added
+                    .push((new_line, content.to_string()));
+                new_line += 1;
+            }
+        }
+    }
+
+    result
+}
+
+/// Parse the start lines of both sides of a hunk header (`@@ -old_start,n +new_start,m @@`).
+fn parse_hunk_header_starts(line: &str) -> Option<(u32, u32)> {
+    let mut ranges = line.split("@@").nth(1)?.split_whitespace();
+    let old_start = ranges
+        .next()?
+        .strip_prefix('-')?
+        .split(',')
+        .next()?
+        .parse()
+        .ok()?;
+    let new_start = ranges
+        .next()?
+        .strip_prefix('+')?
+        .split(',')
+        .next()?
+        .parse()
+        .ok()?;
+    Some((old_start, new_start))
+}
+
+/// Parse the old-file side of a hunk header (`@@ -old_start,old_count ...`) into the
+/// line numbers that were removed.
+fn parse_hunk_header_deleted_lines(line: &str) -> Option<Vec<u32>> {
+    let hunk_info = line.split("@@").nth(1)?.trim();
+    let old_range = hunk_info
+        .split_whitespace()
+        .find(|r| r.starts_with('-'))?
+        .trim_start_matches('-');
+
+    let mut old_parts = old_range.split(',');
+    let start: u32 = old_parts.next()?.parse().ok()?;
+    let count: u32 = match old_parts.next() {
+        Some(count) => count.parse().ok()?,
+        None => 1,
+    };
+
+    Some((start..start + count).collect())
+}
+
 fn normalize_diff_path_token(path: &str) -> String {
-    let unescaped = crate::utils::unescape_git_path(path.trim_end());
+    let unescaped = crate::git::gitutil::decode_git_path(path.trim_end());
     let prefixes = ["a/", "b/", "c/", "w/", "i/", "o/"];
     for prefix in prefixes {
         if let Some(stripped) = unescaped.strip_prefix(prefix) {
@@ -3094,6 +3593,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_diff_deleted_lines() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\n\
+index 1111111..2222222 100644\n\
+--- a/src/lib.rs\n\
++++ b/src/lib.rs\n\
+@@ -2,3 +1,0 @@\n\
+-one\n\
+--- not a header\n\
+-three\n\
+@@ -10 +8 @@\n\
+-old\n\
++new\n\
+diff --git a/added.rs b/added.rs\n\
+new file mode 100644\n\
+--- /dev/null\n\
++++ b/added.rs\n\
+@@ -0,0 +1 @@\n\
++hello\n";
+
+        let result = parse_diff_deleted_lines(diff);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.get("src/lib.rs"), Some(&vec![2, 3, 4, 10]));
+    }
+
+    #[test]
+    fn test_parse_diff_line_changes() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\n\
+index 1111111..2222222 100644\n\
+--- a/src/lib.rs\n\
++++ b/src/lib.rs\n\
+@@ -2,2 +1,0 @@\n\
+-one\n\
+--- two\n\
+@@ -10 +8,2 @@\n\
+-old\n\
++new\n\
+++++ plus\n\
+diff --git a/added.rs b/added.rs\n\
+new file mode 100644\n\
+--- /dev/null\n\
++++ b/added.rs\n\
+@@ -0,0 +1 @@\n\
++hello\n\
+diff --git a/removed.rs b/removed.rs\n\
+deleted file mode 100644\n\
+--- a/removed.rs\n\
++++ /dev/null\n\
+@@ -1 +0,0 @@\n\
+-bye\n";
+
+        let result = parse_diff_line_changes(diff);
+        assert_eq!(result.len(), 3);
+        assert_eq!(result["added.rs"].added, vec![(1, "hello".to_string())]);
+        assert!(result["added.rs"].deleted.is_empty());
+        assert_eq!(result["removed.rs"].deleted, vec![(1, "bye".to_string())]);
+        assert!(result["removed.rs"].added.is_empty());
+        let changes = result.get("src/lib.rs").unwrap();
+        assert_eq!(
+            changes.deleted,
+            vec![
+                (2, "one".to_string()),
+                (3, "-- two".to_string()),
+                (10, "old".to_string())
+            ]
+        );
+        assert_eq!(
+            changes.added,
+            vec![(8, "new".to_string()), (9, "+++ plus".to_string())]
+        );
+    }
+
     #[test]
     fn test_parse_diff_added_lines_with_insertions_standard_prefix() {
         // Test diff with standard b/ prefix (commit-to-commit diff)
@@ -3255,4 +3826,81 @@ index 0000000..abc1234 100644
         let content = String::from_utf8(content).expect("utf8 attrs");
         assert!(content.contains("generated/** linguist-generated=true"));
     }
+
+    fn lock_contention_error() -> GitAiError {
+        GitAiError::GitCliError {
+            code: Some(128),
+            stderr: "fatal: Unable to create '/repo/.git/index.lock': File exists.\n".to_string(),
+            args: vec!["add".to_string()],
+        }
+    }
+
+    #[test]
+    fn lock_contention_is_retried_until_git_succeeds() {
+        let mut attempts = 0;
+        let output = retry_transient_git_errors(|| {
+            attempts += 1;
+            if attempts < 3 {
+                return Err(lock_contention_error());
+            }
+            Ok(Output {
+                status: std::process::ExitStatus::default(),
+                stdout: b"ok".to_vec(),
+                stderr: Vec::new(),
+            })
+        })
+        .expect("the third attempt succeeds");
+        assert_eq!(output.stdout, b"ok");
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn genuine_git_failures_are_not_retried() {
+        let mut attempts = 0;
+        let err = retry_transient_git_errors(|| {
+            attempts += 1;
+            Err(GitAiError::GitCliError {
+                code: Some(128),
+                stderr: "fatal: bad revision 'nope'\n".to_string(),
+                args: vec!["rev-parse".to_string()],
+            })
+        })
+        .unwrap_err();
+        assert!(matches!(err, GitAiError::GitCliError { .. }));
+        assert_eq!(attempts, 1);
+
+        let mut attempts = 0;
+        assert!(
+            retry_transient_git_errors(|| {
+                attempts += 1;
+                Err(lock_contention_error())
+            })
+            .is_err()
+        );
+        assert_eq!(attempts, GIT_LOCK_ATTEMPTS);
+    }
+
+    #[test]
+    fn exec_git_waits_for_a_held_index_lock() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        run_git(temp.path(), &["init"]);
+        fs::write(temp.path().join("README.md"), "# repo\n").expect("write readme");
+
+        // Another git process holds the index for a moment
+        let lock = temp.path().join(".git").join("index.lock");
+        fs::write(&lock, "").expect("write lock");
+        let releaser = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            fs::remove_file(lock).expect("remove lock");
+        });
+
+        let result = exec_git(&[
+            "-C".to_string(),
+            temp.path().to_str().unwrap().to_string(),
+            "add".to_string(),
+            "README.md".to_string(),
+        ]);
+        releaser.join().unwrap();
+        result.expect("git add succeeds once the lock is released");
+    }
 }
@@ -1,3 +1,4 @@
+use crate::git::attestation_storage::{AttestationStorage, configured_storage};
 use crate::git::refs::{
     AI_AUTHORSHIP_PUSH_REFSPEC, copy_ref, merge_notes_from_ref, ref_exists, tracking_ref_for_remote,
 };
@@ -189,6 +190,12 @@ pub fn fetch_authorship_notes(
 }
 // for use with post-push hook
 pub fn push_authorship_notes(repository: &Repository, remote_name: &str) -> Result<(), GitAiError> {
+    // Sidecar attestations stay in .git/ai and are never shared
+    if configured_storage(repository) == AttestationStorage::Sidecar {
+        debug_log("skipping authorship push: git-ai.storage is sidecar");
+        return Ok(());
+    }
+
     // STEP 1: Fetch remote notes into tracking ref and merge before pushing
     // This ensures we don't lose notes from other branches/clones
     let tracking_ref = tracking_ref_for_remote(remote_name);
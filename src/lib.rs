@@ -10,5 +10,9 @@ pub mod git;
 pub mod mdm;
 pub mod metrics;
 pub mod observability;
+pub mod repo;
 pub mod repo_url;
+pub mod sources;
 pub mod utils;
+
+pub use repo::Repo;
@@ -0,0 +1,77 @@
+//! Library entry point for reading git-ai data from a repository.
+//!
+//! `Repo` is opened once and answers typed queries, so embedders and tests can ask for a commit's
+//! stats or authorship log without running the `git-ai` binary and parsing its output. It wraps
+//! the same [`Repository`] the commands use, so the answers match the CLI's.
+
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::ignore::effective_ignore_patterns;
+use crate::authorship::stats::{
+    CommitStats, CommitStatsOptions, stats_for_commit_stats_with_options,
+};
+use crate::error::GitAiError;
+use crate::git::refs::get_authorship;
+use crate::git::repository::{Repository, find_repository_in_path};
+use std::path::{Path, PathBuf};
+
+pub struct Repo {
+    repository: Repository,
+    ignore_patterns: Vec<String>,
+}
+
+impl Repo {
+    /// Open the repository containing `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Repo, GitAiError> {
+        let path = path.as_ref();
+        let path = path
+            .to_str()
+            .ok_or_else(|| GitAiError::Utf8Path(path.to_path_buf()))?;
+        Ok(Repo::from_repository(find_repository_in_path(path)?))
+    }
+
+    /// Wrap an already opened repository.
+    pub fn from_repository(repository: Repository) -> Repo {
+        // Read once: the repository's ignore files do not change while it is open
+        let ignore_patterns = effective_ignore_patterns(&repository, &[], &[]);
+        Repo {
+            repository,
+            ignore_patterns,
+        }
+    }
+
+    /// The root of the working tree.
+    pub fn workdir(&self) -> Result<PathBuf, GitAiError> {
+        self.repository.workdir()
+    }
+
+    /// The full SHA of the commit `rev` names.
+    pub fn resolve_commit(&self, rev: &str) -> Result<String, GitAiError> {
+        Ok(self.repository.revparse_single(rev)?.peel_to_commit()?.id())
+    }
+
+    /// Stats for one commit, as `git-ai stats <rev>` reports them.
+    pub fn commit_stats(&self, rev: &str) -> Result<CommitStats, GitAiError> {
+        self.commit_stats_with_options(rev, &CommitStatsOptions::default())
+    }
+
+    /// Stats for one commit, with the optional parts of the computation `options` asks for.
+    pub fn commit_stats_with_options(
+        &self,
+        rev: &str,
+        options: &CommitStatsOptions,
+    ) -> Result<CommitStats, GitAiError> {
+        let sha = self.resolve_commit(rev)?;
+        stats_for_commit_stats_with_options(&self.repository, &sha, &self.ignore_patterns, options)
+    }
+
+    /// The authorship log stored for the commit `rev` names, or `None` if it has none.
+    pub fn authorship_log(&self, rev: &str) -> Result<Option<AuthorshipLog>, GitAiError> {
+        let sha = self.resolve_commit(rev)?;
+        Ok(get_authorship(&self.repository, &sha))
+    }
+
+    /// The underlying repository, for the lower-level functions that take one.
+    pub fn repository(&self) -> &Repository {
+        &self.repository
+    }
+}
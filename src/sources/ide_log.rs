@@ -0,0 +1,119 @@
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::working_log::AgentId;
+use crate::error::GitAiError;
+use crate::sources::AiSource;
+use crate::utils::same_path;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// Line ranges an IDE or agent appends to a log file as it inserts AI code, one
+/// `<path>:<line>` or `<path>:<start>-<end>` entry per line, with paths relative to the
+/// repository root. Blank lines and lines starting with `#` are skipped.
+#[derive(Debug, Clone)]
+pub struct IdeLogSource {
+    pub log_path: PathBuf,
+    pub agent_id: AgentId,
+}
+
+impl IdeLogSource {
+    /// A source crediting the lines `log_path` lists to an `ide_log` agent.
+    pub fn new(log_path: PathBuf) -> Self {
+        IdeLogSource {
+            log_path,
+            agent_id: AgentId {
+                tool: "ide_log".to_string(),
+                id: "ide_log".to_string(),
+                model: "unknown".to_string(),
+            },
+        }
+    }
+
+    /// The distinct paths the log has entries for.
+    pub fn file_paths(&self) -> Result<Vec<String>, GitAiError> {
+        let log = std::fs::read_to_string(&self.log_path)?;
+        let paths: BTreeSet<String> = parse_ide_log_entries(&log)?
+            .into_iter()
+            .map(|(path, _)| path.to_string())
+            .collect();
+        Ok(paths.into_iter().collect())
+    }
+}
+
+impl AiSource for IdeLogSource {
+    fn agent_id(&self) -> AgentId {
+        self.agent_id.clone()
+    }
+
+    fn ai_line_ranges(
+        &self,
+        file_path: &str,
+        _content: &str,
+    ) -> Result<Vec<LineRange>, GitAiError> {
+        let log = std::fs::read_to_string(&self.log_path)?;
+        parse_ide_log(&log, file_path)
+    }
+}
+
+/// The ranges `log` records for `file_path`.
+fn parse_ide_log(log: &str, file_path: &str) -> Result<Vec<LineRange>, GitAiError> {
+    Ok(parse_ide_log_entries(log)?
+        .into_iter()
+        .filter(|(path, _)| same_path(path, file_path))
+        .map(|(_, range)| range)
+        .collect())
+}
+
+/// Every `(path, range)` entry of `log`, in order.
+fn parse_ide_log_entries(log: &str) -> Result<Vec<(&str, LineRange)>, GitAiError> {
+    let mut entries = Vec::new();
+    for (index, entry) in log.lines().enumerate() {
+        let entry = entry.trim();
+        if entry.is_empty() || entry.starts_with('#') {
+            continue;
+        }
+        let invalid = || {
+            GitAiError::Generic(format!(
+                "line {}: expected `<path>:<start>[-<end>]`, got `{}`",
+                index + 1,
+                entry
+            ))
+        };
+        let (path, lines) = entry.rsplit_once(':').ok_or_else(invalid)?;
+        let range = match lines.split_once('-') {
+            Some((start, end)) => LineRange::Range(
+                start.parse().map_err(|_| invalid())?,
+                end.parse().map_err(|_| invalid())?,
+            ),
+            None => LineRange::Single(lines.parse().map_err(|_| invalid())?),
+        };
+        entries.push((path, range));
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ide_log_keeps_entries_for_the_file() {
+        let log = "# inserted by the IDE\nsrc/lib.rs:3-5\nsrc/main.rs:1\n\nsrc/lib.rs:9\n";
+        assert_eq!(
+            parse_ide_log(log, "src/lib.rs").unwrap(),
+            vec![LineRange::Range(3, 5), LineRange::Single(9)]
+        );
+        assert!(parse_ide_log(log, "README.md").unwrap().is_empty());
+        assert!(parse_ide_log("src/lib.rs:three", "src/lib.rs").is_err());
+    }
+
+    #[test]
+    fn test_file_paths_lists_each_logged_path_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let log_path = tmp.path().join("ai.log");
+        std::fs::write(&log_path, "src/lib.rs:3-5\nsrc/main.rs:1\nsrc/lib.rs:9\n").unwrap();
+        assert_eq!(
+            IdeLogSource::new(log_path).file_paths().unwrap(),
+            vec!["src/lib.rs".to_string(), "src/main.rs".to_string()]
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::working_log::AgentId;
+use crate::error::GitAiError;
+use crate::sources::AiSource;
+
+/// Lines between a begin and an end marker comment are AI. The markers are matched anywhere in
+/// a line, so they work in any language's comment syntax, and the marker lines themselves are
+/// not counted. A begin marker without an end runs to the end of the file.
+#[derive(Debug, Clone)]
+pub struct MarkerCommentSource {
+    pub begin_marker: String,
+    pub end_marker: String,
+    pub agent_id: AgentId,
+}
+
+impl Default for MarkerCommentSource {
+    fn default() -> Self {
+        MarkerCommentSource {
+            begin_marker: "git-ai:begin".to_string(),
+            end_marker: "git-ai:end".to_string(),
+            agent_id: AgentId {
+                tool: "markers".to_string(),
+                id: "markers".to_string(),
+                model: "unknown".to_string(),
+            },
+        }
+    }
+}
+
+impl AiSource for MarkerCommentSource {
+    fn agent_id(&self) -> AgentId {
+        self.agent_id.clone()
+    }
+
+    fn ai_line_ranges(
+        &self,
+        _file_path: &str,
+        content: &str,
+    ) -> Result<Vec<LineRange>, GitAiError> {
+        let mut ai_lines = Vec::new();
+        let mut inside = false;
+        for (index, line) in content.lines().enumerate() {
+            if line.contains(&self.begin_marker) {
+                inside = true;
+            } else if line.contains(&self.end_marker) {
+                inside = false;
+            } else if inside {
+                ai_lines.push(index as u32 + 1);
+            }
+        }
+        Ok(LineRange::compress_lines(&ai_lines))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_between_markers_are_ai() {
+        let content = "fn human() {}\n// git-ai:begin\nfn ai1() {}\nfn ai2() {}\n// git-ai:end\nfn human2() {}\n# git-ai:begin\nai = 3\n";
+        let ranges = MarkerCommentSource::default()
+            .ai_line_ranges("lib.rs", content)
+            .unwrap();
+        assert_eq!(ranges, vec![LineRange::Range(3, 4), LineRange::Single(8)]);
+    }
+}
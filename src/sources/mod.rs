@@ -0,0 +1,197 @@
+//! Pluggable sources of AI authorship.
+//!
+//! Agent presets learn about AI edits from the hooks an agent calls. An [`AiSource`] instead
+//! answers, for a file as it is now, which of its lines AI wrote, so agents without hooks can
+//! be supported by reading whatever trail they leave: marker comments, an editor's log, or
+//! anything a custom implementation knows about. [`record_ai_source`] feeds those answers to
+//! the checkpoint recorder, so the lines are committed and reported like any agent's.
+
+pub mod ide_log;
+pub mod markers;
+pub mod telemetry;
+
+pub use ide_log::IdeLogSource;
+pub use markers::MarkerCommentSource;
+
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::working_log::{AgentId, CheckpointKind};
+use crate::commands::checkpoint;
+use crate::commands::checkpoint_agent::agent_presets::AgentRunResult;
+use crate::error::GitAiError;
+use crate::git::repository::Repository;
+use std::collections::HashMap;
+
+/// Tells which lines of a file AI wrote.
+pub trait AiSource {
+    /// The agent the lines are credited to.
+    fn agent_id(&self) -> AgentId;
+
+    /// The 1-based, inclusive ranges of `content` that AI wrote. `file_path` is relative to
+    /// the repository root and `content` is the file as it is in the working tree.
+    fn ai_line_ranges(&self, file_path: &str, content: &str) -> Result<Vec<LineRange>, GitAiError>;
+}
+
+/// Record a checkpoint crediting the lines `source` reports in `file_paths` (relative to the
+/// repository root) to its agent. Returns the number of lines credited.
+///
+/// Like the AI tab preset, this is a human checkpoint of the files without those lines
+/// followed by an AI checkpoint of the files as they are, so the AI checkpoint adds exactly
+/// the reported lines. Edits the working log has not seen yet stay human.
+pub fn record_ai_source(
+    repo: &Repository,
+    source: &dyn AiSource,
+    file_paths: &[String],
+) -> Result<usize, GitAiError> {
+    let workdir = repo.workdir()?;
+
+    let mut without_ai: HashMap<String, String> = HashMap::new();
+    let mut with_ai: HashMap<String, String> = HashMap::new();
+    let mut ai_lines = 0;
+    for file_path in file_paths {
+        let absolute_path = workdir.join(file_path);
+        let content = std::fs::read_to_string(&absolute_path)?;
+        let ranges = source.ai_line_ranges(file_path, &content)?;
+
+        let mut stripped = String::with_capacity(content.len());
+        let mut file_ai_lines = 0;
+        for (index, line) in content.split_inclusive('\n').enumerate() {
+            if ranges.iter().any(|range| range.contains(index as u32 + 1)) {
+                file_ai_lines += 1;
+            } else {
+                stripped.push_str(line);
+            }
+        }
+        if file_ai_lines == 0 {
+            continue;
+        }
+
+        let key = absolute_path.to_string_lossy().to_string();
+        without_ai.insert(key.clone(), stripped);
+        with_ai.insert(key, content);
+        ai_lines += file_ai_lines;
+    }
+    if ai_lines == 0 {
+        return Ok(0);
+    }
+
+    let author = match repo.config_get_str("user.name") {
+        Ok(Some(name)) if !name.trim().is_empty() => name,
+        _ => "unknown".to_string(),
+    };
+    let repo_working_dir = Some(workdir.to_string_lossy().to_string());
+    let paths: Vec<String> = with_ai.keys().cloned().collect();
+
+    checkpoint::run(
+        repo,
+        &author,
+        CheckpointKind::Human,
+        false,
+        false,
+        true,
+        Some(AgentRunResult {
+            agent_id: source.agent_id(),
+            agent_metadata: None,
+            checkpoint_kind: CheckpointKind::Human,
+            transcript: None,
+            repo_working_dir: repo_working_dir.clone(),
+            edited_filepaths: None,
+            will_edit_filepaths: Some(paths.clone()),
+            dirty_files: Some(without_ai),
+        }),
+        false,
+    )?;
+    checkpoint::run(
+        repo,
+        &author,
+        CheckpointKind::AiAgent,
+        false,
+        false,
+        true,
+        Some(AgentRunResult {
+            agent_id: source.agent_id(),
+            agent_metadata: None,
+            checkpoint_kind: CheckpointKind::AiAgent,
+            transcript: None,
+            repo_working_dir,
+            edited_filepaths: Some(paths),
+            will_edit_filepaths: None,
+            dirty_files: Some(with_ai),
+        }),
+        false,
+    )?;
+
+    Ok(ai_lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::TmpRepo;
+
+    /// Reports fixed line ranges, like an agent that keeps its own record of what it wrote.
+    struct MockSource(Vec<LineRange>);
+
+    impl AiSource for MockSource {
+        fn agent_id(&self) -> AgentId {
+            AgentId {
+                tool: "mock_source".to_string(),
+                id: "session-1".to_string(),
+                model: "mock-model".to_string(),
+            }
+        }
+
+        fn ai_line_ranges(
+            &self,
+            _file_path: &str,
+            _content: &str,
+        ) -> Result<Vec<LineRange>, GitAiError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn test_mock_source_drives_attribution() {
+        let tmp_repo = TmpRepo::new().unwrap();
+        tmp_repo
+            .write_file("lib.rs", "fn base() {}\n", true)
+            .unwrap();
+        tmp_repo
+            .trigger_checkpoint_with_author("test_user")
+            .unwrap();
+        tmp_repo.commit_with_message("Initial commit").unwrap();
+
+        tmp_repo
+            .write_file(
+                "lib.rs",
+                "fn base() {}\nfn ai1() {}\nfn ai2() {}\nfn human() {}\nfn ai3() {}\n",
+                true,
+            )
+            .unwrap();
+        let source = MockSource(vec![LineRange::Range(2, 3), LineRange::Single(5)]);
+        let recorded =
+            record_ai_source(tmp_repo.gitai_repo(), &source, &["lib.rs".to_string()]).unwrap();
+        assert_eq!(recorded, 3);
+
+        let log = tmp_repo.commit_with_message("Add helpers").unwrap();
+        let file = log
+            .file_attestation("lib.rs")
+            .expect("lib.rs should have attestations");
+        let mut ai_lines: Vec<u32> = file
+            .entries
+            .iter()
+            .flat_map(|entry| &entry.line_ranges)
+            .flat_map(|range| match range {
+                LineRange::Single(line) => *line..=*line,
+                LineRange::Range(start, end) => *start..=*end,
+            })
+            .collect();
+        ai_lines.sort_unstable();
+        assert_eq!(ai_lines, vec![2, 3, 5]);
+        assert!(
+            log.metadata
+                .prompts
+                .values()
+                .any(|prompt| prompt.agent_id.tool == "mock_source")
+        );
+    }
+}
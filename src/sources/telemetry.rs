@@ -0,0 +1,168 @@
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::working_log::AgentId;
+use crate::error::GitAiError;
+use crate::sources::AiSource;
+use crate::utils::nfc_path;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// The AI edits one tool made according to an IDE's telemetry export.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelemetrySource {
+    pub tool: String,
+    /// Line ranges by NFC-normalized path, relative to the repository root
+    pub ranges_by_path: BTreeMap<String, Vec<LineRange>>,
+}
+
+impl TelemetrySource {
+    /// The paths this tool edited.
+    pub fn file_paths(&self) -> Vec<String> {
+        self.ranges_by_path.keys().cloned().collect()
+    }
+}
+
+impl AiSource for TelemetrySource {
+    fn agent_id(&self) -> AgentId {
+        AgentId {
+            tool: self.tool.clone(),
+            id: "telemetry".to_string(),
+            model: "unknown".to_string(),
+        }
+    }
+
+    fn ai_line_ranges(
+        &self,
+        file_path: &str,
+        _content: &str,
+    ) -> Result<Vec<LineRange>, GitAiError> {
+        Ok(self
+            .ranges_by_path
+            .get(nfc_path(file_path).as_ref())
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+/// Read a telemetry CSV of `path,start_line,end_line,tool` rows into one source per tool,
+/// ordered by tool name.
+pub fn load_telemetry(path: &Path) -> Result<Vec<TelemetrySource>, GitAiError> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        GitAiError::Generic(format!(
+            "Failed to read telemetry {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    parse_telemetry(&content)
+        .map_err(|e| GitAiError::Generic(format!("Invalid telemetry {}: {}", path.display(), e)))
+}
+
+/// Parse telemetry CSV. Fields may be quoted, with `""` for a quote inside them, so paths can
+/// hold commas. An optional `path,start_line,end_line,tool` header row is skipped.
+pub fn parse_telemetry(content: &str) -> Result<Vec<TelemetrySource>, String> {
+    let mut by_tool: BTreeMap<String, BTreeMap<String, Vec<LineRange>>> = BTreeMap::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = split_csv_record(line).map_err(|e| format!("line {}: {}", index + 1, e))?;
+        let [path, start, end, tool] = fields.as_slice() else {
+            return Err(format!(
+                "line {}: expected `path,start_line,end_line,tool`, got {} fields",
+                index + 1,
+                fields.len()
+            ));
+        };
+        if index == 0 && path == "path" && start == "start_line" {
+            continue;
+        }
+
+        let parse_line = |value: &str| {
+            value
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|line| *line > 0)
+                .ok_or_else(|| format!("line {}: invalid line number `{}`", index + 1, value))
+        };
+        let (start, end) = (parse_line(start)?, parse_line(end)?);
+        if end < start {
+            return Err(format!(
+                "line {}: end_line {} is before start_line {}",
+                index + 1,
+                end,
+                start
+            ));
+        }
+        let range = if start == end {
+            LineRange::Single(start)
+        } else {
+            LineRange::Range(start, end)
+        };
+
+        by_tool
+            .entry(tool.trim().to_string())
+            .or_default()
+            .entry(nfc_path(path.trim()).into_owned())
+            .or_default()
+            .push(range);
+    }
+
+    Ok(by_tool
+        .into_iter()
+        .map(|(tool, ranges_by_path)| TelemetrySource {
+            tool,
+            ranges_by_path,
+        })
+        .collect())
+}
+
+fn split_csv_record(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_telemetry_groups_ranges_by_tool() {
+        let csv = "path,start_line,end_line,tool\nsrc/lib.rs,2,4,cursor\n\"src/a,b.rs\",1,1,copilot\nsrc/lib.rs,8,8,cursor\n";
+        let sources = parse_telemetry(csv).unwrap();
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].tool, "copilot");
+        assert_eq!(
+            sources[0].ai_line_ranges("src/a,b.rs", "").unwrap(),
+            vec![LineRange::Single(1)]
+        );
+        assert_eq!(sources[1].tool, "cursor");
+        assert_eq!(
+            sources[1].ai_line_ranges("src/lib.rs", "").unwrap(),
+            vec![LineRange::Range(2, 4), LineRange::Single(8)]
+        );
+
+        assert!(parse_telemetry("src/lib.rs,2,cursor").is_err());
+        assert!(parse_telemetry("src/lib.rs,5,2,cursor").is_err());
+        assert!(parse_telemetry("\"src/lib.rs,1,1,cursor").is_err());
+    }
+}
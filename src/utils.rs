@@ -1,8 +1,10 @@
 use crate::error::GitAiError;
 use crate::git::diff_tree_to_tree::Diff;
+use std::borrow::Cow;
 use std::io::IsTerminal;
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
+use unicode_normalization::{UnicodeNormalization, is_nfc};
 
 /// Check if debug logging is enabled via environment variable
 ///
@@ -10,6 +12,8 @@ use std::process::{Command, Stdio};
 static DEBUG_ENABLED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
 static DEBUG_PERFORMANCE_LEVEL: std::sync::OnceLock<u8> = std::sync::OnceLock::new();
 static IS_TERMINAL: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+/// Set by `--color`/`--no-color`, overriding `NO_COLOR` and terminal detection
+static COLOR_OVERRIDE: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
 
 fn is_debug_enabled() -> bool {
     *DEBUG_ENABLED.get_or_init(|| {
@@ -93,11 +97,32 @@ pub fn _print_diff(diff: &Diff, old_label: &str, new_label: &str) {
     }
 }
 
+/// Git's binary heuristic: content with a NUL byte in its first 8000 bytes is binary.
+pub fn is_binary_content(content: &[u8]) -> bool {
+    content.iter().take(8000).any(|&b| b == 0)
+}
+
 #[inline]
 pub fn normalize_to_posix(path: &str) -> String {
     path.replace('\\', "/")
 }
 
+/// A repository path in Unicode NFC. macOS file APIs hand out NFD names (`e` followed by a
+/// combining accent) while git and the attestation logs usually hold NFC (a single `é`), so
+/// paths are composed before they are stored or compared.
+pub fn nfc_path(path: &str) -> Cow<'_, str> {
+    if is_nfc(path) {
+        Cow::Borrowed(path)
+    } else {
+        Cow::Owned(path.nfc().collect())
+    }
+}
+
+/// Whether two repository paths name the same file, whatever their Unicode normalization.
+pub fn same_path(a: &str, b: &str) -> bool {
+    a == b || nfc_path(a) == nfc_path(b)
+}
+
 fn resolve_git_ai_exe_from_invocation_path(path: PathBuf) -> PathBuf {
     let canonical_path = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
 
@@ -196,6 +221,39 @@ pub fn is_interactive_terminal() -> bool {
     *IS_TERMINAL.get_or_init(|| std::io::stdin().is_terminal())
 }
 
+/// Force colored output on or off for the rest of the process
+pub fn set_color_override(enabled: bool) {
+    let _ = COLOR_OVERRIDE.set(enabled);
+}
+
+/// Whether human-readable output on stdout should use ANSI colors. `--color`/`--no-color`
+/// win; otherwise color is off when `NO_COLOR` is set (see https://no-color.org) or stdout is
+/// not a terminal.
+pub fn color_enabled() -> bool {
+    match COLOR_OVERRIDE.get() {
+        Some(enabled) => *enabled,
+        None => default_color_enabled(
+            std::env::var_os("NO_COLOR").as_deref(),
+            std::io::stdout().is_terminal(),
+        ),
+    }
+}
+
+fn default_color_enabled(no_color: Option<&std::ffi::OsStr>, stdout_is_terminal: bool) -> bool {
+    // The convention only counts a non-empty value
+    let no_color = no_color.is_some_and(|value| !value.is_empty());
+    stdout_is_terminal && !no_color
+}
+
+/// Wrap `text` in the ANSI SGR `code` (e.g. "32" for green) when color is enabled
+pub fn paint(code: &str, text: &str) -> String {
+    if color_enabled() {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
 /// A cross-platform exclusive file lock.
 ///
 /// Holds an exclusive advisory lock (Unix) or exclusive-access file handle (Windows)
@@ -212,6 +270,23 @@ impl LockFile {
         let file = try_lock_exclusive(path)?;
         Some(Self { _file: file })
     }
+
+    /// Wait up to `timeout` for an exclusive lock on the given path.
+    ///
+    /// The OS drops the lock when its holder exits, so a lock file left behind by a crashed
+    /// process is taken over right away; the timeout only covers a holder that is still running.
+    pub fn acquire(path: &std::path::Path, timeout: std::time::Duration) -> Option<Self> {
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            if let Some(lock) = Self::try_acquire(path) {
+                return Some(lock);
+            }
+            if std::time::Instant::now() >= deadline {
+                return None;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
 }
 
 #[cfg(unix)]
@@ -245,110 +320,32 @@ fn try_lock_exclusive(path: &std::path::Path) -> Option<std::fs::File> {
 /// Windows-specific flag to prevent console window creation
 #[cfg(windows)]
 pub const CREATE_NO_WINDOW: u32 = 0x08000000;
-/// Unescape a git-quoted path that may contain octal escape sequences.
-///
-/// Git quotes filenames containing non-ASCII characters (and some special characters)
-/// using C-style escaping with octal sequences. For example, a Chinese filename like
-/// "中文.txt" would appear as `"\344\270\255\346\226\207.txt"` in git output.
-///
-/// This function handles:
-/// - Quoted paths: removes surrounding quotes and unescapes content
-/// - Octal escapes: converts `\NNN` sequences back to UTF-8 bytes
-/// - Other escapes: `\\`, `\"`, `\n`, `\t`, etc.
-/// - Unquoted paths: returned as-is
-///
-/// # Examples
-///
-/// ```
-/// use git_ai::utils::unescape_git_path;
-///
-/// // Unquoted path - returned as-is
-/// assert_eq!(unescape_git_path("simple.txt"), "simple.txt");
-///
-/// // Quoted path with spaces
-/// assert_eq!(unescape_git_path("\"path with spaces.txt\""), "path with spaces.txt");
-///
-/// // Chinese characters encoded as octal
-/// assert_eq!(unescape_git_path("\"\\344\\270\\255\\346\\226\\207.txt\""), "中文.txt");
-/// ```
-pub fn unescape_git_path(path: &str) -> String {
-    // If not quoted, return as-is
-    if !path.starts_with('"') || !path.ends_with('"') {
-        return path.to_string();
-    }
-
-    // Remove surrounding quotes
-    let inner = &path[1..path.len() - 1];
-
-    // Parse escape sequences and collect bytes
-    let mut bytes: Vec<u8> = Vec::with_capacity(inner.len());
-    let mut chars = inner.chars().peekable();
-
-    while let Some(c) = chars.next() {
-        if c == '\\' {
-            match chars.peek() {
-                Some('\\') => {
-                    chars.next();
-                    bytes.push(b'\\');
-                }
-                Some('"') => {
-                    chars.next();
-                    bytes.push(b'"');
-                }
-                Some('n') => {
-                    chars.next();
-                    bytes.push(b'\n');
-                }
-                Some('t') => {
-                    chars.next();
-                    bytes.push(b'\t');
-                }
-                Some('r') => {
-                    chars.next();
-                    bytes.push(b'\r');
-                }
-                Some(d) if d.is_ascii_digit() => {
-                    // Octal escape sequence: \NNN (1-3 octal digits)
-                    let mut octal = String::new();
-                    for _ in 0..3 {
-                        if let Some(&d) = chars.peek() {
-                            if d.is_ascii_digit() && d <= '7' {
-                                octal.push(chars.next().unwrap());
-                            } else {
-                                break;
-                            }
-                        } else {
-                            break;
-                        }
-                    }
-                    if let Ok(byte_val) = u8::from_str_radix(&octal, 8) {
-                        bytes.push(byte_val);
-                    }
-                }
-                _ => {
-                    // Unknown escape - keep the backslash
-                    bytes.push(b'\\');
-                }
-            }
-        } else {
-            // Regular character - encode as UTF-8
-            let mut buf = [0u8; 4];
-            let encoded = c.encode_utf8(&mut buf);
-            bytes.extend_from_slice(encoded.as_bytes());
-        }
-    }
-
-    // Convert bytes to UTF-8 string
-    String::from_utf8(bytes).unwrap_or_else(|e| {
-        // If invalid UTF-8, try lossy conversion
-        String::from_utf8_lossy(e.as_bytes()).into_owned()
-    })
-}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_same_path_ignores_unicode_normalization() {
+        let nfc = "docs/中文/caf\u{e9}.md";
+        let nfd = "docs/中文/cafe\u{301}.md";
+        assert_ne!(nfc, nfd);
+        assert!(same_path(nfc, nfd));
+        assert_eq!(nfc_path(nfd), nfc);
+        assert!(matches!(nfc_path(nfc), Cow::Borrowed(_)));
+        assert!(!same_path(nfc, "docs/中文/cafe.md"));
+    }
+
+    #[test]
+    fn test_default_color_follows_no_color_and_terminal() {
+        use std::ffi::OsStr;
+        assert!(default_color_enabled(None, true));
+        assert!(!default_color_enabled(None, false));
+        assert!(!default_color_enabled(Some(OsStr::new("1")), true));
+        // An empty NO_COLOR does not count as set
+        assert!(default_color_enabled(Some(OsStr::new("")), true));
+    }
+
     // =========================================================================
     // LockFile Tests
     // =========================================================================
@@ -385,6 +382,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lockfile_acquire_waits_for_holder() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock_path = dir.path().join("test.lock");
+        let first = LockFile::try_acquire(&lock_path).expect("first acquire should succeed");
+        assert!(
+            LockFile::acquire(&lock_path, std::time::Duration::from_millis(50)).is_none(),
+            "acquire should time out while the lock is held"
+        );
+
+        let waiter = {
+            let lock_path = lock_path.clone();
+            std::thread::spawn(move || {
+                LockFile::acquire(&lock_path, std::time::Duration::from_secs(10)).is_some()
+            })
+        };
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        drop(first);
+        assert!(
+            waiter.join().unwrap(),
+            "acquire should succeed once released"
+        );
+    }
+
+    #[test]
+    fn test_lockfile_leftover_file_does_not_block() {
+        // A crashed holder leaves the file behind but not the lock
+        let dir = tempfile::tempdir().unwrap();
+        let lock_path = dir.path().join("test.lock");
+        std::fs::write(&lock_path, "").unwrap();
+        let lock = LockFile::acquire(&lock_path, std::time::Duration::from_millis(50));
+        assert!(lock.is_some(), "an unlocked leftover file should not block");
+    }
+
     #[test]
     fn test_lockfile_nonexistent_parent_returns_none() {
         let dir = tempfile::tempdir().unwrap();
@@ -503,516 +534,6 @@ mod tests {
         assert!(!spawned, "spawn should be skipped when guard env is empty");
     }
 
-    // =========================================================================
-    // unescape_git_path Tests
-    // =========================================================================
-
-    #[test]
-    fn test_unescape_git_path_simple() {
-        // Unquoted path - no change
-        assert_eq!(unescape_git_path("simple.txt"), "simple.txt");
-        assert_eq!(unescape_git_path("path/to/file.rs"), "path/to/file.rs");
-    }
-
-    #[test]
-    fn test_unescape_git_path_quoted_with_spaces() {
-        // Quoted path with spaces
-        assert_eq!(
-            unescape_git_path("\"path with spaces.txt\""),
-            "path with spaces.txt"
-        );
-        assert_eq!(
-            unescape_git_path("\"dir name/file name.txt\""),
-            "dir name/file name.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_git_path_chinese_characters() {
-        // Chinese characters "中文" encoded as octal: \344\270\255\346\226\207
-        assert_eq!(
-            unescape_git_path("\"\\344\\270\\255\\346\\226\\207.txt\""),
-            "中文.txt"
-        );
-
-        // More complex Chinese filename: "中文文件.txt"
-        // 中 = \344\270\255, 文 = \346\226\207, 件 = \344\273\266
-        assert_eq!(
-            unescape_git_path(
-                "\"\\344\\270\\255\\346\\226\\207\\346\\226\\207\\344\\273\\266.txt\""
-            ),
-            "中文文件.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_git_path_emoji() {
-        // Emoji "🚀" (rocket) = U+1F680 = \360\237\232\200 in octal UTF-8
-        assert_eq!(unescape_git_path("\"\\360\\237\\232\\200.txt\""), "🚀.txt");
-
-        // Emoji "😀" (grinning face) = U+1F600 = \360\237\230\200 in octal UTF-8
-        assert_eq!(unescape_git_path("\"\\360\\237\\230\\200.txt\""), "😀.txt");
-
-        // Mixed: "test_🎉_file.txt" where 🎉 = \360\237\216\211
-        assert_eq!(
-            unescape_git_path("\"test_\\360\\237\\216\\211_file.txt\""),
-            "test_🎉_file.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_git_path_escaped_characters() {
-        // Escaped backslash
-        assert_eq!(
-            unescape_git_path("\"path\\\\with\\\\slashes\""),
-            "path\\with\\slashes"
-        );
-
-        // Escaped quotes
-        assert_eq!(unescape_git_path("\"file\\\"name.txt\""), "file\"name.txt");
-
-        // Escaped newline and tab
-        assert_eq!(unescape_git_path("\"line1\\nline2\""), "line1\nline2");
-        assert_eq!(unescape_git_path("\"col1\\tcol2\""), "col1\tcol2");
-    }
-
-    #[test]
-    fn test_unescape_git_path_mixed_content() {
-        // Mix of ASCII, Chinese, and escapes
-        assert_eq!(
-            unescape_git_path("\"src/\\344\\270\\255\\346\\226\\207/file.txt\""),
-            "src/中文/file.txt"
-        );
-    }
-
-    // =========================================================================
-    // Phase 1: CJK Extended Coverage Tests
-    // =========================================================================
-
-    #[test]
-    fn test_unescape_japanese_hiragana() {
-        // Japanese Hiragana "ひらがな" = \343\201\262\343\202\211\343\201\214\343\201\252
-        assert_eq!(
-            unescape_git_path(
-                "\"\\343\\201\\262\\343\\202\\211\\343\\201\\214\\343\\201\\252.txt\""
-            ),
-            "ひらがな.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_japanese_katakana() {
-        // Japanese Katakana "カタカナ" = \343\202\253\343\202\277\343\202\253\343\203\212
-        assert_eq!(
-            unescape_git_path(
-                "\"\\343\\202\\253\\343\\202\\277\\343\\202\\253\\343\\203\\212.txt\""
-            ),
-            "カタカナ.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_korean_hangul() {
-        // Korean Hangul "한글" = \355\225\234\352\270\200
-        assert_eq!(
-            unescape_git_path("\"\\355\\225\\234\\352\\270\\200.txt\""),
-            "한글.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_traditional_chinese() {
-        // Traditional Chinese "繁體" = \347\271\201\351\253\224
-        assert_eq!(
-            unescape_git_path("\"\\347\\271\\201\\351\\253\\224.txt\""),
-            "繁體.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_mixed_cjk() {
-        // Mixed CJK: "日中韓" (Japanese, Chinese, Korean characters mixed)
-        // 日 = \346\227\245, 中 = \344\270\255, 韓 = \351\237\223
-        assert_eq!(
-            unescape_git_path("\"\\346\\227\\245\\344\\270\\255\\351\\237\\223.txt\""),
-            "日中韓.txt"
-        );
-    }
-
-    // =========================================================================
-    // Phase 2: RTL Scripts Tests (Arabic, Hebrew, Persian, Urdu)
-    // =========================================================================
-
-    #[test]
-    fn test_unescape_arabic() {
-        // Arabic "مرحبا" (marhaba = hello)
-        // م = \331\205, ر = \330\261, ح = \330\255, ب = \330\250, ا = \330\247
-        assert_eq!(
-            unescape_git_path("\"\\331\\205\\330\\261\\330\\255\\330\\250\\330\\247.txt\""),
-            "مرحبا.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_hebrew() {
-        // Hebrew "שלום" (shalom = hello/peace)
-        // ש = \327\251, ל = \327\234, ו = \327\225, ם = \327\235
-        assert_eq!(
-            unescape_git_path("\"\\327\\251\\327\\234\\327\\225\\327\\235.txt\""),
-            "שלום.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_persian() {
-        // Persian "فارسی" (farsi)
-        // ف = \331\201, ا = \330\247, ر = \330\261, س = \330\263, ی = \333\214
-        assert_eq!(
-            unescape_git_path("\"\\331\\201\\330\\247\\330\\261\\330\\263\\333\\214.txt\""),
-            "فارسی.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_urdu() {
-        // Urdu "اردو" (urdu)
-        // ا = \330\247, ر = \330\261, د = \330\257, و = \331\210
-        assert_eq!(
-            unescape_git_path("\"\\330\\247\\330\\261\\330\\257\\331\\210.txt\""),
-            "اردو.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_mixed_rtl_ltr() {
-        // Mixed RTL/LTR: "test_مرحبا_file" (ASCII + Arabic + ASCII)
-        assert_eq!(
-            unescape_git_path(
-                "\"test_\\331\\205\\330\\261\\330\\255\\330\\250\\330\\247_file.txt\""
-            ),
-            "test_مرحبا_file.txt"
-        );
-    }
-
-    // =========================================================================
-    // Phase 3: Indic Scripts Tests (Hindi, Tamil, Bengali, Telugu, Gujarati)
-    // =========================================================================
-
-    #[test]
-    fn test_unescape_hindi_devanagari() {
-        // Hindi "हिंदी" (Hindi in Devanagari script)
-        // ह = \340\244\271, ि = \340\244\277, ं = \340\244\202, द = \340\244\246, ी = \340\245\200
-        assert_eq!(
-            unescape_git_path(
-                "\"\\340\\244\\271\\340\\244\\277\\340\\244\\202\\340\\244\\246\\340\\245\\200.txt\""
-            ),
-            "हिंदी.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_tamil() {
-        // Tamil "தமிழ்" (Tamil)
-        // த = \340\256\244, ம = \340\256\256, ி = \340\256\277, ழ = \340\256\264, ் = \340\257\215
-        assert_eq!(
-            unescape_git_path(
-                "\"\\340\\256\\244\\340\\256\\256\\340\\256\\277\\340\\256\\264\\340\\257\\215.txt\""
-            ),
-            "தமிழ்.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_bengali() {
-        // Bengali "বাংলা" (Bangla)
-        // ব = \340\246\254, া = \340\246\276, ং = \340\246\202, ল = \340\246\262, া = \340\246\276
-        assert_eq!(
-            unescape_git_path(
-                "\"\\340\\246\\254\\340\\246\\276\\340\\246\\202\\340\\246\\262\\340\\246\\276.txt\""
-            ),
-            "বাংলা.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_telugu() {
-        // Telugu "తెలుగు" (Telugu)
-        // త = \340\260\244, ె = \340\261\206, ల = \340\260\262, ు = \340\261\201, గ = \340\260\227, ు = \340\261\201
-        assert_eq!(
-            unescape_git_path(
-                "\"\\340\\260\\244\\340\\261\\206\\340\\260\\262\\340\\261\\201\\340\\260\\227\\340\\261\\201.txt\""
-            ),
-            "తెలుగు.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_gujarati() {
-        // Gujarati "ગુજરાતી" (Gujarati)
-        // ગ = \340\252\227, ુ = \340\253\201, જ = \340\252\234, ર = \340\252\260, ા = \340\252\276, ત = \340\252\244, ી = \340\253\200
-        assert_eq!(
-            unescape_git_path(
-                "\"\\340\\252\\227\\340\\253\\201\\340\\252\\234\\340\\252\\260\\340\\252\\276\\340\\252\\244\\340\\253\\200.txt\""
-            ),
-            "ગુજરાતી.txt"
-        );
-    }
-
-    // =========================================================================
-    // Phase 4: Southeast Asian Scripts Tests (Thai, Vietnamese, Khmer, Lao)
-    // =========================================================================
-
-    #[test]
-    fn test_unescape_thai() {
-        // Thai "ไทย" (Thai)
-        // ไ = \340\271\204, ท = \340\270\227, ย = \340\270\242
-        assert_eq!(
-            unescape_git_path("\"\\340\\271\\204\\340\\270\\227\\340\\270\\242.txt\""),
-            "ไทย.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_vietnamese() {
-        // Vietnamese "tiếng" with tone marks
-        // t = 't', i = 'i', ế = \341\272\277, n = 'n', g = 'g'
-        assert_eq!(
-            unescape_git_path("\"ti\\341\\272\\277ng.txt\""),
-            "tiếng.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_khmer() {
-        // Khmer "ខ្មែរ" (Khmer)
-        // ខ = \341\236\201, ្ = \341\237\222, ម = \341\236\230, ែ = \341\237\202, រ = \341\236\232
-        assert_eq!(
-            unescape_git_path(
-                "\"\\341\\236\\201\\341\\237\\222\\341\\236\\230\\341\\237\\202\\341\\236\\232.txt\""
-            ),
-            "ខ្មែរ.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_lao() {
-        // Lao "ລາວ" (Lao)
-        // ລ = \340\272\245, າ = \340\272\262, ວ = \340\272\247
-        assert_eq!(
-            unescape_git_path("\"\\340\\272\\245\\340\\272\\262\\340\\272\\247.txt\""),
-            "ລາວ.txt"
-        );
-    }
-
-    // =========================================================================
-    // Phase 5: Cyrillic and Greek Scripts Tests
-    // =========================================================================
-
-    #[test]
-    fn test_unescape_russian_cyrillic() {
-        // Russian "Русский" (Russian)
-        // Р = \320\240, у = \321\203, с = \321\201, к = \320\272, и = \320\270, й = \320\271
-        assert_eq!(
-            unescape_git_path(
-                "\"\\320\\240\\321\\203\\321\\201\\321\\201\\320\\272\\320\\270\\320\\271.txt\""
-            ),
-            "Русский.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_ukrainian_cyrillic() {
-        // Ukrainian "Україна" (Ukraine)
-        // У = \320\243, к = \320\272, р = \321\200, а = \320\260, ї = \321\227, н = \320\275, а = \320\260
-        assert_eq!(
-            unescape_git_path(
-                "\"\\320\\243\\320\\272\\321\\200\\320\\260\\321\\227\\320\\275\\320\\260.txt\""
-            ),
-            "Україна.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_greek() {
-        // Greek "Ελλάδα" (Greece)
-        // Ε = \316\225, λ = \316\273, λ = \316\273, ά = \316\254, δ = \316\264, α = \316\261
-        assert_eq!(
-            unescape_git_path(
-                "\"\\316\\225\\316\\273\\316\\273\\316\\254\\316\\264\\316\\261.txt\""
-            ),
-            "Ελλάδα.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_greek_polytonic() {
-        // Greek polytonic "Ἑλληνική" (Hellenic with diacritics)
-        // Ἑ = \341\274\231, λ = \316\273, λ = \316\273, η = \316\267, ν = \316\275, ι = \316\271, κ = \316\272, ή = \316\256
-        assert_eq!(
-            unescape_git_path(
-                "\"\\341\\274\\231\\316\\273\\316\\273\\316\\267\\316\\275\\316\\271\\316\\272\\316\\256.txt\""
-            ),
-            "Ἑλληνική.txt"
-        );
-    }
-
-    // =========================================================================
-    // Phase 6: Extended Emoji Tests (ZWJ, skin tones, flags)
-    // =========================================================================
-
-    #[test]
-    fn test_unescape_emoji_skin_tone() {
-        // Emoji with skin tone modifier 👋🏽 = 👋 (U+1F44B) + 🏽 (U+1F3FD)
-        // 👋 = \360\237\221\213, 🏽 = \360\237\217\275
-        assert_eq!(
-            unescape_git_path("\"\\360\\237\\221\\213\\360\\237\\217\\275.txt\""),
-            "👋🏽.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_emoji_zwj_sequence() {
-        // ZWJ emoji sequence: 👨‍💻 (man technologist) = man + ZWJ + laptop
-        // 👨 = \360\237\221\250, ZWJ = \342\200\215, 💻 = \360\237\222\273
-        assert_eq!(
-            unescape_git_path("\"\\360\\237\\221\\250\\342\\200\\215\\360\\237\\222\\273.txt\""),
-            "👨‍💻.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_emoji_flag() {
-        // Flag emoji 🇯🇵 (Japan) = regional indicator J + regional indicator P
-        // 🇯 = \360\237\207\257, 🇵 = \360\237\207\265
-        assert_eq!(
-            unescape_git_path("\"\\360\\237\\207\\257\\360\\237\\207\\265.txt\""),
-            "🇯🇵.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_multiple_emoji() {
-        // Multiple emoji: 🚀🎉 (rocket + party)
-        // 🚀 = \360\237\232\200, 🎉 = \360\237\216\211
-        assert_eq!(
-            unescape_git_path("\"\\360\\237\\232\\200\\360\\237\\216\\211.txt\""),
-            "🚀🎉.txt"
-        );
-    }
-
-    // =========================================================================
-    // Phase 7: Special Unicode Characters Tests (math, currency, symbols)
-    // =========================================================================
-
-    #[test]
-    fn test_unescape_math_symbols() {
-        // Math symbols: ∑ (summation) = \342\210\221
-        assert_eq!(unescape_git_path("\"\\342\\210\\221.txt\""), "∑.txt");
-    }
-
-    #[test]
-    fn test_unescape_currency_symbols() {
-        // Currency: € (euro) = \342\202\254
-        assert_eq!(unescape_git_path("\"\\342\\202\\254.txt\""), "€.txt");
-    }
-
-    #[test]
-    fn test_unescape_box_drawing() {
-        // Box drawing: ┌ (box drawings light down and right) = \342\224\214
-        assert_eq!(unescape_git_path("\"\\342\\224\\214.txt\""), "┌.txt");
-    }
-
-    #[test]
-    fn test_unescape_dingbats() {
-        // Dingbats: ✓ (check mark) = \342\234\223
-        assert_eq!(unescape_git_path("\"\\342\\234\\223.txt\""), "✓.txt");
-    }
-
-    // =========================================================================
-    // Phase 8: Unicode Normalization Tests (NFC vs NFD)
-    // =========================================================================
-
-    #[test]
-    fn test_unescape_nfc_precomposed() {
-        // NFC precomposed: é (U+00E9) = \303\251
-        assert_eq!(unescape_git_path("\"caf\\303\\251.txt\""), "café.txt");
-    }
-
-    #[test]
-    fn test_unescape_nfd_decomposed() {
-        // NFD decomposed: e + combining acute (U+0065 + U+0301) = e + \314\201
-        assert_eq!(
-            unescape_git_path("\"cafe\\314\\201.txt\""),
-            "cafe\u{0301}.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_combining_diaeresis() {
-        // Combining diaeresis: i + ̈ (U+0069 + U+0308) = i + \314\210
-        assert_eq!(
-            unescape_git_path("\"nai\\314\\210ve.txt\""),
-            "nai\u{0308}ve.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_angstrom() {
-        // Å (A with ring above, U+00C5) = \303\205
-        assert_eq!(
-            unescape_git_path("\"\\303\\205ngstr\\303\\266m.txt\""),
-            "Ångström.txt"
-        );
-    }
-
-    // =========================================================================
-    // Phase 9: Escape Sequence Edge Cases
-    // =========================================================================
-
-    #[test]
-    fn test_unescape_incomplete_octal() {
-        // Incomplete octal at end of string
-        assert_eq!(unescape_git_path("\"file\\34\""), "file\x1c");
-        assert_eq!(unescape_git_path("\"file\\3\""), "file\x03");
-    }
-
-    #[test]
-    fn test_unescape_invalid_octal() {
-        // Invalid octal digit (8 and 9 are not valid octal)
-        assert_eq!(
-            unescape_git_path("\"file\\389.txt\""),
-            "file\x038\u{0039}.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_backslash_only() {
-        // Backslash at end without following character
-        assert_eq!(unescape_git_path("\"file\\\""), "file\\");
-    }
-
-    #[test]
-    fn test_unescape_mixed_escapes() {
-        // Mix of different escape types
-        assert_eq!(
-            unescape_git_path("\"path\\nwith\\ttab\\\\and\\344\\270\\255.txt\""),
-            "path\nwith\ttab\\and中.txt"
-        );
-    }
-
-    #[test]
-    fn test_unescape_empty_quoted() {
-        // Empty quoted string
-        assert_eq!(unescape_git_path("\"\""), "");
-    }
-
-    #[test]
-    fn test_unescape_unmatched_quotes() {
-        // Unmatched quotes - returned as-is
-        assert_eq!(unescape_git_path("\"unmatched"), "\"unmatched");
-        assert_eq!(unescape_git_path("unmatched\""), "unmatched\"");
-    }
-
     // =========================================================================
     // normalize_to_posix Tests
     // =========================================================================
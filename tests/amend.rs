@@ -1,5 +1,6 @@
 #[macro_use]
 mod repos;
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
 use repos::test_file::ExpectedLineExt;
 use repos::test_repo::TestRepo;
 
@@ -489,3 +490,42 @@ fn test_amend_repeated_round_trips_preserve_exact_line_authorship() {
         "// AI trailing note".ai()
     ]);
 }
+
+#[test]
+fn test_amend_supersedes_original_authorship_log() {
+    let repo = TestRepo::new();
+    let mut base = repo.filename("base.txt");
+    base.set_contents(lines!["human line"]);
+    let mut first = repo.filename("first.rs");
+    first.set_contents(lines!["fn first() {}".ai()]);
+    let original = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // Amend in a new AI file
+    let mut second = repo.filename("second.rs");
+    second.set_contents(lines!["fn second() {}".ai(), "fn third() {}".ai()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.git(&["commit", "--amend", "-m", "Initial commit (amended)"])
+        .unwrap();
+    let amended = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    assert_ne!(amended, original.commit_sha);
+
+    let raw_note = repo
+        .git_og(&["notes", "--ref=ai", "show", &amended])
+        .expect("the amended commit should have an authorship log");
+    let log = AuthorshipLog::deserialize_from_string(raw_note.trim()).unwrap();
+    let mut files: Vec<&str> = log
+        .attestations
+        .iter()
+        .map(|attestation| attestation.file_path.as_str())
+        .collect();
+    files.sort_unstable();
+    assert_eq!(files, vec!["first.rs", "second.rs"]);
+    assert_eq!(log.metadata.base_commit_sha, amended);
+
+    // The original commit is no longer reachable, so its stale log is gone
+    assert!(
+        repo.git_og(&["notes", "--ref=ai", "show", &original.commit_sha])
+            .is_err()
+    );
+    second.assert_lines_and_blame(lines!["fn second() {}".ai(), "fn third() {}".ai()]);
+}
@@ -0,0 +1,142 @@
+#[macro_use]
+mod repos;
+
+use git_ai::authorship::authorship_log::{LineRange, PromptRecord};
+use git_ai::authorship::authorship_log_serialization::{
+    AttestationEntry, AuthorshipLog, FileAttestation,
+};
+use git_ai::authorship::notes::{fetch_notes, read_notes, write_notes};
+use git_ai::authorship::transcript::Message;
+use git_ai::authorship::working_log::AgentId;
+use git_ai::git::repository as GitAiRepository;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::process::Command;
+
+fn log_with_ai_line(commit_sha: &str, file: &str, line: u32) -> AuthorshipLog {
+    let mut log = AuthorshipLog::new();
+    log.metadata.base_commit_sha = commit_sha.to_string();
+    log.metadata.prompts.insert(
+        "prompt1".to_string(),
+        PromptRecord {
+            agent_id: AgentId {
+                tool: "cursor".to_string(),
+                id: "session1".to_string(),
+                model: "claude-3-sonnet".to_string(),
+            },
+            human_author: Some("Test User <test@example.com>".to_string()),
+            messages: vec![Message::user("Add a helper".to_string(), None)],
+            total_additions: 1,
+            total_deletions: 0,
+            accepted_lines: 1,
+            overriden_lines: 0,
+            messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
+        },
+    );
+    let mut attestation = FileAttestation::new(file.to_string());
+    attestation.add_entry(AttestationEntry::new(
+        "prompt1".to_string(),
+        vec![LineRange::Single(line)],
+    ));
+    log.attestations.push(attestation);
+    log
+}
+
+#[test]
+fn test_written_note_is_read_back_and_counted_in_stats() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn human() {}", "fn helper() {}"]);
+    let commit_sha = repo.stage_all_and_commit("Add helper").unwrap().commit_sha;
+    assert_eq!(repo.stats().unwrap().ai_additions, 0);
+
+    let gitai_repo =
+        GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    write_notes(
+        &gitai_repo,
+        &commit_sha,
+        &log_with_ai_line(&commit_sha, "lib.rs", 2),
+    )
+    .unwrap();
+
+    let raw_note = repo
+        .git_og(&["notes", "--ref=ai", "show", &commit_sha])
+        .expect("the log should be stored under refs/notes/ai");
+    assert!(raw_note.contains("lib.rs"));
+    assert_eq!(repo.git_og(&["status", "--porcelain"]).unwrap().trim(), "");
+
+    let log = read_notes(&gitai_repo, &commit_sha).expect("note should be readable");
+    assert_eq!(log.ai_count(), 1);
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.ai_additions, 1);
+    assert_eq!(stats.human_additions, 1);
+}
+
+#[test]
+fn test_notes_for_commit_authored_elsewhere_are_fetched() {
+    let (local, upstream) = TestRepo::new_with_remote();
+    let mut file = local.filename("lib.rs");
+    file.set_contents(lines!["fn human() {}", "fn helper() {}"]);
+    let commit_sha = local.stage_all_and_commit("Add helper").unwrap().commit_sha;
+
+    let local_repo =
+        GitAiRepository::find_repository_in_path(local.path().to_str().unwrap()).unwrap();
+    write_notes(
+        &local_repo,
+        &commit_sha,
+        &log_with_ai_line(&commit_sha, "lib.rs", 2),
+    )
+    .unwrap();
+    local
+        .git(&["push", "origin", "HEAD"])
+        .expect("push should send the branch and its notes");
+
+    // A plain clone on another machine brings the commit but not refs/notes/ai
+    let other_path = std::env::temp_dir().join(format!(
+        "{}-other",
+        upstream.path().file_name().unwrap().to_string_lossy()
+    ));
+    let clone = Command::new("git")
+        .args([
+            "clone",
+            upstream.path().to_str().unwrap(),
+            other_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("failed to clone upstream");
+    assert!(clone.status.success());
+    let other = TestRepo::new_at_path(&other_path);
+    let other_repo =
+        GitAiRepository::find_repository_in_path(other.path().to_str().unwrap()).unwrap();
+    assert!(read_notes(&other_repo, &commit_sha).is_none());
+
+    assert!(fetch_notes(&other_repo, "origin").unwrap());
+    let log = read_notes(&other_repo, &commit_sha).expect("fetched note should be readable");
+    assert_eq!(log.ai_count(), 1);
+    assert_eq!(other.stats().unwrap().ai_additions, 1);
+}
+
+#[test]
+fn test_committed_note_lists_files_in_path_order() {
+    let repo = TestRepo::new();
+    for name in ["zeta.rs", "src/数据.rs", "Alpha.rs", "alpha.rs"] {
+        let mut file = repo.filename(name);
+        file.set_contents(lines!["fn ai() {}".ai()]);
+    }
+    let commit_sha = repo.stage_all_and_commit("Add files").unwrap().commit_sha;
+
+    let raw_note = repo
+        .git_og(&["notes", "--ref=ai", "show", &commit_sha])
+        .unwrap();
+    let files: Vec<&str> = raw_note
+        .lines()
+        .take_while(|line| *line != "---")
+        .filter(|line| !line.starts_with("  "))
+        .collect();
+    assert_eq!(
+        files,
+        vec!["Alpha.rs", "alpha.rs", "src/数据.rs", "zeta.rs"]
+    );
+}
@@ -163,6 +163,67 @@ fn test_blame_success_json_format() {
 
     assert!(json["lines"].is_object());
     assert!(json["prompts"].is_object());
+
+    let details = json["line_details"]
+        .as_array()
+        .expect("line_details should be an array");
+    assert_eq!(details.len(), 2);
+    assert_eq!(details[0]["line_number"], 1);
+    assert_eq!(details[0]["content"], "Human line");
+    assert_eq!(details[0]["author_type"], "Human");
+    assert_eq!(details[1]["content"], "AI line");
+    assert_eq!(details[1]["author_type"], "Ai");
+}
+
+#[test]
+fn test_blame_working_tree_attributes_unstaged_ai_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("pending.txt");
+    file.set_contents(lines!["Committed line".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    // Checkpointed but neither staged nor committed
+    file.set_contents(lines![
+        "Committed line".human(),
+        "AI line one".ai(),
+        "AI line two".ai(),
+        "Human edit".human(),
+    ]);
+
+    let output = repo
+        .git_ai(&["blame", "--working-tree", "--json", "pending.txt"])
+        .unwrap();
+    let json: serde_json::Value =
+        serde_json::from_str(&output).expect("Output should be valid JSON");
+    let details = json["line_details"].as_array().unwrap();
+    assert_eq!(details.len(), 4);
+    let author_types: Vec<&str> = details
+        .iter()
+        .map(|line| line["author_type"].as_str().unwrap())
+        .collect();
+    assert_eq!(author_types, vec!["Human", "Ai", "Ai", "Human"]);
+    assert!(
+        details[1]["commit_sha"]
+            .as_str()
+            .unwrap()
+            .bytes()
+            .all(|b| b == b'0')
+    );
+    assert_eq!(json["lines"]["2-3"].as_str().map(str::len), Some(16));
+
+    let output = repo
+        .git_ai(&["blame", "--working-tree", "pending.txt"])
+        .unwrap();
+    let ai_lines: Vec<&str> = output
+        .lines()
+        .filter(|line| line.contains("mock_ai"))
+        .collect();
+    assert_eq!(ai_lines.len(), 2, "{}", output);
+    assert!(ai_lines.iter().all(|line| line.contains("AI line")));
+
+    // Without the flag, uncommitted lines are left as git reports them
+    let output = repo.git_ai(&["blame", "pending.txt"]).unwrap();
+    assert!(!output.contains("mock_ai"), "{}", output);
 }
 
 // =============================================================================
@@ -570,6 +631,124 @@ fn test_blame_format_json_line_ranges() {
     );
 }
 
+#[test]
+fn test_blame_range_restricts_output_to_slice() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("测试文件.rs");
+
+    file.set_contents(lines![
+        "fn one() {}".human(),
+        "fn two() {}".ai(),
+        "fn three() {}".human(),
+        "fn four() {}".ai()
+    ]);
+    repo.stage_all_and_commit("Test").unwrap();
+
+    let output = repo
+        .git_ai(&["blame", "--json", "--range", "2,3", "测试文件.rs"])
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).expect("Should be valid JSON");
+
+    let details = json["line_details"].as_array().unwrap();
+    let line_numbers: Vec<u64> = details
+        .iter()
+        .map(|line| line["line_number"].as_u64().unwrap())
+        .collect();
+    assert_eq!(line_numbers, vec![2, 3]);
+    assert_eq!(details[0]["author_type"], "Ai");
+    assert_eq!(details[1]["content"], "fn three() {}");
+    let ranges: Vec<&String> = json["lines"].as_object().unwrap().keys().collect();
+    assert_eq!(ranges, vec!["2"]);
+
+    let text = repo
+        .git_ai(&["blame", "--range=2,3", "测试文件.rs"])
+        .unwrap();
+    assert_eq!(text.lines().count(), 2);
+    assert!(text.contains("fn two() {}"));
+    assert!(text.contains("fn three() {}"));
+    assert!(!text.contains("fn four() {}"));
+}
+
+#[test]
+fn test_blame_show_deleted_lists_deleted_ai_lines_with_deleting_commit() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+
+    file.set_contents(lines![
+        "fn kept() {}".human(),
+        "fn ai_one() {}".ai(),
+        "fn ai_two() {}".ai(),
+        "fn human() {}".human()
+    ]);
+    let first = repo.stage_all_and_commit("Add functions").unwrap();
+    file.delete_range(1, 4);
+    let deletion = repo.stage_all_and_commit("Remove functions").unwrap();
+
+    let range = format!("{}..HEAD", first.commit_sha);
+    let output = repo
+        .git_ai(&["blame", "lib.rs", "--show-deleted", &range, "--json"])
+        .unwrap();
+    let deleted: serde_json::Value = serde_json::from_str(&output).expect("Should be valid JSON");
+    let deleted = deleted.as_array().unwrap();
+    assert_eq!(deleted.len(), 3);
+    for line in deleted {
+        assert_eq!(line["deleted_by"], deletion.commit_sha.as_str());
+    }
+    assert_eq!(deleted[0]["content"], "fn ai_one() {}");
+    assert_eq!(deleted[0]["line"], 2);
+    assert_eq!(deleted[0]["ai"], true);
+    assert_eq!(deleted[1]["content"], "fn ai_two() {}");
+    assert_eq!(deleted[1]["ai"], true);
+    assert_eq!(deleted[2]["content"], "fn human() {}");
+    assert_eq!(deleted[2]["ai"], false);
+
+    let text = repo
+        .git_ai(&["blame", "lib.rs", &format!("--show-deleted={}", range)])
+        .unwrap();
+    let short_sha = &deletion.commit_sha[..7];
+    assert_eq!(
+        text.lines()
+            .filter(|line| line.starts_with(short_sha) && line.contains("AI"))
+            .count(),
+        2
+    );
+    assert!(text.contains("-fn ai_one() {}"));
+    assert!(!text.contains("fn kept() {}"));
+}
+
+#[test]
+fn test_blame_in_bare_clone_reads_file_from_head() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("src/lib.rs");
+    file.set_contents(lines!["fn human() {}".human(), "fn ai() {}".ai()]);
+    repo.stage_all_and_commit("Add lib").unwrap();
+
+    let temp = tempfile::tempdir().unwrap();
+    let bare = temp.path().join("repo.git");
+    repo.git_og(&["clone", "--bare", ".", bare.to_str().unwrap()])
+        .unwrap();
+    repo.git_og(&[
+        "push",
+        bare.to_str().unwrap(),
+        "refs/notes/ai:refs/notes/ai",
+    ])
+    .unwrap();
+
+    let output = repo
+        .git_ai_from_working_dir(&bare, &["blame", "src/lib.rs"])
+        .unwrap();
+    let ai_line = output
+        .lines()
+        .find(|line| line.contains("fn ai() {}"))
+        .unwrap();
+    assert!(ai_line.contains("mock_ai"));
+    let human_line = output
+        .lines()
+        .find(|line| line.contains("fn human() {}"))
+        .unwrap();
+    assert!(!human_line.contains("mock_ai"));
+}
+
 #[test]
 fn test_blame_format_default_with_flags() {
     // Output format: Default format with various flags
@@ -632,6 +811,8 @@ fn test_blame_ai_authorship_hunk_splitting() {
             accepted_lines: 1,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
 
@@ -652,6 +833,8 @@ fn test_blame_ai_authorship_hunk_splitting() {
             accepted_lines: 1,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
 
@@ -712,6 +895,8 @@ fn test_blame_ai_authorship_no_splitting() {
             accepted_lines: 2,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
 
@@ -1124,6 +1124,8 @@ fn test_blame_ai_human_author() {
             accepted_lines: 1,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
 
@@ -1145,6 +1147,8 @@ fn test_blame_ai_human_author() {
             accepted_lines: 1,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
 
@@ -1185,3 +1189,71 @@ fn test_blame_ai_human_author() {
         ]
     );
 }
+
+#[test]
+fn test_blame_with_churn_annotates_hunks_with_commit_deletions() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("churn.rs");
+    file.set_contents(lines!["one", "two", "three", "four", "five", "six"]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    // AI replaces three lines with two
+    file.set_contents(lines![
+        "one".human(),
+        "ai a".ai(),
+        "ai b".ai(),
+        "five".human(),
+        "six".human(),
+    ]);
+    let ai_commit = repo.stage_all_and_commit("AI rewrite").unwrap();
+
+    // Record exactly the three AI deletions in the commit's attestation log
+    let mut authorship_log = ai_commit.authorship_log.clone();
+    assert!(!authorship_log.metadata.prompts.is_empty());
+    for (index, prompt) in authorship_log.metadata.prompts.values_mut().enumerate() {
+        prompt.total_deletions = if index == 0 { 3 } else { 0 };
+    }
+    let gitai_repo = GitAiRepository::find_repository_in_path(repo.path().to_str().unwrap())
+        .expect("Failed to find repository");
+    notes_add(
+        &gitai_repo,
+        &ai_commit.commit_sha,
+        &authorship_log.serialize_to_string().unwrap(),
+    )
+    .unwrap();
+
+    // A human drops two lines without any checkpoint
+    std::fs::write(repo.path().join("churn.rs"), "one\nai a\nai b\nseven\n").unwrap();
+    let human_commit = repo.stage_all_and_commit("Human cleanup").unwrap();
+
+    let raw = repo
+        .git_ai(&["blame", "--json", "--with-churn", "churn.rs"])
+        .expect("blame --json --with-churn should succeed");
+    let json: serde_json::Value =
+        serde_json::from_str(&raw[raw.find('{').unwrap()..=raw.rfind('}').unwrap()]).unwrap();
+    let churn = &json["churn"];
+    assert_eq!(churn[&ai_commit.commit_sha]["ai_deletions"], 3);
+    assert_eq!(churn[&ai_commit.commit_sha]["human_deletions"], 0);
+    assert_eq!(churn[&human_commit.commit_sha]["ai_deletions"], 0);
+    assert_eq!(churn[&human_commit.commit_sha]["human_deletions"], 2);
+
+    // Only the first line of each hunk carries the annotation
+    let output = repo
+        .git_ai(&["blame", "--with-churn", "churn.rs"])
+        .expect("blame --with-churn should succeed");
+    let line_of = |content: &str| {
+        output
+            .lines()
+            .find(|line| line.ends_with(content))
+            .unwrap_or_else(|| panic!("no blame line for {}: {}", content, output))
+            .to_string()
+    };
+    assert!(line_of(") ai a").contains("-3 AI -0 human"), "{}", output);
+    assert!(!line_of(") ai b").contains("AI -"), "{}", output);
+    assert!(line_of(") seven").contains("-0 AI -2 human"), "{}", output);
+
+    let err = repo
+        .git_ai(&["blame", "--with-churn", "--porcelain", "churn.rs"])
+        .expect_err("--with-churn should reject porcelain output");
+    assert!(err.contains("--with-churn cannot be combined"), "{}", err);
+}
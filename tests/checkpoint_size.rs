@@ -88,3 +88,80 @@ fn test_checkpoint_size_logging_large_ai_rewrites() {
         );
     }
 }
+
+#[test]
+fn test_checkpoint_attributes_files_over_max_size_as_a_whole() {
+    let repo = TestRepo::new();
+
+    // ~1.2 MiB, over the default 1 MiB limit
+    let line_count = 20_000;
+    let mut content = String::with_capacity(line_count * 64);
+    for line_idx in 0..line_count {
+        content.push_str(&format!(
+            "const generated_{line_idx:05} = \"{}\";\n",
+            "x".repeat(32)
+        ));
+    }
+    fs::write(repo.path().join("bundle.js"), &content).expect("should write large file");
+
+    let output = repo
+        .git_ai_with_env(
+            &["checkpoint", "mock_ai", "bundle.js"],
+            &[("GIT_AI_DEBUG", "1")],
+        )
+        .expect("git-ai checkpoint should succeed");
+    assert!(
+        output.contains(&format!(
+            "bundle.js is {} bytes, over the {} byte limit",
+            content.len(),
+            1024 * 1024
+        )),
+        "checkpoint should log the oversized file, got:\n{output}"
+    );
+
+    let checkpoints = repo
+        .current_working_logs()
+        .read_all_checkpoints()
+        .expect("should read checkpoints");
+    let entry = checkpoints
+        .iter()
+        .flat_map(|checkpoint| &checkpoint.entries)
+        .find(|entry| entry.file == "bundle.js")
+        .expect("bundle.js should have a working log entry");
+    assert_eq!(entry.attributions.len(), 1);
+    assert_eq!(entry.line_attributions.len(), 1);
+    assert_eq!(entry.line_attributions[0].start_line, 1);
+    assert_eq!(entry.line_attributions[0].end_line, line_count as u32);
+
+    repo.stage_all_and_commit("add bundle").unwrap();
+
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.git_diff_added_lines, line_count as u32);
+    assert_eq!(stats.ai_additions, line_count as u32);
+    assert_eq!(stats.human_additions, 0);
+}
+
+#[test]
+fn test_checkpoint_max_file_size_flag_overrides_limit() {
+    let repo = TestRepo::new();
+
+    let content = "line\n".repeat(400);
+    fs::write(repo.path().join("small.txt"), &content).expect("should write file");
+
+    let output = repo
+        .git_ai_with_env(
+            &["checkpoint", "mock_ai", "small.txt", "--max-file-size=1024"],
+            &[("GIT_AI_DEBUG", "1")],
+        )
+        .expect("git-ai checkpoint should succeed");
+    assert!(
+        output.contains("small.txt is 2000 bytes, over the 1024 byte limit"),
+        "checkpoint should honour --max-file-size, got:\n{output}"
+    );
+
+    repo.stage_all_and_commit("add small").unwrap();
+
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.git_diff_added_lines, 400);
+    assert_eq!(stats.ai_additions, 400);
+}
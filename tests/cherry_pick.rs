@@ -100,6 +100,43 @@ fn test_single_commit_cherry_pick() {
     );
 }
 
+/// A cherry-pick made outside git-ai's hooks recovers authorship from the `-x` source line
+#[test]
+fn test_cherry_pick_without_hooks_recovers_authorship_from_source() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Initial content"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    repo.filename("feature.rs").set_contents(lines![
+        "fn one() {}".ai(),
+        "fn two() {}".ai(),
+        "fn three() {}".ai(),
+    ]);
+    repo.stage_all_and_commit("Add AI feature").unwrap();
+    let feature_commit = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+    repo.git(&["checkout", &main_branch]).unwrap();
+    repo.git_og(&["cherry-pick", "-x", &feature_commit])
+        .expect("raw cherry-pick should succeed");
+    let head_commit = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    assert!(
+        read_authorship_note(&repo, &head_commit).is_none(),
+        "raw cherry-pick should not have written a note"
+    );
+
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.ai_additions, 3);
+    assert_eq!(stats.human_additions, 0);
+    assert!(
+        read_authorship_note(&repo, &head_commit).is_some(),
+        "recovered authorship should be stored as a note"
+    );
+}
+
 #[test]
 fn test_cherry_pick_preserves_human_only_commit_note_metadata() {
     let repo = TestRepo::new();
@@ -180,6 +217,8 @@ fn test_cherry_pick_preserves_prompt_only_commit_note_metadata() {
             accepted_lines: 0,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
 
@@ -167,7 +167,8 @@ fn benchmark_stats(repo_path: &Path) -> StatsBreakdown {
         .id();
 
     let git_numstat_start = Instant::now();
-    let _git_numstat = get_git_diff_stats(&repo, &head_sha, &[]).expect("git numstat failed");
+    let _git_numstat =
+        get_git_diff_stats(&repo, &head_sha, &[], false, None).expect("git numstat failed");
     let git_numstat = git_numstat_start.elapsed();
 
     let diff_ai_start = Instant::now();
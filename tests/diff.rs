@@ -1065,3 +1065,87 @@ fn test_diff_ignores_git_diff_opts_env_for_internal_diff() {
         ai_diff
     );
 }
+
+#[test]
+fn test_diff_attributes_deleted_lines_from_parent() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("mixed.rs");
+    file.set_contents(lines!["fn keep() {}".human(), "fn generated() {}".ai()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    // Replace the AI line with a human line and add another AI line
+    file.set_contents(lines![
+        "fn keep() {}".human(),
+        "fn handwritten() {}".human(),
+        "fn suggested() {}".ai(),
+    ]);
+    let commit = repo.stage_all_and_commit("Mixed changes").unwrap();
+
+    let output = repo
+        .git_ai(&["diff", &commit.commit_sha, "--no-color"])
+        .expect("git-ai diff should succeed");
+    assert!(!output.contains("\x1b["), "--no-color output: {}", output);
+
+    let lines = parse_diff_output(&output);
+    assert_diff_lines_exact(
+        &lines,
+        &[
+            (" ", "fn keep()", None),
+            ("-", "fn generated()", Some("ai")),
+            ("+", "fn handwritten()", Some("human")),
+            ("+", "fn suggested()", Some("ai")),
+        ],
+    );
+
+    // --color forces ANSI output even though stdout is not a terminal
+    let colored = repo
+        .git_ai(&["diff", "--color", &commit.commit_sha])
+        .expect("git-ai diff --color should succeed");
+    assert!(
+        colored.contains("\x1b[31m-fn generated() {}\x1b[0m"),
+        "deletion should be red: {}",
+        colored
+    );
+    assert!(
+        colored.contains("\x1b[32m+fn suggested() {}\x1b[0m"),
+        "addition should be green: {}",
+        colored
+    );
+}
+
+#[test]
+fn test_no_color_env_strips_escape_sequences() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn keep() {}".human(), "fn generated() {}".ai()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+    file.set_contents(lines!["fn keep() {}".human(), "fn suggested() {}".ai()]);
+    let commit = repo.stage_all_and_commit("Replace generated").unwrap();
+
+    // Debug builds prefix their stderr diagnostics with color, which is not user output
+    let no_color = [("NO_COLOR", "1"), ("GIT_AI_DEBUG", "0")];
+    for args in [
+        vec!["diff", commit.commit_sha.as_str()],
+        vec!["stats", commit.commit_sha.as_str()],
+        vec!["log"],
+        vec!["blame", "lib.rs"],
+    ] {
+        let output = repo
+            .git_ai_with_env(&args, &no_color)
+            .unwrap_or_else(|e| panic!("git-ai {:?} should succeed: {}", args, e));
+        assert!(
+            !output.contains('\x1b'),
+            "git-ai {:?} printed escape sequences with NO_COLOR set: {}",
+            args,
+            output
+        );
+    }
+
+    // An explicit --color still wins over NO_COLOR
+    let colored = repo
+        .git_ai_with_env(&["diff", "--color", &commit.commit_sha], &no_color)
+        .unwrap();
+    assert!(colored.contains("\x1b["), "{}", colored);
+}
@@ -0,0 +1,63 @@
+#[macro_use]
+mod repos;
+
+use git_ai::error::GitAiError;
+use git_ai::git::refs::get_reference_as_authorship_log_v3;
+use git_ai::git::repository::find_repository_in_path;
+use repos::test_repo::TestRepo;
+
+fn open(repo: &TestRepo) -> git_ai::git::repository::Repository {
+    find_repository_in_path(repo.path().to_str().unwrap()).unwrap()
+}
+
+#[test]
+fn test_non_repo_directory_is_not_a_git_repo() {
+    let dir = tempfile::tempdir().unwrap();
+    let err = find_repository_in_path(dir.path().to_str().unwrap())
+        .err()
+        .expect("a plain directory is not a repository");
+    match err {
+        GitAiError::NotAGitRepo(path) => assert_eq!(path, dir.path()),
+        other => panic!("expected NotAGitRepo, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_commit_without_note_is_missing_attestation() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("plain.txt");
+    file.set_contents(lines!["human line"]);
+    let commit = repo.stage_all_and_commit("Initial commit").unwrap();
+    repo.git_og(&[
+        "notes",
+        "--ref=ai",
+        "remove",
+        "--ignore-missing",
+        &commit.commit_sha,
+    ])
+    .unwrap();
+
+    let err = get_reference_as_authorship_log_v3(&open(&repo), &commit.commit_sha).unwrap_err();
+    assert!(
+        matches!(&err, GitAiError::MissingAttestation(sha) if *sha == commit.commit_sha),
+        "expected MissingAttestation, got {:?}",
+        err
+    );
+}
+
+#[test]
+fn test_diff_against_unknown_revision_is_diff_failed() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("plain.txt");
+    file.set_contents(lines!["human line"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let err = open(&repo)
+        .diff_added_lines("no-such-revision", "HEAD", None)
+        .unwrap_err();
+    assert!(
+        matches!(err, GitAiError::DiffFailed(_)),
+        "expected DiffFailed, got {:?}",
+        err
+    );
+}
@@ -0,0 +1,83 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+const SUCCESS: i32 = 0;
+const ERROR: i32 = 1;
+const NO_RESULTS: i32 = 2;
+const THRESHOLD_EXCEEDED: i32 = 3;
+const MISSING_ATTESTATION: i32 = 4;
+const VERIFICATION_FAILED: i32 = 5;
+
+/// A base commit followed by one with two AI lines and one human line.
+fn repo_with_ai_commit() -> (TestRepo, String) {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}".human()]);
+    let base = repo.stage_all_and_commit("Base").unwrap().commit_sha;
+
+    file.set_contents(lines![
+        "fn base() {}".human(),
+        "fn ai1() {}".ai(),
+        "fn ai2() {}".ai(),
+        "fn human() {}".human(),
+    ]);
+    repo.stage_all_and_commit("Add helpers").unwrap();
+    (repo, base)
+}
+
+#[test]
+fn test_stats_exit_codes() {
+    let (repo, base) = repo_with_ai_commit();
+    let range = format!("{}..HEAD", base);
+
+    assert_eq!(repo.git_ai_exit_code(&["stats", "--json"]), SUCCESS);
+    assert_eq!(
+        repo.git_ai_exit_code(&["stats", "--max-ai-percentage", "50"]),
+        THRESHOLD_EXCEEDED
+    );
+    assert_eq!(
+        repo.git_ai_exit_code(&["stats", &range, "--min-human-lines", "2"]),
+        THRESHOLD_EXCEEDED
+    );
+    assert_eq!(
+        repo.git_ai_exit_code(&["stats", "--max-ai-percentage", "not-a-number"]),
+        ERROR
+    );
+    assert_eq!(repo.git_ai_exit_code(&["stats", "no-such-rev"]), ERROR);
+
+    repo.git_og(&["notes", "--ref=ai", "remove", "HEAD"])
+        .unwrap();
+    assert_eq!(
+        repo.git_ai_exit_code(&["stats", &range, "--fail-on-missing-attestation"]),
+        MISSING_ATTESTATION
+    );
+    // Without strict mode the unattested commit is counted as human
+    assert_eq!(repo.git_ai_exit_code(&["stats", &range]), SUCCESS);
+}
+
+#[test]
+fn test_verify_exit_codes() {
+    let (repo, _) = repo_with_ai_commit();
+    assert_eq!(repo.git_ai_exit_code(&["verify"]), SUCCESS);
+
+    // Line 1 predates the commit, so an attestation covering it fails verification
+    let note = repo.git_og(&["notes", "--ref=ai", "show", "HEAD"]).unwrap();
+    let corrupted = note.replacen(" 2-3\n", " 1-3\n", 1);
+    repo.git_og(&["notes", "--ref=ai", "add", "-f", "-m", &corrupted, "HEAD"])
+        .unwrap();
+    assert_eq!(repo.git_ai_exit_code(&["verify"]), VERIFICATION_FAILED);
+
+    assert_eq!(repo.git_ai_exit_code(&["verify", "--bogus"]), ERROR);
+}
+
+#[test]
+fn test_search_without_results_exits_with_no_results() {
+    let (repo, base) = repo_with_ai_commit();
+    // The base commit is human-only, so it has no prompts to find
+    assert_eq!(
+        repo.git_ai_exit_code(&["search", "--commit", &base]),
+        NO_RESULTS
+    );
+}
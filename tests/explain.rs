@@ -0,0 +1,52 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::Value;
+
+fn explain_json(repo: &TestRepo, target: &str) -> Vec<Value> {
+    let raw = repo
+        .git_ai(&["explain", target, "--json"])
+        .expect("git-ai explain should succeed");
+    let start = raw.find('[').expect("output should contain a JSON array");
+    let end = raw.rfind(']').unwrap();
+    serde_json::from_str(&raw[start..=end]).expect("valid provenance json")
+}
+
+#[test]
+fn test_explain_shows_ai_origin_and_human_edit() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("src/lib.rs");
+
+    file.set_contents(lines!["fn a() {}".ai(), "fn b() {}".ai()]);
+    let introduced = repo.stage_all_and_commit("Add lib").unwrap();
+
+    file.set_contents(lines!["fn a() {}".ai(), "fn b() { todo!() }".human()]);
+    let edited = repo.stage_all_and_commit("Stub b").unwrap();
+
+    let chain = explain_json(&repo, "src/lib.rs:2");
+    assert_eq!(chain.len(), 2, "{:?}", chain);
+
+    assert_eq!(chain[0]["commit_sha"], edited.commit_sha.as_str());
+    assert_eq!(chain[0]["change"], "edited");
+    assert_eq!(chain[0]["author_type"], "human");
+    assert_eq!(chain[0]["content"], "fn b() { todo!() }");
+
+    assert_eq!(chain[1]["commit_sha"], introduced.commit_sha.as_str());
+    assert_eq!(chain[1]["change"], "introduced");
+    assert_eq!(chain[1]["author_type"], "ai");
+    assert_eq!(chain[1]["tool"], "mock_ai");
+    assert_eq!(chain[1]["content"], "fn b() {}");
+
+    // The untouched line only has the commit that introduced it
+    let chain = explain_json(&repo, "src/lib.rs:1");
+    assert_eq!(chain.len(), 1);
+    assert_eq!(chain[0]["author_type"], "ai");
+
+    let text = repo.git_ai(&["explain", "src/lib.rs:2"]).unwrap();
+    assert!(
+        text.contains("attribution changed from ai to human"),
+        "{}",
+        text
+    );
+}
@@ -609,3 +609,116 @@ fn wrapper_mode_does_not_install_hook_symlinks() {
         "managed hooks dir should not exist in wrapper-only mode"
     );
 }
+
+// ---------------------------------------------------------------------------
+// 16. install-hooks --repo chains an existing hook and --uninstall restores it
+// ---------------------------------------------------------------------------
+
+#[cfg(unix)]
+fn plain_git(repo: &TestRepo, home: &Path, args: &[&str]) -> std::process::Output {
+    std::process::Command::new("git")
+        .current_dir(repo.path())
+        .args(args)
+        .env("HOME", home)
+        .env("GIT_CONFIG_GLOBAL", home.join(".gitconfig"))
+        .env("GIT_AI_TEST_DB_PATH", repo.test_db_path())
+        .output()
+        .expect("failed to run git")
+}
+
+#[cfg(unix)]
+#[test]
+#[serial]
+fn install_hooks_repo_round_trip_chains_existing_post_commit_hook() {
+    let _mode = EnvVarGuard::set("GIT_AI_TEST_GIT_MODE", "wrapper");
+
+    let repo = TestRepo::new();
+    let home = repo.test_home_path().clone();
+    fs::create_dir_all(&home).expect("failed to create test home");
+    let home_str = home.to_string_lossy().to_string();
+    let gitconfig = home.join(".gitconfig").to_string_lossy().to_string();
+    let env = [
+        ("HOME", home_str.as_str()),
+        ("GIT_CONFIG_GLOBAL", gitconfig.as_str()),
+    ];
+
+    let marker_path = repo.path().join(".git").join("post-commit-marker.txt");
+    let user_hook = repo.path().join(".git").join("hooks").join("post-commit");
+    let user_hook_script = format!(
+        "#!/bin/sh\necho post-commit-fired >> '{}'\n",
+        marker_path.to_string_lossy()
+    );
+    fs::create_dir_all(user_hook.parent().unwrap()).expect("failed to create hooks dir");
+    fs::write(&user_hook, &user_hook_script).expect("failed to write post-commit hook");
+    set_executable(&user_hook);
+
+    repo.git_ai_with_env(&["install-hooks", "--repo"], &env)
+        .expect("install-hooks --repo should succeed");
+    assert!(
+        managed_hooks_dir(&repo)
+            .join("post-commit")
+            .symlink_metadata()
+            .is_ok(),
+        "post-commit should be installed in the managed hooks dir"
+    );
+
+    fs::write(repo.path().join("ai.txt"), "generated\n").expect("failed to write file");
+    repo.git_ai(&["checkpoint", "mock_ai", "ai.txt"])
+        .expect("checkpoint should succeed");
+    assert!(plain_git(&repo, &home, &["add", "ai.txt"]).status.success());
+    let commit = plain_git(&repo, &home, &["commit", "-m", "plain git commit"]);
+    assert!(
+        commit.status.success(),
+        "plain git commit should succeed: {}",
+        String::from_utf8_lossy(&commit.stderr)
+    );
+
+    let note = plain_git(&repo, &home, &["notes", "--ref=ai", "show", "HEAD"]);
+    assert!(
+        note.status.success(),
+        "authorship log should be written for a plain git commit"
+    );
+    assert!(String::from_utf8_lossy(&note.stdout).contains("ai.txt"));
+
+    let marker = fs::read_to_string(&marker_path).expect("user hook should still run");
+    assert_eq!(
+        marker.lines().count(),
+        1,
+        "user hook should fire exactly once"
+    );
+
+    repo.git_ai_with_env(&["install-hooks", "--uninstall", "--repo"], &env)
+        .expect("install-hooks --uninstall --repo should succeed");
+    assert!(
+        !managed_hooks_dir(&repo).exists(),
+        "managed hooks dir should be removed"
+    );
+    assert!(
+        !plain_git(&repo, &home, &["config", "--local", "core.hooksPath"])
+            .status
+            .success(),
+        "core.hooksPath should be unset so git runs .git/hooks again"
+    );
+    assert_eq!(
+        fs::read_to_string(&user_hook).expect("user hook should be kept"),
+        user_hook_script
+    );
+
+    fs::write(repo.path().join("human.txt"), "typed\n").expect("failed to write file");
+    assert!(
+        plain_git(&repo, &home, &["add", "human.txt"])
+            .status
+            .success()
+    );
+    assert!(
+        plain_git(&repo, &home, &["commit", "-m", "after uninstall"])
+            .status
+            .success()
+    );
+    let marker = fs::read_to_string(&marker_path).expect("marker should exist");
+    assert_eq!(
+        marker.lines().count(),
+        2,
+        "user hook should run directly after uninstall"
+    );
+}
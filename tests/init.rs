@@ -0,0 +1,55 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+#[test]
+fn test_init_twice_is_idempotent() {
+    let repo = TestRepo::new();
+    repo.filename("lib.rs")
+        .set_contents(lines!["fn human() {}".human(), "fn ai() {}".ai()]);
+    let commit = repo.stage_all_and_commit("Initial").unwrap();
+
+    let first = repo
+        .git_ai_stdout(&["init", "--storage", "sidecar", "--hooks"])
+        .expect("first init should succeed");
+    assert!(first.contains("config file created"), "got: {}", first);
+    assert!(
+        first.contains("storage set: sidecar (copied 1 authorship log(s) from notes)"),
+        "got: {}",
+        first
+    );
+    assert!(first.contains("repo hooks"), "got: {}", first);
+
+    let config_path = repo.test_home_path().join(".git-ai").join("config.json");
+    let config_after_first = std::fs::read_to_string(&config_path).unwrap();
+
+    let second = repo
+        .git_ai_stdout(&["init", "--storage=sidecar", "--hooks"])
+        .expect("second init should succeed");
+    assert!(second.contains("config file ok"), "got: {}", second);
+    assert!(second.contains("storage ok: sidecar"), "got: {}", second);
+    assert!(second.contains("repo hooks ok"), "got: {}", second);
+
+    // Nothing is rewritten or added twice
+    assert_eq!(
+        std::fs::read_to_string(&config_path).unwrap(),
+        config_after_first
+    );
+    let storage = repo
+        .git_og(&["config", "--local", "--get-all", "git-ai.storage"])
+        .unwrap();
+    assert_eq!(storage.trim(), "sidecar");
+
+    let sidecar = repo
+        .path()
+        .join(".git")
+        .join("ai")
+        .join("authorship")
+        .join(&commit.commit_sha[..2])
+        .join(&commit.commit_sha[2..]);
+    assert!(
+        sidecar.exists(),
+        "authorship log should be in sidecar storage"
+    );
+}
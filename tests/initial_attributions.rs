@@ -63,6 +63,8 @@ fn test_initial_only_no_blame_data() {
             accepted_lines: 0,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
 
@@ -151,6 +153,8 @@ fn test_initial_wins_overlaps() {
             accepted_lines: 0,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
 
@@ -228,6 +232,8 @@ fn test_initial_and_blame_merge() {
             accepted_lines: 0,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
     prompts.insert(
@@ -245,6 +251,8 @@ fn test_initial_and_blame_merge() {
             accepted_lines: 0,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
 
@@ -315,6 +323,8 @@ fn test_partial_file_coverage() {
             accepted_lines: 0,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
 
@@ -403,6 +413,8 @@ fn test_initial_attributions_in_subsequent_checkpoint() {
             accepted_lines: 0,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
 
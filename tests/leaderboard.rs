@@ -0,0 +1,130 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::Value;
+
+fn commit_as(repo: &TestRepo, email: &str, message: &str) {
+    repo.git(&["add", "-A"]).expect("add --all should succeed");
+    repo.commit_with_env(message, &[("GIT_COMMITTER_EMAIL", email)], None)
+        .expect("commit should succeed");
+}
+
+fn leaderboard_json(repo: &TestRepo, args: &[&str]) -> Vec<Value> {
+    let raw = repo
+        .git_ai(args)
+        .expect("git-ai leaderboard should succeed");
+    let start = raw.find('[').expect("output should contain a JSON array");
+    let end = raw.rfind(']').unwrap();
+    serde_json::from_str(&raw[start..=end]).expect("valid leaderboard json")
+}
+
+#[test]
+fn test_leaderboard_orders_committers_by_ai_percentage() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    let base = repo.stage_all_and_commit("Initial").unwrap();
+
+    repo.filename("carol.rs")
+        .set_contents(lines!["fn c1() {}".human(), "fn c2() {}".human()]);
+    commit_as(&repo, "carol@example.com", "Carol's change");
+
+    repo.filename("alice.rs")
+        .set_contents(lines!["fn a1() {}".ai(), "fn a2() {}".ai()]);
+    commit_as(&repo, "alice@example.com", "Alice's change");
+
+    repo.filename("bob.rs")
+        .set_contents(lines!["fn b1() {}".ai(), "fn b2() {}".human()]);
+    commit_as(&repo, "Bob@Example.com", "Bob's change");
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let board = leaderboard_json(&repo, &["leaderboard", &range, "--json"]);
+    let rows: Vec<(&str, u64, f64)> = board
+        .iter()
+        .map(|row| {
+            (
+                row["committer"].as_str().unwrap(),
+                row["total_additions"].as_u64().unwrap(),
+                row["ai_percentage"].as_f64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        rows,
+        vec![
+            ("alice@example.com", 2, 100.0),
+            ("bob@example.com", 2, 50.0),
+            ("carol@example.com", 2, 0.0),
+        ]
+    );
+
+    let anonymized = leaderboard_json(&repo, &["leaderboard", &range, "--json", "--anonymize"]);
+    assert_eq!(anonymized.len(), 3);
+    for (row, plain) in anonymized.iter().zip(&board) {
+        let committer = row["committer"].as_str().unwrap();
+        assert!(!committer.contains('@'), "email leaked: {}", committer);
+        assert_eq!(row["ai_percentage"], plain["ai_percentage"]);
+    }
+}
+
+#[test]
+fn test_author_map_merges_aliases_in_leaderboard_and_stats() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    let base = repo.stage_all_and_commit("Initial").unwrap();
+
+    let commit_by = |email: &str, message: &str| {
+        repo.git(&["add", "-A"]).unwrap();
+        repo.commit_with_env(message, &[("GIT_AUTHOR_EMAIL", email)], None)
+            .unwrap();
+    };
+    repo.filename("laptop.rs")
+        .set_contents(lines!["fn l1() {}".ai(), "fn l2() {}".human()]);
+    commit_by("dev@laptop.local", "From the laptop");
+    repo.filename("work.rs")
+        .set_contents(lines!["fn w1() {}".ai(), "fn w2() {}".ai()]);
+    commit_by("Dev@Example.com", "From work");
+
+    let map = repo.test_home_path().join("authors.txt");
+    std::fs::write(&map, "# one person\ndev@laptop.local = dev@example.com\n").unwrap();
+    let map = map.to_str().unwrap();
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let board = leaderboard_json(
+        &repo,
+        &[
+            "leaderboard",
+            &range,
+            "--by=author",
+            "--author-map",
+            map,
+            "--json",
+        ],
+    );
+    let dev: Vec<&Value> = board
+        .iter()
+        .filter(|row| row["author"].as_str().unwrap().starts_with("dev@"))
+        .collect();
+    assert_eq!(dev.len(), 1, "aliases should share one row: {:?}", board);
+    assert_eq!(dev[0]["author"], "dev@example.com");
+    assert_eq!(dev[0]["commits"], 2);
+    assert_eq!(dev[0]["total_additions"], 4);
+    assert_eq!(dev[0]["ai_additions"], 3);
+
+    // Filtering stats by either alias sums the commits of both
+    let raw = repo
+        .git_ai(&[
+            "stats",
+            &range,
+            "--author-email",
+            "dev@laptop.local",
+            "--author-map",
+            map,
+            "--json",
+        ])
+        .expect("filtered stats should succeed");
+    let stats: Value =
+        serde_json::from_str(&raw[raw.find('{').unwrap()..=raw.rfind('}').unwrap()]).unwrap();
+    assert_eq!(stats["ai_additions"], 3);
+    assert_eq!(stats["human_additions"], 1);
+}
@@ -0,0 +1,166 @@
+#[macro_use]
+mod repos;
+use git_ai::commands::log::LogEntry;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+/// Extract the JSON array from mixed stdout/stderr output.
+fn extract_json_array(output: &str) -> String {
+    let start = output.find('[').unwrap_or(0);
+    let end = output.rfind(']').unwrap_or(output.len().saturating_sub(1));
+    output[start..=end].to_string()
+}
+
+fn log_entries(repo: &TestRepo, args: &[&str]) -> Vec<LogEntry> {
+    let raw = repo.git_ai(args).expect("git-ai log should succeed");
+    serde_json::from_str(&extract_json_array(&raw)).expect("valid log json")
+}
+
+#[test]
+fn test_log_walks_history_from_head() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("main.rs");
+
+    file.set_contents(lines!["fn main() {}".human()]);
+    repo.stage_all_and_commit("Human start").unwrap();
+
+    file.set_contents(lines!["fn main() {}".human(), "// ai".ai(), "// ai 2".ai()]);
+    repo.stage_all_and_commit("AI additions").unwrap();
+
+    let entries = log_entries(&repo, &["log", "--json"]);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].summary, "AI additions");
+    assert_eq!(entries[0].stats.ai_additions, 2);
+    assert_eq!(entries[0].stats.human_additions, 0);
+    assert_eq!(entries[1].summary, "Human start");
+    assert_eq!(entries[1].stats.human_additions, 1);
+    assert!(entries.iter().all(|entry| !entry.is_merge));
+}
+
+#[test]
+fn test_log_max_count_and_range() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("notes.txt");
+
+    file.set_contents(lines!["one".human()]);
+    let first = repo.stage_all_and_commit("First").unwrap();
+    file.set_contents(lines!["one".human(), "two".ai()]);
+    repo.stage_all_and_commit("Second").unwrap();
+    file.set_contents(lines!["one".human(), "two".ai(), "three".ai()]);
+    repo.stage_all_and_commit("Third").unwrap();
+
+    let entries = log_entries(&repo, &["log", "--max-count", "1", "--json"]);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].summary, "Third");
+
+    let range = format!("{}..HEAD", first.commit_sha);
+    let entries = log_entries(&repo, &["log", &range, "--json"]);
+    let summaries: Vec<&str> = entries.iter().map(|e| e.summary.as_str()).collect();
+    assert_eq!(summaries, vec!["Third", "Second"]);
+}
+
+#[test]
+fn test_log_no_merges_skips_merge_commits() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("base.txt");
+    file.set_contents(lines!["base".human()]);
+    repo.stage_all_and_commit("Base").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let mut feature = repo.filename("feature.txt");
+    feature.set_contents(lines!["feature".ai()]);
+    repo.stage_all_and_commit("Feature work").unwrap();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    let mut other = repo.filename("other.txt");
+    other.set_contents(lines!["other".human()]);
+    repo.stage_all_and_commit("Mainline work").unwrap();
+    repo.git(&["merge", "feature", "-m", "merge feature"])
+        .unwrap();
+
+    let entries = log_entries(&repo, &["log", "--json"]);
+    assert!(entries[0].is_merge, "HEAD should be the merge commit");
+
+    let entries = log_entries(&repo, &["log", "--no-merges", "--json"]);
+    assert!(entries.iter().all(|entry| !entry.is_merge));
+    assert_eq!(entries.len(), 3);
+}
+
+#[test]
+fn test_log_author_email_filter() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("team.txt");
+    file.set_contents(lines!["base".human()]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    file.set_contents(lines!["base".human(), "ai".ai()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.commit_with_env(
+        "Carol AI",
+        &[
+            ("GIT_AUTHOR_NAME", "Carol"),
+            ("GIT_AUTHOR_EMAIL", "carol+ai@example.com"),
+        ],
+        None,
+    )
+    .unwrap();
+
+    let entries = log_entries(
+        &repo,
+        &["log", "--author-email", "carol+ai@example.com", "--json"],
+    );
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].summary, "Carol AI");
+    assert_eq!(entries[0].author_email, "carol+ai@example.com");
+    assert_eq!(entries[0].stats.ai_additions, 1);
+
+    let entries = log_entries(
+        &repo,
+        &["log", "--author-email", "nobody@example.com", "--json"],
+    );
+    assert!(entries.is_empty());
+}
+
+#[test]
+fn test_log_reuses_cached_commit_stats() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("main.rs");
+    file.set_contents(lines!["fn main() {}".human()]);
+    repo.stage_all_and_commit("Human start").unwrap();
+    file.set_contents(lines!["fn main() {}".human(), "// ai".ai()]);
+    let head = repo.stage_all_and_commit("AI addition").unwrap();
+
+    let first = extract_json_array(&repo.git_ai(&["log", "--json"]).unwrap());
+    let cache_file = repo
+        .path()
+        .join(".git/ai/cache/stats")
+        .join(format!("{}.json", head.commit_sha));
+    assert!(cache_file.exists(), "first run should populate the cache");
+
+    let second = extract_json_array(&repo.git_ai(&["log", "--json"]).unwrap());
+    assert_eq!(first, second);
+
+    // A cache hit is served without recomputing, so an edited entry shows up in the output
+    let cached = std::fs::read_to_string(&cache_file).unwrap();
+    std::fs::write(
+        &cache_file,
+        cached.replace("\"ai_additions\":1", "\"ai_additions\":42"),
+    )
+    .unwrap();
+    let entries = log_entries(&repo, &["log", "--json"]);
+    assert_eq!(entries[0].stats.ai_additions, 42);
+
+    // Entries written for another authorship log version are recomputed
+    let cached = std::fs::read_to_string(&cache_file).unwrap();
+    std::fs::write(
+        &cache_file,
+        cached.replace(
+            "\"authorship_log_version\":\"authorship/3.1.0\"",
+            "\"authorship_log_version\":\"authorship/2.0.0\"",
+        ),
+    )
+    .unwrap();
+    let entries = log_entries(&repo, &["log", "--json"]);
+    assert_eq!(entries[0].stats.ai_additions, 1);
+}
@@ -5,12 +5,6 @@ use repos::test_file::ExpectedLineExt;
 use repos::test_repo::TestRepo;
 use std::fs;
 
-fn extract_json_object(output: &str) -> String {
-    let start = output.find('{').unwrap_or(0);
-    let end = output.rfind('}').unwrap_or(output.len().saturating_sub(1));
-    output[start..=end].to_string()
-}
-
 fn gbk_hello_world() -> Vec<u8> {
     // "你好世界" in GBK encoding (4 characters, 8 bytes)
     // 你=0xC4E3, 好=0xBAC3, 世=0xCAC0, 界=0xBDE7
@@ -155,8 +149,7 @@ fn test_stats_with_non_utf8_file_only() {
     fs::write(&file_path, gbk_multiline()).unwrap();
     repo.stage_all_and_commit("Add GBK file").unwrap();
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert!(
@@ -182,8 +175,7 @@ fn test_stats_with_non_utf8_and_utf8_files_mixed() {
 
     repo.stage_all_and_commit("Add mixed files").unwrap();
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -209,8 +201,7 @@ fn test_stats_json_output_valid_with_non_utf8_file() {
     fs::write(&file_path, latin1_bytes()).unwrap();
     repo.stage_all_and_commit("Add Latin-1 file").unwrap();
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let result: Result<CommitStats, _> = serde_json::from_str(&json);
     assert!(
         result.is_ok(),
@@ -350,8 +341,7 @@ fn test_ai_attribution_preserved_with_non_utf8_in_same_commit() {
         "Should have attestation specifically for ai_output.py"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -380,8 +370,7 @@ fn test_human_and_ai_edits_with_non_utf8_file_present() {
     ]);
     repo.stage_all_and_commit("Add code file").unwrap();
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(stats.ai_additions, 1, "1 AI line should be counted");
@@ -416,8 +405,7 @@ fn test_multiple_non_utf8_encodings_in_one_commit() {
         result.err()
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
     assert!(
         stats.git_diff_added_lines >= 5,
@@ -569,8 +557,7 @@ fn test_binary_and_non_utf8_with_ai_file() {
         "AI file should get attestation even with binary and non-UTF-8 neighbors"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert!(
@@ -841,8 +828,7 @@ fn test_stats_after_editing_non_utf8_file() {
     fs::write(&file_path, gbk_multiline()).unwrap();
     repo.stage_all_and_commit("Edit GBK file").unwrap();
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let result: Result<CommitStats, _> = serde_json::from_str(&json);
     assert!(
         result.is_ok(),
@@ -879,8 +865,7 @@ fn test_large_non_utf8_file() {
         result.err()
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
     assert!(
         stats.git_diff_added_lines >= 500,
@@ -914,3 +899,35 @@ fn test_file_with_null_bytes_in_content() {
         result.err()
     );
 }
+
+#[test]
+fn test_ai_binary_file_gets_file_level_attestation_and_no_lines() {
+    let repo = TestRepo::new();
+
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // PNG signature followed by an IHDR chunk header, which contains null bytes
+    let png: Vec<u8> = vec![
+        0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, b'I', b'H', b'D',
+        b'R', 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06,
+    ];
+    fs::write(repo.path().join("logo.png"), png).unwrap();
+    fs::write(repo.path().join("app.rs"), "fn main() {}\nfn helper() {}\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "logo.png", "app.rs"])
+        .unwrap();
+    repo.stage_all_and_commit("Add logo and app").unwrap();
+
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
+    let stats: CommitStats = serde_json::from_str(&json).unwrap();
+    assert_eq!(stats.git_diff_added_lines, 2, "binary adds no lines");
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.human_additions, 0);
+
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", "HEAD"])
+        .expect("commit should have an authorship note");
+    assert!(note.contains("logo.png binary\n"), "got: {}", note);
+    assert!(note.contains("app.rs\n"), "got: {}", note);
+}
@@ -399,6 +399,8 @@ fn test_rebase_preserves_prompt_only_commit_note_metadata() {
             accepted_lines: 0,
             overriden_lines: 0,
             messages_url: None,
+            session_id: None,
+            suggested_line_hashes: Vec::new(),
         },
     );
 
@@ -1449,3 +1451,46 @@ cat {} > "$1"
         "function feature3() {}".ai()
     ]);
 }
+
+#[test]
+fn test_replay_carries_authorship_onto_commits_squashed_without_hooks() {
+    let repo = TestRepo::new();
+    let mut base_file = repo.filename("base.txt");
+    base_file.set_contents(lines!["base content"]);
+    let base = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut feature1 = repo.filename("feature1.txt");
+    feature1.set_contents(lines!["// AI feature 1".ai(), "line 1".ai()]);
+    repo.stage_all_and_commit("AI commit 1").unwrap();
+    let mut feature2 = repo.filename("feature2.txt");
+    feature2.set_contents(lines!["// AI feature 2".ai(), "line 2".ai()]);
+    let original_head = repo.stage_all_and_commit("AI commit 2").unwrap();
+
+    // Squash both commits with plain git, so no hook rewrites their authorship
+    repo.git_og(&["reset", "--soft", &base.commit_sha]).unwrap();
+    repo.git_og(&["commit", "-m", "Squashed AI work"]).unwrap();
+    let squashed = repo
+        .git_og(&["rev-parse", "HEAD"])
+        .unwrap()
+        .trim()
+        .to_string();
+    assert!(read_authorship_note(&repo, &squashed).is_none());
+
+    let output = repo
+        .git_ai(&[
+            "replay",
+            &format!("{}..{}", base.commit_sha, original_head.commit_sha),
+            &format!("{}..HEAD", base.commit_sha),
+        ])
+        .unwrap();
+    assert!(output.contains("onto 1 rewritten commit"), "{}", output);
+
+    let stats = repo.git_ai(&["stats", "HEAD", "--json"]).unwrap();
+    let json = stats.lines().find(|line| line.starts_with('{')).unwrap();
+    let stats: serde_json::Value = serde_json::from_str(json).unwrap();
+    assert_eq!(stats["ai_additions"], 4);
+    assert_eq!(stats["human_additions"], 0);
+
+    feature1.assert_lines_and_blame(lines!["// AI feature 1".ai(), "line 1".ai()]);
+    feature2.assert_lines_and_blame(lines!["// AI feature 2".ai(), "line 2".ai()]);
+}
@@ -0,0 +1,184 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+/// A base commit followed by one with two AI lines and one human line.
+fn repo_with_ai_commit() -> TestRepo {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}".human()]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    file.set_contents(lines![
+        "fn base() {}".human(),
+        "fn ai1() {}".ai(),
+        "fn ai2() {}".ai(),
+        "fn human() {}".human(),
+    ]);
+    repo.stage_all_and_commit("Add helpers").unwrap();
+    repo
+}
+
+#[test]
+fn test_record_force_regenerates_deleted_log() {
+    let repo = repo_with_ai_commit();
+    let before = repo.stats().unwrap();
+    assert_eq!(before.ai_additions, 2);
+
+    repo.git_og(&["notes", "--ref=ai", "remove", "HEAD"])
+        .unwrap();
+    let unattested = repo.stats().unwrap();
+    assert_eq!(unattested.ai_additions, 0);
+
+    repo.git_ai(&["record", "--commit", "HEAD", "--force"])
+        .expect("record should regenerate the log");
+
+    let after = repo.stats().unwrap();
+    assert_eq!(after.git_diff_added_lines, before.git_diff_added_lines);
+    assert_eq!(after.ai_additions, before.ai_additions);
+    assert_eq!(after.ai_accepted, before.ai_accepted);
+    assert_eq!(after.human_additions, before.human_additions);
+
+    // Recording again writes the same attestations
+    let first_log = repo.git_og(&["notes", "--ref=ai", "show", "HEAD"]).unwrap();
+    repo.git_ai(&["record", "--commit", "HEAD", "--force"])
+        .expect("record should be repeatable");
+    let second_log = repo.git_og(&["notes", "--ref=ai", "show", "HEAD"]).unwrap();
+    assert_eq!(
+        first_log.split("---").next(),
+        second_log.split("---").next()
+    );
+}
+
+#[test]
+fn test_record_requires_force_to_rewrite_existing_log() {
+    let repo = repo_with_ai_commit();
+
+    let err = repo
+        .git_ai(&["record", "--commit", "HEAD"])
+        .expect_err("an existing log should not be rewritten without --force");
+    assert!(err.contains("pass --force to rewrite it"));
+}
+
+#[test]
+fn test_record_without_checkpoints_falls_back_with_warning() {
+    let repo = repo_with_ai_commit();
+    let working_logs = repo
+        .current_working_logs()
+        .dir
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    std::fs::remove_dir_all(&working_logs).unwrap();
+
+    // With the log still there, nothing better can replace it
+    let output = repo
+        .git_ai(&["record", "--commit", "HEAD", "--force"])
+        .expect("record should keep the existing log");
+    assert!(output.contains("kept its existing authorship log"));
+    assert_eq!(repo.stats().unwrap().ai_additions, 2);
+
+    repo.git_og(&["notes", "--ref=ai", "remove", "HEAD"])
+        .unwrap();
+    let output = repo
+        .git_ai(&["record", "--commit", "HEAD", "--force"])
+        .expect("record should fall back to a best-effort log");
+    assert!(output.contains("recorded a best-effort log"));
+    assert!(
+        repo.git_og(&["notes", "--ref=ai", "show", "HEAD"]).is_ok(),
+        "the commit should have a log again"
+    );
+}
+
+#[test]
+fn test_record_from_telemetry_attests_listed_lines() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    // The IDE wrote these files; its telemetry says which lines came from which tool
+    let module_dir = repo.path().join("src/模块");
+    std::fs::create_dir_all(&module_dir).unwrap();
+    std::fs::write(
+        module_dir.join("lib.rs"),
+        "fn human() {}\nfn ai1() {}\nfn ai2() {}\nfn ai3() {}\n",
+    )
+    .unwrap();
+    std::fs::write(repo.path().join("a,b.rs"), "fn tab() {}\nfn typed() {}\n").unwrap();
+    let telemetry = repo.test_home_path().join("telemetry.csv");
+    std::fs::write(
+        &telemetry,
+        "path,start_line,end_line,tool\nsrc/模块/lib.rs,2,4,cursor\n\"a,b.rs\",1,1,copilot\n",
+    )
+    .unwrap();
+
+    let output = repo
+        .git_ai(&["record", "--from-telemetry", telemetry.to_str().unwrap()])
+        .expect("record --from-telemetry should succeed");
+    assert!(output.contains("Recorded 4 AI lines"), "got: {}", output);
+
+    repo.stage_all_and_commit("IDE edits").unwrap();
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.git_diff_added_lines, 6);
+    assert_eq!(stats.ai_additions, 4);
+    assert_eq!(stats.human_additions, 2);
+    let tools: Vec<&String> = stats.tool_model_breakdown.keys().collect();
+    assert_eq!(tools.len(), 2, "got: {:?}", tools);
+}
+
+#[test]
+fn test_record_from_markers_attests_marked_lines() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    std::fs::write(
+        repo.path().join("lib.rs"),
+        "fn human() {}\n// git-ai:begin\nfn ai1() {}\nfn ai2() {}\n// git-ai:end\n",
+    )
+    .unwrap();
+
+    let output = repo
+        .git_ai(&["record", "--from-markers", "lib.rs"])
+        .expect("record --from-markers should succeed");
+    assert!(output.contains("Recorded 2 AI lines"), "got: {}", output);
+
+    repo.stage_all_and_commit("Marked edits").unwrap();
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.git_diff_added_lines, 5);
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.human_additions, 3);
+}
+
+#[test]
+fn test_record_from_ide_log_attests_logged_lines() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    std::fs::write(
+        repo.path().join("lib.rs"),
+        "fn human() {}\nfn ai1() {}\nfn ai2() {}\n",
+    )
+    .unwrap();
+    let ide_log = repo.test_home_path().join("ai.log");
+    std::fs::create_dir_all(repo.test_home_path()).unwrap();
+    std::fs::write(&ide_log, "# inserted by the IDE\nlib.rs:2-3\n").unwrap();
+
+    let output = repo
+        .git_ai(&["record", "--from-ide-log", ide_log.to_str().unwrap()])
+        .expect("record --from-ide-log should succeed");
+    assert!(output.contains("Recorded 2 AI lines"), "got: {}", output);
+
+    repo.stage_all_and_commit("IDE edits").unwrap();
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.human_additions, 1);
+    assert!(
+        stats
+            .tool_model_breakdown
+            .keys()
+            .any(|key| key.starts_with("ide_log"))
+    );
+}
@@ -0,0 +1,61 @@
+#[macro_use]
+mod repos;
+use git_ai::Repo;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+#[test]
+fn test_repo_commit_stats_match_the_cli() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}".human()]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+
+    file.set_contents(lines![
+        "fn base() {}".human(),
+        "fn ai1() {}".ai(),
+        "fn ai2() {}".ai(),
+        "fn human() {}".human(),
+    ]);
+    let head = repo.stage_all_and_commit("Add helpers").unwrap();
+
+    let lib_repo = Repo::open(repo.path()).expect("open repository");
+    assert_eq!(lib_repo.resolve_commit("HEAD").unwrap(), head.commit_sha);
+
+    let stats = lib_repo.commit_stats("HEAD").unwrap();
+    assert_eq!(stats.git_diff_added_lines, 3);
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.human_additions, 1);
+
+    // Same answer as the binary, without running it
+    let cli_stats = repo.stats().unwrap();
+    assert_eq!(stats.ai_additions, cli_stats.ai_additions);
+    assert_eq!(stats.human_additions, cli_stats.human_additions);
+
+    let base_stats = lib_repo.commit_stats(&base.commit_sha).unwrap();
+    assert_eq!(base_stats.ai_additions, 0);
+    assert_eq!(base_stats.human_additions, 1);
+
+    let log = lib_repo
+        .authorship_log("HEAD")
+        .unwrap()
+        .expect("HEAD has an authorship log");
+    assert_eq!(log.ai_count(), 2);
+}
+
+#[test]
+fn test_repo_open_outside_a_repository_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(Repo::open(dir.path()).is_err());
+}
+
+#[test]
+fn test_repo_commit_stats_unknown_revision_fails() {
+    let repo = TestRepo::new();
+    repo.filename("a.rs").set_contents(lines!["fn a() {}".ai()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    let lib_repo = Repo::open(repo.path()).unwrap();
+    assert!(lib_repo.commit_stats("no-such-rev").is_err());
+    assert!(lib_repo.authorship_log("no-such-rev").is_err());
+}
@@ -0,0 +1,209 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::Value;
+
+/// Extract the JSON array from mixed stdout/stderr output.
+fn extract_json_array(output: &str) -> String {
+    let start = output.find('[').unwrap_or(0);
+    let end = output.rfind(']').unwrap_or(output.len().saturating_sub(1));
+    output[start..=end].to_string()
+}
+
+fn report_regions(repo: &TestRepo, args: &[&str]) -> Vec<(String, u64, u64, String)> {
+    let raw = repo.git_ai(args).expect("git-ai report should succeed");
+    let regions: Vec<Value> =
+        serde_json::from_str(&extract_json_array(&raw)).expect("valid report json");
+    regions
+        .iter()
+        .map(|region| {
+            (
+                region["path"].as_str().unwrap().to_string(),
+                region["start_line"].as_u64().unwrap(),
+                region["end_line"].as_u64().unwrap(),
+                region["author_type"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_report_annotations_coalesce_utf8_paths() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    let mut component = repo.filename("src/模块/组件.ts");
+    component.set_contents(lines![
+        "// 组件".human(),
+        "export function a() {}".ai(),
+        "export function b() {}".ai(),
+        "export function c() {}".ai(),
+        "// 人工审核".human(),
+        "// 结束".human(),
+    ]);
+    repo.stage_all_and_commit("Add component").unwrap();
+
+    let regions = report_regions(&repo, &["report", "--format=annotations"]);
+    let path = "src/模块/组件.ts".to_string();
+    assert_eq!(
+        regions,
+        vec![
+            (path.clone(), 1, 1, "Human".to_string()),
+            (path.clone(), 2, 4, "Ai".to_string()),
+            (path, 5, 6, "Human".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_report_annotations_over_range() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.rs");
+    file.set_contents(lines!["fn main() {}".human()]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+
+    file.set_contents(lines!["fn main() {}".human(), "fn ai() {}".ai()]);
+    repo.stage_all_and_commit("AI helper").unwrap();
+    file.set_contents(lines![
+        "fn main() {}".human(),
+        "fn ai() {}".ai(),
+        "fn human() {}".human(),
+    ]);
+    repo.stage_all_and_commit("Human helper").unwrap();
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let regions = report_regions(&repo, &["report", &range, "--format", "annotations"]);
+    assert_eq!(
+        regions,
+        vec![
+            ("app.rs".to_string(), 2, 2, "Ai".to_string()),
+            ("app.rs".to_string(), 3, 3, "Human".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_report_rejects_unknown_format() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    let err = repo
+        .git_ai(&["report", "--format=sarif"])
+        .expect_err("unknown format should fail");
+    assert!(err.contains("Unsupported report format"), "got: {}", err);
+}
+
+#[test]
+fn test_report_markdown_table() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    let base = repo.stage_all_and_commit("Initial").unwrap();
+
+    repo.filename("src/模块/组件.ts").set_contents(lines![
+        "// 组件".human(),
+        "export function a() {}".ai(),
+        "export function b() {}".ai(),
+        "export function c() {}".ai(),
+    ]);
+    repo.filename("util.rs")
+        .set_contents(lines!["fn util() {}".human()]);
+    repo.stage_all_and_commit("Add component").unwrap();
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let output = repo
+        .git_ai(&["report", &range, "--format=markdown"])
+        .expect("markdown report should succeed");
+    let start = output
+        .find("| File |")
+        .expect("output should contain the table header");
+
+    assert_eq!(
+        &output[start..],
+        "| File | AI lines | Human lines | AI % | Human % |\n\
+         | --- | ---: | ---: | ---: | ---: |\n\
+         | src/模块/组件.ts | 3 | 1 | 75.0% | 25.0% |\n\
+         | util.rs | 0 | 1 | 0.0% | 100.0% |\n\
+         | **Total** | **3** | **2** | **60.0%** | **40.0%** |\n\
+         \n\
+         ```\n\
+         5 lines added across 2 files: 60.0% AI (3 lines), 40.0% human (2 lines)\n\
+         ```\n"
+    );
+}
+
+#[test]
+fn test_report_html_is_standalone_with_totals() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    let base = repo.stage_all_and_commit("Initial").unwrap();
+
+    repo.filename("src/a&b.rs").set_contents(lines![
+        "fn a() {}".ai(),
+        "fn b() {}".ai(),
+        "fn c() {}".ai(),
+        "// reviewed".human(),
+    ]);
+    repo.stage_all_and_commit("Add a").unwrap();
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let output = repo
+        .git_ai(&["report", &range, "--format=html"])
+        .expect("html report should succeed");
+    let start = output
+        .find("<!DOCTYPE html>")
+        .expect("output should start an HTML document");
+    let html = &output[start..];
+
+    assert!(html.trim_end().ends_with("</html>"), "got: {}", html);
+    assert!(html.contains("<svg"), "chart should be inline SVG");
+    assert!(!html.contains("<script"), "no script should be needed");
+    assert!(!html.contains("src=\"http"), "no external resources");
+    assert!(!html.contains("href=\"http"), "no external resources");
+
+    assert!(html.contains("<td>src/a&amp;b.rs</td>"), "got: {}", html);
+    assert!(
+        html.contains(
+            "<tr><th>Total</th><th class=\"num\">3</th><th class=\"num\">1</th>\
+             <th class=\"num\">75.0%</th><th class=\"num\">25.0%</th></tr>"
+        ),
+        "got: {}",
+        html
+    );
+}
+
+#[test]
+fn test_report_locale_formats_markdown_but_not_json() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    // 163 of 200 added lines are AI: 81.5%
+    let lines: Vec<_> = (0..163)
+        .map(|i| format!("let ai_{} = {};", i, i).ai())
+        .chain((0..37).map(|i| format!("let human_{} = {};", i, i).human()))
+        .collect();
+    repo.filename("lib.rs").set_contents(lines);
+    repo.stage_all_and_commit("Mostly AI").unwrap();
+
+    let markdown = repo
+        .git_ai_stdout(&["report", "--format=markdown", "--locale", "de"])
+        .expect("markdown report should succeed");
+    assert!(
+        markdown.contains("| lib.rs | 163 | 37 | 81,5% | 18,5% |"),
+        "got: {}",
+        markdown
+    );
+    assert!(!markdown.contains("81.5"), "got: {}", markdown);
+
+    let english = repo
+        .git_ai_stdout(&["report", "--format=markdown", "--locale=en"])
+        .expect("markdown report should succeed");
+    assert!(english.contains("81.5%"), "got: {}", english);
+
+    let stats: Value =
+        serde_json::from_str(&repo.git_ai_stdout(&["stats", "HEAD", "--json"]).unwrap()).unwrap();
+    assert_eq!(stats["ai_percentage"], 81.5);
+}
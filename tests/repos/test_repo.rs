@@ -310,6 +310,7 @@ impl TestRepo {
         if self.git_mode.uses_wrapper() {
             command.env("GIT_AI", "git");
         }
+        self.configure_feature_flags_env(command);
     }
 
     fn configure_git_ai_env(&self, command: &mut Command) {
@@ -318,6 +319,24 @@ impl TestRepo {
             command.env("GIT_CONFIG_GLOBAL", self.test_home.join(".gitconfig"));
             command.env("GIT_AI_GLOBAL_GIT_HOOKS", "true");
         }
+        self.configure_feature_flags_env(command);
+    }
+
+    /// Pass `feature_flags` to git-ai, including the hooks git runs, through the
+    /// `GIT_AI_*` environment variables
+    fn configure_feature_flags_env(&self, command: &mut Command) {
+        command.env(
+            "GIT_AI_REWRITE_STASH",
+            self.feature_flags.rewrite_stash.to_string(),
+        );
+        command.env(
+            "GIT_AI_CHECKPOINT_INTER_COMMIT_MOVE",
+            self.feature_flags.inter_commit_move.to_string(),
+        );
+        command.env(
+            "GIT_AI_AUTH_KEYRING",
+            self.feature_flags.auth_keyring.to_string(),
+        );
     }
 
     /// Patch the git-ai config for this test repo
@@ -580,10 +599,9 @@ impl TestRepo {
         }
     }
 
-    pub fn git_ai_with_env(&self, args: &[&str], envs: &[(&str, &str)]) -> Result<String, String> {
-        let binary_path = get_binary_path();
-
-        let mut command = Command::new(binary_path);
+    /// A git-ai command run in the repo with the test environment, config patch and database
+    fn git_ai_command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(get_binary_path());
         command.args(args).current_dir(&self.path);
         self.configure_git_ai_env(&mut command);
 
@@ -596,6 +614,11 @@ impl TestRepo {
 
         // Add test database path for isolation
         command.env("GIT_AI_TEST_DB_PATH", self.test_db_path.to_str().unwrap());
+        command
+    }
+
+    pub fn git_ai_with_env(&self, args: &[&str], envs: &[(&str, &str)]) -> Result<String, String> {
+        let mut command = self.git_ai_command(args);
 
         // Add custom environment variables
         for (key, value) in envs {
@@ -680,6 +703,51 @@ impl TestRepo {
         }
     }
 
+    /// Run a git-ai command and return only its exit code, for asserting the exit-code contract
+    pub fn git_ai_exit_code(&self, args: &[&str]) -> i32 {
+        self.git_ai_command(args)
+            .output()
+            .unwrap_or_else(|_| panic!("Failed to execute git-ai command: {:?}", args))
+            .status
+            .code()
+            .expect("git-ai should exit with a code, not a signal")
+    }
+
+    /// Run a git-ai command and return only its stdout, for parsing machine-readable output
+    pub fn git_ai_stdout(&self, args: &[&str]) -> Result<String, String> {
+        self.git_ai_stdout_from_working_dir(&self.path, args)
+    }
+
+    /// Like `git_ai_stdout`, run from a working directory instead of the repository root
+    pub fn git_ai_stdout_from_working_dir(
+        &self,
+        working_dir: &std::path::Path,
+        args: &[&str],
+    ) -> Result<String, String> {
+        let output = self
+            .git_ai_command(args)
+            .current_dir(working_dir)
+            .output()
+            .unwrap_or_else(|_| panic!("Failed to execute git-ai command: {:?}", args));
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).to_string())
+        }
+    }
+
+    /// Start a long-running git-ai command with stdout piped, for commands like `watch`
+    pub fn spawn_git_ai(&self, args: &[&str]) -> std::process::Child {
+        use std::process::Stdio;
+
+        self.git_ai_command(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap_or_else(|_| panic!("Failed to spawn git-ai command: {:?}", args))
+    }
+
     pub fn filename(&self, filename: &str) -> TestFile<'_> {
         let file_path = self.path.join(filename);
 
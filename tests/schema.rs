@@ -0,0 +1,40 @@
+mod repos;
+use repos::test_repo::TestRepo;
+use serde_json::Value;
+
+fn schema_json(repo: &TestRepo, args: &[&str]) -> Value {
+    let raw = repo.git_ai(args).expect("git-ai schema should succeed");
+    let start = raw.find('{').unwrap_or(0);
+    let end = raw.rfind('}').unwrap_or(raw.len().saturating_sub(1));
+    serde_json::from_str(&raw[start..=end]).expect("valid schema json")
+}
+
+#[test]
+fn test_schema_lists_commit_stats_fields() {
+    let repo = TestRepo::new();
+
+    let schemas = schema_json(&repo, &["schema"]);
+    let properties = &schemas["commit-stats"]["properties"];
+    for field in [
+        "ai_additions",
+        "human_additions",
+        "git_diff_added_lines",
+        "git_diff_deleted_lines",
+        "tool_model_breakdown",
+    ] {
+        assert!(
+            properties.get(field).is_some(),
+            "commit-stats schema is missing {}",
+            field
+        );
+    }
+    assert!(
+        schemas["authorship-metadata"]["properties"]
+            .get("prompts")
+            .is_some()
+    );
+
+    let commit_stats = schema_json(&repo, &["schema", "commit-stats"]);
+    assert_eq!(commit_stats["title"], "CommitStats");
+    assert!(repo.git_ai(&["schema", "no-such-schema"]).is_err());
+}
@@ -366,6 +366,66 @@ fn test_partial_staging_filters_unstaged_lines() {
     ]);
 }
 
+#[test]
+fn test_partial_hunk_staging_commits_only_staged_ai_lines() {
+    // Like `git add -p` accepting the first hunk only: the staged hunk holds AI lines, the
+    // unstaged one holds human lines further down the same file
+    let repo = TestRepo::new();
+    let mut file = repo.filename("hunks.ts");
+
+    file.set_contents(lines![
+        "line1", "line2", "line3", "line4", "line5", "line6", "line7", "line8", "line9", "line10",
+        "line11", "line12",
+    ]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["ai_line1".ai(), "ai_line2".ai()]);
+    file.insert_at(14, lines!["human_line1".human(), "human_line2".human()]);
+
+    let diff = repo.git_og(&["diff", "--", "hunks.ts"]).unwrap();
+    let second_hunk = diff
+        .match_indices("\n@@ ")
+        .nth(1)
+        .map(|(index, _)| index + 1)
+        .expect("diff should have two hunks");
+    let patch_path = repo.path().join(".git").join("first-hunk.patch");
+    fs::write(&patch_path, &diff[..second_hunk]).unwrap();
+    repo.git_og(&["apply", "--cached", patch_path.to_str().unwrap()])
+        .unwrap();
+
+    let commit = repo.commit("Stage AI hunk only").unwrap();
+    assert_eq!(commit.authorship_log.attestations.len(), 1);
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.git_diff_added_lines, 2);
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.human_additions, 0);
+
+    // The human hunk goes in on its own and carries no AI lines
+    repo.stage_all_and_commit("Stage human hunk").unwrap();
+    let stats = repo.stats().unwrap();
+    assert_eq!(stats.git_diff_added_lines, 2);
+    assert_eq!(stats.ai_additions, 0);
+    assert_eq!(stats.human_additions, 2);
+    file.assert_lines_and_blame(lines![
+        "line1".human(),
+        "ai_line1".ai(),
+        "ai_line2".ai(),
+        "line2".human(),
+        "line3".human(),
+        "line4".human(),
+        "line5".human(),
+        "line6".human(),
+        "line7".human(),
+        "line8".human(),
+        "line9".human(),
+        "line10".human(),
+        "line11".human(),
+        "line12".human(),
+        "human_line1".human(),
+        "human_line2".human(),
+    ]);
+}
+
 #[test]
 fn test_human_stages_some_ai_lines() {
     // Test where AI adds multiple lines but human only stages some of them
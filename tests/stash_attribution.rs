@@ -997,3 +997,76 @@ fn test_stash_apply_reset_apply_again() {
         "Expected AI prompts in authorship log after multiple apply/reset cycles"
     );
 }
+
+#[test]
+fn test_stash_branch_keeps_ai_attribution() {
+    let repo = TestRepo::new();
+
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(vec!["# Test Repo".to_string()]);
+    repo.stage_all_and_commit("initial commit")
+        .expect("commit should succeed");
+
+    let mut example = repo.filename("example.txt");
+    example.set_contents(vec!["AI line 1".ai(), "AI line 2".ai()]);
+    repo.git_ai(&["checkpoint", "mock_ai"])
+        .expect("checkpoint should succeed");
+
+    repo.git(&["stash"]).expect("stash should succeed");
+    assert!(repo.read_file("example.txt").is_none());
+
+    // `stash branch` checks out a new branch at the stash's base and pops the stash onto it
+    repo.git(&["stash", "branch", "from-stash"])
+        .expect("stash branch should succeed");
+    assert!(repo.read_file("example.txt").is_some());
+
+    let commit = repo
+        .stage_all_and_commit("commit stashed work on its own branch")
+        .expect("commit should succeed");
+
+    example.assert_lines_and_blame(vec!["AI line 1".ai(), "AI line 2".ai()]);
+    assert!(
+        !commit.authorship_log.metadata.prompts.is_empty(),
+        "Expected AI prompts in authorship log after stash branch"
+    );
+}
+
+#[test]
+fn test_stash_pop_by_numeric_index() {
+    let repo = TestRepo::new();
+
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(vec!["# Test Repo".to_string()]);
+    repo.stage_all_and_commit("initial commit")
+        .expect("commit should succeed");
+
+    let mut older = repo.filename("older.txt");
+    older.set_contents(vec!["older AI line".ai()]);
+    repo.git_ai(&["checkpoint", "mock_ai"])
+        .expect("checkpoint should succeed");
+    repo.git(&["stash", "push", "-m", "older"])
+        .expect("first stash should succeed");
+
+    let mut newer = repo.filename("newer.txt");
+    newer.set_contents(vec!["newer human line".human()]);
+    repo.git_ai(&["checkpoint"])
+        .expect("checkpoint should succeed");
+    repo.git(&["stash", "push", "-m", "newer"])
+        .expect("second stash should succeed");
+
+    // `git stash pop 1` is git's shorthand for stash@{1}
+    repo.git(&["stash", "pop", "1"])
+        .expect("stash pop by index should succeed");
+    assert!(repo.read_file("older.txt").is_some());
+    assert!(repo.read_file("newer.txt").is_none());
+
+    let commit = repo
+        .stage_all_and_commit("apply older stash")
+        .expect("commit should succeed");
+
+    older.assert_lines_and_blame(vec!["older AI line".ai()]);
+    assert!(
+        !commit.authorship_log.metadata.prompts.is_empty(),
+        "Expected AI prompts in authorship log after popping by index"
+    );
+}
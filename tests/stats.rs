@@ -1,5 +1,7 @@
 mod repos;
-use git_ai::authorship::stats::CommitStats;
+use git_ai::authorship::stats::{CommitStats, compute_commit_stats};
+use git_ai::feature_flags::FeatureFlags;
+use git_ai::git::repository::find_repository_in_path;
 use insta::assert_debug_snapshot;
 use repos::test_file::ExpectedLineExt;
 use repos::test_repo::TestRepo;
@@ -9,17 +11,21 @@ use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 use std::process::Command;
 
-/// Extract the first complete JSON object from mixed stdout/stderr output.
-fn extract_json_object(output: &str) -> String {
-    let start = output.find('{').unwrap_or(0);
-    let end = output.rfind('}').unwrap_or(output.len().saturating_sub(1));
-    output[start..=end].to_string()
+fn stats_from_args(repo: &TestRepo, args: &[&str]) -> CommitStats {
+    let stdout = repo
+        .git_ai_stdout(args)
+        .expect("git-ai stats should succeed");
+    serde_json::from_str(&stdout).expect("valid stats json")
 }
 
-fn stats_from_args(repo: &TestRepo, args: &[&str]) -> CommitStats {
-    let raw = repo.git_ai(args).expect("git-ai stats should succeed");
-    let json = extract_json_object(&raw);
-    serde_json::from_str(&json).expect("valid stats json")
+/// A repo whose commits carry AI attestations over moved, copied and reverted lines
+fn repo_with_inter_commit_move() -> TestRepo {
+    let mut repo = TestRepo::new();
+    repo.set_feature_flags(FeatureFlags {
+        inter_commit_move: true,
+        ..FeatureFlags::default()
+    });
+    repo
 }
 
 fn run_git(cwd: &Path, args: &[&str]) {
@@ -134,8 +140,7 @@ fn test_authorship_log_stats() {
 
     assert_eq!(first_commit.authorship_log.attestations.len(), 1);
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
     assert_eq!(stats.human_additions, 4);
     assert_eq!(stats.mixed_additions, 1);
@@ -212,11 +217,10 @@ fn test_stats_cli_range() {
 
     // Sanity check individual commit stats
     let range = format!("{}..{}", first.commit_sha, second.commit_sha);
-    let raw = repo
-        .git_ai(&["stats", &range, "--json"])
+    let output = repo
+        .git_ai_stdout(&["stats", &range, "--json"])
         .expect("git-ai stats range should succeed");
 
-    let output = extract_json_object(&raw);
     let stats: git_ai::authorship::range_authorship::RangeAuthorshipStats =
         serde_json::from_str(&output).unwrap();
 
@@ -255,92 +259,1727 @@ fn test_stats_cli_range_ignores_repo_external_diff_helper() {
         "sanity check: proxied git diff should use configured external helper"
     );
 
-    let range = format!("{}..{}", first.commit_sha, second.commit_sha);
-    let raw = repo
-        .git_ai(&["stats", &range, "--json"])
-        .expect("git-ai stats range should succeed with external diff configured");
-    assert!(
-        !raw.contains(&marker),
-        "git-ai stats output should not include external helper output, got:\n{}",
-        raw
-    );
+    let range = format!("{}..{}", first.commit_sha, second.commit_sha);
+    let raw = repo
+        .git_ai(&["stats", &range, "--json"])
+        .expect("git-ai stats range should succeed with external diff configured");
+    assert!(
+        !raw.contains(&marker),
+        "git-ai stats output should not include external helper output, got:\n{}",
+        raw
+    );
+
+    let output = repo
+        .git_ai_stdout(&["stats", &range, "--json"])
+        .expect("git-ai stats range should succeed with external diff configured");
+    let stats: git_ai::authorship::range_authorship::RangeAuthorshipStats =
+        serde_json::from_str(&output).unwrap();
+    assert_eq!(stats.authorship_stats.total_commits, 1);
+    assert!(
+        stats.range_stats.git_diff_added_lines >= 1,
+        "expected at least one added line in range, got {}",
+        stats.range_stats.git_diff_added_lines
+    );
+    assert!(stats.range_stats.ai_additions >= 1);
+}
+
+#[test]
+fn test_stats_cli_range_with_hostile_diff_config() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("stats-range-hostile.txt");
+    file.set_contents(lines!["base".human()]);
+    let first = repo.stage_all_and_commit("initial").unwrap();
+
+    file.set_contents(lines!["base".human(), "ai line".ai()]);
+    let second = repo.stage_all_and_commit("ai second").unwrap();
+
+    configure_hostile_diff_settings(&repo);
+
+    let range = format!("{}..{}", first.commit_sha, second.commit_sha);
+    let output = repo
+        .git_ai_stdout(&["stats", &range, "--json"])
+        .expect("git-ai stats range should succeed with hostile diff config");
+    let stats: git_ai::authorship::range_authorship::RangeAuthorshipStats =
+        serde_json::from_str(&output).unwrap();
+
+    assert_eq!(stats.authorship_stats.total_commits, 1);
+    assert!(stats.range_stats.git_diff_added_lines >= 1);
+    assert!(stats.range_stats.ai_additions >= 1);
+}
+
+#[test]
+fn test_stats_cli_range_aggregates_three_commits() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("pr.txt");
+    file.set_contents(lines!["base".human()]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+
+    file.set_contents(lines![
+        "base".human(),
+        "ai 1".ai(),
+        "ai 2".ai(),
+        "human 1".human(),
+    ]);
+    repo.stage_all_and_commit("Mixed").unwrap();
+
+    file.set_contents(lines![
+        "base".human(),
+        "ai 1".ai(),
+        "ai 2".ai(),
+        "human 1".human(),
+        "ai scratch".ai(),
+    ]);
+    repo.stage_all_and_commit("AI scratch line").unwrap();
+
+    // The scratch line added by AI is removed again, so it must not count towards the range.
+    file.set_contents(lines![
+        "base".human(),
+        "ai 1".ai(),
+        "ai 2".ai(),
+        "human 1".human(),
+        "human 2".human(),
+    ]);
+    let head = repo.stage_all_and_commit("Replace scratch").unwrap();
+
+    let range = format!("{}..{}", base.commit_sha, head.commit_sha);
+    let output = repo
+        .git_ai_stdout(&["stats", &range, "--json"])
+        .expect("git-ai stats range should succeed");
+    let stats: git_ai::authorship::range_authorship::RangeAuthorshipStats =
+        serde_json::from_str(&output).unwrap();
+
+    assert_eq!(stats.authorship_stats.total_commits, 3);
+    assert_eq!(stats.range_stats.git_diff_added_lines, 4);
+    assert_eq!(stats.range_stats.ai_additions, 2);
+    assert_eq!(stats.range_stats.human_additions, 2);
+    assert_eq!(stats.range_stats.ai_percentage, 50.0);
+}
+
+#[test]
+fn test_stats_author_email_filters_range() {
+    let repo = TestRepo::new();
+    let alice = [
+        ("GIT_AUTHOR_NAME", "Alice"),
+        ("GIT_AUTHOR_EMAIL", "alice@example.com"),
+    ];
+    let bob = [
+        ("GIT_AUTHOR_NAME", "Bob"),
+        ("GIT_AUTHOR_EMAIL", "bob@example.com"),
+    ];
+
+    let mut file = repo.filename("team.txt");
+    file.set_contents(lines!["base".human()]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+
+    file.set_contents(lines!["base".human(), "alice ai 1".ai(), "alice ai 2".ai()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.commit_with_env("Alice AI", &alice, None).unwrap();
+
+    file.set_contents(lines![
+        "base".human(),
+        "alice ai 1".ai(),
+        "alice ai 2".ai(),
+        "bob 1".human(),
+        "bob 2".human(),
+        "bob 3".human(),
+    ]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.commit_with_env("Bob human", &bob, None).unwrap();
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let alice_stats = stats_from_args(
+        &repo,
+        &[
+            "stats",
+            &range,
+            "--author-email",
+            "alice@example.com",
+            "--json",
+        ],
+    );
+    assert_eq!(alice_stats.ai_additions, 2);
+    assert_eq!(alice_stats.human_additions, 0);
+
+    let bob_stats = stats_from_args(
+        &repo,
+        &[
+            "stats",
+            &range,
+            "--author-email",
+            "BOB@example.com",
+            "--json",
+        ],
+    );
+    assert_eq!(bob_stats.ai_additions, 0);
+    assert_eq!(bob_stats.human_additions, 3);
+
+    let err = repo
+        .git_ai(&["stats", "--author-email", "alice@example.com"])
+        .expect_err("--author-email without a range should fail");
+    assert!(err.contains("requires a commit range"), "got: {}", err);
+}
+
+#[test]
+fn test_stats_count_identical_files_stored_once_in_log() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    for path in ["gen/a/index.ts", "gen/b/index.ts"] {
+        repo.filename(path).set_contents(lines![
+            "export * from './one';".ai(),
+            "export * from './two';".ai(),
+            "export * from './three';".ai(),
+            "export * from './four';".ai(),
+        ]);
+    }
+    let commit = repo.stage_all_and_commit("Generated indexes").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", &commit.commit_sha, "--json"]);
+    assert_eq!(stats.ai_additions, 8);
+    assert_eq!(stats.human_additions, 0);
+
+    // The second copy only references the entries of the first
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", &commit.commit_sha])
+        .unwrap();
+    let attestations = &note[..note.find("\n---\n").unwrap()];
+    assert_eq!(attestations.matches(" 1-4").count(), 1, "{}", attestations);
+    assert!(
+        attestations.contains("gen/b/index.ts\n  = "),
+        "{}",
+        attestations
+    );
+    assert_eq!(commit.authorship_log.attestations.len(), 2);
+    assert_eq!(
+        commit.authorship_log.attestations[0].entries,
+        commit.authorship_log.attestations[1].entries
+    );
+}
+
+#[test]
+fn test_stats_in_repository_without_commits_is_all_zeros() {
+    let repo = TestRepo::new();
+
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(
+        serde_json::to_value(&stats).unwrap(),
+        serde_json::to_value(CommitStats::default()).unwrap()
+    );
+
+    let log = repo.git_ai(&["log", "--json"]).expect("log should succeed");
+    assert_eq!(log.trim(), "[]");
+
+    fs::write(repo.path().join("draft.txt"), "draft\n").unwrap();
+    let err = repo
+        .git_ai(&["blame", "draft.txt"])
+        .expect_err("blame without commits should fail");
+    assert!(err.contains("no commits yet"), "got: {}", err);
+}
+
+#[test]
+fn test_stats_author_and_committer_filters_diverge_after_rebase() {
+    let repo = TestRepo::new();
+    let alice = [
+        ("GIT_AUTHOR_NAME", "Alice"),
+        ("GIT_AUTHOR_EMAIL", "alice@example.com"),
+        ("GIT_COMMITTER_NAME", "Alice"),
+        ("GIT_COMMITTER_EMAIL", "alice@example.com"),
+    ];
+    let bob = [
+        ("GIT_COMMITTER_NAME", "Bob"),
+        ("GIT_COMMITTER_EMAIL", "bob@example.com"),
+    ];
+
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    repo.filename("feature.rs")
+        .set_contents(lines!["fn a() {}".ai(), "fn b() {}".ai()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.commit_with_env("Alice AI", &alice, None).unwrap();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    repo.filename("main.rs")
+        .set_contents(lines!["fn main() {}".human()]);
+    repo.stage_all_and_commit("Main advances").unwrap();
+
+    // Bob rebases Alice's commit: she stays the author, he becomes the committer
+    repo.git(&["checkout", "feature"]).unwrap();
+    repo.git_with_env(&["rebase", &default_branch], &bob, None)
+        .unwrap();
+    let identities = repo.git(&["log", "-1", "--format=%ae %ce"]).unwrap();
+    assert_eq!(identities.trim(), "alice@example.com bob@example.com");
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let filtered =
+        |flag: &str, email: &str| stats_from_args(&repo, &["stats", &range, flag, email, "--json"]);
+
+    let by_author = filtered("--author-email", "alice@example.com");
+    assert_eq!(by_author.ai_additions, 2);
+    assert_eq!(by_author.human_additions, 0);
+
+    let by_committer = filtered("--committer-email", "bob@example.com");
+    assert_eq!(by_committer.ai_additions, 2);
+    assert_eq!(by_committer.human_additions, 0);
+
+    // Each filter only looks at its own identity
+    assert_eq!(
+        filtered("--committer-email", "alice@example.com").ai_additions,
+        0
+    );
+    assert_eq!(
+        filtered("--author-email", "bob@example.com").ai_additions,
+        0
+    );
+
+    let board_by = |by: &str| -> Vec<String> {
+        let output = repo
+            .git_ai_stdout(&["leaderboard", &range, by, "--json"])
+            .expect("git-ai leaderboard should succeed");
+        let rows: Vec<serde_json::Value> = serde_json::from_str(&output).unwrap();
+        let key = &by["--by=".len()..];
+        rows.iter()
+            .map(|row| row[key].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert!(board_by("--by=author").contains(&"alice@example.com".to_string()));
+    assert!(!board_by("--by=author").contains(&"bob@example.com".to_string()));
+    assert!(board_by("--by=committer").contains(&"bob@example.com".to_string()));
+    assert!(!board_by("--by=committer").contains(&"alice@example.com".to_string()));
+}
+
+#[test]
+fn test_stats_file_glob_restricts_to_matching_paths() {
+    let repo = TestRepo::new();
+    let alice = [
+        ("GIT_AUTHOR_NAME", "Alice"),
+        ("GIT_AUTHOR_EMAIL", "alice@example.com"),
+    ];
+    let bob = [
+        ("GIT_AUTHOR_NAME", "Bob"),
+        ("GIT_AUTHOR_EMAIL", "bob@example.com"),
+    ];
+
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+
+    let mut code = repo.filename("src/模块/lib.rs");
+    let mut notes = repo.filename("notes.txt");
+    code.set_contents(lines![
+        "fn main() {}".human(),
+        "fn a() {}".ai(),
+        "fn b() {}".ai()
+    ]);
+    notes.set_contents(lines!["one".human(), "two".human(), "three".human()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.commit_with_env("Alice", &alice, None).unwrap();
+
+    code.set_contents(lines![
+        "fn main() {}".human(),
+        "fn a() {}".ai(),
+        "fn b() {}".ai(),
+        "fn c() {}".human(),
+    ]);
+    notes.set_contents(lines![
+        "one".human(),
+        "two".human(),
+        "three".human(),
+        "four".human(),
+        "five".human(),
+    ]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.commit_with_env("Bob", &bob, None).unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "HEAD~1", "--json"]);
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.human_additions, 4);
+
+    let stats = stats_from_args(
+        &repo,
+        &["stats", "HEAD~1", "--file", "src/**/*.rs", "--json"],
+    );
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.human_additions, 1);
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let stats = stats_from_args(&repo, &["stats", &range, "--file=src/**/*.rs", "--json"]);
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.human_additions, 2);
+
+    let stats = stats_from_args(
+        &repo,
+        &[
+            "stats",
+            &range,
+            "--author-email",
+            "bob@example.com",
+            "--file",
+            "src/**/*.rs",
+            "--json",
+        ],
+    );
+    assert_eq!(stats.ai_additions, 0);
+    assert_eq!(stats.human_additions, 1);
+
+    let stats = stats_from_args(
+        &repo,
+        &[
+            "stats", &range, "--file", "*.txt", "--file", "*.md", "--json",
+        ],
+    );
+    assert_eq!(stats.ai_additions, 0);
+    assert_eq!(stats.human_additions, 5);
+}
+
+#[test]
+fn test_stats_range_json_has_no_progress_output_without_tty() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}".human()]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+    for n in 0..3 {
+        file.insert_at(n + 1, lines![format!("fn ai_{}() {{}}", n).ai()]);
+        repo.stage_all_and_commit(&format!("AI {}", n)).unwrap();
+    }
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let output = repo
+        .git_ai(&["stats", &range, "--json"])
+        .expect("range stats should succeed");
+    assert!(!output.contains("commits\r"), "got: {:?}", output);
+    assert!(!output.contains("/3 commits"), "got: {:?}", output);
+    let output = repo
+        .git_ai_stdout(&["stats", &range, "--json"])
+        .expect("range stats should succeed");
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["range_stats"]["ai_additions"], 3);
+
+    let output = repo
+        .git_ai(&["stats", &range])
+        .expect("range stats should succeed");
+    assert!(!output.contains("/3 commits"), "got: {:?}", output);
+}
+
+#[test]
+fn test_stats_net_counts_only_surviving_ai_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn one() {}".human(), "fn two() {}".human()]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+
+    file.insert_at(
+        2,
+        lines![
+            "fn ai_1() {}".ai(),
+            "fn ai_2() {}".ai(),
+            "fn ai_3() {}".ai(),
+            "fn ai_4() {}".ai()
+        ],
+    );
+    repo.stage_all_and_commit("AI adds four").unwrap();
+    file.delete_range(2, 4);
+    repo.stage_all_and_commit("Human removes half").unwrap();
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let summed = repo.git_ai_stdout(&["stats", &range, "--json"]).unwrap();
+    let summed: serde_json::Value = serde_json::from_str(&summed).unwrap();
+    assert_eq!(summed["range_stats"]["ai_additions"], 4);
+
+    // Over the range only lines introduced after the base are counted
+    let net = repo
+        .git_ai_stdout(&["stats", &range, "--net", "--json"])
+        .unwrap();
+    let net: serde_json::Value = serde_json::from_str(&net).unwrap();
+    assert_eq!(net["ai_lines"], 2);
+    assert_eq!(net["human_lines"], 0);
+
+    // Without a range the whole HEAD tree is counted
+    let net = repo.git_ai_stdout(&["stats", "--net", "--json"]).unwrap();
+    let net: serde_json::Value = serde_json::from_str(&net).unwrap();
+    assert_eq!(net["ai_lines"], 2);
+    assert_eq!(net["human_lines"], 2);
+    assert_eq!(net["ai_percentage"], 50.0);
+}
+
+#[test]
+fn test_stats_fail_on_missing_attestation_lists_unattested_commits() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}".human()]);
+    let first = repo.stage_all_and_commit("Base").unwrap();
+
+    file.insert_at(1, lines!["fn ai() {}".ai()]);
+    let unattested = repo.stage_all_and_commit("Lost note").unwrap();
+    repo.git_og(&["notes", "--ref=ai", "remove", &unattested.commit_sha])
+        .unwrap();
+
+    file.insert_at(2, lines!["fn human() {}".human()]);
+    repo.stage_all_and_commit("Attested").unwrap();
+
+    let range = format!("{}..HEAD", first.commit_sha);
+
+    // Lenient by default
+    repo.git_ai(&["stats", &range, "--json"]).unwrap();
+
+    let err = repo
+        .git_ai(&["stats", &range, "--json", "--fail-on-missing-attestation"])
+        .expect_err("strict mode should fail when a commit has no authorship log");
+    assert!(
+        err.contains("1 commit(s) have no authorship log"),
+        "{}",
+        err
+    );
+    assert!(err.contains(&unattested.commit_sha[..7]), "{}", err);
+}
+
+#[test]
+fn test_stats_exclude_generated_skips_marked_and_conventional_files() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    repo.filename("src/schema.rs").set_contents(lines![
+        "// @generated by schema-gen, do not edit".ai(),
+        "pub struct Row {}".ai(),
+        "pub struct Column {}".ai(),
+    ]);
+    repo.filename("api/service.pb.go")
+        .set_contents(lines!["package api".ai()]);
+    repo.filename("src/main.rs")
+        .set_contents(lines!["fn main() {}".human(), "fn helper() {}".human()]);
+    repo.stage_all_and_commit("Add generated and handwritten code")
+        .unwrap();
+
+    let all = stats_from_args(&repo, &["stats", "HEAD", "--json"]);
+    assert_eq!(all.ai_additions, 4);
+    assert_eq!(all.human_additions, 2);
+
+    let handwritten = stats_from_args(&repo, &["stats", "HEAD", "--json", "--exclude-generated"]);
+    assert_eq!(handwritten.ai_additions, 0);
+    assert_eq!(handwritten.human_additions, 2);
+    assert_eq!(handwritten.git_diff_added_lines, 2);
+}
+
+#[test]
+fn test_stats_profile_reports_phase_timings_only_when_requested() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    repo.filename("src/lib.rs")
+        .set_contents(lines!["fn ai() {}".ai(), "fn human() {}".human()]);
+    repo.stage_all_and_commit("Add lib").unwrap();
+
+    for args in [
+        vec!["stats", "--json", "--profile"],
+        vec!["stats", "HEAD~1..HEAD", "--json", "--profile"],
+        vec!["stats", "HEAD~1..HEAD", "--csv", "--profile"],
+    ] {
+        let output = repo.git_ai(&args).unwrap();
+        assert!(output.contains("stats profile:"), "{:?}: {}", args, output);
+        for phase in ["diff", "notes", "attribution match", "serialization"] {
+            assert!(
+                output
+                    .lines()
+                    .any(|line| line.trim_start().starts_with(phase) && line.contains("ms")),
+                "{:?} is missing the {} phase: {}",
+                args,
+                phase,
+                output
+            );
+        }
+    }
+
+    // The stats themselves are unchanged by profiling
+    let profiled = stats_from_args(&repo, &["stats", "--json", "--profile"]);
+    assert_eq!(profiled.ai_additions, 1);
+    assert_eq!(profiled.human_additions, 1);
+
+    let output = repo.git_ai(&["stats", "--json"]).unwrap();
+    assert!(!output.contains("stats profile:"));
+    assert!(!output.contains("attribution match"));
+
+    // Failing a threshold exits early but still reports the timings
+    let stderr = repo
+        .git_ai(&["stats", "--json", "--profile", "--max-ai-percentage", "10"])
+        .expect_err("50% AI should fail a 10% threshold");
+    assert!(stderr.contains("Threshold failed"), "{}", stderr);
+    assert!(stderr.contains("stats profile:"), "{}", stderr);
+}
+
+#[test]
+fn test_stats_baseline_excludes_lines_from_before_it() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn old_human() {}".human(), "fn old_ai() {}".ai()]);
+    let baseline = repo.stage_all_and_commit("Before adoption").unwrap();
+
+    file.insert_at(2, lines!["fn new_ai_1() {}".ai(), "fn new_ai_2() {}".ai()]);
+    repo.stage_all_and_commit("AI after adoption").unwrap();
+    file.insert_at(4, lines!["fn new_human() {}".human()]);
+    repo.stage_all_and_commit("Human after adoption").unwrap();
+
+    for args in [
+        vec!["stats", "--baseline", &baseline.commit_sha, "--json"],
+        vec![
+            "stats",
+            "HEAD",
+            "--json",
+            &format!("--baseline={}", baseline.commit_sha),
+        ],
+    ] {
+        let output = repo.git_ai_stdout(&args).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json["ai_lines"], 2);
+        assert_eq!(json["human_lines"], 1);
+        assert_eq!(json["base"], baseline.commit_sha.as_str());
+    }
+
+    // The whole tree still counts the lines written before the baseline
+    let output = repo.git_ai_stdout(&["stats", "--net", "--json"]).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["ai_lines"], 3);
+    assert_eq!(json["human_lines"], 2);
+
+    let range = format!("{}..HEAD", baseline.commit_sha);
+    assert!(
+        repo.git_ai(&["stats", &range, "--baseline", &baseline.commit_sha])
+            .is_err()
+    );
+}
+
+#[test]
+fn test_stats_ndjson_streams_one_valid_object_per_commit() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}".human()]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+    let mut shas = Vec::new();
+    for n in 0..4 {
+        file.insert_at(n + 1, lines![format!("fn ai_{}() {{}}", n).ai()]);
+        shas.push(
+            repo.stage_all_and_commit(&format!("AI {}", n))
+                .unwrap()
+                .commit_sha,
+        );
+    }
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    for jobs in ["1", "3"] {
+        let output = repo
+            .git_ai(&["stats", &range, "--format=ndjson", "--jobs", jobs])
+            .expect("ndjson stats should succeed");
+        let lines: Vec<&str> = output.lines().filter(|line| !line.is_empty()).collect();
+        assert_eq!(lines.len(), 4, "got: {:?}", output);
+
+        let mut seen = Vec::new();
+        for line in lines {
+            let stats: CommitStats = serde_json::from_str(line).expect("each line is stats json");
+            assert_eq!(stats.ai_additions, 1);
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            seen.push(value["commit"].as_str().unwrap().to_string());
+        }
+        seen.sort();
+        let mut expected = shas.clone();
+        expected.sort();
+        assert_eq!(seen, expected);
+    }
+}
+
+#[test]
+fn test_stats_classify_tests_buckets_test_and_src_files() {
+    let repo = TestRepo::new();
+    let mut test_file = repo.filename("tests/parser.rs");
+    test_file.set_contents(lines!["fn parses() {}".ai(), "fn rejects() {}".ai()]);
+    let mut src_file = repo.filename("src/parser.rs");
+    src_file.set_contents(lines!["fn parse() {}".human(), "fn helper() {}".ai()]);
+    repo.stage_all_and_commit("Parser with tests").unwrap();
+
+    let output = repo
+        .git_ai_stdout(&["stats", "--classify-tests", "--json"])
+        .expect("classified stats should succeed");
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let test: CommitStats = serde_json::from_value(json["test"].clone()).unwrap();
+    let production: CommitStats = serde_json::from_value(json["production"].clone()).unwrap();
+    assert_eq!(test.ai_additions, 2);
+    assert_eq!(test.human_additions, 0);
+    assert_eq!(production.ai_additions, 1);
+    assert_eq!(production.human_additions, 1);
+
+    // Custom patterns replace the defaults
+    let output = repo
+        .git_ai_stdout(&[
+            "stats",
+            "--classify-tests",
+            "--test-pattern",
+            "src/**",
+            "--json",
+        ])
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["test"]["ai_additions"], 1);
+    assert_eq!(json["production"]["ai_additions"], 2);
+}
+
+#[test]
+fn test_stats_by_session_groups_ai_lines_by_session_env() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("tagged.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+    repo.git_ai_with_env(
+        &["checkpoint", "mock_ai", "tagged.rs"],
+        &[
+            ("GIT_AI_SESSION_ID", "session-42"),
+            ("GIT_AI_TOOL", "my-agent"),
+        ],
+    )
+    .expect("tagged checkpoint should succeed");
+    fs::write(repo.path().join("untagged.rs"), "fn c() {}\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "untagged.rs"])
+        .expect("untagged checkpoint should succeed");
+    let commit = repo.stage_all_and_commit("Two sessions").unwrap();
+
+    let prompts = &commit.authorship_log.metadata.prompts;
+    let tagged = prompts
+        .values()
+        .find(|prompt| prompt.session_id.as_deref() == Some("session-42"))
+        .expect("session id should be recorded on the prompt");
+    assert_eq!(tagged.agent_id.tool, "my-agent");
+    assert_eq!(
+        prompts
+            .values()
+            .filter(|prompt| prompt.session_id.is_none())
+            .count(),
+        1
+    );
+
+    let stats = stats_from_args(&repo, &["stats", "--by-session", "--json"]);
+    assert_eq!(stats.ai_additions, 3);
+    assert_eq!(stats.ai_by_session.get("session-42"), Some(&2));
+    assert_eq!(stats.ai_by_session.get("unknown"), Some(&1));
+
+    // Without the flag the breakdown is left out of the JSON
+    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
+    assert!(!raw.contains("ai_by_session"));
+}
+
+#[test]
+fn test_stats_by_language_sums_files_per_language() {
+    let repo = TestRepo::new();
+    let mut rust = repo.filename("src/lib.rs");
+    rust.set_contents(lines![
+        "fn a() {}".ai(),
+        "fn b() {}".ai(),
+        "fn c() {}".human()
+    ]);
+    let mut ts = repo.filename("web/app.ts");
+    ts.set_contents(lines![
+        "export const a = 1;".ai(),
+        "export const b = 2;".human()
+    ]);
+    let mut json = repo.filename("config.json");
+    json.set_contents(lines!["{".human(), "}".human()]);
+    let mut notes = repo.filename("NOTES");
+    notes.set_contents(lines!["plain text".human()]);
+    repo.stage_all_and_commit("Mixed languages").unwrap();
+
+    let output = repo
+        .git_ai_stdout(&["stats", "--by-language", "--json"])
+        .expect("language stats should succeed");
+    let languages: std::collections::BTreeMap<String, CommitStats> =
+        serde_json::from_str(&output).unwrap();
+
+    let counts: Vec<(&str, u32, u32)> = languages
+        .iter()
+        .map(|(language, stats)| (language.as_str(), stats.ai_additions, stats.human_additions))
+        .collect();
+    assert_eq!(
+        counts,
+        vec![
+            ("JSON", 0, 2),
+            ("Rust", 2, 1),
+            ("TypeScript", 1, 1),
+            ("other", 0, 1)
+        ]
+    );
+}
+
+#[test]
+fn test_stats_group_by_dir_sums_files_per_directory_prefix() {
+    let repo = TestRepo::new();
+    repo.filename("services/a/x.rs")
+        .set_contents(lines!["fn a() {}".ai(), "fn b() {}".human()]);
+    repo.filename("services/b/y.rs")
+        .set_contents(lines!["fn c() {}".ai()]);
+    repo.filename("src/模块/z.rs")
+        .set_contents(lines!["fn d() {}".ai(), "fn e() {}".ai()]);
+    repo.filename("README.md")
+        .set_contents(lines!["# readme".human()]);
+    repo.stage_all_and_commit("Nested directories").unwrap();
+
+    let grouped = |flag: &str| -> Vec<(String, u32, u32)> {
+        let output = repo
+            .git_ai_stdout(&["stats", flag, "--json"])
+            .expect("directory stats should succeed");
+        let directories: std::collections::BTreeMap<String, CommitStats> =
+            serde_json::from_str(&output).unwrap();
+        directories
+            .into_iter()
+            .map(|(dir, stats)| (dir, stats.ai_additions, stats.human_additions))
+            .collect()
+    };
+
+    assert_eq!(
+        grouped("--group-by-dir"),
+        vec![
+            (".".to_string(), 0, 1),
+            ("services".to_string(), 2, 1),
+            ("src".to_string(), 2, 0)
+        ]
+    );
+    assert_eq!(
+        grouped("--group-by-dir=2"),
+        vec![
+            (".".to_string(), 0, 1),
+            ("services/a".to_string(), 1, 1),
+            ("services/b".to_string(), 1, 0),
+            ("src/模块".to_string(), 2, 0)
+        ]
+    );
+}
+
+#[test]
+fn test_stats_counts_file_without_trailing_newline_like_git() {
+    let repo = TestRepo::new();
+    // set_contents joins lines without a trailing newline
+    let mut file = repo.filename("no_eol.rs");
+    file.set_contents(lines!["fn a() {}".ai(), "fn b() {}".ai()]);
+    repo.stage_all_and_commit("AI file without trailing newline")
+        .unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 2);
+    assert_eq!(stats.ai_additions, stats.git_diff_added_lines);
+
+    // Appending gives the old last line its newline, so git counts it as added again
+    fs::write(
+        repo.path().join("no_eol.rs"),
+        "fn a() {}\nfn b() {}\nfn c() {}",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "no_eol.rs"])
+        .expect("checkpoint should succeed");
+    repo.stage_all_and_commit("AI appends to it").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 2);
+    assert_eq!(stats.ai_additions, stats.git_diff_added_lines);
+    assert_eq!(stats.human_additions, 0);
+}
+
+#[test]
+fn test_stats_since_until_filters_by_commit_date() {
+    let repo = TestRepo::new();
+    let commit_on = |message: &str, date: &str, email: &str| {
+        repo.git(&["add", "-A"]).unwrap();
+        repo.commit_with_env(
+            message,
+            &[
+                ("GIT_AUTHOR_EMAIL", email),
+                ("GIT_AUTHOR_DATE", date),
+                ("GIT_COMMITTER_DATE", date),
+            ],
+            None,
+        )
+        .unwrap();
+    };
+
+    repo.filename("jan.txt")
+        .set_contents(lines!["jan 1".ai(), "jan 2".ai()]);
+    commit_on("January", "2024-01-15 12:00:00 +0000", "alice@example.com");
+    repo.filename("feb.txt")
+        .set_contents(lines!["feb 1".ai(), "feb 2".human()]);
+    commit_on("February", "2024-02-10 12:00:00 +0000", "alice@example.com");
+    repo.filename("mar.txt").set_contents(lines![
+        "mar 1".human(),
+        "mar 2".human(),
+        "mar 3".human()
+    ]);
+    commit_on("March", "2024-03-05 12:00:00 +0000", "bob@example.com");
+    repo.filename("apr.txt").set_contents(lines!["apr 1".ai()]);
+    commit_on("April", "2024-04-20 12:00:00 +0000", "alice@example.com");
+
+    let window = stats_from_args(
+        &repo,
+        &[
+            "stats",
+            "--since",
+            "2024-02-01",
+            "--until=2024-03-31",
+            "--json",
+        ],
+    );
+    assert_eq!(window.ai_additions, 1);
+    assert_eq!(window.human_additions, 4);
+
+    let alice_window = stats_from_args(
+        &repo,
+        &[
+            "stats",
+            "--since=2024-02-01",
+            "--author-email",
+            "alice@example.com",
+            "--json",
+        ],
+    );
+    assert_eq!(alice_window.ai_additions, 2);
+    assert_eq!(alice_window.human_additions, 1);
+}
+
+#[test]
+fn test_stats_thresholds_gate_exit_status() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    let base = repo.stage_all_and_commit("Initial").unwrap().commit_sha;
+
+    repo.filename("lib.rs").set_contents(lines![
+        "fn a1() {}".ai(),
+        "fn a2() {}".ai(),
+        "fn a3() {}".ai(),
+        "fn a4() {}".ai(),
+        "fn h1() {}".human(),
+    ]);
+    let head = repo.stage_all_and_commit("80% AI").unwrap().commit_sha;
+    let range = format!("{}..{}", base, head);
+
+    // Exactly at the limits passes
+    let stats = stats_from_args(
+        &repo,
+        &[
+            "stats",
+            &head,
+            "--json",
+            "--max-ai-percentage",
+            "80",
+            "--min-human-lines=1",
+        ],
+    );
+    assert_eq!(stats.ai_percentage, 80.0);
+    repo.git_ai(&["stats", &range, "--max-ai-percentage=80"])
+        .expect("range at the AI limit should pass");
+
+    // Just past them fails and reports the offending values
+    let err = repo
+        .git_ai(&["stats", &head, "--max-ai-percentage", "79.9"])
+        .expect_err("80% AI should exceed a 79.9% limit");
+    assert!(err.contains("AI percentage 80%"), "got: {}", err);
+    let err = repo
+        .git_ai(&["stats", &range, "--json", "--min-human-lines", "2"])
+        .expect_err("one human line should fail a minimum of two");
+    assert!(err.contains("1 human lines added"), "got: {}", err);
+}
+
+#[test]
+fn test_moved_ai_block_keeps_ai_attribution() {
+    let repo = repo_with_inter_commit_move();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines![
+        "fn h1() {}".human(),
+        "fn h2() {}".human(),
+        "fn ai1() {}".ai(),
+        "fn ai2() {}".ai(),
+        "fn ai3() {}".ai(),
+        "fn h3() {}".human(),
+        "fn h4() {}".human(),
+        "fn h5() {}".human(),
+        "fn h6() {}".human(),
+        "fn h7() {}".human(),
+        "fn h8() {}".human(),
+    ]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    // A human cuts the AI block and pastes it further down, unchanged
+    file.delete_range(2, 5);
+    file.insert_at(7, lines!["fn ai1() {}", "fn ai2() {}", "fn ai3() {}"]);
+    let moved = repo.stage_all_and_commit("Move AI block").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", &moved.commit_sha, "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 3);
+    assert_eq!(stats.ai_additions, 3);
+    assert_eq!(stats.human_additions, 0);
+
+    // Moving it back with a line edited on the way no longer counts as a move
+    file.delete_range(7, 10);
+    file.insert_at(
+        2,
+        lines!["fn ai1() {}", "fn ai2_renamed() {}", "fn ai3() {}"],
+    );
+    let edited = repo.stage_all_and_commit("Move and edit AI block").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", &edited.commit_sha, "--json"]);
+    assert_eq!(stats.ai_additions, 0);
+    assert_eq!(stats.human_additions, 3);
+}
+
+#[test]
+fn test_moved_ai_block_is_not_carried_over_without_inter_commit_move() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines![
+        "fn h1() {}".human(),
+        "fn ai1() {}".ai(),
+        "fn ai2() {}".ai(),
+        "fn ai3() {}".ai(),
+        "fn h2() {}".human(),
+        "fn h3() {}".human(),
+        "fn h4() {}".human(),
+        "fn h5() {}".human(),
+        "fn h6() {}".human(),
+    ]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    file.delete_range(1, 4);
+    file.insert_at(6, lines!["fn ai1() {}", "fn ai2() {}", "fn ai3() {}"]);
+    let moved = repo.stage_all_and_commit("Move AI block").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", &moved.commit_sha, "--json"]);
+    assert_eq!(stats.ai_additions, 0);
+    assert_eq!(stats.human_additions, 3);
+}
+
+#[test]
+fn test_ai_block_moved_to_another_file_keeps_ai_attribution() {
+    let repo = repo_with_inter_commit_move();
+    let mut source = repo.filename("a.rs");
+    source.set_contents(lines![
+        "fn h1() {}".human(),
+        "fn ai1() {}".ai(),
+        "fn ai2() {}".ai(),
+        "fn ai3() {}".ai(),
+        "fn h2() {}".human(),
+    ]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    // A human cuts the AI block out of a.rs and pastes it, unchanged, into a new b.rs
+    source.delete_range(1, 4);
+    repo.filename("b.rs")
+        .set_contents(lines!["fn ai1() {}", "fn ai2() {}", "fn ai3() {}"]);
+    let moved = repo.stage_all_and_commit("Move AI block").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", &moved.commit_sha, "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 3);
+    assert_eq!(stats.ai_additions, 3);
+    assert_eq!(stats.human_additions, 0);
+}
+
+#[test]
+fn test_ai_file_split_into_two_keeps_ai_attribution_in_both() {
+    let repo = TestRepo::new();
+    repo.filename("shapes.rs").set_contents(lines![
+        "use std::fmt;".human(),
+        "fn circle() {}".ai(),
+        "fn circle_area() {}".ai(),
+        "fn circle_fmt() {}".ai(),
+        "fn square() {}".ai(),
+        "fn square_area() {}".ai(),
+        "fn square_fmt() {}".ai(),
+    ]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    // A human splits shapes.rs into one file per shape and deletes it
+    std::fs::remove_file(repo.path().join("shapes.rs")).unwrap();
+    let mut circle = repo.filename("circle.rs");
+    circle.set_contents(lines![
+        "use std::fmt;",
+        "fn circle() {}",
+        "fn circle_area() {}",
+        "fn circle_fmt() {}",
+    ]);
+    let mut square = repo.filename("square.rs");
+    square.set_contents(lines![
+        "// Squares",
+        "fn square() {}",
+        "fn square_area() {}",
+        "fn square_fmt() {}",
+    ]);
+    let split = repo.stage_all_and_commit("Split shapes.rs").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", &split.commit_sha, "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 8);
+    assert_eq!(stats.ai_additions, 6);
+    assert_eq!(stats.human_additions, 2);
+
+    circle.assert_lines_and_blame(lines![
+        "use std::fmt;".human(),
+        "fn circle() {}".ai(),
+        "fn circle_area() {}".ai(),
+        "fn circle_fmt() {}".ai(),
+    ]);
+    square.assert_lines_and_blame(lines![
+        "// Squares".human(),
+        "fn square() {}".ai(),
+        "fn square_area() {}".ai(),
+        "fn square_fmt() {}".ai(),
+    ]);
+}
+
+#[test]
+fn test_ai_file_renamed_and_split_keeps_ai_attribution() {
+    let repo = TestRepo::new();
+    repo.filename("shapes.rs").set_contents(lines![
+        "fn circle() {}".ai(),
+        "fn circle_area() {}".ai(),
+        "fn circle_fmt() {}".ai(),
+        "fn square() {}".ai(),
+        "fn square_area() {}".ai(),
+        "fn square_fmt() {}".ai(),
+    ]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    // shapes.rs is renamed to circle.rs, and its second half moves out to square.rs
+    repo.git(&["mv", "shapes.rs", "circle.rs"]).unwrap();
+    let mut circle = repo.filename("circle.rs");
+    circle.set_contents(lines![
+        "fn circle() {}",
+        "fn circle_area() {}",
+        "fn circle_fmt() {}"
+    ]);
+    let mut square = repo.filename("square.rs");
+    square.set_contents(lines![
+        "fn square() {}",
+        "fn square_area() {}",
+        "fn square_fmt() {}"
+    ]);
+    let split = repo.stage_all_and_commit("Rename and split").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", &split.commit_sha, "--json"]);
+    assert_eq!(stats.ai_additions, stats.git_diff_added_lines);
+    assert_eq!(stats.human_additions, 0);
+
+    square.assert_lines_and_blame(lines![
+        "fn square() {}".ai(),
+        "fn square_area() {}".ai(),
+        "fn square_fmt() {}".ai(),
+    ]);
+}
+
+#[test]
+fn test_copied_ai_file_keeps_ai_attribution_on_unchanged_lines() {
+    let repo = repo_with_inter_commit_move();
+    repo.filename("a.rs").set_contents(lines![
+        "fn ai1() {}".ai(),
+        "fn ai2() {}".ai(),
+        "fn ai3() {}".ai(),
+        "fn ai4() {}".ai(),
+        "fn h1() {}".human(),
+    ]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    // A human copies a.rs, edits one line of the copy and touches a.rs in the same commit, so
+    // git's copy detection considers a.rs as a source
+    let source = repo.path().join("a.rs");
+    let copy = repo.path().join("b.rs");
+    std::fs::copy(&source, &copy).unwrap();
+    let contents = std::fs::read_to_string(&copy).unwrap();
+    std::fs::write(
+        &copy,
+        contents.replace("fn ai2() {}", "fn ai2() { todo!() }"),
+    )
+    .unwrap();
+    std::fs::write(&source, format!("fn h0() {{}}\n{}", contents)).unwrap();
+    let copied = repo.stage_all_and_commit("Copy a.rs").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", &copied.commit_sha, "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 6);
+    assert_eq!(stats.ai_additions, 3);
+    assert_eq!(stats.human_additions, 3);
+
+    let mut copy_file = repo.filename("b.rs");
+    copy_file.assert_lines_and_blame(lines![
+        "fn ai1() {}".ai(),
+        "fn ai2() { todo!() }".human(),
+        "fn ai3() {}".ai(),
+        "fn ai4() {}".ai(),
+        "fn h1() {}".human(),
+    ]);
+}
+
+#[test]
+fn test_reverted_ai_deletion_is_ai_again() {
+    let repo = repo_with_inter_commit_move();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines![
+        "fn h1() {}".human(),
+        "fn ai1() {}".ai(),
+        "fn ai2() {}".ai(),
+        "fn h2() {}".human(),
+    ]);
+    repo.stage_all_and_commit("Add helpers").unwrap();
+
+    file.delete_range(1, 3);
+    let removal = repo.stage_all_and_commit("Remove helpers").unwrap();
+
+    repo.git(&["revert", "--no-commit", &removal.commit_sha])
+        .unwrap();
+    let message = format!(
+        "Revert \"Remove helpers\"\n\nThis reverts commit {}.",
+        removal.commit_sha
+    );
+    let revert = repo.commit(&message).unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", &revert.commit_sha, "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 2);
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.human_additions, 0);
+}
+
+#[test]
+fn test_stats_repos_aggregates_and_reports_failures() {
+    let first = TestRepo::new();
+    first.filename("a.rs").set_contents(lines![
+        "fn a1() {}".ai(),
+        "fn a2() {}".ai(),
+        "fn h() {}".human()
+    ]);
+    first.stage_all_and_commit("First repo").unwrap();
+
+    let second = TestRepo::new();
+    second.filename("b.rs").set_contents(lines![
+        "fn b() {}".ai(),
+        "fn h1() {}".human(),
+        "fn h2() {}".human()
+    ]);
+    second.stage_all_and_commit("Second repo").unwrap();
+
+    let not_a_repo = tempfile::tempdir().unwrap();
+    let first_path = first.path().to_string_lossy().to_string();
+    let second_path = second.path().to_string_lossy().to_string();
+    let missing_path = not_a_repo.path().to_string_lossy().to_string();
+
+    let raw = first
+        .git_ai_stdout(&["stats", "--repos", &first_path, &second_path, &missing_path])
+        .expect("stats --repos should succeed when some repos can be read");
+    let aggregate: serde_json::Value = serde_json::from_str(&raw).expect("valid multi-repo json");
+
+    let repos = aggregate["repos"].as_array().unwrap();
+    assert_eq!(repos.len(), 3, "got: {}", raw);
+    assert_eq!(repos[0]["path"], first_path.as_str());
+    assert_eq!(repos[0]["stats"]["ai_additions"], 2);
+    assert_eq!(repos[0]["stats"]["human_additions"], 1);
+    assert_eq!(repos[1]["stats"]["ai_additions"], 1);
+    assert_eq!(repos[1]["stats"]["human_additions"], 2);
+    assert!(repos[2]["stats"].is_null());
+    assert!(repos[2]["error"].is_string());
+
+    assert_eq!(aggregate["total"]["ai_additions"], 3);
+    assert_eq!(aggregate["total"]["human_additions"], 3);
+}
+
+#[test]
+fn test_stats_cli_empty_tree_range() {
+    let repo = TestRepo::new();
+
+    // First commit: AI line
+    let mut file = repo.filename("history.txt");
+    file.set_contents(lines!["AI Line 1".ai()]);
+    let _first = repo.stage_all_and_commit("Initial AI").unwrap();
+
+    // Second commit: human line
+    file.set_contents(lines!["AI Line 1".ai(), "Human Line 2".human()]);
+    repo.stage_all_and_commit("Human adds line").unwrap();
+
+    // Git's empty tree OID
+    let empty_tree = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+    let head = repo
+        .git(&["rev-parse", "HEAD"])
+        .expect("rev-parse HEAD should succeed")
+        .trim()
+        .to_string();
+    let range = format!("{}..{}", empty_tree, head);
+
+    let output = repo
+        .git_ai_stdout(&["stats", &range, "--json"])
+        .expect("git-ai stats empty-tree range should succeed");
+
+    let stats: git_ai::authorship::range_authorship::RangeAuthorshipStats =
+        serde_json::from_str(&output).unwrap();
+
+    // Entire history from empty tree to HEAD:
+    // - 2 commits in range
+    // - 1 AI-added line, 1 human-added line in final diff
+    assert_eq!(stats.authorship_stats.total_commits, 2);
+    assert_eq!(stats.range_stats.git_diff_added_lines, 2);
+    assert_eq!(stats.range_stats.ai_additions, 1);
+    // human_additions is computed as git_diff_added_lines - ai_accepted
+    assert_eq!(stats.range_stats.human_additions, 1);
+}
+
+#[test]
+fn test_stats_classifies_deleted_lines_by_original_author() {
+    let repo = TestRepo::new();
+
+    let mut file = repo.filename("notes.txt");
+    file.set_contents(lines![
+        "keep".human(),
+        "human 1".human(),
+        "human 2".human(),
+        "human 3".human(),
+    ]);
+    repo.stage_all_and_commit("Human lines").unwrap();
+
+    file.set_contents(lines![
+        "keep".human(),
+        "human 1".human(),
+        "human 2".human(),
+        "human 3".human(),
+        "ai 1".ai(),
+    ]);
+    repo.stage_all_and_commit("AI line").unwrap();
+
+    // AI removes the three human lines and its own earlier line.
+    fs::write(repo.path().join("notes.txt"), "keep\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai"]).unwrap();
+    repo.stage_all_and_commit("AI cleanup").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.git_diff_deleted_lines, 4);
+    assert_eq!(stats.human_deletions, 3);
+    assert_eq!(stats.ai_deletions, 1);
+}
+
+fn agent_checkpoint(repo: &TestRepo, tool: &str, file: &str) {
+    let hook_input = serde_json::json!({
+        "type": "ai_agent",
+        "repo_working_dir": repo.path().to_string_lossy(),
+        "edited_filepaths": [repo.path().join(file).to_string_lossy()],
+        "transcript": {"messages": []},
+        "agent_name": tool,
+        "model": "test-model",
+        "conversation_id": format!("{}-session", tool),
+    });
+    repo.git_ai(&[
+        "checkpoint",
+        "agent-v1",
+        "--hook-input",
+        &hook_input.to_string(),
+    ])
+    .expect("agent checkpoint should succeed");
+}
+
+#[test]
+fn test_stats_ai_by_tool_breakdown() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    let path = repo.path().join("tools.txt");
+    fs::write(&path, "cursor 1\ncursor 2\n").unwrap();
+    agent_checkpoint(&repo, "cursor", "tools.txt");
+    fs::write(
+        &path,
+        "cursor 1\ncursor 2\ncopilot 1\ncopilot 2\ncopilot 3\n",
+    )
+    .unwrap();
+    agent_checkpoint(&repo, "github-copilot", "tools.txt");
+    repo.stage_all_and_commit("Mixed tools").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.ai_additions, 5);
+    assert_eq!(stats.ai_by_tool.get("cursor"), Some(&2));
+    assert_eq!(stats.ai_by_tool.get("github-copilot"), Some(&3));
+    assert_eq!(stats.ai_by_tool.values().sum::<u32>(), stats.ai_additions);
+}
+
+#[test]
+fn test_stats_ai_by_tool_uses_tool_env_of_each_checkpoint() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    let path = repo.path().join("tools.txt");
+    fs::write(&path, "cursor 1\ncursor 2\n").unwrap();
+    repo.git_ai_with_env(
+        &["checkpoint", "mock_ai", "tools.txt"],
+        &[("GIT_AI_TOOL", "cursor")],
+    )
+    .expect("cursor checkpoint should succeed");
+    fs::write(
+        &path,
+        "cursor 1\ncursor 2\ncopilot 1\ncopilot 2\ncopilot 3\n",
+    )
+    .unwrap();
+    repo.git_ai_with_env(
+        &["checkpoint", "mock_ai", "tools.txt"],
+        &[("GIT_AI_TOOL", "github-copilot")],
+    )
+    .expect("copilot checkpoint should succeed");
+    let commit = repo.stage_all_and_commit("Mixed tools").unwrap();
+
+    // Each attestation entry names its tool through the prompt its hash points at
+    let file = &commit.authorship_log.attestations[0];
+    let mut tools: Vec<&str> = file
+        .entries
+        .iter()
+        .map(|entry| {
+            commit.authorship_log.metadata.prompts[&entry.hash]
+                .agent_id
+                .tool
+                .as_str()
+        })
+        .collect();
+    tools.sort();
+    assert_eq!(tools, vec!["cursor", "github-copilot"]);
+
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.ai_by_tool.get("cursor"), Some(&2));
+    assert_eq!(stats.ai_by_tool.get("github-copilot"), Some(&3));
+    assert_eq!(stats.ai_by_tool.values().sum::<u32>(), stats.ai_additions);
+}
+
+#[test]
+fn test_stats_merge_commit_keeps_branch_ai_attribution() {
+    let repo = TestRepo::new();
+    let mut base = repo.filename("base.txt");
+    base.set_contents(lines!["base".human()]);
+    repo.stage_all_and_commit("Base").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let mut feature = repo.filename("feature.rs");
+    feature.set_contents(lines!["fn a() {}".ai(), "fn b() {}".ai()]);
+    repo.stage_all_and_commit("AI feature part 1").unwrap();
+    feature.set_contents(lines![
+        "fn a() {}".ai(),
+        "fn b() {}".ai(),
+        "fn c() {}".ai(),
+        "// reviewed".human(),
+    ]);
+    repo.stage_all_and_commit("AI feature part 2").unwrap();
+
+    // A --no-ff merge of an untouched mainline is the fast-forward-equivalent case.
+    repo.git(&["checkout", &default_branch]).unwrap();
+    repo.git(&["merge", "--no-ff", "feature", "-m", "merge feature"])
+        .unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 4);
+    assert_eq!(stats.ai_additions, 3);
+    assert_eq!(stats.human_additions, 1);
+
+    // Merge stats are cached, and a cache hit skips the blame
+    let merge_sha = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    let cache_file = repo
+        .path()
+        .join(".git/ai/cache/stats")
+        .join(format!("{}.json", merge_sha));
+    let cached = std::fs::read_to_string(&cache_file).unwrap();
+    std::fs::write(
+        &cache_file,
+        cached.replace("\"ai_additions\":3", "\"ai_additions\":42"),
+    )
+    .unwrap();
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.ai_additions, 42);
+}
+
+#[test]
+fn test_stats_merge_strategy_over_range_with_merge() {
+    let repo = TestRepo::new();
+    repo.filename("base.txt")
+        .set_contents(lines!["base".human()]);
+    let base = repo.stage_all_and_commit("Base").unwrap().commit_sha;
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    repo.filename("feature.rs")
+        .set_contents(lines!["fn a() {}".ai(), "fn b() {}".ai()]);
+    repo.stage_all_and_commit("AI feature").unwrap();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    repo.filename("main.rs")
+        .set_contents(lines!["fn main() {}".human()]);
+    repo.stage_all_and_commit("Mainline work").unwrap();
+    repo.git(&["merge", "--no-ff", "feature", "-m", "merge feature"])
+        .unwrap();
+    let range = format!("{}..HEAD", base);
+
+    // By default merges add nothing of their own: the feature lines count once, on their commit
+    let raw = repo.git_ai(&["stats", &range, "--format=ndjson"]).unwrap();
+    let rows: Vec<serde_json::Value> = raw
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 2, "merge commit should be skipped: {}", raw);
+    let ai: u64 = rows
+        .iter()
+        .map(|row| row["ai_additions"].as_u64().unwrap())
+        .sum();
+    let human: u64 = rows
+        .iter()
+        .map(|row| row["human_additions"].as_u64().unwrap())
+        .sum();
+    assert_eq!((ai, human), (2, 1));
+
+    // Including merges counts the feature again, as the merge's diff against its first parent
+    let stats = stats_from_args(&repo, &["stats", &range, "--include-merges", "--json"]);
+    assert_eq!(stats.ai_additions, 4);
+    assert_eq!(stats.human_additions, 1);
+
+    // The first-parent chain skips the feature commit and counts its lines on the merge instead
+    let stats = stats_from_args(&repo, &["stats", &range, "--first-parent", "--json"]);
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.human_additions, 1);
+
+    let err = repo
+        .git_ai(&["stats", "--first-parent", "--json"])
+        .expect_err("--first-parent needs a range to walk");
+    assert!(err.contains("require a commit range"), "{}", err);
+}
+
+#[test]
+fn test_stats_respects_gitai_ignore_file() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    repo.filename("src/app.rs")
+        .set_contents(lines!["fn app() {}".human()]);
+    repo.filename("deps.lock")
+        .set_contents(lines!["a".human(), "b".human(), "c".human()]);
+    repo.filename("dist/bundle.js")
+        .set_contents(lines!["bundle 1".human(), "bundle 2".human()]);
+    repo.stage_all_and_commit("App with generated files")
+        .unwrap();
+
+    let before = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(
+        before.human_additions, 3,
+        "dist/ counts until it is ignored"
+    );
+
+    // Written after the commit: ignore rules apply when stats are computed, not recorded.
+    fs::write(repo.path().join(".gitai-ignore"), "*.lock\ndist/\n").unwrap();
+
+    let after = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(after.git_diff_added_lines, 1);
+    assert_eq!(after.human_additions, 1);
+}
+
+#[test]
+fn test_stats_ignore_whitespace_skips_reindented_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines![
+        "fn answer() -> u32 {".ai(),
+        "  let x = 40;".ai(),
+        "  x + 2".ai(),
+        "}".ai(),
+    ]);
+    repo.stage_all_and_commit("AI function").unwrap();
+
+    file.set_contents(lines![
+        "fn answer() -> u32 {".human(),
+        "    let x = 40;".human(),
+        "    x + 2".human(),
+        "}".human(),
+    ]);
+    repo.stage_all_and_commit("Reindent").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.human_additions, 2);
+
+    let stats = stats_from_args(&repo, &["stats", "--ignore-whitespace", "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 0);
+    assert_eq!(stats.git_diff_deleted_lines, 0);
+    assert_eq!(stats.human_additions, 0);
+}
+
+#[test]
+fn test_stats_diff_algorithm_changes_reported_hunks() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("io.rs");
+    file.set_contents(lines![
+        "    log();".human(),
+        "    log();".human(),
+        "    log();".human(),
+        "    flush();".human(),
+    ]);
+    repo.stage_all_and_commit("Human calls").unwrap();
+
+    // Myers keeps three of the old lines; histogram anchors on the unique `flush()` and
+    // keeps only two, so it reports one more line added and one more deleted
+    file.set_contents(lines![
+        "    log();".ai(),
+        "    flush();".ai(),
+        "    log();".ai(),
+        "    flush();".ai(),
+        "    close();".ai(),
+    ]);
+    repo.stage_all_and_commit("AI reorders calls").unwrap();
+
+    let myers = stats_from_args(&repo, &["stats", "--diff-algorithm", "myers", "--json"]);
+    assert_eq!(myers.git_diff_added_lines, 2);
+    assert_eq!(myers.git_diff_deleted_lines, 1);
+    assert_eq!(
+        myers.ai_additions + myers.human_additions,
+        myers.git_diff_added_lines
+    );
+
+    let histogram = stats_from_args(&repo, &["stats", "--diff-algorithm=histogram", "--json"]);
+    assert_eq!(histogram.git_diff_added_lines, 3);
+    assert_eq!(histogram.git_diff_deleted_lines, 2);
+    assert_eq!(
+        histogram.ai_additions + histogram.human_additions,
+        histogram.git_diff_added_lines
+    );
+
+    let err = repo
+        .git_ai(&["stats", "--diff-algorithm", "fastest"])
+        .expect_err("an unknown algorithm should be rejected");
+    assert!(err.contains("Invalid --diff-algorithm: fastest"));
+}
+
+#[test]
+fn test_stats_attribute_identical_to_prior_counts_retyped_ai_line() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines![
+        "fn main() {}".human(),
+        "fn helper() -> u32 { 42 }".ai(),
+    ]);
+    repo.stage_all_and_commit("AI helper").unwrap();
+
+    file.set_contents(lines!["fn main() {}".human()]);
+    repo.stage_all_and_commit("Remove helper").unwrap();
+
+    // A human types the deleted AI line back in, character for character
+    file.set_contents(lines![
+        "fn main() {}".human(),
+        "fn helper() -> u32 { 42 }".human(),
+    ]);
+    repo.stage_all_and_commit("Restore helper by hand").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.ai_additions, 0);
+    assert_eq!(stats.human_additions, 1);
 
-    let output = extract_json_object(&raw);
-    let stats: git_ai::authorship::range_authorship::RangeAuthorshipStats =
-        serde_json::from_str(&output).unwrap();
-    assert_eq!(stats.authorship_stats.total_commits, 1);
-    assert!(
-        stats.range_stats.git_diff_added_lines >= 1,
-        "expected at least one added line in range, got {}",
-        stats.range_stats.git_diff_added_lines
+    let stats = stats_from_args(
+        &repo,
+        &["stats", "--attribute-identical-to-prior", "--json"],
     );
-    assert!(stats.range_stats.ai_additions >= 1);
+    assert_eq!(stats.ai_additions, 1);
+    assert_eq!(stats.human_additions, 0);
 }
 
 #[test]
-fn test_stats_cli_range_with_hostile_diff_config() {
+fn test_stats_line_ending_conversion_keeps_ai_attribution() {
     let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial").unwrap();
 
-    let mut file = repo.filename("stats-range-hostile.txt");
-    file.set_contents(lines!["base".human()]);
-    let first = repo.stage_all_and_commit("initial").unwrap();
+    let path = repo.path().join("crlf.txt");
+    fs::write(&path, "ai one\nai two\nai three\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "crlf.txt"]).unwrap();
+    repo.stage_all_and_commit("AI lines with LF").unwrap();
 
-    file.set_contents(lines!["base".human(), "ai line".ai()]);
-    let second = repo.stage_all_and_commit("ai second").unwrap();
+    // Same content, re-saved with CRLF line endings by a human editor.
+    fs::write(&path, "ai one\r\nai two\r\nai three\r\n").unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+    repo.stage_all_and_commit("Convert to CRLF").unwrap();
 
-    configure_hostile_diff_settings(&repo);
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 3);
+    assert_eq!(stats.ai_additions, 3);
+    assert_eq!(stats.human_additions, 0);
+}
 
-    let range = format!("{}..{}", first.commit_sha, second.commit_sha);
-    let raw = repo
-        .git_ai(&["stats", &range, "--json"])
-        .expect("git-ai stats range should succeed with hostile diff config");
-    let output = extract_json_object(&raw);
-    let stats: git_ai::authorship::range_authorship::RangeAuthorshipStats =
-        serde_json::from_str(&output).unwrap();
+#[test]
+fn test_compute_commit_stats_matches_stats_command() {
+    let repo = TestRepo::new();
+    repo.filename("src/lib.rs").set_contents(lines![
+        "fn a() {}".ai(),
+        "fn b() {}".ai(),
+        "fn c() {}".human()
+    ]);
+    repo.filename("Cargo.lock").set_contents(lines!["lockdata"]);
+    repo.stage_all_and_commit("Add lib").unwrap();
+
+    let library_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    let commit = library_repo
+        .revparse_single("HEAD")
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    let stats = compute_commit_stats(&library_repo, &commit).unwrap();
+    assert_eq!(stats.git_diff_added_lines, 3);
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.human_additions, 1);
 
-    assert_eq!(stats.authorship_stats.total_commits, 1);
-    assert!(stats.range_stats.git_diff_added_lines >= 1);
-    assert!(stats.range_stats.ai_additions >= 1);
+    let cli = stats_from_args(&repo, &["stats", "HEAD", "--json"]);
+    assert_eq!(
+        serde_json::to_value(&stats).unwrap(),
+        serde_json::to_value(&cli).unwrap()
+    );
 }
 
 #[test]
-fn test_stats_cli_empty_tree_range() {
+fn test_stats_partial_line_edit_keeps_ai_characters() {
     let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial").unwrap();
 
-    // First commit: AI line
-    let mut file = repo.filename("history.txt");
-    file.set_contents(lines!["AI Line 1".ai()]);
-    let _first = repo.stage_all_and_commit("Initial AI").unwrap();
+    let path = repo.path().join("main.rs");
+    fs::write(&path, "fn main() {}\nlet x = 1;\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "main.rs"]).unwrap();
 
-    // Second commit: human line
-    file.set_contents(lines!["AI Line 1".ai(), "Human Line 2".human()]);
-    repo.stage_all_and_commit("Human adds line").unwrap();
+    // A human fixes a single token of the AI-written line.
+    fs::write(&path, "fn main() {}\nlet x = 2;\n").unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+    repo.stage_all_and_commit("AI code with a human tweak")
+        .unwrap();
 
-    // Git's empty tree OID
-    let empty_tree = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
-    let head = repo
-        .git(&["rev-parse", "HEAD"])
-        .expect("rev-parse HEAD should succeed")
-        .trim()
-        .to_string();
-    let range = format!("{}..{}", empty_tree, head);
+    let note = repo.git_og(&["notes", "--ref=ai", "show", "HEAD"]).unwrap();
+    assert!(
+        note.lines()
+            .any(|line| line.contains(",2:") || line.contains(" 2:")),
+        "expected a partial-line attestation for line 2: {}",
+        note
+    );
 
-    let raw = repo
-        .git_ai(&["stats", &range, "--json"])
-        .expect("git-ai stats empty-tree range should succeed");
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.ai_chars + stats.human_chars, 22);
+    assert!(
+        (1..=2).contains(&stats.human_chars),
+        "only the edited token should be human: {:?}",
+        stats
+    );
+    assert!(stats.ai_chars >= 20, "got {:?}", stats);
+}
 
-    let output = extract_json_object(&raw);
-    let stats: git_ai::authorship::range_authorship::RangeAuthorshipStats =
-        serde_json::from_str(&output).unwrap();
+#[test]
+fn test_stats_splits_accepted_ai_lines_into_verbatim_and_modified() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial").unwrap();
 
-    // Entire history from empty tree to HEAD:
-    // - 2 commits in range
-    // - 1 AI-added line, 1 human-added line in final diff
-    assert_eq!(stats.authorship_stats.total_commits, 2);
-    assert_eq!(stats.range_stats.git_diff_added_lines, 2);
-    assert_eq!(stats.range_stats.ai_additions, 1);
-    // human_additions is computed as git_diff_added_lines - ai_accepted
-    assert_eq!(stats.range_stats.human_additions, 1);
+    let path = repo.path().join("main.rs");
+    fs::write(&path, "let kept = 1;\nlet tidied = 2;\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "main.rs"]).unwrap();
+
+    // Trailing whitespace leaves the line with the agent but changes its text.
+    fs::write(&path, "let kept = 1;\nlet tidied = 2;  \n").unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+    repo.stage_all_and_commit("AI code with a reformatted line")
+        .unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(stats.ai_accepted, 2, "got {:?}", stats);
+    assert_eq!(stats.ai_accepted_verbatim, 1);
+    assert_eq!(stats.ai_accepted_modified, 1);
 }
 
 #[test]
@@ -359,6 +1998,7 @@ fn test_markdown_stats_deletion_only() {
         git_diff_deleted_lines: 5,
         git_diff_added_lines: 0,
         tool_model_breakdown: BTreeMap::new(),
+        ..Default::default()
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -382,6 +2022,7 @@ fn test_markdown_stats_all_human() {
         git_diff_deleted_lines: 0,
         git_diff_added_lines: 10,
         tool_model_breakdown: BTreeMap::new(),
+        ..Default::default()
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -405,6 +2046,7 @@ fn test_markdown_stats_all_ai() {
         git_diff_deleted_lines: 0,
         git_diff_added_lines: 15,
         tool_model_breakdown: BTreeMap::new(),
+        ..Default::default()
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -428,6 +2070,7 @@ fn test_markdown_stats_mixed() {
         git_diff_deleted_lines: 5,
         git_diff_added_lines: 30,
         tool_model_breakdown: BTreeMap::new(),
+        ..Default::default()
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -451,6 +2094,7 @@ fn test_markdown_stats_no_mixed() {
         git_diff_deleted_lines: 0,
         git_diff_added_lines: 20,
         tool_model_breakdown: BTreeMap::new(),
+        ..Default::default()
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -475,6 +2119,7 @@ fn test_markdown_stats_minimal_human() {
         git_diff_deleted_lines: 0,
         git_diff_added_lines: 100,
         tool_model_breakdown: BTreeMap::new(),
+        ..Default::default()
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -511,6 +2156,7 @@ fn test_markdown_stats_formatting() {
         git_diff_deleted_lines: 2,
         git_diff_added_lines: 13,
         tool_model_breakdown,
+        ..Default::default()
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -651,18 +2297,49 @@ fn test_stats_in_bare_clone_uses_root_gitattributes_linguist_generated() {
         stderr
     );
 
-    let combined = if stdout.is_empty() {
-        stderr.to_string()
-    } else if stderr.is_empty() {
-        stdout.to_string()
-    } else {
-        format!("{}{}", stdout, stderr)
-    };
-    let json = extract_json_object(&combined);
-    let stats: CommitStats = serde_json::from_str(&json).expect("valid stats json");
+    let stats: CommitStats = serde_json::from_str(&stdout).expect("valid stats json");
     assert_eq!(stats.git_diff_added_lines, 1);
 }
 
+#[test]
+fn test_stats_in_bare_clone_reads_attribution_from_notes() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    repo.filename("src/main.rs")
+        .set_contents(lines!["fn main() {}".ai(), "// human".human()]);
+    repo.stage_all_and_commit("Add main").unwrap();
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let bare = temp.path().join("repo.git");
+    run_git(
+        temp.path(),
+        &[
+            "clone",
+            "--bare",
+            repo.path().to_str().unwrap(),
+            bare.to_str().unwrap(),
+        ],
+    );
+    // A bare clone only copies branches and tags, so bring the authorship notes along
+    run_git(
+        &bare,
+        &[
+            "fetch",
+            repo.path().to_str().unwrap(),
+            "refs/notes/ai:refs/notes/ai",
+        ],
+    );
+
+    let output = repo
+        .git_ai_stdout_from_working_dir(&bare, &["stats", "HEAD", "--json"])
+        .expect("stats should succeed in a bare clone");
+    let stats: CommitStats = serde_json::from_str(&output).unwrap();
+    assert_eq!(stats.git_diff_added_lines, 2);
+    assert_eq!(stats.ai_additions, 1);
+    assert_eq!(stats.human_additions, 1);
+}
+
 #[test]
 fn test_stats_ignore_flag_is_additive_to_defaults() {
     let repo = TestRepo::new();
@@ -700,10 +2377,9 @@ fn test_stats_range_uses_default_ignores() {
         .unwrap();
 
     let range = format!("{}..{}", first.commit_sha, second.commit_sha);
-    let raw = repo
-        .git_ai(&["stats", &range, "--json"])
+    let json = repo
+        .git_ai_stdout(&["stats", &range, "--json"])
         .expect("git-ai stats range should succeed");
-    let json = extract_json_object(&raw);
     let range_stats: git_ai::authorship::range_authorship::RangeAuthorshipStats =
         serde_json::from_str(&json).unwrap();
 
@@ -736,3 +2412,459 @@ fn test_post_commit_large_ignored_files_do_not_trigger_skip_warning() {
     assert_eq!(stats.ai_additions, 0);
     assert_eq!(stats.human_additions, 0);
 }
+
+/// Extract the CSV block (header plus rows) from mixed stdout/stderr output.
+fn extract_csv_rows(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip_while(|line| !line.starts_with("commit,"))
+        .take_while(|line| !line.trim().is_empty())
+        .map(|line| line.trim_end_matches('\r').to_string())
+        .collect()
+}
+
+#[test]
+fn test_stats_csv_single_commit() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    repo.filename("src/main.rs")
+        .set_contents(lines!["fn main() {}".ai(), "// human note".human()]);
+    let commit = repo.stage_all_and_commit("Add main").unwrap();
+
+    let raw = repo
+        .git_ai(&["stats", "HEAD", "--csv"])
+        .expect("git-ai stats --csv should succeed");
+    let rows = extract_csv_rows(&raw);
+
+    assert_eq!(rows.len(), 2, "expected header plus one row: {:?}", rows);
+    assert_eq!(rows[0], git_ai::authorship::stats::CSV_COLUMNS.join(","));
+    let fields: Vec<&str> = rows[1].splitn(11, ',').collect();
+    assert_eq!(fields[0], commit.commit_sha);
+    assert_eq!(fields[1], "1", "human_additions");
+    assert_eq!(fields[3], "1", "ai_additions");
+    assert_eq!(fields[9], "2", "git_diff_added_lines");
+}
+
+#[test]
+fn test_stats_range_rows_include_deletions_and_chars() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}".human()]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+    file.set_contents(lines!["fn base() {}".human(), "fn ai() {}".ai()]);
+    let added = repo.stage_all_and_commit("AI line").unwrap();
+    file.set_contents(lines!["fn base() {}".human()]);
+    let removed = repo.stage_all_and_commit("Remove the AI line").unwrap();
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let rows_for = |extra: &[&str]| -> Vec<serde_json::Value> {
+        let mut args = vec!["stats", range.as_str(), "--format=ndjson"];
+        args.extend(extra);
+        repo.git_ai_stdout(&args)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    };
+    let row = |rows: &[serde_json::Value], sha: &str| {
+        rows.iter()
+            .find(|row| row["commit"] == sha)
+            .cloned()
+            .unwrap_or_else(|| panic!("no row for {}: {:?}", sha, rows))
+    };
+
+    // The breakdowns blame deleted lines and read changed files, so rows skip them by default
+    let rows = rows_for(&[]);
+    assert_eq!(row(&rows, &added.commit_sha)["ai_chars"], 0);
+    assert_eq!(row(&rows, &removed.commit_sha)["ai_deletions"], 0);
+
+    let rows = rows_for(&["--breakdowns"]);
+    assert!(row(&rows, &added.commit_sha)["ai_chars"].as_u64().unwrap() > 0);
+    assert_eq!(row(&rows, &removed.commit_sha)["ai_deletions"], 1);
+    assert_eq!(row(&rows, &removed.commit_sha)["human_deletions"], 0);
+}
+
+#[test]
+fn test_stats_csv_range_emits_row_per_commit() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    let first = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    repo.filename("a.txt").set_contents(lines!["a".ai()]);
+    let second = repo.stage_all_and_commit("Add a").unwrap();
+    repo.filename("b.txt").set_contents(lines!["b".human()]);
+    let third = repo.stage_all_and_commit("Add b").unwrap();
+
+    let range = format!("{}..{}", first.commit_sha, third.commit_sha);
+    let raw = repo
+        .git_ai(&["stats", &range, "--csv"])
+        .expect("git-ai stats range --csv should succeed");
+    let rows = extract_csv_rows(&raw);
+
+    assert_eq!(rows.len(), 3, "expected header plus two rows: {:?}", rows);
+    assert!(rows[1].starts_with(&third.commit_sha));
+    assert!(rows[2].starts_with(&second.commit_sha));
+}
+
+#[test]
+fn test_stats_table_keeps_columns_aligned_with_cjk_paths() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    let first = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    repo.filename("src/中文文件.rs")
+        .set_contents(lines!["fn ai() {}".ai(), "fn human() {}".human()]);
+    repo.filename("src/main.rs")
+        .set_contents(lines!["fn main() {}".human()]);
+    repo.stage_all_and_commit("Add files").unwrap();
+
+    // Border and cell separators must sit at the same display column on every line
+    let separator_columns = |line: &str| -> Vec<usize> {
+        let mut column = 0;
+        let mut separators = Vec::new();
+        for c in line.chars() {
+            if c == '|' || c == '+' {
+                separators.push(column);
+            }
+            column += unicode_width::UnicodeWidthChar::width(c).unwrap_or(0);
+        }
+        separators
+    };
+
+    let range = format!("{}..HEAD", first.commit_sha);
+    for args in [
+        vec!["stats", "HEAD", "--format=table", "--per-file"],
+        vec!["stats", range.as_str(), "--format", "table"],
+    ] {
+        let output = repo.git_ai(&args).unwrap();
+        let lines: Vec<&str> = output
+            .lines()
+            .filter(|line| line.starts_with('+') || line.starts_with('|'))
+            .collect();
+        assert!(lines.len() >= 5, "expected a bordered table: {}", output);
+        let expected = separator_columns(lines[0]);
+        for line in &lines {
+            assert_eq!(separator_columns(line), expected, "misaligned: {}", output);
+        }
+    }
+
+    let output = repo
+        .git_ai(&["stats", "HEAD", "--format=table", "--per-file"])
+        .unwrap();
+    assert!(output.contains("| Path "), "{}", output);
+    assert!(output.contains(" src/中文文件.rs "), "{}", output);
+    assert!(output.contains("50.0%"), "{}", output);
+}
+
+#[test]
+fn test_stats_csv_range_parallel_matches_serial() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    let first = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    for i in 0..6 {
+        repo.filename(&format!("file{}.rs", i)).set_contents(lines![
+            format!("fn ai_{}() {{}}", i).ai(),
+            format!("// note {}", i).human(),
+        ]);
+        repo.stage_all_and_commit(&format!("Add file {}", i))
+            .unwrap();
+    }
+
+    let range = format!("{}..HEAD", first.commit_sha);
+    let serial = extract_csv_rows(
+        &repo
+            .git_ai(&["stats", &range, "--csv", "--jobs", "1"])
+            .expect("serial stats should succeed"),
+    );
+    let parallel = extract_csv_rows(
+        &repo
+            .git_ai(&["stats", &range, "--csv", "--jobs", "4"])
+            .expect("parallel stats should succeed"),
+    );
+
+    assert_eq!(
+        serial.len(),
+        7,
+        "expected header plus six rows: {:?}",
+        serial
+    );
+    assert_eq!(serial, parallel);
+}
+
+#[test]
+fn test_stats_json_range_parallel_matches_serial() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    let first = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    for i in 0..6 {
+        repo.filename(&format!("file{}.rs", i)).set_contents(lines![
+            format!("fn ai_{}() {{}}", i).ai(),
+            format!("// note {}", i).human(),
+        ]);
+        repo.stage_all_and_commit(&format!("Add file {}", i))
+            .unwrap();
+    }
+
+    let range = format!("{}..HEAD", first.commit_sha);
+    let serial: serde_json::Value = serde_json::from_str(
+        &repo
+            .git_ai_stdout(&["stats", &range, "--json", "--jobs", "1"])
+            .expect("serial stats should succeed"),
+    )
+    .unwrap();
+    let parallel: serde_json::Value = serde_json::from_str(
+        &repo
+            .git_ai_stdout(&["stats", &range, "--json", "--jobs", "4"])
+            .expect("parallel stats should succeed"),
+    )
+    .unwrap();
+
+    assert_eq!(serial["authorship_stats"]["total_commits"], 6);
+    assert_eq!(serial["authorship_stats"]["commits_with_authorship"], 6);
+    assert_eq!(serial, parallel);
+}
+
+#[test]
+fn test_stats_csv_and_json_are_mutually_exclusive() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let result = repo.git_ai(&["stats", "HEAD", "--json", "--csv"]);
+    assert!(result.is_err(), "--json with --csv should fail");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_stats_symlink_contributes_no_lines() {
+    let repo = TestRepo::new();
+    repo.filename("target.rs")
+        .set_contents(lines!["fn target() {}".human()]);
+    repo.stage_all_and_commit("Add target").unwrap();
+
+    std::os::unix::fs::symlink("target.rs", repo.path().join("link.rs")).unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "link.rs"]).unwrap();
+    repo.stage_all_and_commit("Add link").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "HEAD", "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 0);
+    assert_eq!(stats.ai_additions, 0);
+    assert_eq!(stats.human_additions, 0);
+
+    // An AI-touched link is attested as a whole rather than by line
+    if let Ok(note) = repo.git_og(&["notes", "--ref=ai", "show", "HEAD"]) {
+        assert!(
+            !note.contains("link.rs\n"),
+            "link target must not be attested as a line: {}",
+            note
+        );
+    }
+}
+
+#[test]
+fn test_stats_submodule_pointer_contributes_no_lines() {
+    let repo = TestRepo::new();
+    repo.filename("README.md")
+        .set_contents(lines!["# Repo".human()]);
+    let first = repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // A bare gitlink is enough; the submodule's own objects never need to exist here
+    std::fs::create_dir_all(repo.path().join("vendor/lib")).unwrap();
+    let gitlink = format!("160000,{},vendor/lib", first.commit_sha);
+    repo.git_og(&["update-index", "--add", "--cacheinfo", &gitlink])
+        .unwrap();
+    repo.commit("Add submodule").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "HEAD", "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 0);
+    assert_eq!(stats.git_diff_deleted_lines, 0);
+
+    repo.filename("notes.txt")
+        .set_contents(lines!["note".human()]);
+    repo.git(&["add", "notes.txt"]).unwrap();
+    let head = repo.git_og(&["rev-parse", "HEAD"]).unwrap();
+    let gitlink = format!("160000,{},vendor/lib", head.trim());
+    repo.git_og(&["update-index", "--cacheinfo", &gitlink])
+        .unwrap();
+    repo.commit("Bump submodule").unwrap();
+
+    let stats = stats_from_args(&repo, &["stats", "HEAD", "--json"]);
+    assert_eq!(stats.git_diff_added_lines, 1);
+    assert_eq!(stats.git_diff_deleted_lines, 0);
+    assert_eq!(stats.human_additions, 1);
+    assert_eq!(stats.ai_additions, 0);
+}
+
+#[test]
+fn test_stats_json_stdout_parses_without_extraction() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}".human()]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+    file.set_contents(lines!["fn base() {}".human(), "fn ai() {}".ai()]);
+    repo.stage_all_and_commit("AI").unwrap();
+
+    // Debug builds log to stderr on every run, so stdout must hold the JSON and nothing else
+    let stdout = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
+    let stats: CommitStats = serde_json::from_str(&stdout).expect("stdout should be pure JSON");
+    assert_eq!(stats.ai_additions, 1);
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let stdout = repo.git_ai_stdout(&["stats", &range, "--json"]).unwrap();
+    let range_stats: serde_json::Value =
+        serde_json::from_str(&stdout).expect("range stdout should be pure JSON");
+    assert_eq!(range_stats["range_stats"]["ai_additions"], 1);
+}
+
+#[test]
+fn test_stats_relative_to_counts_only_branch_commits() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Base").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    repo.filename("feature.rs")
+        .set_contents(lines!["fn a() {}".ai(), "fn b() {}".ai()]);
+    repo.stage_all_and_commit("Feature AI").unwrap();
+    repo.filename("more.rs")
+        .set_contents(lines!["fn c() {}".ai(), "fn d() {}".human()]);
+    repo.stage_all_and_commit("More feature work").unwrap();
+
+    // Work landing on the default branch after the fork is not part of the branch
+    repo.git(&["checkout", &default_branch]).unwrap();
+    repo.filename("main.rs").set_contents(lines![
+        "fn m1() {}".ai(),
+        "fn m2() {}".ai(),
+        "fn m3() {}".ai()
+    ]);
+    repo.stage_all_and_commit("Main advances").unwrap();
+    repo.git(&["checkout", "feature"]).unwrap();
+
+    let stdout = repo
+        .git_ai_stdout(&["stats", "--relative-to", &default_branch, "--json"])
+        .expect("stats --relative-to should succeed");
+    let stats: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(stats["authorship_stats"]["total_commits"], 2);
+    assert_eq!(stats["range_stats"]["git_diff_added_lines"], 4);
+    assert_eq!(stats["range_stats"]["ai_additions"], 3);
+    assert_eq!(stats["range_stats"]["human_additions"], 1);
+
+    let err = repo
+        .git_ai(&["stats", "HEAD~1..HEAD", "--relative-to", &default_branch])
+        .expect_err("--relative-to should reject an explicit range");
+    assert!(
+        err.contains("can't be combined with a commit range"),
+        "got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_stats_ranges_work_on_detached_head() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}".human()]);
+    repo.stage_all_and_commit("Base").unwrap();
+    file.set_contents(lines![
+        "fn base() {}".human(),
+        "fn ai1() {}".ai(),
+        "fn ai2() {}".ai()
+    ]);
+    let ai_commit = repo.stage_all_and_commit("AI").unwrap();
+    file.set_contents(lines![
+        "fn base() {}".human(),
+        "fn ai1() {}".ai(),
+        "fn ai2() {}".ai(),
+        "fn later() {}".human()
+    ]);
+    let later = repo.stage_all_and_commit("Later").unwrap();
+    repo.git(&["tag", "v1"]).unwrap();
+
+    // CI checks out a specific commit, leaving HEAD detached behind the branch tip
+    repo.git(&["checkout", &ai_commit.commit_sha]).unwrap();
+
+    let stdout = repo
+        .git_ai_stdout(&["stats", "HEAD~1..HEAD", "--json"])
+        .expect("stats over a range should work on a detached HEAD");
+    let stats: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(stats["authorship_stats"]["total_commits"], 1);
+    assert_eq!(stats["range_stats"]["ai_additions"], 2);
+
+    let log = repo
+        .git_ai(&["log", "HEAD~1..HEAD"])
+        .expect("log over a range should work on a detached HEAD");
+    assert!(log.contains(&ai_commit.commit_sha[..7]), "got: {}", log);
+
+    // A range past the detached HEAD, whose end has several refs, is checked on its own
+    let range = format!("{}..{}", ai_commit.commit_sha, later.commit_sha);
+    let stdout = repo
+        .git_ai_stdout(&["stats", &range, "--json"])
+        .expect("stats over a range ahead of a detached HEAD should work");
+    let stats: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(stats["range_stats"]["human_additions"], 1);
+}
+
+#[test]
+fn test_stats_top_files_ranks_range_by_ai_lines() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    let base = repo.stage_all_and_commit("Base").unwrap();
+
+    repo.filename("src/模块/lib.rs").set_contents(lines![
+        "fn a() {}".ai(),
+        "fn b() {}".ai(),
+        "fn c() {}".human()
+    ]);
+    repo.filename("one.rs")
+        .set_contents(lines!["fn one() {}".ai(), "fn two() {}".human()]);
+    repo.stage_all_and_commit("First").unwrap();
+
+    // Lines added to the same file in a later commit count toward its total
+    repo.filename("src/模块/lib.rs").set_contents(lines![
+        "fn a() {}".ai(),
+        "fn b() {}".ai(),
+        "fn c() {}".human(),
+        "fn d() {}".ai()
+    ]);
+    repo.filename("two.rs")
+        .set_contents(lines!["fn x() {}".ai(), "fn y() {}".ai()]);
+    repo.filename("human.rs")
+        .set_contents(lines!["fn h() {}".human()]);
+    repo.stage_all_and_commit("Second").unwrap();
+
+    let range = format!("{}..HEAD", base.commit_sha);
+    let stdout = repo
+        .git_ai_stdout(&["stats", &range, "--top-files", "2", "--json"])
+        .expect("stats --top-files should succeed");
+    let ranked: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let rows: Vec<(&str, u64, u64)> = ranked
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| {
+            (
+                row["file_path"].as_str().unwrap(),
+                row["ai_additions"].as_u64().unwrap(),
+                row["human_additions"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(rows, vec![("src/模块/lib.rs", 3, 1), ("two.rs", 2, 0)]);
+
+    let terminal = repo
+        .git_ai_stdout(&["stats", &range, "--top-files=3"])
+        .expect("terminal --top-files should succeed");
+    assert!(terminal.contains("src/模块/lib.rs"), "got: {}", terminal);
+    assert!(terminal.contains("one.rs"), "got: {}", terminal);
+    assert!(!terminal.contains("human.rs"), "got: {}", terminal);
+
+    let err = repo
+        .git_ai(&["stats", &range, "--top-files", "0"])
+        .expect_err("a zero count should be rejected");
+    assert!(err.contains("Invalid --top-files count"), "got: {}", err);
+}
@@ -0,0 +1,79 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::Value;
+
+/// Extract the JSON array from mixed stdout/stderr output.
+fn extract_json_array(output: &str) -> String {
+    let start = output.find('[').unwrap_or(0);
+    let end = output.rfind(']').unwrap_or(output.len().saturating_sub(1));
+    output[start..=end].to_string()
+}
+
+fn commit_at(repo: &TestRepo, message: &str, date: &str) {
+    repo.git(&["add", "-A"]).expect("add --all should succeed");
+    repo.commit_with_env(
+        message,
+        &[("GIT_AUTHOR_DATE", date), ("GIT_COMMITTER_DATE", date)],
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_trend_by_week_splits_on_iso_week_boundary() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    commit_at(&repo, "Initial", "2024-01-01 09:00:00 +0000");
+    let base = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+    repo.filename("a.rs").set_contents(lines![
+        "fn a1() {}".ai(),
+        "fn a2() {}".ai(),
+        "fn a3() {}".ai(),
+        "fn h1() {}".human(),
+    ]);
+    commit_at(&repo, "Mostly AI", "2024-01-03 12:00:00 +0000");
+
+    // Last second of ISO week 1 (Sunday)
+    repo.filename("b.rs")
+        .set_contents(lines!["fn b() {}".human()]);
+    commit_at(&repo, "Late Sunday", "2024-01-07 23:59:59 +0000");
+
+    // First second of ISO week 2 (Monday)
+    repo.filename("c.rs").set_contents(lines![
+        "fn c1() {}".ai(),
+        "fn c2() {}".human(),
+        "fn c3() {}".human(),
+        "fn c4() {}".human(),
+    ]);
+    commit_at(&repo, "Mostly human", "2024-01-08 00:00:00 +0000");
+
+    let range = format!("{}..HEAD", base);
+    let raw = repo
+        .git_ai(&["trend", &range, "--by=week"])
+        .expect("trend should succeed");
+    let buckets: Vec<Value> =
+        serde_json::from_str(&extract_json_array(&raw)).expect("valid trend json");
+
+    assert_eq!(buckets.len(), 2, "got: {}", raw);
+    assert_eq!(buckets[0]["bucket"], "2024-W01");
+    assert_eq!(buckets[0]["ai_additions"], 3);
+    assert_eq!(buckets[0]["human_additions"], 2);
+    assert_eq!(buckets[0]["ai_percentage"].as_f64(), Some(60.0));
+    assert_eq!(buckets[1]["bucket"], "2024-W02");
+    assert_eq!(buckets[1]["ai_additions"], 1);
+    assert_eq!(buckets[1]["human_additions"], 3);
+    assert_eq!(buckets[1]["ai_percentage"].as_f64(), Some(25.0));
+
+    let raw = repo
+        .git_ai(&["trend", &range, "--by", "month"])
+        .expect("trend should succeed");
+    let buckets: Vec<Value> =
+        serde_json::from_str(&extract_json_array(&raw)).expect("valid trend json");
+    assert_eq!(buckets.len(), 1, "got: {}", raw);
+    assert_eq!(buckets[0]["bucket"], "2024-01");
+    assert_eq!(buckets[0]["ai_additions"], 4);
+    assert_eq!(buckets[0]["human_additions"], 5);
+}
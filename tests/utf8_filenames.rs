@@ -11,13 +11,6 @@ use git_ai::authorship::stats::CommitStats;
 use repos::test_file::ExpectedLineExt;
 use repos::test_repo::TestRepo;
 
-/// Extract the first complete JSON object from mixed stdout/stderr output.
-fn extract_json_object(output: &str) -> String {
-    let start = output.find('{').unwrap_or(0);
-    let end = output.rfind('}').unwrap_or(output.len().saturating_sub(1));
-    output[start..=end].to_string()
-}
-
 #[test]
 fn test_chinese_filename_ai_attribution() {
     let repo = TestRepo::new();
@@ -46,8 +39,7 @@ fn test_chinese_filename_ai_attribution() {
     );
 
     // Get stats and verify AI attribution is correct
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     // The key check: ai_additions should NOT be 0
@@ -102,8 +94,7 @@ fn test_emoji_filename_ai_attribution() {
     );
 
     // Get stats and verify AI attribution is correct
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     // The key check: ai_additions should NOT be 0
@@ -175,8 +166,7 @@ fn test_mixed_ascii_and_utf8_filenames() {
     );
 
     // Get stats and verify AI attribution is correct for all files
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     // Total: 2 + 3 + 1 = 6 AI lines
@@ -224,8 +214,7 @@ fn test_utf8_content_in_file() {
     assert_eq!(commit.authorship_log.attestations.len(), 1);
 
     // Get stats and verify AI attribution is correct
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -270,6 +259,83 @@ fn test_utf8_filename_blame() {
     ]);
 }
 
+#[test]
+fn test_utf8_filename_blame_json_line_details() {
+    let repo = TestRepo::new();
+
+    let mut chinese_file = repo.filename("测试文件.rs");
+    chinese_file.set_contents(lines![
+        "// 你好".human(),
+        "fn main() {".ai(),
+        "    println!(\"世界\");".ai(),
+        "}".human(),
+    ]);
+    let commit = repo.stage_all_and_commit("Add test file").unwrap();
+
+    let output = repo.git_ai(&["blame", "测试文件.rs", "--json"]).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).expect("valid blame json");
+    let details = json["line_details"]
+        .as_array()
+        .expect("line_details should be an array");
+
+    assert_eq!(details.len(), 4);
+    let summary: Vec<(u64, &str, &str)> = details
+        .iter()
+        .map(|line| {
+            (
+                line["line_number"].as_u64().unwrap(),
+                line["content"].as_str().unwrap(),
+                line["author_type"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (1, "// 你好", "Human"),
+            (2, "fn main() {", "Ai"),
+            (3, "    println!(\"世界\");", "Ai"),
+            (4, "}", "Human"),
+        ]
+    );
+    for line in details {
+        assert_eq!(line["commit_sha"].as_str().unwrap(), commit.commit_sha);
+    }
+}
+
+#[test]
+fn test_utf8_filename_line_authorship_library_api() {
+    use git_ai::authorship::line_authorship;
+    use git_ai::commands::blame::BlameAuthorType;
+
+    let repo = TestRepo::new();
+
+    let mut chinese_file = repo.filename("测试文件.rs");
+    chinese_file.set_contents(lines![
+        "// 你好".human(),
+        "fn main() {".ai(),
+        "    println!(\"世界\");".ai(),
+        "}".human(),
+    ]);
+    repo.stage_all_and_commit("Add test file").unwrap();
+
+    let repo_path = repo.path().to_str().unwrap();
+    assert_eq!(
+        line_authorship(repo_path, "测试文件.rs", 1),
+        Some(BlameAuthorType::Human)
+    );
+    assert_eq!(
+        line_authorship(repo_path, "测试文件.rs", 2),
+        Some(BlameAuthorType::Ai)
+    );
+    assert_eq!(
+        line_authorship(repo_path, "测试文件.rs", 3),
+        Some(BlameAuthorType::Ai)
+    );
+    assert_eq!(line_authorship(repo_path, "测试文件.rs", 99), None);
+    assert_eq!(line_authorship(repo_path, "不存在.rs", 1), None);
+}
+
 #[test]
 fn test_nested_directory_with_utf8_filename() {
     let repo = TestRepo::new();
@@ -297,8 +363,7 @@ fn test_nested_directory_with_utf8_filename() {
     );
 
     // Get stats and verify AI attribution
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -334,8 +399,7 @@ fn test_utf8_filename_with_human_and_ai_lines() {
     repo.stage_all_and_commit("Add data file").unwrap();
 
     // Get stats and verify attribution
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(stats.ai_additions, 2, "2 lines should be attributed to AI");
@@ -391,8 +455,7 @@ fn test_japanese_hiragana_katakana_filename() {
     );
 
     // Get stats and verify AI attribution is correct
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -432,8 +495,7 @@ fn test_japanese_kanji_filename() {
         "File path should preserve Japanese Kanji characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -478,8 +540,7 @@ fn test_filename_with_all_unicode_categories() {
         "File path should preserve all Unicode categories"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -516,8 +577,7 @@ fn test_deeply_nested_utf8_directories() {
         "File path should preserve all nested UTF-8 directories"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -567,8 +627,7 @@ fn test_many_utf8_files_in_single_commit() {
         "Should have 6 attestations for all UTF-8 files"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -602,8 +661,7 @@ fn test_filename_starting_with_emoji() {
         "File path starting with emoji should be preserved"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(stats.ai_additions, 1, "The line should be attributed to AI");
@@ -634,8 +692,7 @@ fn test_filename_ending_with_emoji() {
         "File path ending with emoji should be preserved"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(stats.ai_additions, 1, "The line should be attributed to AI");
@@ -668,8 +725,7 @@ fn test_filename_only_non_ascii() {
         "File path with only non-ASCII should be preserved"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(stats.ai_additions, 1, "The line should be attributed to AI");
@@ -711,8 +767,7 @@ fn test_precomposed_nfc_filename() {
         "Should have 1 attestation"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -751,8 +806,7 @@ fn test_decomposed_nfd_filename() {
         "Should have 1 attestation"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -793,8 +847,7 @@ fn test_combining_diacritical_marks() {
         "Should have 1 attestation"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -830,8 +883,7 @@ fn test_swedish_angstrom() {
         "Should have 1 attestation"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -873,8 +925,7 @@ fn test_mathematical_symbols_filename() {
         "File path should preserve mathematical symbols"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -916,8 +967,7 @@ fn test_currency_symbols_filename() {
         "File path should preserve currency symbols"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -951,8 +1001,7 @@ fn test_box_drawing_characters_filename() {
         "File path should preserve box drawing characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -991,8 +1040,7 @@ fn test_dingbats_and_symbols_filename() {
         "File path should preserve dingbats and symbols"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1036,8 +1084,7 @@ fn test_emoji_with_skin_tone_modifiers() {
         "File path should preserve emoji with skin tone modifier"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1075,8 +1122,7 @@ fn test_emoji_zwj_sequences() {
         "File path should preserve ZWJ emoji sequences"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1114,8 +1160,7 @@ fn test_emoji_flag_sequences() {
         "File path should preserve flag emoji (regional indicator sequences)"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1153,8 +1198,7 @@ fn test_multiple_complex_emoji_filename() {
         "File path should preserve multiple emoji"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1193,8 +1237,7 @@ fn test_emoji_in_directory_names() {
         "File path should preserve emoji in directory names"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1232,8 +1275,7 @@ fn test_russian_cyrillic_filename() {
         "File path should preserve Russian Cyrillic characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1268,8 +1310,7 @@ fn test_ukrainian_cyrillic_filename() {
         "File path should preserve Ukrainian Cyrillic characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1303,8 +1344,7 @@ fn test_greek_filename() {
         "File path should preserve Greek characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1340,8 +1380,7 @@ fn test_greek_polytonic_filename() {
         "File path should preserve Greek polytonic characters with diacritics"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1379,8 +1418,7 @@ fn test_thai_filename() {
         "File path should preserve Thai characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1414,8 +1452,7 @@ fn test_vietnamese_filename() {
         "File path should preserve Vietnamese tone marks"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1449,8 +1486,7 @@ fn test_khmer_filename() {
         "File path should preserve Khmer characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1484,8 +1520,7 @@ fn test_lao_filename() {
         "File path should preserve Lao characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1523,8 +1558,7 @@ fn test_hindi_devanagari_filename() {
         "File path should preserve Hindi/Devanagari characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1558,8 +1592,7 @@ fn test_tamil_filename() {
         "File path should preserve Tamil characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1593,8 +1626,7 @@ fn test_bengali_filename() {
         "File path should preserve Bengali characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1628,8 +1660,7 @@ fn test_telugu_filename() {
         "File path should preserve Telugu characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1663,8 +1694,7 @@ fn test_gujarati_filename() {
         "File path should preserve Gujarati characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1701,8 +1731,7 @@ fn test_devanagari_combining_chars() {
         "File path should preserve Devanagari combining characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1736,8 +1765,7 @@ fn test_korean_hangul_filename() {
         "File path should preserve Korean Hangul characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1773,8 +1801,7 @@ fn test_chinese_traditional_filename() {
         "File path should preserve Traditional Chinese characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1813,8 +1840,7 @@ fn test_mixed_cjk_filename() {
         "File path should preserve mixed CJK characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1861,8 +1887,7 @@ fn test_arabic_filename() {
         "File path should preserve Arabic characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1896,8 +1921,7 @@ fn test_hebrew_filename() {
         "File path should preserve Hebrew characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1931,8 +1955,7 @@ fn test_persian_filename() {
         "File path should preserve Persian characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -1966,8 +1989,7 @@ fn test_urdu_filename() {
         "File path should preserve Urdu characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -2001,8 +2023,7 @@ fn test_rtl_with_ltr_mixed_filename() {
         "File path should preserve mixed RTL/LTR characters"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -2042,8 +2063,7 @@ fn test_rtl_directory_path() {
         "File path should preserve Arabic characters in both directory and file names"
     );
 
-    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
-    let json = extract_json_object(&raw);
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
     let stats: CommitStats = serde_json::from_str(&json).unwrap();
 
     assert_eq!(
@@ -2055,3 +2075,135 @@ fn test_rtl_directory_path() {
         "No lines should be attributed to human"
     );
 }
+
+#[test]
+fn test_per_file_stats_with_utf8_filenames() {
+    let repo = TestRepo::new();
+
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut chinese_file = repo.filename("中文文件.txt");
+    chinese_file.set_contents(lines!["第一行".ai(), "第二行".ai(), "人工".human(),]);
+
+    let mut ascii_file = repo.filename("notes.txt");
+    ascii_file.set_contents(lines!["human only".human()]);
+
+    repo.stage_all_and_commit("Add files").unwrap();
+
+    let json = repo
+        .git_ai_stdout(&["stats", "--json", "--per-file"])
+        .unwrap();
+    let stats: CommitStats = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(stats.files.len(), 2, "Should break down both files");
+
+    let chinese = stats
+        .files
+        .iter()
+        .find(|f| f.file_path == "中文文件.txt")
+        .expect("Chinese filename should be decoded, not octal-escaped");
+    assert_eq!(chinese.ai_additions, 2);
+    assert_eq!(chinese.ai_accepted, 2);
+    assert_eq!(chinese.human_additions, 1);
+
+    let notes = stats
+        .files
+        .iter()
+        .find(|f| f.file_path == "notes.txt")
+        .expect("ASCII file should be present");
+    assert_eq!(notes.ai_additions, 0);
+    assert_eq!(notes.human_additions, 1);
+
+    // Without --per-file the breakdown is omitted entirely
+    let raw = repo.git_ai(&["stats", "--json"]).unwrap();
+    assert!(!raw.contains("\"files\""));
+}
+
+#[test]
+fn test_utf8_filename_rename_keeps_attribution() {
+    let repo = TestRepo::new();
+
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // AI creates a config file with a Chinese filename
+    let mut config_file = repo.filename("配置文件.txt");
+    config_file.set_contents(lines!["设置一".ai(), "设置二".ai(), "设置三".ai(),]);
+    repo.stage_all_and_commit("Add config").unwrap();
+
+    // Pure rename: no lines should be counted as new additions
+    repo.git(&["mv", "配置文件.txt", "新配置文件.txt"]).unwrap();
+    repo.stage_all_and_commit("Rename config").unwrap();
+
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
+    let stats: CommitStats = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        stats.git_diff_added_lines, 0,
+        "Renamed lines should not count as additions"
+    );
+    assert_eq!(stats.git_diff_deleted_lines, 0);
+    assert_eq!(stats.ai_additions, 0);
+    assert_eq!(stats.human_additions, 0);
+
+    // Rename plus a human edit: only the new line is attributed
+    repo.git(&["mv", "新配置文件.txt", "最终配置.txt"]).unwrap();
+    let mut renamed = repo.filename("最终配置.txt");
+    renamed.set_contents(lines![
+        "设置一".ai(),
+        "设置二".ai(),
+        "设置三".ai(),
+        "设置四".human(),
+    ]);
+    repo.stage_all_and_commit("Rename and extend config")
+        .unwrap();
+
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
+    let stats: CommitStats = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        stats.git_diff_added_lines, 1,
+        "Only the appended line should count as an addition"
+    );
+    assert_eq!(stats.human_additions, 1);
+    assert_eq!(stats.ai_additions, 0);
+}
+
+#[test]
+fn test_utf8_filename_rename_with_edit_attributes_only_new_lines() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let carried: Vec<String> = (1..=18)
+        .map(|i| format!("  \"字段{:02}\": {},", i, i))
+        .collect();
+    let mut data = repo.filename("数据.json");
+    data.set_contents(carried.iter().map(|line| line.as_str().ai()).collect());
+    repo.stage_all_and_commit("Add data").unwrap();
+
+    // 18 of the 20 lines carry over unchanged; only the two appended AI lines are new
+    repo.git(&["mv", "数据.json", "新数据.json"]).unwrap();
+    let mut renamed = repo.filename("新数据.json");
+    let mut contents: Vec<_> = carried.iter().map(|line| line.as_str().ai()).collect();
+    contents.push("  \"字段19\": 19,".ai());
+    contents.push("  \"字段20\": 20".ai());
+    renamed.set_contents(contents);
+    let commit = repo.stage_all_and_commit("Rename and extend data").unwrap();
+
+    assert_eq!(
+        commit.authorship_log.ai_count(),
+        2,
+        "carried-over lines keep their earlier attestation"
+    );
+
+    let json = repo.git_ai_stdout(&["stats", "--json"]).unwrap();
+    let stats: CommitStats = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(stats.git_diff_added_lines, 2);
+    assert_eq!(stats.ai_additions, 2);
+    assert_eq!(stats.human_additions, 0);
+}
@@ -0,0 +1,79 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+fn commit_with_ai_lines(repo: &TestRepo) {
+    let mut file = repo.filename("app.rs");
+    file.set_contents(lines!["fn main() {}".human()]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    file.set_contents(lines![
+        "fn main() {}".human(),
+        "fn ai_one() {}".ai(),
+        "fn ai_two() {}".ai(),
+        "fn human() {}".human(),
+    ]);
+    repo.stage_all_and_commit("Add helpers").unwrap();
+}
+
+fn rewrite_head_note(repo: &TestRepo, from: &str, to: &str) {
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", "HEAD"])
+        .expect("HEAD should have an authorship note");
+    assert!(
+        note.contains(from),
+        "note did not contain {:?}: {}",
+        from,
+        note
+    );
+    let corrupted = note.replacen(from, to, 1);
+    repo.git_og(&["notes", "--ref=ai", "add", "-f", "-m", &corrupted, "HEAD"])
+        .expect("rewriting the note should succeed");
+}
+
+#[test]
+fn test_verify_passes_for_untouched_logs() {
+    let repo = TestRepo::new();
+    commit_with_ai_lines(&repo);
+
+    let output = repo
+        .git_ai(&["verify", "HEAD~1..HEAD"])
+        .expect("verify should pass");
+    assert!(output.contains('✓'), "got: {}", output);
+}
+
+#[test]
+fn test_verify_fails_when_attestation_covers_unchanged_line() {
+    let repo = TestRepo::new();
+    commit_with_ai_lines(&repo);
+
+    // Line 1 predates the commit, so attesting it must be flagged
+    rewrite_head_note(&repo, " 2-3\n", " 1-3\n");
+
+    let err = repo
+        .git_ai(&["verify"])
+        .expect_err("verify should fail on a corrupted attestation");
+    assert!(
+        err.contains("failed authorship verification"),
+        "got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_verify_fails_when_attestation_points_at_missing_file() {
+    let repo = TestRepo::new();
+    commit_with_ai_lines(&repo);
+
+    rewrite_head_note(&repo, "app.rs\n", "missing.rs\n");
+
+    let err = repo
+        .git_ai(&["verify", "HEAD"])
+        .expect_err("verify should fail on a corrupted attestation");
+    assert!(
+        err.contains("failed authorship verification"),
+        "got: {}",
+        err
+    );
+}
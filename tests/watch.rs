@@ -0,0 +1,61 @@
+mod repos;
+
+use repos::test_repo::TestRepo;
+use serde_json::Value;
+use std::io::{BufRead, BufReader};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Wait until watch prints the given counts, skipping intermediate updates
+fn wait_for_counts(lines: &mpsc::Receiver<String>, ai_lines: u64, human_lines: u64) {
+    let deadline = Instant::now() + Duration::from_secs(30);
+    let mut seen = Vec::new();
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        let Ok(line) = lines.recv_timeout(remaining) else {
+            break;
+        };
+        let counts: Value = serde_json::from_str(&line).unwrap();
+        if counts["ai_lines"] == ai_lines && counts["human_lines"] == human_lines {
+            return;
+        }
+        seen.push(line);
+    }
+    panic!(
+        "watch never reported ai={} human={}; saw {:?}",
+        ai_lines, human_lines, seen
+    );
+}
+
+#[test]
+fn test_watch_reports_updated_counts_after_file_changes() {
+    let repo = TestRepo::new();
+    repo.filename("README.md")
+        .set_contents(vec!["# Repo".to_string()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut child = repo.spawn_git_ai(&["watch", "--json", "--debounce", "50"]);
+    let stdout = child.stdout.take().unwrap();
+    let (tx, lines) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if line.starts_with('{') && tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    wait_for_counts(&lines, 0, 0);
+
+    // An agent writes a file and records its checkpoint
+    let file_path = repo.path().join("agent.rs");
+    std::fs::write(&file_path, "fn one() {}\nfn two() {}\n").unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "agent.rs"]).unwrap();
+    wait_for_counts(&lines, 2, 0);
+
+    // A human saves another line without a checkpoint
+    std::fs::write(&file_path, "fn one() {}\nfn two() {}\nfn mine() {}\n").unwrap();
+    wait_for_counts(&lines, 2, 1);
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}